@@ -1873,6 +1873,21 @@ class GraphView(object):
             GraphView: The filtered view
         """
 
+    def filter_nodes(self, filter: PropertyFilter) -> GraphView:
+        """
+        Return a filtered view that only includes nodes that satisfy the filter
+
+        The filter is evaluated against the latest property value in the view. Edges are
+        only included if both their source and destination satisfy the filter.
+
+        Arguments
+            filter (PropertyFilter): The filter to apply to the node properties. Construct a
+                                     filter using `Prop`.
+
+        Returns:
+            GraphView: The filtered view
+        """
+
     def find_edges(self, properties_dict):
         """
         Get the edges that match the properties name and value
@@ -3361,6 +3376,19 @@ class PersistentGraph(GraphView):
          The deleted edge
         """
 
+    def delete_node(self, timestamp: int, id: str | int):
+        """
+        Deletes a node given the timestamp and the node id.
+        All edges incident to the node that are alive at the time of the deletion are deleted as well.
+
+        Arguments:
+          timestamp (int): The timestamp of the deletion.
+          id (str | int): The id of the node.
+
+        Returns:
+         The deleted node
+        """
+
     @staticmethod
     def deserialise(bytes: bytes):
         """
@@ -3828,6 +3856,8 @@ class Prop(object):
     property value (these filters always exclude entities that do not
     have the property) or use one of the methods to construct
     other kinds of filters.
+
+    Index into map properties using `Prop("name")["key"]`.
     """
 
     def __eq__(self, value):
@@ -3836,6 +3866,9 @@ class Prop(object):
     def __ge__(self, value):
         """Return self>=value."""
 
+    def __getitem__(self, key):
+        """Return self[key]."""
+
     def __gt__(self, value):
         """Return self>value."""
 
@@ -3854,6 +3887,18 @@ class Prop(object):
     def any(self, values):
         """Create a filter that keeps entities if their property value is in the set"""
 
+    def contains(self, value):
+        """
+        Create a filter that keeps entities if their string property contains `value` as a substring
+        or if their list property contains `value` as an element
+        """
+
+    def ends_with(self, suffix):
+        """Create a filter that keeps entities if their string property ends with `suffix`"""
+
+    def eq_ignore_case(self, value):
+        """Create a filter that keeps entities if their string property is equal to `value` ignoring case"""
+
     def is_none(self):
         """Create a filter that only keeps entities that do not have the property"""
 
@@ -3866,6 +3911,16 @@ class Prop(object):
         if they don't have the property
         """
 
+    def regex(self, pattern):
+        """
+        Create a filter that keeps entities if their string property matches the regular expression
+
+        The pattern is not anchored, use `^` and `$` to match the full string.
+        """
+
+    def starts_with(self, prefix):
+        """Create a filter that keeps entities if their string property starts with `prefix`"""
+
 class Properties(object):
     """A view of the properties of an entity"""
 
@@ -3935,7 +3990,29 @@ class Properties(object):
         fallback to the static property if the temporal value does not exist.
         """
 
-class PropertyFilter(object): ...
+class PropertyFilter(object):
+    """
+    A filter on property values
+
+    Construct filters using `Prop` and combine them with `&` (and), `|` (or) and `~` (not).
+    Note that `&` and `|` bind more tightly than comparisons in python, so the individual
+    filters need to be wrapped in parentheses, e.g., `(Prop("a") > 1) & (Prop("b") == "x")`.
+    """
+
+    def __and__(self, value):
+        """Return self&value."""
+
+    def __invert__(self):
+        """~self"""
+
+    def __or__(self, value):
+        """Return self|value."""
+
+    def __rand__(self, value):
+        """Return value&self."""
+
+    def __ror__(self, value):
+        """Return value|self."""
 
 class PyGraphEncoder(object):
     def __call__(self, *args, **kwargs):
@@ -69,7 +69,11 @@ def balance(
     """
 
 def betweenness_centrality(
-    g: GraphView, k: Optional[int] = None, normalized: bool = True
+    g: GraphView,
+    k: Optional[int] = None,
+    normalized: bool = True,
+    weight: Optional[str] = None,
+    threads: Optional[int] = None,
 ) -> AlgorithmResult:
     """
     Computes the betweenness centrality for nodes in a given graph.
@@ -79,11 +83,41 @@ def betweenness_centrality(
         k (int, optional): Specifies the number of nodes to consider for the centrality computation.
             All nodes are considered by default.
         normalized (bool): Indicates whether to normalize the centrality values.
+        weight (str, optional): The name of the edge property with the lengths of the edges.
+            Edges have length 1 if not set or if they don't have the property.
+        threads (int, optional): The number of threads to use for the computation.
 
     Returns:
         AlgorithmResult: Returns an `AlgorithmResult` containing the betweenness centrality of each node.
     """
 
+def closeness_centrality(
+    g: GraphView,
+    weight: Optional[str] = None,
+    directed: bool = True,
+    sample: Optional[int] = None,
+    rng_seed: Optional[int] = None,
+) -> AlgorithmResult:
+    """
+    Computes the closeness centrality of the nodes of a graph.
+
+    The closeness of a node reached from `r` other nodes at a total distance `d` is `(r / d) * (r / (n - 1))`,
+    the inverse of the average distance to the node scaled by the fraction of the graph that reaches it.
+    In a directed graph the distances to a node from the other nodes are used.
+
+    Arguments:
+        g (GraphView): A reference to the graph.
+        weight (str, optional): The name of the edge property with the lengths of the edges.
+            Edges have length 1 if not set or if they don't have the property.
+        directed (bool): If false the direction of the edges is ignored. Defaults to true.
+        sample (int, optional): Estimate the distances from this number of randomly sampled source nodes
+            instead of all of them.
+        rng_seed (int, optional): The seed of the sampling of the source nodes.
+
+    Returns:
+        AlgorithmResult: Returns an `AlgorithmResult` containing the closeness centrality of each node.
+    """
+
 def cohesive_fruchterman_reingold(
     graph, iterations=100, scale=1.0, node_start_size=1.0, cooloff_factor=0.95, dt=0.1
 ):
@@ -140,6 +174,58 @@ def directed_graph_density(g: GraphView):
         float : Directed graph density of G.
     """
 
+def edge_betweenness_centrality(
+    g: GraphView,
+    k: Optional[int] = None,
+    normalized: bool = True,
+    weight: Optional[str] = None,
+    threads: Optional[int] = None,
+) -> dict[tuple[str, str], float]:
+    """
+    Computes the betweenness centrality for edges in a given graph, the number of shortest paths passing
+    through each edge.
+
+    Arguments:
+        g (GraphView): A reference to the graph.
+        k (int, optional): Specifies the number of nodes to consider for the centrality computation.
+            All nodes are considered by default.
+        normalized (bool): Indicates whether to normalize the centrality values.
+        weight (str, optional): The name of the edge property with the lengths of the edges.
+            Edges have length 1 if not set or if they don't have the property.
+        threads (int, optional): The number of threads to use for the computation.
+
+    Returns:
+        dict[tuple[str, str], float]: The betweenness centrality of each edge, keyed by the names of its source
+        and destination.
+    """
+
+def eigenvector_centrality(
+    g: GraphView,
+    weight: Optional[str] = None,
+    directed: bool = True,
+    iter_count: int = 100,
+    max_diff: Optional[float] = None,
+) -> AlgorithmResult:
+    """
+    Computes the eigenvector centrality of the nodes of a graph.
+
+    The score of a node is proportional to the sum of the scores of the nodes pointing at it. The scores are
+    normalised to unit L2 norm.
+    An exception is raised if the scores have not converged after `iter_count` iterations.
+
+    Arguments:
+        g (GraphView): A reference to the graph.
+        weight (str, optional): The name of the edge property with the weights of the edges.
+            Edges have weight 1 if not set or if they don't have the property.
+        directed (bool): If false the direction of the edges is ignored. Defaults to true.
+        iter_count (int): Maximum number of iterations to run. Note that this will terminate early if convergence is reached.
+        max_diff (float, optional): The algorithm will terminate if the sum of the absolute difference in scores between
+            iterations is less than the number of nodes times this value. Defaults to 1e-6.
+
+    Returns:
+        AlgorithmResult: Returns an `AlgorithmResult` containing the eigenvector centrality of each node.
+    """
+
 def fruchterman_reingold(
     graph: GraphView,
     iterations: int | None = 100,
@@ -254,6 +340,30 @@ def global_temporal_three_node_motif_multi(g: GraphView, deltas: list[int]):
         list[list[int]] : A list of 40d arrays, each array is the motif count for a particular value of delta, returned in the order that the deltas were given as input.
     """
 
+def harmonic_centrality(
+    g: GraphView,
+    weight: Optional[str] = None,
+    directed: bool = True,
+    sample: Optional[int] = None,
+    rng_seed: Optional[int] = None,
+) -> AlgorithmResult:
+    """
+    Computes the harmonic centrality of the nodes of a graph, the sum of the inverse distances to a node from
+    all other nodes.
+
+    Arguments:
+        g (GraphView): A reference to the graph.
+        weight (str, optional): The name of the edge property with the lengths of the edges.
+            Edges have length 1 if not set or if they don't have the property.
+        directed (bool): If false the direction of the edges is ignored. Defaults to true.
+        sample (int, optional): Estimate the distances from this number of randomly sampled source nodes
+            instead of all of them.
+        rng_seed (int, optional): The seed of the sampling of the source nodes.
+
+    Returns:
+        AlgorithmResult: Returns an `AlgorithmResult` containing the harmonic centrality of each node.
+    """
+
 def hits(g: GraphView, iter_count: int = 20, threads: Optional[int] = None):
     """
     HITS (Hubs and Authority) Algorithm:
@@ -294,6 +404,39 @@ def in_components(g: GraphView):
         AlgorithmResult : AlgorithmResult object mapping each node to an array containing the ids of all nodes within their 'in-component'
     """
 
+def katz_centrality(
+    g: GraphView,
+    weight: Optional[str] = None,
+    directed: bool = True,
+    alpha: float = 0.1,
+    beta: float = 1.0,
+    normalized: bool = True,
+    iter_count: int = 1000,
+    max_diff: Optional[float] = None,
+) -> AlgorithmResult:
+    """
+    Computes the Katz centrality of the nodes of a graph.
+
+    The score of a node is `alpha` times the sum of the scores of the nodes pointing at it plus `beta`. The iteration
+    only converges if `alpha` is smaller than the inverse of the largest eigenvalue of the adjacency matrix.
+    An exception is raised if the scores have not converged after `iter_count` iterations.
+
+    Arguments:
+        g (GraphView): A reference to the graph.
+        weight (str, optional): The name of the edge property with the weights of the edges.
+            Edges have weight 1 if not set or if they don't have the property.
+        directed (bool): If false the direction of the edges is ignored. Defaults to true.
+        alpha (float): The attenuation factor. Defaults to 0.1.
+        beta (float): The score every node receives regardless of its neighbours. Defaults to 1.
+        normalized (bool): If true the scores are normalised to unit L2 norm. Defaults to true.
+        iter_count (int): Maximum number of iterations to run. Note that this will terminate early if convergence is reached.
+        max_diff (float, optional): The algorithm will terminate if the sum of the absolute difference in scores between
+            iterations is less than the number of nodes times this value. Defaults to 1e-6.
+
+    Returns:
+        AlgorithmResult: Returns an `AlgorithmResult` containing the Katz centrality of each node.
+    """
+
 def label_propagation(g: GraphView, seed: Optional[bytes] = None) -> list[set[Node]]:
     """
     Computes components using a label propagation algorithm
@@ -307,6 +450,59 @@ def label_propagation(g: GraphView, seed: Optional[bytes] = None) -> list[set[No
 
     """
 
+def leiden(
+    graph: GraphView,
+    resolution: float = 1.0,
+    weight_prop: str | None = None,
+    initial_partition: dict[str, int] | None = None,
+    tol: None | float = None,
+    rng_seed: int | None = None,
+) -> AlgorithmResult:
+    """
+    Leiden algorithm for community detection
+
+    Unlike Louvain, the communities found by Leiden are guaranteed to be connected.
+
+    Arguments:
+        graph (GraphView): the graph view
+        resolution (float): the resolution parameter for modularity, larger values result in smaller communities
+        weight_prop (str | None): the edge property to use for weights (has to be float)
+        initial_partition (dict[str, int] | None): the starting community of nodes by name, nodes without one start in their own community
+        tol (None | float): the floating point tolerance for deciding if improvements are significant (default: 1e-8)
+        rng_seed (int | None): the seed for the order in which nodes are visited
+
+    Returns:
+        AlgorithmResult: the community of each node, numbered from 0
+    """
+
+def leiden_rolling(
+    graph: GraphView,
+    window: int | str,
+    step: int | str | None = None,
+    resolution: float = 1.0,
+    weight_prop: str | None = None,
+    tol: None | float = None,
+    rng_seed: int | None = None,
+) -> list[tuple[int | None, dict[str, int]]]:
+    """
+    Track communities over time by running the Leiden algorithm on rolling windows of the graph
+
+    Each window starts from the communities of the previous window and communities keep their id
+    from one window to the next while they overlap, new communities get new ids.
+
+    Arguments:
+        graph (GraphView): the graph view
+        window (int | str): the size of the windows
+        step (int | str | None): the step between windows, defaults to `window`
+        resolution (float): the resolution parameter for modularity
+        weight_prop (str | None): the edge property to use for weights (has to be float)
+        tol (None | float): the floating point tolerance for deciding if improvements are significant (default: 1e-8)
+        rng_seed (int | None): the seed for the order in which nodes are visited
+
+    Returns:
+        list[tuple[int | None, dict[str, int]]]: the end of each window and the community of each node in the window
+    """
+
 def local_clustering_coefficient(g: GraphView, v: InputNode):
     """
     Local clustering coefficient - measures the degree to which nodes in a graph tend to cluster together.
@@ -571,6 +767,89 @@ def temporal_bipartite_graph_projection(
         GraphView: Projected (unipartite) temporal graph.
     """
 
+def temporal_earliest_arrival(
+    g: GraphView,
+    source: InputNode,
+    start_time: Optional[TimeInput] = None,
+    duration: Optional[str] = None,
+) -> dict:
+    """
+    Finds the journeys from a source that arrive the earliest at every node reachable from it.
+
+    A journey is a sequence of exploded edges where each edge departs from the node the previous one arrived at,
+    no earlier than its arrival. An edge departing at `t` arrives at `t + duration`. Restrict the graph to a window
+    to only consider journeys within it.
+
+    Arguments:
+        g (GraphView): The graph to search in.
+        source (InputNode): The source node.
+        start_time (TimeInput, optional): Journeys depart from the source at or after this time. Defaults to any time.
+        duration (str, optional): The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+
+    Returns:
+        dict[str, TemporalPath]: The earliest arrival journey to every reachable node, the source is not included.
+    """
+
+def temporal_fastest_paths(
+    g: GraphView,
+    source: InputNode,
+    start_time: Optional[TimeInput] = None,
+    duration: Optional[str] = None,
+) -> dict:
+    """
+    Finds the journeys from a source with the least time elapsed between departing the source and arriving at every
+    node reachable from it, including the time spent waiting for connections.
+
+    Arguments:
+        g (GraphView): The graph to search in.
+        source (InputNode): The source node.
+        start_time (TimeInput, optional): Journeys depart from the source at or after this time. Defaults to any time.
+        duration (str, optional): The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+
+    Returns:
+        dict[str, TemporalPath]: The fastest journey to every reachable node, ties are broken by the earliest arrival.
+    """
+
+def temporal_latest_departure(
+    g: GraphView,
+    target: InputNode,
+    end_time: Optional[TimeInput] = None,
+    duration: Optional[str] = None,
+) -> dict:
+    """
+    Finds the journeys to a target that depart the latest from every node that can reach it.
+
+    Arguments:
+        g (GraphView): The graph to search in.
+        target (InputNode): The target node.
+        end_time (TimeInput, optional): Journeys arrive at the target at or before this time. Defaults to any time.
+        duration (str, optional): The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+
+    Returns:
+        dict[str, TemporalPath]: The latest departure journey from every node that can reach the target, the target is not included.
+    """
+
+def temporal_shortest_paths(
+    g: GraphView,
+    source: InputNode,
+    start_time: Optional[TimeInput] = None,
+    duration: Optional[str] = None,
+) -> dict:
+    """
+    Finds the journeys from a source with the smallest sum of the traversal durations of their edges to every node
+    reachable from it, not counting the time spent waiting for connections.
+
+    Arguments:
+        g (GraphView): The graph to search in.
+        source (InputNode): The source node.
+        start_time (TimeInput, optional): Journeys depart from the source at or after this time. Defaults to any time.
+        duration (str, optional): The name of the edge property with the traversal time of the edges. If not set, edges
+            take no time and the journeys with the fewest edges are returned.
+
+    Returns:
+        dict[str, TemporalPath]: The shortest journey to every reachable node, ties are broken by the earliest arrival.
+    """
+
 def temporally_reachable_nodes(
     g: GraphView,
     max_hops: int,
@@ -624,3 +903,38 @@ def weakly_connected_components(g: GraphView, iter_count: int = 9223372036854775
     Returns:
         AlgorithmResult : AlgorithmResult object mapping nodes to their component ids.
     """
+
+def weighted_pagerank(
+    g: GraphView,
+    weight: Optional[str] = None,
+    aggregation: str = "sum",
+    personalisation: Optional[dict[InputNode, float] | list[InputNode]] = None,
+    iter_count: int = 20,
+    max_diff: Optional[float] = None,
+    use_l2_norm=True,
+    damping_factor=0.85,
+):
+    """
+    Weighted and personalised Pagerank -- pagerank centrality where the score flows along the edges in proportion to their weights
+
+    Nodes pass their score to their out-neighbours in proportion to the weights of the edges, and random jumps (and the score of nodes
+    without outgoing weight) land on the nodes of the personalisation. Giving a set of seed nodes as personalisation ranks the nodes by
+    their proximity to the seeds.
+
+    Arguments:
+        g (GraphView) : Raphtory graph
+        weight (Optional[str]) : The name of the edge property with the weights. All edges have the same weight if not set.
+        aggregation (str) : How the values of the weight property across the updates of an edge are combined, one of "sum", "latest" or "count".
+            "count" uses the number of updates of the edge as its weight. Defaults to "sum".
+        personalisation (Optional[dict[InputNode, float] | list[InputNode]]) : The teleport weight of each node, or a list of seed nodes
+            which all have the same weight. Random jumps are uniform if not set.
+        iter_count (int) : Maximum number of iterations to run. Note that this will terminate early if convergence is reached.
+        max_diff (Optional[float]) : Optional parameter providing an alternative stopping condition.
+            The algorithm will terminate if the sum of the absolute difference in pagerank values between iterations
+            is less than the max diff value given.
+        use_l2_norm (bool) : Whether to use the L2 norm of the differences for the stopping condition
+        damping_factor (float) : Probability of following an edge rather than jumping
+
+    Returns:
+        AlgorithmResult : AlgorithmResult with string keys and float values mapping node names to their pagerank value.
+    """
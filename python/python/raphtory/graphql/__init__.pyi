@@ -29,6 +29,14 @@ class GraphServer(object):
         otlp_agent_port=None,
         otlp_tracing_service_name=None,
         config_path=None,
+        api_keys=None,
+        jwt_key_path=None,
+        jwt_algorithm=None,
+        anonymous_permissions=None,
+        max_query_depth=None,
+        max_query_complexity=None,
+        query_timeout_seconds=None,
+        max_result_size=None,
     ):
         """Create and return a new object.  See help(type) for accurate signature."""
 
@@ -147,7 +155,7 @@ class GraphqlGraphs(object):
 class RaphtoryClient(object):
     """A client for handling GraphQL operations in the context of Raphtory."""
 
-    def __new__(self, url):
+    def __new__(self, url, api_key=None, token=None):
         """Create and return a new object.  See help(type) for accurate signature."""
 
     def copy_graph(self, path, new_path):
@@ -424,6 +432,19 @@ class RemoteGraph(object):
           RemoteEdge
         """
 
+    def delete_node(self, timestamp: int, id: str | int):
+        """
+        Deletes a node in the remote graph, given the timestamp and the node id.
+        All edges incident to the node that are alive at the time of the deletion are deleted as well.
+
+        Arguments:
+          timestamp (int): The timestamp of the deletion.
+          id (str|int): The id of the node.
+
+        Returns:
+          RemoteNode
+        """
+
     def edge(self, src: str | int, dst: str | int):
         """
         Gets a remote edge with the specified source and destination nodes
@@ -513,7 +534,18 @@ class RunningGraphServer(object):
 
     def __enter__(self): ...
     def __exit__(self, _exc_type, _exc_val, _exc_tb): ...
-    def get_client(self): ...
+    def get_client(self, api_key=None, token=None):
+        """
+        Get a client for the server
+
+        Arguments:
+          api_key (str, optional): an API key configured on the server.
+          token (str, optional): a JWT signed with the key configured on the server.
+
+        Returns:
+          RaphtoryClient: A client for the server
+        """
+
     def stop(self):
         """Stop the server and wait for it to finish"""
 
@@ -170,6 +170,14 @@ class VectorSelection(object):
         """Return the nodes present in the current selection"""
 
 class VectorisedGraph(object):
+    def build_ann_index(self):
+        """
+        Build an approximate nearest-neighbour index over the node and edge documents
+
+        Once built, the index is used by the similarity searches on this graph. Searches fall back
+        to an exact scan if the index can't provide enough results matching the window.
+        """
+
     def documents_by_similarity(
         self,
         query: str | list,
@@ -188,6 +196,9 @@ class VectorisedGraph(object):
           VectorSelection: The vector selection resulting from the search
         """
 
+    def drop_ann_index(self):
+        """Remove the approximate nearest-neighbour index so searches go back to being exact"""
+
     def edges_by_similarity(
         self,
         query: str | list,
@@ -227,6 +238,9 @@ class VectorisedGraph(object):
           VectorSelection: The vector selection resulting from the search
         """
 
+    def has_ann_index(self) -> bool:
+        """Whether similarity searches use an approximate nearest-neighbour index"""
+
     def nodes_by_similarity(
         self,
         query: str | list,
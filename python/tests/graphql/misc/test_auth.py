@@ -0,0 +1,74 @@
+import os
+import tempfile
+
+import pytest
+from raphtory import Graph
+from raphtory.graphql import GraphServer, RaphtoryClient
+
+API_KEYS = {
+    "writer-key": {"team_a": "write", "*": "read"},
+    "reader-key": {"team_a": "read"},
+}
+
+
+def make_work_dir():
+    work_dir = tempfile.mkdtemp()
+    g = Graph()
+    g.add_edge(1, "ben", "hamza")
+    for namespace in ["team_a", "team_b"]:
+        os.makedirs(os.path.join(work_dir, namespace))
+        g.save_to_file(os.path.join(work_dir, namespace, "g"))
+    return work_dir
+
+
+def test_requests_without_credentials_are_rejected():
+    work_dir = make_work_dir()
+    with GraphServer(work_dir, api_keys=API_KEYS).start():
+        client = RaphtoryClient("http://localhost:1736")
+        with pytest.raises(Exception) as excinfo:
+            client.query('{ graph(path: "team_a/g") { countNodes } }')
+        assert "Permission denied" in str(excinfo.value)
+
+        client = RaphtoryClient("http://localhost:1736", api_key="wrong-key")
+        with pytest.raises(Exception) as excinfo:
+            client.query('{ graph(path: "team_a/g") { countNodes } }')
+        assert "rejected the credentials" in str(excinfo.value)
+
+
+def test_read_and_write_permissions_per_namespace():
+    work_dir = make_work_dir()
+    with GraphServer(work_dir, api_keys=API_KEYS).start() as server:
+        reader = server.get_client(api_key="reader-key")
+        assert reader.query('{ graph(path: "team_a/g") { countNodes } }') == {
+            "graph": {"countNodes": 2}
+        }
+        assert reader.query("{ graphs { path } }") == {
+            "graphs": {"path": ["team_a/g"]}
+        }
+        with pytest.raises(Exception) as excinfo:
+            reader.query('{ graph(path: "team_b/g") { countNodes } }')
+        assert "Permission denied" in str(excinfo.value)
+        with pytest.raises(Exception) as excinfo:
+            reader.delete_graph("team_a/g")
+        assert "Permission denied" in str(excinfo.value)
+
+        writer = RaphtoryClient("http://localhost:1736", api_key="writer-key")
+        writer.copy_graph("team_b/g", "team_a/g2")
+        with pytest.raises(Exception) as excinfo:
+            writer.copy_graph("team_a/g", "team_b/g2")
+        assert "Permission denied" in str(excinfo.value)
+        writer.delete_graph("team_a/g2")
+
+
+def test_anonymous_permissions():
+    work_dir = make_work_dir()
+    with GraphServer(
+        work_dir, api_keys=API_KEYS, anonymous_permissions={"team_b": "read"}
+    ).start():
+        client = RaphtoryClient("http://localhost:1736")
+        assert client.query('{ graph(path: "team_b/g") { countNodes } }') == {
+            "graph": {"countNodes": 2}
+        }
+        with pytest.raises(Exception) as excinfo:
+            client.query('{ graph(path: "team_a/g") { countNodes } }')
+        assert "Permission denied" in str(excinfo.value)
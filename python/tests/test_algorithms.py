@@ -286,6 +286,35 @@ def test_page_rank():
     assert actual.get_all_with_names() == expected
 
 
+
+def test_weighted_page_rank():
+    g = gen_graph()
+    unweighted = algorithms.pagerank(g, iter_count=1000)
+    weighted = algorithms.weighted_pagerank(g, iter_count=1000)
+    for node, score in unweighted.get_all_with_names().items():
+        assert weighted.get(node) == pytest.approx(score, abs=1e-5)
+
+    g = Graph()
+    g.add_edge(0, 1, 2, {"amount": 1.0})
+    g.add_edge(1, 1, 3, {"amount": 5.0})
+    g.add_edge(2, 1, 3, {"amount": 4.0})
+    g.add_edge(3, 2, 1, {"amount": 1.0})
+    g.add_edge(4, 3, 1, {"amount": 1.0})
+    g.add_edge(5, 4, 1, {"amount": 1.0})
+    for aggregation, ratio in [("sum", 9.0), ("latest", 4.0), ("count", 2.0)]:
+        actual = algorithms.weighted_pagerank(g, "amount", aggregation, iter_count=1000)
+        assert (actual.get(3) - 0.0375) / (actual.get(2) - 0.0375) == pytest.approx(
+            ratio
+        )
+
+    seeded = algorithms.weighted_pagerank(g, "amount", personalisation=[1])
+    assert seeded.get(4) == pytest.approx(0.0)
+    seeded = algorithms.weighted_pagerank(g, "amount", personalisation={1: 1.0, 4: 3.0})
+    assert seeded.get(4) == pytest.approx(0.15 * 0.75)
+
+    with pytest.raises(Exception, match="Invalid weight aggregation"):
+        algorithms.weighted_pagerank(g, "amount", "mean")
+
 def test_temporal_reachability():
     g = gen_graph()
 
@@ -308,6 +337,30 @@ def test_temporal_reachability():
     assert actual.get_all_with_names() == expected
 
 
+def test_temporal_paths():
+    from raphtory import Graph
+
+    g = Graph()
+    g.add_edge(1, "a", "b", {"duration": 2})
+    g.add_edge(4, "b", "d", {"duration": 1})
+    g.add_edge(5, "a", "c", {"duration": 1})
+    g.add_edge(6, "c", "d", {"duration": 1})
+
+    earliest = algorithms.temporal_earliest_arrival(g, "a", 0, "duration")
+    assert earliest["d"].nodes == ["a", "b", "d"]
+    assert (earliest["d"].departure, earliest["d"].arrival) == (1, 5)
+    assert [e.time for e in earliest["d"].edges] == [1, 4]
+
+    latest = algorithms.temporal_latest_departure(g, "d", 7, "duration")
+    assert latest["a"].nodes == ["a", "c", "d"]
+
+    fastest = algorithms.temporal_fastest_paths(g, "a", duration="duration")
+    assert fastest["d"].duration == 2
+
+    shortest = algorithms.temporal_shortest_paths(g, "a")
+    assert shortest["d"].nodes == ["a", "b", "d"]
+
+
 def test_degree_centrality():
     from raphtory import Graph
     from raphtory.algorithms import degree_centrality
@@ -440,6 +493,105 @@ def test_betweenness_centrality():
         "5": 0.0,
     }
 
+    assert (
+        betweenness_centrality(g, threads=2).get_all_with_names()
+        == res.get_all_with_names()
+    )
+
+
+def test_weighted_and_edge_betweenness_centrality():
+    g = Graph()
+    edges = [
+        (1, 2, 1.0),
+        (1, 3, 4.0),
+        (2, 3, 2.0),
+        (3, 4, 1.0),
+        (4, 1, 3.0),
+        (5, 4, 1.0),
+    ]
+    for src, dst, length in edges:
+        g.add_edge(0, src, dst, {"length": length})
+
+    # expected values computed with networkx
+    res = algorithms.betweenness_centrality(g, normalized=False, weight="length")
+    assert res.get_all_with_names() == {
+        "1": 5.0,
+        "2": 4.0,
+        "3": 3.0,
+        "4": 6.0,
+        "5": 0.0,
+    }
+    assert algorithms.edge_betweenness_centrality(g, normalized=False) == {
+        ("1", "2"): 4.0,
+        ("1", "3"): 4.0,
+        ("2", "3"): 3.0,
+        ("3", "4"): 6.0,
+        ("4", "1"): 9.0,
+        ("5", "4"): 4.0,
+    }
+
+
+def test_closeness_harmonic_eigenvector_katz_centrality():
+    g = Graph()
+    edges = [
+        (1, 2, 1.0),
+        (1, 3, 4.0),
+        (2, 3, 2.0),
+        (3, 4, 1.0),
+        (4, 1, 3.0),
+        (5, 4, 1.0),
+    ]
+    for src, dst, weight in edges:
+        g.add_edge(0, src, dst, {"weight": weight})
+
+    # expected values computed with networkx
+    def check(res, expected):
+        for node, value in zip([1, 2, 3, 4, 5], expected):
+            assert res.get(node) == pytest.approx(value, abs=1e-4)
+
+    check(
+        algorithms.closeness_centrality(g),
+        [0.5, 0.444444, 0.571429, 0.666667, 0.0],
+    )
+    check(
+        algorithms.closeness_centrality(g, "weight"),
+        [0.235294, 0.266667, 0.222222, 0.444444, 0.0],
+    )
+    check(
+        algorithms.closeness_centrality(g, directed=False),
+        [0.8, 0.571429, 0.8, 0.8, 0.5],
+    )
+    check(
+        algorithms.harmonic_centrality(g),
+        [2.333333, 2.166667, 2.833333, 3.0, 0.0],
+    )
+    check(
+        algorithms.harmonic_centrality(g, sample=5, rng_seed=1),
+        [2.333333, 2.166667, 2.833333, 3.0, 0.0],
+    )
+    check(
+        algorithms.eigenvector_centrality(g, directed=False, max_diff=1e-9),
+        [0.537077, 0.406693, 0.537077, 0.474750, 0.179750],
+    )
+    check(
+        algorithms.katz_centrality(g),
+        [0.440604, 0.436673, 0.480341, 0.479908, 0.392613],
+    )
+    check(
+        algorithms.katz_centrality(g, "weight", alpha=0.05),
+        [0.457078, 0.414340, 0.524336, 0.437278, 0.391486],
+    )
+
+    g.add_edge(1, 5, 1, {"weight": -1.0})
+    with pytest.raises(Exception, match="is not a non-negative number"):
+        algorithms.closeness_centrality(g, "weight")
+
+    star = Graph()
+    for leaf in range(1, 151):
+        star.add_edge(0, 0, leaf)
+    with pytest.raises(Exception, match="failed to converge"):
+        algorithms.katz_centrality(star, directed=False)
+
 
 def test_hits_algorithm():
     g = graph_loader.lotr_graph()
@@ -501,6 +653,37 @@ def test_label_propagation_algorithm():
         assert group in result
 
 
+def test_leiden():
+    g = Graph()
+    for clique in [[1, 2, 3, 4], [5, 6, 7, 8]]:
+        for i, src in enumerate(clique):
+            for dst in clique[i + 1 :]:
+                g.add_edge(0, src, dst)
+                g.add_edge(0, dst, src)
+    g.add_edge(0, 4, 5)
+    g.add_edge(0, 5, 4)
+
+    result = algorithms.leiden(g, rng_seed=42)
+    assert [result.get(n) for n in range(1, 9)] == [0, 0, 0, 0, 1, 1, 1, 1]
+
+    initial = {str(n): 0 if n < 5 else 1 for n in range(1, 9)}
+    result = algorithms.leiden(g, initial_partition=initial, rng_seed=42)
+    assert len(result.group_by()) == 2
+
+    # node 4 moves to the other community and a new community appears
+    for clique in [[1, 2, 3], [4, 5, 6, 7, 8], [11, 12, 13]]:
+        for i, src in enumerate(clique):
+            for dst in clique[i + 1 :]:
+                g.add_edge(10, src, dst)
+                g.add_edge(10, dst, src)
+    result = algorithms.leiden_rolling(g, 10, rng_seed=7)
+    assert [end for end, _ in result] == [10, 20]
+    first, second = result[0][1], result[1][1]
+    assert second["1"] == first["1"]
+    assert second["4"] == first["5"]
+    assert second["11"] not in (first["1"], first["5"])
+
+
 def test_temporal_SEIR():
     g = Graph()
     g.add_edge(1, 1, 2)
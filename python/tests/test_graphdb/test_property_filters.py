@@ -91,3 +91,63 @@ def test_filter_exploded_edges():
     assert graph.filter_exploded_edges(Prop("test_bool") == True).edges.id == [
         (2, 3)
     ]  # worth adding special support for this?
+
+
+def test_filter_nodes():
+    graph = Graph()
+    graph.add_node(0, 1, {"country": "UK", "age": 30})
+    graph.add_node(0, 2, {"country": "UK"})
+    graph.add_node(0, 3, {"country": "US", "age": 50})
+    graph.add_node(2, 2, {"country": "US"})
+    graph.add_edge(1, 1, 2)
+    graph.add_edge(1, 2, 3)
+
+    assert graph.filter_nodes(Prop("country") == "UK").nodes.id == [1]
+    assert graph.filter_nodes(Prop("country") == "US").nodes.id == [2, 3]
+    assert graph.filter_nodes(Prop("country") == "US").edges.id == [(2, 3)]
+    assert graph.before(2).filter_nodes(Prop("country") == "UK").nodes.id == [1, 2]
+    assert graph.before(2).filter_nodes(Prop("country") == "UK").edges.id == [(1, 2)]
+    assert graph.filter_nodes(Prop("age") > 40).nodes.id == [3]
+    assert graph.filter_nodes(Prop("age").is_none()).nodes.id == [2]
+    assert graph.filter_nodes(Prop("country").any({"FR", "US"})).nodes.id == [2, 3]
+
+
+def test_filter_composite():
+    graph = build_graph()
+
+    assert graph.filter_edges(
+        (Prop("test_str") == "first") | (Prop("test_int") == 3)
+    ).edges.id == [(1, 2), (3, 4)]
+    assert graph.filter_edges(
+        (Prop("test_str").is_some()) & (Prop("test_int") < 1)
+    ).edges.id == [(1, 2)]
+    assert graph.filter_edges(~(Prop("test_int") > 1)).edges.id == [(1, 2), (2, 3)]
+    assert graph.filter_exploded_edges(
+        ((Prop("test_int") == 2) | (Prop("test_str") == "second"))
+        & ~(Prop("test_int") == 1)
+    ).edges.id == [(3, 4)]
+
+
+def test_filter_string_predicates():
+    graph = Graph()
+    graph.add_edge(0, 1, 2, {"url": "https://raphtory.com/docs"})
+    graph.add_edge(0, 2, 3, {"url": "http://example.com/Raphtory"})
+    graph.add_edge(0, 3, 4, {"url": "HTTPS://RAPHTORY.COM/DOCS", "tags": ["a", "b"]})
+    graph.add_edge(0, 4, 5, {"meta": {"address": {"city": "London"}, "score": 3}})
+
+    assert graph.filter_edges(Prop("url").starts_with("https://")).edges.id == [(1, 2)]
+    assert graph.filter_edges(Prop("url").ends_with("Raphtory")).edges.id == [(2, 3)]
+    assert graph.filter_edges(Prop("url").contains("example")).edges.id == [(2, 3)]
+    assert graph.filter_edges(
+        Prop("url").eq_ignore_case("https://raphtory.com/docs")
+    ).edges.id == [(1, 2), (3, 4)]
+    assert graph.filter_edges(Prop("url").regex("(?i)^https?://raphtory")).edges.id == [
+        (1, 2),
+        (3, 4),
+    ]
+    assert graph.filter_exploded_edges(Prop("tags").contains("b")).edges.id == [(3, 4)]
+    assert graph.filter_edges(Prop("meta")["score"] > 2).edges.id == [(4, 5)]
+    assert graph.filter_edges(
+        Prop("meta")["address"]["city"] == "London"
+    ).edges.id == [(4, 5)]
+    assert graph.filter_edges(Prop("meta")["address"]["city"] == "Paris").edges.id == []
@@ -138,6 +138,20 @@ def test_search():
     # or we could also have a method entity.get_documents for the entities we return (not trivial)
 
 
+def test_ann_index():
+    vg = create_graph()
+    assert not vg.has_ann_index()
+    exact = vg.documents_by_similarity([1.0, 0.0, 0.0], 3).get_documents()
+
+    vg.build_ann_index()
+    assert vg.has_ann_index()
+    docs = vg.documents_by_similarity([1.0, 0.0, 0.0], 3).get_documents()
+    assert [doc.content for doc in docs] == [doc.content for doc in exact]
+
+    vg.drop_ann_index()
+    assert not vg.has_ann_index()
+
+
 def test_expansion():
     vg = create_graph()
 
@@ -1,6 +1,9 @@
+#[cfg(feature = "storage")]
 use crate::arrow2::{array::Arrow2Arrow, datatypes::ArrowDataType, types::NativeType};
+#[cfg(feature = "storage")]
 use arrow::datatypes::ArrowPrimitiveType;
 
+pub mod mutation;
 pub(crate) mod table_provider;
 
 #[derive(thiserror::Error, Debug)]
@@ -25,8 +28,15 @@ pub enum ExecError {
 
     #[error("IO Failure {0}")]
     IOError(#[from] std::io::Error),
+
+    #[error("Failed to update graph: {0}")]
+    GraphError(#[from] raphtory::core::utils::errors::GraphError),
+
+    #[error("Unsupported update: {0}")]
+    Unsupported(String),
 }
 
+#[cfg(feature = "storage")]
 fn arrow2_to_arrow_buf<U: ArrowPrimitiveType>(
     buffer: &crate::arrow2::buffer::Buffer<U::Native>,
 ) -> arrow::array::PrimitiveArray<U>
@@ -0,0 +1,1189 @@
+use std::collections::{HashMap, HashSet};
+
+use arrow::{
+    array::AsArray,
+    datatypes::{Float32Type, Float64Type, Int32Type, Int64Type, UInt32Type, UInt64Type},
+};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::DataType;
+use raphtory::{
+    core::{
+        entities::{properties::PropError, GidType, VID},
+        utils::errors::GraphError,
+        Direction, PropType,
+    },
+    db::{
+        api::mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        graph::views::deletion_graph::PersistentGraph,
+    },
+    prelude::*,
+};
+
+use crate::{executor::ExecError, parser::ast::*};
+
+/// Graphs that the updating clauses of a Cypher query can be applied to
+pub trait CypherMutableGraph:
+    AdditionOps + PropertyAdditionOps + InternalAdditionOps + InternalPropertyAdditionOps
+{
+    /// Whether DELETE can be applied to the graph
+    fn supports_deletions(&self) -> bool;
+
+    /// Delete the edge between `src` and `dst` in `layer` at time `t`
+    fn delete_edge_at(&self, t: i64, src: &GID, dst: &GID, layer: &str) -> Result<(), ExecError>;
+
+    /// Delete the node and all of its edges at time `t`
+    fn delete_node_at(&self, t: i64, node: &GID) -> Result<(), ExecError>;
+}
+
+impl CypherMutableGraph for Graph {
+    fn supports_deletions(&self) -> bool {
+        false
+    }
+
+    fn delete_edge_at(
+        &self,
+        _t: i64,
+        _src: &GID,
+        _dst: &GID,
+        _layer: &str,
+    ) -> Result<(), ExecError> {
+        Err(deletions_unsupported())
+    }
+
+    fn delete_node_at(&self, _t: i64, _node: &GID) -> Result<(), ExecError> {
+        Err(deletions_unsupported())
+    }
+}
+
+impl CypherMutableGraph for PersistentGraph {
+    fn supports_deletions(&self) -> bool {
+        true
+    }
+
+    fn delete_edge_at(&self, t: i64, src: &GID, dst: &GID, layer: &str) -> Result<(), ExecError> {
+        self.delete_edge(t, src, dst, Some(layer))?;
+        Ok(())
+    }
+
+    fn delete_node_at(&self, t: i64, node: &GID) -> Result<(), ExecError> {
+        self.delete_node(t, node)?;
+        Ok(())
+    }
+}
+
+/// Counts of the changes made by the updating clauses of a query
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateStats {
+    pub nodes_created: usize,
+    pub edges_created: usize,
+    pub properties_set: usize,
+    pub nodes_deleted: usize,
+    pub edges_deleted: usize,
+}
+
+/// A node in a CREATE or MERGE pattern that is not bound yet, new nodes are identified by their `id` property
+#[derive(Debug)]
+struct NodeUpdate {
+    var_name: String,
+    id: usize,
+    node_type: Option<String>,
+    props: Vec<(String, usize)>,
+}
+
+/// A relationship in a CREATE or MERGE pattern, the type of the relationship is the layer of the edge
+#[derive(Debug)]
+struct EdgeUpdate {
+    var_name: String,
+    src: String,
+    dst: String,
+    layer: Option<String>,
+    props: Vec<(String, usize)>,
+}
+
+#[derive(Debug)]
+enum SetOp {
+    Property {
+        var_name: String,
+        key: String,
+        value: usize,
+    },
+    NodeType {
+        var_name: String,
+        node_type: String,
+    },
+}
+
+#[derive(Debug)]
+enum Update {
+    Create {
+        nodes: Vec<NodeUpdate>,
+        edges: Vec<EdgeUpdate>,
+    },
+    Merge {
+        nodes: Vec<NodeUpdate>,
+        edges: Vec<EdgeUpdate>,
+        on_create: Vec<SetOp>,
+        on_match: Vec<SetOp>,
+    },
+    Set(Vec<SetOp>),
+    Delete {
+        var_names: Vec<String>,
+        detach: bool,
+    },
+}
+
+/// The updating clauses of a query ready to be applied once for every row matched by its MATCH clauses
+///
+/// Every expression used as a value (node ids, properties) is evaluated as part of the MATCH query,
+/// the updates refer to them by their position in `values`.
+#[derive(Debug)]
+pub struct UpdatePlan {
+    matches: Vec<Match>,
+    updates: Vec<Update>,
+    values: Vec<Expr>,
+    node_binds: Vec<String>,
+    edge_binds: Vec<String>,
+}
+
+impl UpdatePlan {
+    pub fn new(query: Query) -> Result<Self, ExecError> {
+        let Query::SingleQuery(SingleQuery { clauses }) = query;
+
+        let mut matches = vec![];
+        let mut update_clauses = vec![];
+        for clause in clauses {
+            match clause {
+                Clause::Match(m) if update_clauses.is_empty() => matches.push(m),
+                Clause::Match(_) => {
+                    return Err(ExecError::Unsupported(
+                        "MATCH after updating clauses".to_string(),
+                    ))
+                }
+                Clause::Return(_) => {
+                    return Err(ExecError::Unsupported(
+                        "RETURN in queries with updating clauses".to_string(),
+                    ))
+                }
+                clause => update_clauses.push(clause),
+            }
+        }
+
+        if update_clauses.is_empty() {
+            return Err(ExecError::Unsupported(
+                "query without updating clauses, use run_cypher_on_graph to read from the graph"
+                    .to_string(),
+            ));
+        }
+
+        let mut node_binds = vec![];
+        let mut edge_binds = vec![];
+        for Match { pattern, .. } in matches.iter() {
+            for part in pattern.0.iter() {
+                for node in std::iter::once(&part.node).chain(part.rel_chain.iter().map(|(_, n)| n))
+                {
+                    if !node.anonymous && !node_binds.contains(&node.name) {
+                        node_binds.push(node.name.clone());
+                    }
+                }
+                for (rel, _) in part.rel_chain.iter() {
+                    if rel.var_length.is_none() && !edge_binds.contains(&rel.name) {
+                        edge_binds.push(rel.name.clone());
+                    }
+                }
+            }
+        }
+
+        let mut planner = Planner {
+            nodes: node_binds.iter().cloned().collect(),
+            edges: edge_binds.iter().cloned().collect(),
+            values: vec![],
+        };
+
+        let updates = update_clauses
+            .into_iter()
+            .map(|clause| planner.update(clause))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            matches,
+            updates,
+            values: planner.values,
+            node_binds,
+            edge_binds,
+        })
+    }
+
+    /// The MATCH part of the query returning the binds and values used by the updates,
+    /// `None` if the query has no MATCH clauses
+    pub fn read_query(&self) -> Option<Query> {
+        if self.matches.is_empty() {
+            return None;
+        }
+
+        let node_items = self.node_binds.iter().map(|name| Expr::var(name, ["id"]));
+        let edge_items = self.edge_binds.iter().flat_map(|name| {
+            ["src", "dst", "layer_id"]
+                .into_iter()
+                .map(move |col| Expr::var(name, [col]))
+        });
+        // literals are evaluated by the updates, only the other values are computed by the query
+        let values = self.values.iter().filter(|expr| !is_literal(expr)).cloned();
+        let mut items = node_items
+            .chain(edge_items)
+            .chain(values)
+            .enumerate()
+            .map(|(i, expr)| ReturnItem {
+                expr,
+                as_name: Some(format!("c{}", i)),
+            })
+            .collect::<Vec<_>>();
+
+        if items.is_empty() {
+            // something has to be selected for every matched row
+            items.push(ReturnItem {
+                expr: Expr::Literal(Literal::Int(1)),
+                as_name: Some("c0".to_string()),
+            });
+        }
+
+        let clauses = self
+            .matches
+            .iter()
+            .map(|m| Clause::Match(bound_props_to_where(m)))
+            .chain(std::iter::once(Clause::return_(false, None, items)))
+            .collect::<Vec<_>>();
+        Some(Query::single(clauses))
+    }
+
+    /// Apply the updates at time `t` for every row returned by the read query,
+    /// or once if the query has no MATCH clauses
+    ///
+    /// The changes of all the rows are checked before any of them is applied, the graph is left
+    /// unchanged if one of them fails.
+    pub fn apply<G: CypherMutableGraph>(
+        &self,
+        graph: &G,
+        t: i64,
+        batches: &[RecordBatch],
+    ) -> Result<UpdateStats, ExecError> {
+        let mut changes = Changes::new(graph, t);
+
+        if self.matches.is_empty() {
+            let values = self
+                .values
+                .iter()
+                .map(eval_literal)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut scope = Scope::new(values);
+            for update in self.updates.iter() {
+                scope.plan(update, &mut changes)?;
+            }
+            return changes.apply();
+        }
+
+        let layer_names = graph.edge_meta().layer_meta().get_keys();
+        // edge tables have a row for every update of an edge, only apply the updates once for every match
+        let mut seen = HashSet::new();
+        for batch in batches {
+            for row in 0..batch.num_rows() {
+                let mut columns = batch.columns().iter();
+                let mut scope = Scope::new(vec![]);
+                for name in self.node_binds.iter() {
+                    let id = gid_at(graph, columns.next(), row)?;
+                    scope.nodes.insert(name.clone(), id);
+                }
+                for name in self.edge_binds.iter() {
+                    let src = gid_at(graph, columns.next(), row)?;
+                    let dst = gid_at(graph, columns.next(), row)?;
+                    let layer_id = id_at(columns.next(), row)?;
+                    let layer = layer_names
+                        .get(layer_id)
+                        .ok_or_else(|| ExecError::LayerNotFound(layer_id.to_string()))?
+                        .to_string();
+                    scope.edges.insert(name.clone(), (src, dst, layer));
+                }
+                let nodes = self
+                    .node_binds
+                    .iter()
+                    .map(|name| scope.nodes[name].clone())
+                    .collect::<Vec<_>>();
+                let edges = self
+                    .edge_binds
+                    .iter()
+                    .map(|name| scope.edges[name].clone())
+                    .collect::<Vec<_>>();
+                if !seen.insert((nodes, edges)) {
+                    continue;
+                }
+                for value in self.values.iter() {
+                    let value = if is_literal(value) {
+                        eval_literal(value)?
+                    } else {
+                        prop_at(columns.next().ok_or_else(missing_column)?, row)?
+                    };
+                    scope.values.push(value);
+                }
+
+                for update in self.updates.iter() {
+                    scope.plan(update, &mut changes)?;
+                }
+            }
+        }
+        changes.apply()
+    }
+}
+
+/// Keeps track of the variables bound while planning the updates and collects the values they use
+struct Planner {
+    nodes: HashSet<String>,
+    edges: HashSet<String>,
+    values: Vec<Expr>,
+}
+
+impl Planner {
+    fn value(&mut self, expr: Expr) -> usize {
+        self.values.push(expr);
+        self.values.len() - 1
+    }
+
+    fn update(&mut self, clause: Clause) -> Result<Update, ExecError> {
+        match clause {
+            Clause::Create(Create { pattern }) => {
+                let mut nodes = vec![];
+                let mut edges = vec![];
+                for part in pattern.0 {
+                    self.pattern_part(part, &mut nodes, &mut edges)?;
+                }
+                Ok(Update::Create { nodes, edges })
+            }
+            Clause::Merge(Merge {
+                pattern,
+                on_create,
+                on_match,
+            }) => {
+                let mut nodes = vec![];
+                let mut edges = vec![];
+                self.pattern_part(pattern, &mut nodes, &mut edges)?;
+                Ok(Update::Merge {
+                    nodes,
+                    edges,
+                    on_create: self.set_items(on_create)?,
+                    on_match: self.set_items(on_match)?,
+                })
+            }
+            Clause::Set(Set { items }) => Ok(Update::Set(self.set_items(items)?)),
+            Clause::Delete(Delete { detach, exprs }) => {
+                let var_names = exprs
+                    .into_iter()
+                    .map(|expr| match expr {
+                        Expr::Var { var_name, attrs } if attrs.is_empty() => {
+                            self.check_bound(&var_name)?;
+                            Ok(var_name)
+                        }
+                        expr => Err(ExecError::Unsupported(format!(
+                            "DELETE expects variables, found {:?}",
+                            expr
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Update::Delete { var_names, detach })
+            }
+            clause => Err(ExecError::Unsupported(format!(
+                "clause {:?} in queries with updating clauses",
+                clause
+            ))),
+        }
+    }
+
+    fn check_bound(&self, var_name: &str) -> Result<(), ExecError> {
+        if self.nodes.contains(var_name) || self.edges.contains(var_name) {
+            Ok(())
+        } else {
+            Err(ExecError::Unsupported(format!(
+                "variable {} is not bound",
+                var_name
+            )))
+        }
+    }
+
+    fn pattern_part(
+        &mut self,
+        part: PatternPart,
+        nodes: &mut Vec<NodeUpdate>,
+        edges: &mut Vec<EdgeUpdate>,
+    ) -> Result<(), ExecError> {
+        let mut last = self.node(part.node, nodes)?;
+        for (rel, node) in part.rel_chain {
+            let next = self.node(node, nodes)?;
+            if rel.var_length.is_some() {
+                return Err(ExecError::Unsupported(format!(
+                    "creating variable length relationship {}",
+                    rel.name
+                )));
+            }
+            let (src, dst) = match rel.direction {
+                Direction::OUT => (last, next.clone()),
+                Direction::IN => (next.clone(), last),
+                Direction::BOTH => {
+                    return Err(ExecError::Unsupported(format!(
+                        "creating undirected relationship {}, relationships need a direction",
+                        rel.name
+                    )))
+                }
+            };
+            if rel.rel_types.len() > 1 {
+                return Err(ExecError::Unsupported(format!(
+                    "creating relationship {} with more than one type",
+                    rel.name
+                )));
+            }
+            if !self.edges.insert(rel.name.clone()) {
+                return Err(ExecError::Unsupported(format!(
+                    "variable {} is already bound",
+                    rel.name
+                )));
+            }
+            let props = self.props(rel.props);
+            edges.push(EdgeUpdate {
+                var_name: rel.name,
+                src,
+                dst,
+                layer: rel.rel_types.into_iter().next(),
+                props,
+            });
+            last = next;
+        }
+        Ok(())
+    }
+
+    /// Bind the node pattern, nodes that are not bound yet are added to `nodes`
+    fn node(
+        &mut self,
+        node: NodePattern,
+        nodes: &mut Vec<NodeUpdate>,
+    ) -> Result<String, ExecError> {
+        if self.nodes.contains(&node.name) {
+            if node.props.is_some() || !node.labels.is_empty() {
+                return Err(ExecError::Unsupported(format!(
+                    "variable {} is already bound, properties and labels can only be given to new nodes",
+                    node.name
+                )));
+            }
+            return Ok(node.name);
+        }
+
+        if node.labels.len() > 1 {
+            return Err(ExecError::Unsupported(format!(
+                "node {} with more than one label, nodes have a single type",
+                node.name
+            )));
+        }
+
+        let mut props = node.props.unwrap_or_default();
+        let id = props.remove("id").ok_or_else(|| {
+            ExecError::Unsupported(format!(
+                "node {} without an id property, new nodes are identified by their id",
+                node.name
+            ))
+        })?;
+        let id = self.value(id);
+        let props = self.props(Some(props));
+
+        self.nodes.insert(node.name.clone());
+        nodes.push(NodeUpdate {
+            var_name: node.name.clone(),
+            id,
+            node_type: node.labels.into_iter().next(),
+            props,
+        });
+        Ok(node.name)
+    }
+
+    fn props(&mut self, props: Option<HashMap<String, Expr>>) -> Vec<(String, usize)> {
+        props
+            .into_iter()
+            .flatten()
+            .map(|(key, expr)| (key, self.value(expr)))
+            .collect()
+    }
+
+    fn set_items(&mut self, items: Vec<SetItem>) -> Result<Vec<SetOp>, ExecError> {
+        let mut ops = vec![];
+        for item in items {
+            match item {
+                SetItem::Property {
+                    var_name,
+                    key,
+                    value,
+                } => {
+                    self.check_bound(&var_name)?;
+                    let value = self.value(value);
+                    ops.push(SetOp::Property {
+                        var_name,
+                        key,
+                        value,
+                    })
+                }
+                SetItem::Properties { var_name, props } => {
+                    self.check_bound(&var_name)?;
+                    for (key, value) in self.props(Some(props)) {
+                        ops.push(SetOp::Property {
+                            var_name: var_name.clone(),
+                            key,
+                            value,
+                        })
+                    }
+                }
+                SetItem::Labels { var_name, labels } => {
+                    if !self.nodes.contains(&var_name) {
+                        return Err(ExecError::Unsupported(format!(
+                            "SET {}:..., labels can only be set on bound nodes",
+                            var_name
+                        )));
+                    }
+                    let [node_type] = <[String; 1]>::try_from(labels).map_err(|_| {
+                        ExecError::Unsupported(format!(
+                            "SET {} with more than one label, nodes have a single type",
+                            var_name
+                        ))
+                    })?;
+                    ops.push(SetOp::NodeType {
+                        var_name,
+                        node_type,
+                    })
+                }
+            }
+        }
+        Ok(ops)
+    }
+}
+
+/// The nodes and edges bound for one matched row together with the values of its expressions
+struct Scope {
+    nodes: HashMap<String, GID>,
+    edges: HashMap<String, (GID, GID, String)>,
+    values: Vec<Option<Prop>>,
+}
+
+impl Scope {
+    fn new(values: Vec<Option<Prop>>) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+            values,
+        }
+    }
+
+    fn props(&self, props: &[(String, usize)]) -> Vec<(String, Prop)> {
+        props
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), self.values[*value].clone()?)))
+            .collect()
+    }
+
+    /// Add the changes of `update` for this row to `changes`
+    fn plan<G: CypherMutableGraph>(
+        &mut self,
+        update: &Update,
+        changes: &mut Changes<G>,
+    ) -> Result<(), ExecError> {
+        match update {
+            Update::Create { nodes, edges } => {
+                for node in nodes {
+                    self.add_node(node, changes)?;
+                }
+                for edge in edges {
+                    self.add_edge(edge, changes)?;
+                }
+            }
+            Update::Merge {
+                nodes,
+                edges,
+                on_create,
+                on_match,
+            } => {
+                let mut matched = true;
+                for node in nodes {
+                    let id = self.node_id(node)?;
+                    if changes.has_node(&id) {
+                        self.nodes.insert(node.var_name.clone(), id);
+                    } else {
+                        self.add_node(node, changes)?;
+                        matched = false;
+                    }
+                }
+                for edge in edges {
+                    let (src, dst) = (&self.nodes[&edge.src], &self.nodes[&edge.dst]);
+                    let layer = edge.layer.as_deref().unwrap_or("_default");
+                    if changes.has_edge(src, dst, layer) {
+                        let edge_ref = (src.clone(), dst.clone(), layer.to_string());
+                        self.edges.insert(edge.var_name.clone(), edge_ref);
+                    } else {
+                        self.add_edge(edge, changes)?;
+                        matched = false;
+                    }
+                }
+                let ops = if matched { on_match } else { on_create };
+                for op in ops {
+                    self.set(op, changes)?;
+                }
+            }
+            Update::Set(ops) => {
+                for op in ops {
+                    self.set(op, changes)?;
+                }
+            }
+            Update::Delete { var_names, detach } => {
+                for var_name in var_names {
+                    if let Some((src, dst, layer)) = self.edges.get(var_name) {
+                        changes.delete_edge(src, dst, layer)?;
+                    } else if let Some(node) = self.nodes.get(var_name) {
+                        changes.delete_node(var_name, node, *detach)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn node_id(&self, node: &NodeUpdate) -> Result<GID, ExecError> {
+        match &self.values[node.id] {
+            Some(Prop::I64(id)) if *id >= 0 => Ok(GID::U64(*id as u64)),
+            Some(Prop::U64(id)) => Ok(GID::U64(*id)),
+            Some(Prop::Str(id)) => Ok(GID::Str(id.to_string())),
+            id => Err(ExecError::Unsupported(format!(
+                "node id {:?} for {}, ids are non-negative integers or strings",
+                id, node.var_name
+            ))),
+        }
+    }
+
+    fn add_node<G: CypherMutableGraph>(
+        &mut self,
+        node: &NodeUpdate,
+        changes: &mut Changes<G>,
+    ) -> Result<(), ExecError> {
+        let id = self.node_id(node)?;
+        changes.add_node(&id, self.props(&node.props), node.node_type.as_deref())?;
+        self.nodes.insert(node.var_name.clone(), id);
+        Ok(())
+    }
+
+    fn add_edge<G: CypherMutableGraph>(
+        &mut self,
+        edge: &EdgeUpdate,
+        changes: &mut Changes<G>,
+    ) -> Result<(), ExecError> {
+        let (src, dst) = (&self.nodes[&edge.src], &self.nodes[&edge.dst]);
+        changes.add_edge(src, dst, edge.layer.as_deref(), self.props(&edge.props))?;
+        let layer = edge.layer.as_deref().unwrap_or("_default");
+        let edge_ref = (src.clone(), dst.clone(), layer.to_string());
+        self.edges.insert(edge.var_name.clone(), edge_ref);
+        Ok(())
+    }
+
+    fn set<G: CypherMutableGraph>(
+        &self,
+        op: &SetOp,
+        changes: &mut Changes<G>,
+    ) -> Result<(), ExecError> {
+        match op {
+            SetOp::Property {
+                var_name,
+                key,
+                value,
+            } => {
+                // setting a property to null is a no-op, properties can't be removed
+                let Some(value) = self.values[*value].clone() else {
+                    return Ok(());
+                };
+                let props = vec![(key.clone(), value)];
+                if let Some((src, dst, layer)) = self.edges.get(var_name) {
+                    if !changes.has_edge(src, dst, layer) {
+                        return Err(missing_entity(var_name));
+                    }
+                    changes.add_edge(src, dst, Some(layer), props)
+                } else {
+                    let node = self
+                        .nodes
+                        .get(var_name)
+                        .filter(|node| changes.has_node(node))
+                        .ok_or_else(|| missing_entity(var_name))?;
+                    changes.add_node(node, props, None)
+                }
+            }
+            SetOp::NodeType {
+                var_name,
+                node_type,
+            } => {
+                let node = self
+                    .nodes
+                    .get(var_name)
+                    .filter(|node| changes.has_node(node))
+                    .ok_or_else(|| missing_entity(var_name))?;
+                changes.set_node_type(node, node_type)
+            }
+        }
+    }
+}
+
+/// A change to the graph planned by the updating clauses
+#[derive(Debug)]
+enum GraphUpdate {
+    AddNode {
+        id: GID,
+        props: Vec<(String, Prop)>,
+        node_type: Option<String>,
+    },
+    AddEdge {
+        src: GID,
+        dst: GID,
+        layer: Option<String>,
+        props: Vec<(String, Prop)>,
+    },
+    SetNodeType {
+        id: GID,
+        node_type: String,
+    },
+    DeleteEdge {
+        src: GID,
+        dst: GID,
+        layer: String,
+    },
+    DeleteNode {
+        id: GID,
+    },
+}
+
+/// The changes of a query, checked against the graph and the earlier changes as they are planned
+/// so that they can all be applied once every row has been planned
+struct Changes<'a, G> {
+    graph: &'a G,
+    t: i64,
+    updates: Vec<GraphUpdate>,
+    stats: UpdateStats,
+    id_type: Option<GidType>,
+    /// Nodes and `(src, dst, layer)` edges added by earlier changes
+    nodes: HashSet<GID>,
+    edges: HashSet<(GID, GID, String)>,
+    /// Types of the nodes and of the properties that are new to the graph set by earlier changes
+    node_types: HashMap<GID, String>,
+    node_prop_types: HashMap<String, PropType>,
+    edge_prop_types: HashMap<String, PropType>,
+    deleted_edges: HashSet<(GID, GID, String)>,
+    /// Nodes deleted without DETACH, they can't have any edges left once all the changes are applied
+    attached_nodes: Vec<(String, GID)>,
+    deleted_nodes: HashSet<GID>,
+}
+
+impl<'a, G: CypherMutableGraph> Changes<'a, G> {
+    fn new(graph: &'a G, t: i64) -> Self {
+        Self {
+            graph,
+            t,
+            updates: vec![],
+            stats: UpdateStats::default(),
+            id_type: graph.id_type(),
+            nodes: HashSet::new(),
+            edges: HashSet::new(),
+            node_types: HashMap::new(),
+            node_prop_types: HashMap::new(),
+            edge_prop_types: HashMap::new(),
+            deleted_edges: HashSet::new(),
+            attached_nodes: vec![],
+            deleted_nodes: HashSet::new(),
+        }
+    }
+
+    fn has_node(&self, id: &GID) -> bool {
+        self.nodes.contains(id) || self.graph.node(id).is_some()
+    }
+
+    fn has_edge(&self, src: &GID, dst: &GID, layer: &str) -> bool {
+        self.edges
+            .contains(&(src.clone(), dst.clone(), layer.to_string()))
+            || self
+                .graph
+                .edge(src, dst)
+                .is_some_and(|edge| edge.layer_names().iter().any(|name| name == layer))
+    }
+
+    fn add_node(
+        &mut self,
+        id: &GID,
+        props: Vec<(String, Prop)>,
+        node_type: Option<&str>,
+    ) -> Result<(), ExecError> {
+        self.check_id(id)?;
+        self.check_props(&props, true)?;
+        if let Some(node_type) = node_type {
+            self.check_node_type(id, node_type)?;
+        }
+        if !self.has_node(id) {
+            self.stats.nodes_created += 1;
+            self.nodes.insert(id.clone());
+        }
+        self.stats.properties_set += props.len();
+        self.updates.push(GraphUpdate::AddNode {
+            id: id.clone(),
+            props,
+            node_type: node_type.map(|node_type| node_type.to_string()),
+        });
+        Ok(())
+    }
+
+    fn add_edge(
+        &mut self,
+        src: &GID,
+        dst: &GID,
+        layer: Option<&str>,
+        props: Vec<(String, Prop)>,
+    ) -> Result<(), ExecError> {
+        self.check_props(&props, false)?;
+        let layer_name = layer.unwrap_or("_default");
+        if !self.has_edge(src, dst, layer_name) {
+            self.stats.edges_created += 1;
+            self.edges
+                .insert((src.clone(), dst.clone(), layer_name.to_string()));
+        }
+        self.stats.properties_set += props.len();
+        self.updates.push(GraphUpdate::AddEdge {
+            src: src.clone(),
+            dst: dst.clone(),
+            layer: layer.map(|layer| layer.to_string()),
+            props,
+        });
+        Ok(())
+    }
+
+    fn set_node_type(&mut self, id: &GID, node_type: &str) -> Result<(), ExecError> {
+        self.check_node_type(id, node_type)?;
+        self.updates.push(GraphUpdate::SetNodeType {
+            id: id.clone(),
+            node_type: node_type.to_string(),
+        });
+        Ok(())
+    }
+
+    fn delete_edge(&mut self, src: &GID, dst: &GID, layer: &str) -> Result<(), ExecError> {
+        self.check_deletions()?;
+        // rows matching the same edge delete it once
+        if !self
+            .deleted_edges
+            .insert((src.clone(), dst.clone(), layer.to_string()))
+        {
+            return Ok(());
+        }
+        self.stats.edges_deleted += 1;
+        self.updates.push(GraphUpdate::DeleteEdge {
+            src: src.clone(),
+            dst: dst.clone(),
+            layer: layer.to_string(),
+        });
+        Ok(())
+    }
+
+    fn delete_node(&mut self, var_name: &str, id: &GID, detach: bool) -> Result<(), ExecError> {
+        self.check_deletions()?;
+        if !detach {
+            self.attached_nodes.push((var_name.to_string(), id.clone()));
+        }
+        if !self.deleted_nodes.insert(id.clone()) {
+            return Ok(());
+        }
+        self.stats.nodes_deleted += 1;
+        self.updates
+            .push(GraphUpdate::DeleteNode { id: id.clone() });
+        Ok(())
+    }
+
+    fn check_deletions(&self) -> Result<(), ExecError> {
+        if self.graph.supports_deletions() {
+            Ok(())
+        } else {
+            Err(deletions_unsupported())
+        }
+    }
+
+    /// New nodes need ids of the same type as the nodes of the graph
+    fn check_id(&mut self, id: &GID) -> Result<(), ExecError> {
+        match self.id_type {
+            Some(id_type) if id_type != id.dtype() => Err(ExecError::Unsupported(format!(
+                "node id {} of type {}, the graph has {} ids",
+                id,
+                id.dtype(),
+                id_type
+            ))),
+            Some(_) => Ok(()),
+            None => {
+                self.id_type = Some(id.dtype());
+                Ok(())
+            }
+        }
+    }
+
+    /// Properties keep the type they were first added with
+    fn check_props(&mut self, props: &[(String, Prop)], is_node: bool) -> Result<(), ExecError> {
+        let graph = self.graph;
+        let (meta, prop_types) = if is_node {
+            (graph.node_meta(), &mut self.node_prop_types)
+        } else {
+            (graph.edge_meta(), &mut self.edge_prop_types)
+        };
+        for (name, prop) in props {
+            let actual = prop.dtype();
+            let expected = meta
+                .get_prop_id(name, false)
+                .and_then(|id| meta.temporal_prop_meta().get_dtype(id))
+                .filter(|dtype| *dtype != PropType::Empty)
+                .or_else(|| prop_types.get(name).copied());
+            match expected {
+                Some(expected) if expected != actual => {
+                    return Err(GraphError::from(PropError::PropertyTypeError {
+                        name: name.clone(),
+                        expected,
+                        actual,
+                    })
+                    .into())
+                }
+                Some(_) => {}
+                None => {
+                    prop_types.insert(name.clone(), actual);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Nodes can be given a type once
+    fn check_node_type(&mut self, id: &GID, node_type: &str) -> Result<(), ExecError> {
+        if node_type == "_default" {
+            return Err(GraphError::NodeTypeError(
+                "_default type is not allowed to be used on nodes".to_string(),
+            )
+            .into());
+        }
+        let current = self.node_types.get(id).cloned().or_else(|| {
+            self.graph
+                .node(id)
+                .and_then(|node| node.node_type())
+                .map(|node_type| node_type.to_string())
+        });
+        match current {
+            Some(current) if current != node_type => {
+                Err(GraphError::NodeTypeError("Cannot change node type".to_string()).into())
+            }
+            _ => {
+                self.node_types.insert(id.clone(), node_type.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether the node has edges at the time of the changes once they are applied
+    fn has_edges_after(&self, id: &GID) -> bool {
+        let edges = self
+            .graph
+            .at(self.t)
+            .node(id)
+            .map(|node| {
+                node.edges()
+                    .explode_layers()
+                    .iter()
+                    .filter_map(|edge| {
+                        let layer = edge.layer_name().ok()?.to_string();
+                        Some((edge.src().id(), edge.dst().id(), layer))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let new_edges = self
+            .edges
+            .iter()
+            .filter(|(src, dst, _)| src == id || dst == id)
+            .cloned();
+        edges
+            .into_iter()
+            .chain(new_edges)
+            .any(|edge| !self.deleted_edges.contains(&edge))
+    }
+
+    fn apply(self) -> Result<UpdateStats, ExecError> {
+        for (var_name, id) in self.attached_nodes.iter() {
+            if self.has_edges_after(id) {
+                return Err(ExecError::Unsupported(format!(
+                    "DELETE {}, the node still has relationships, use DETACH DELETE",
+                    var_name
+                )));
+            }
+        }
+
+        let (graph, t) = (self.graph, self.t);
+        for update in self.updates {
+            match update {
+                GraphUpdate::AddNode {
+                    id,
+                    props,
+                    node_type,
+                } => {
+                    graph.add_node(t, &id, props, node_type.as_deref())?;
+                }
+                GraphUpdate::AddEdge {
+                    src,
+                    dst,
+                    layer,
+                    props,
+                } => {
+                    graph.add_edge(t, &src, &dst, props, layer.as_deref())?;
+                }
+                GraphUpdate::SetNodeType { id, node_type } => {
+                    graph
+                        .node(&id)
+                        .ok_or_else(|| GraphError::NodeMissingError(id.clone()))?
+                        .set_node_type(&node_type)?;
+                }
+                GraphUpdate::DeleteEdge { src, dst, layer } => {
+                    graph.delete_edge_at(t, &src, &dst, &layer)?;
+                }
+                GraphUpdate::DeleteNode { id } => {
+                    graph.delete_node_at(t, &id)?;
+                }
+            }
+        }
+        Ok(self.stats)
+    }
+}
+
+fn deletions_unsupported() -> ExecError {
+    ExecError::Unsupported(
+        "DELETE on an event graph, deletions require a PersistentGraph".to_string(),
+    )
+}
+
+fn missing_entity(var_name: &str) -> ExecError {
+    ExecError::Unsupported(format!("{} is no longer part of the graph", var_name))
+}
+
+fn missing_column() -> ExecError {
+    ExecError::Unsupported("missing column in the result of the MATCH query".to_string())
+}
+
+/// The global id of the node with the id at `row` of an array produced by the MATCH query
+fn gid_at<G: CypherMutableGraph>(
+    graph: &G,
+    col: Option<&ArrayRef>,
+    row: usize,
+) -> Result<GID, ExecError> {
+    let vid = VID(id_at(col, row)?);
+    graph
+        .node(vid)
+        .map(|node| node.id())
+        .ok_or_else(|| ExecError::Unsupported(format!("node {:?} is not part of the graph", vid)))
+}
+
+fn id_at(col: Option<&ArrayRef>, row: usize) -> Result<usize, ExecError> {
+    match prop_at(col.ok_or_else(missing_column)?, row)? {
+        Some(Prop::U64(id)) => Ok(id as usize),
+        id => Err(ExecError::Unsupported(format!(
+            "unexpected id {:?} in the result of the MATCH query",
+            id
+        ))),
+    }
+}
+
+/// The value at `row` of an array produced by the MATCH query
+fn prop_at(col: &ArrayRef, row: usize) -> Result<Option<Prop>, ExecError> {
+    if col.is_null(row) {
+        return Ok(None);
+    }
+    let prop = match col.data_type() {
+        DataType::Boolean => Prop::Bool(col.as_boolean().value(row)),
+        DataType::Int32 => Prop::I32(col.as_primitive::<Int32Type>().value(row)),
+        DataType::Int64 => Prop::I64(col.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt32 => Prop::U32(col.as_primitive::<UInt32Type>().value(row)),
+        DataType::UInt64 => Prop::U64(col.as_primitive::<UInt64Type>().value(row)),
+        DataType::Float32 => Prop::F32(col.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => Prop::F64(col.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => Prop::str(col.as_string::<i32>().value(row)),
+        DataType::LargeUtf8 => Prop::str(col.as_string::<i64>().value(row)),
+        dt => {
+            return Err(ExecError::Unsupported(format!(
+                "property values of type {}",
+                dt
+            )))
+        }
+    };
+    Ok(Some(prop))
+}
+
+/// Values that don't depend on the matched rows, [`eval_literal`] evaluates them
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(_) => true,
+        Expr::Nested(expr) => is_literal(expr),
+        Expr::UnaryOp {
+            op: UnaryOpType::Neg,
+            expr,
+        } => is_literal(expr),
+        _ => false,
+    }
+}
+
+/// The properties of the bound nodes of a pattern as conditions of the WHERE clause, so that the
+/// nodes keep their name in the query
+fn bound_props_to_where(m: &Match) -> Match {
+    let mut m = m.clone();
+    let mut conditions = vec![];
+    for part in m.pattern.0.iter_mut() {
+        let nodes =
+            std::iter::once(&mut part.node).chain(part.rel_chain.iter_mut().map(|(_, n)| n));
+        for node in nodes.filter(|node| !node.anonymous) {
+            for (key, value) in node.props.take().into_iter().flatten() {
+                conditions.push(Expr::eq(Expr::var(&node.name, [key]), value));
+            }
+        }
+    }
+    m.where_clause = conditions
+        .into_iter()
+        .chain(m.where_clause)
+        .reduce(Expr::and);
+    m
+}
+
+/// Evaluate a value of a query without MATCH clauses, only literals can be used
+fn eval_literal(expr: &Expr) -> Result<Option<Prop>, ExecError> {
+    match expr {
+        Expr::Literal(literal) => literal_to_prop(literal),
+        Expr::Nested(expr) => eval_literal(expr),
+        Expr::UnaryOp {
+            op: UnaryOpType::Neg,
+            expr,
+        } => match eval_literal(expr)? {
+            Some(Prop::I64(i)) => Ok(Some(Prop::I64(-i))),
+            Some(Prop::F64(f)) => Ok(Some(Prop::F64(-f))),
+            prop => Err(ExecError::Unsupported(format!("negating {:?}", prop))),
+        },
+        expr => Err(ExecError::Unsupported(format!(
+            "expression {:?} in a query without MATCH, only literals can be used",
+            expr
+        ))),
+    }
+}
+
+fn literal_to_prop(literal: &Literal) -> Result<Option<Prop>, ExecError> {
+    let prop = match literal {
+        Literal::Null => return Ok(None),
+        Literal::Bool(b) => Prop::Bool(*b),
+        Literal::Str(s) => Prop::str(s.as_str()),
+        Literal::Int(i) => Prop::I64(*i),
+        Literal::Float(f) => Prop::F64(*f),
+        Literal::List(items) => Prop::List(
+            items
+                .iter()
+                .filter_map(|item| literal_to_prop(item).transpose())
+                .collect::<Result<Vec<_>, _>>()?
+                .into(),
+        ),
+    };
+    Ok(Some(prop))
+}
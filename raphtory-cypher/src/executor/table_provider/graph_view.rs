@@ -0,0 +1,500 @@
+use std::{any::Any, fmt::Formatter, sync::Arc};
+
+use arrow::datatypes::*;
+use arrow_array::{
+    ArrayRef, BooleanArray, Float32Array, Float64Array, Int32Array, Int64Array, PrimitiveArray,
+    RecordBatch, StringArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow_schema::{Field, Fields, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion::{
+    common::Statistics,
+    config::ConfigOptions,
+    datasource::{TableProvider, TableType},
+    error::DataFusionError,
+    execution::{context::SessionState, SendableRecordBatchStream, TaskContext},
+    logical_expr::Expr,
+    physical_plan::{
+        metrics::MetricsSet, stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType,
+        ExecutionPlan, PlanProperties,
+    },
+};
+use itertools::Itertools;
+use raphtory::{
+    core::{entities::properties::props::Meta, PropType, PropUnwrap},
+    db::api::view::StaticGraphViewOps,
+    prelude::*,
+};
+
+use crate::{executor::ExecError, transpiler::EdgeTables};
+
+use super::plan_properties;
+
+/// Number of rows in each record batch produced by the scans
+const BATCH_SIZE: usize = 100_000;
+
+/// Tables exposing any graph view to DataFusion
+///
+/// There is one edge table per layer in the view, named after the layer, with one row per edge
+/// update in the view and a `nodes` table with one row per node in the view. Property columns hold
+/// the value at the time of the update for edges and the latest value in the view for nodes.
+#[derive(Clone)]
+pub struct GraphViewTables<G> {
+    graph: G,
+    layer_names: Vec<String>,
+    edge_schema: SchemaRef,
+    node_schema: SchemaRef,
+}
+
+impl<G: StaticGraphViewOps> GraphViewTables<G> {
+    pub fn new(graph: G) -> Self {
+        let layer_names = graph.unique_layers().map(|name| name.to_string()).collect();
+        let edge_schema = edge_schema(graph.edge_meta());
+        let node_schema = node_schema(&graph);
+        Self {
+            graph,
+            layer_names,
+            edge_schema,
+            node_schema,
+        }
+    }
+
+    pub fn layer_names(&self) -> &[String] {
+        &self.layer_names
+    }
+
+    pub fn edge_table(&self, layer_name: &str) -> Result<GraphViewEdgeTableProvider<G>, ExecError> {
+        let layer_id = self
+            .layer_names
+            .iter()
+            .any(|name| name == layer_name)
+            .then(|| self.graph.edge_meta().get_layer_id(layer_name))
+            .flatten()
+            .ok_or_else(|| ExecError::LayerNotFound(layer_name.to_string()))?;
+        Ok(GraphViewEdgeTableProvider {
+            graph: self.graph.clone(),
+            layer_id,
+            layer_name: layer_name.to_string(),
+            schema: self.edge_schema.clone(),
+        })
+    }
+
+    pub fn node_table(&self) -> GraphViewNodeTableProvider<G> {
+        GraphViewNodeTableProvider {
+            graph: self.graph.clone(),
+            schema: self.node_schema.clone(),
+        }
+    }
+}
+
+impl<G: StaticGraphViewOps> EdgeTables for GraphViewTables<G> {
+    fn layer_names(&self) -> Vec<String> {
+        self.layer_names.clone()
+    }
+
+    fn layer_fields(&self, layer_name: &str) -> Option<Fields> {
+        self.layer_names
+            .iter()
+            .any(|name| name == layer_name)
+            .then(|| self.edge_schema.fields().clone())
+    }
+}
+
+fn edge_schema(meta: &Meta) -> SchemaRef {
+    let mut fields = vec![
+        Field::new("id", DataType::UInt64, false),
+        Field::new("layer_id", DataType::UInt64, false),
+        Field::new("src", DataType::UInt64, false),
+        Field::new("dst", DataType::UInt64, false),
+        Field::new("time", DataType::Int64, false),
+    ];
+    fields.extend(property_fields(meta));
+    Arc::new(Schema::new(fields))
+}
+
+fn node_schema<G: StaticGraphViewOps>(graph: &G) -> SchemaRef {
+    let gid_dt = if graph
+        .nodes()
+        .iter()
+        .all(|node| matches!(node.id(), GID::U64(_)))
+    {
+        DataType::UInt64
+    } else {
+        DataType::Utf8
+    };
+    let mut fields = vec![
+        Field::new("id", DataType::UInt64, false),
+        Field::new("gid", gid_dt, false),
+    ];
+    fields.extend(property_fields(graph.node_meta()));
+    Arc::new(Schema::new(fields))
+}
+
+/// One nullable column for every property, temporal properties take precedence over constant
+/// properties with the same name
+fn property_fields(meta: &Meta) -> Vec<Field> {
+    let mut fields: Vec<Field> = vec![];
+    for prop_meta in [meta.temporal_prop_meta(), meta.const_prop_meta()] {
+        for (id, name) in prop_meta.get_keys().iter().enumerate() {
+            if fields.iter().all(|field| field.name() != name.as_ref()) {
+                let dtype = prop_meta.get_dtype(id).unwrap_or(PropType::Empty);
+                fields.push(Field::new(name.as_ref(), arrow_dtype(dtype), true));
+            }
+        }
+    }
+    fields
+}
+
+/// Property types that don't have an arrow equivalent are exposed as strings
+fn arrow_dtype(prop_type: PropType) -> DataType {
+    match prop_type {
+        PropType::U8 => DataType::UInt8,
+        PropType::U16 => DataType::UInt16,
+        PropType::I32 => DataType::Int32,
+        PropType::I64 => DataType::Int64,
+        PropType::U32 => DataType::UInt32,
+        PropType::U64 => DataType::UInt64,
+        PropType::F32 => DataType::Float32,
+        PropType::F64 => DataType::Float64,
+        PropType::Bool => DataType::Boolean,
+        _ => DataType::Utf8,
+    }
+}
+
+fn prop_column(dtype: &DataType, values: Vec<Option<Prop>>) -> ArrayRef {
+    let values = values.into_iter();
+    match dtype {
+        DataType::UInt8 => Arc::new(values.map(|v| v.into_u8()).collect::<UInt8Array>()),
+        DataType::UInt16 => Arc::new(values.map(|v| v.into_u16()).collect::<UInt16Array>()),
+        DataType::Int32 => Arc::new(values.map(|v| v.into_i32()).collect::<Int32Array>()),
+        DataType::Int64 => Arc::new(values.map(|v| v.into_i64()).collect::<Int64Array>()),
+        DataType::UInt32 => Arc::new(values.map(|v| v.into_u32()).collect::<UInt32Array>()),
+        DataType::UInt64 => Arc::new(values.map(|v| v.into_u64()).collect::<UInt64Array>()),
+        DataType::Float32 => Arc::new(values.map(|v| v.into_f32()).collect::<Float32Array>()),
+        DataType::Float64 => Arc::new(values.map(|v| v.into_f64()).collect::<Float64Array>()),
+        DataType::Boolean => Arc::new(values.map(|v| v.into_bool()).collect::<BooleanArray>()),
+        _ => Arc::new(
+            values
+                .map(|v| match v {
+                    Some(Prop::Str(s)) => Some(s.to_string()),
+                    v => v.map(|v| v.to_string()),
+                })
+                .collect::<StringArray>(),
+        ),
+    }
+}
+
+fn gid_column(dtype: &DataType, gids: Vec<GID>) -> ArrayRef {
+    match dtype {
+        DataType::UInt64 => Arc::new(gids.iter().map(|gid| gid.as_u64()).collect::<UInt64Array>()),
+        _ => Arc::new(
+            gids.iter()
+                .map(|gid| Some(gid.to_str()))
+                .collect::<StringArray>(),
+        ),
+    }
+}
+
+fn project_schema(
+    schema: &SchemaRef,
+    projection: Option<&Vec<usize>>,
+) -> Result<(SchemaRef, Arc<[usize]>), DataFusionError> {
+    match projection {
+        Some(proj) => Ok((Arc::new(schema.project(proj)?), Arc::from(proj.as_slice()))),
+        None => Ok((schema.clone(), (0..schema.fields().len()).collect())),
+    }
+}
+
+fn make_record_batch(
+    schema: &SchemaRef,
+    columns: Vec<ArrayRef>,
+    num_rows: usize,
+) -> Result<RecordBatch, DataFusionError> {
+    let options = arrow_array::RecordBatchOptions::new().with_row_count(Some(num_rows));
+    RecordBatch::try_new_with_options(schema.clone(), columns, &options)
+        .map_err(|arrow_err| DataFusionError::ArrowError(arrow_err, None))
+}
+
+pub struct GraphViewEdgeTableProvider<G> {
+    graph: G,
+    layer_id: usize,
+    layer_name: String,
+    schema: SchemaRef,
+}
+
+#[async_trait]
+impl<G: StaticGraphViewOps> TableProvider for GraphViewEdgeTableProvider<G> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        let (schema, projection) = project_schema(&self.schema, projection)?;
+        Ok(Arc::new(GraphViewScanExecPlan {
+            scan: Scan::Edges {
+                graph: self.graph.clone(),
+                layer_id: self.layer_id,
+                layer_name: self.layer_name.clone(),
+                full_schema: self.schema.clone(),
+            },
+            props: plan_properties(schema.clone(), 1),
+            schema,
+            projection,
+        }))
+    }
+}
+
+pub struct GraphViewNodeTableProvider<G> {
+    graph: G,
+    schema: SchemaRef,
+}
+
+#[async_trait]
+impl<G: StaticGraphViewOps> TableProvider for GraphViewNodeTableProvider<G> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        let (schema, projection) = project_schema(&self.schema, projection)?;
+        Ok(Arc::new(GraphViewScanExecPlan {
+            scan: Scan::Nodes {
+                graph: self.graph.clone(),
+                full_schema: self.schema.clone(),
+            },
+            props: plan_properties(schema.clone(), 1),
+            schema,
+            projection,
+        }))
+    }
+}
+
+#[derive(Clone)]
+enum Scan<G> {
+    Edges {
+        graph: G,
+        layer_id: usize,
+        layer_name: String,
+        full_schema: SchemaRef,
+    },
+    Nodes {
+        graph: G,
+        full_schema: SchemaRef,
+    },
+}
+
+impl<G: StaticGraphViewOps> Scan<G> {
+    fn record_batches(
+        &self,
+        schema: SchemaRef,
+        projection: Arc<[usize]>,
+    ) -> Result<Vec<RecordBatch>, DataFusionError> {
+        match self {
+            Scan::Edges {
+                graph,
+                layer_id,
+                layer_name,
+                full_schema,
+            } => {
+                let layer = graph
+                    .layers(layer_name.as_str())
+                    .map_err(|err| DataFusionError::External(Box::new(err)))?;
+                let edges = layer.edges().explode().into_iter();
+                let chunks = edges.chunks(BATCH_SIZE);
+                let mut batches = vec![];
+                let mut row_id = 0u64;
+                for chunk in &chunks {
+                    let chunk = chunk.collect::<Vec<_>>();
+                    let num_rows = chunk.len();
+                    let columns = projection
+                        .iter()
+                        .map(|&col| -> ArrayRef {
+                            match col {
+                                0 => Arc::new(UInt64Array::from_iter_values(
+                                    row_id..row_id + num_rows as u64,
+                                )),
+                                1 => Arc::new(UInt64Array::from_value(*layer_id as u64, num_rows)),
+                                2 => Arc::new(UInt64Array::from_iter_values(
+                                    chunk.iter().map(|e| e.src().node.0 as u64),
+                                )),
+                                3 => Arc::new(UInt64Array::from_iter_values(
+                                    chunk.iter().map(|e| e.dst().node.0 as u64),
+                                )),
+                                4 => Arc::new(PrimitiveArray::<Int64Type>::from_iter_values(
+                                    chunk.iter().map(|e| e.edge.time_t().unwrap_or_default()),
+                                )),
+                                col => {
+                                    let field = full_schema.field(col);
+                                    let values = chunk
+                                        .iter()
+                                        .map(|e| e.properties().get(field.name()))
+                                        .collect();
+                                    prop_column(field.data_type(), values)
+                                }
+                            }
+                        })
+                        .collect();
+                    batches.push(make_record_batch(&schema, columns, num_rows)?);
+                    row_id += num_rows as u64;
+                }
+                Ok(batches)
+            }
+            Scan::Nodes { graph, full_schema } => {
+                let nodes = graph.nodes().into_iter();
+                let chunks = nodes.chunks(BATCH_SIZE);
+                let mut batches = vec![];
+                for chunk in &chunks {
+                    let chunk = chunk.collect::<Vec<_>>();
+                    let num_rows = chunk.len();
+                    let columns = projection
+                        .iter()
+                        .map(|&col| -> ArrayRef {
+                            match col {
+                                0 => Arc::new(UInt64Array::from_iter_values(
+                                    chunk.iter().map(|n| n.node.0 as u64),
+                                )),
+                                1 => gid_column(
+                                    full_schema.field(col).data_type(),
+                                    chunk.iter().map(|n| n.id()).collect(),
+                                ),
+                                col => {
+                                    let field = full_schema.field(col);
+                                    let values = chunk
+                                        .iter()
+                                        .map(|n| n.properties().get(field.name()))
+                                        .collect();
+                                    prop_column(field.data_type(), values)
+                                }
+                            }
+                        })
+                        .collect();
+                    batches.push(make_record_batch(&schema, columns, num_rows)?);
+                }
+                Ok(batches)
+            }
+        }
+    }
+}
+
+struct GraphViewScanExecPlan<G> {
+    scan: Scan<G>,
+    schema: SchemaRef,
+    props: PlanProperties,
+    projection: Arc<[usize]>,
+}
+
+impl<G> GraphViewScanExecPlan<G> {
+    fn table_name(&self) -> &str {
+        match &self.scan {
+            Scan::Edges { layer_name, .. } => layer_name,
+            Scan::Nodes { .. } => "nodes",
+        }
+    }
+}
+
+impl<G> std::fmt::Debug for GraphViewScanExecPlan<G> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "GraphViewScanExecPlan[table={:?}]", self.table_name())
+    }
+}
+
+impl<G> DisplayAs for GraphViewScanExecPlan<G> {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "GraphViewScanExecPlan[table={:?}]", self.table_name())
+    }
+}
+
+#[async_trait]
+impl<G: StaticGraphViewOps> ExecutionPlan for GraphViewScanExecPlan<G> {
+    fn name(&self) -> &str {
+        "GraphViewScanExecPlan"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.props
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        Ok(self)
+    }
+
+    fn repartitioned(
+        &self,
+        _target_partitions: usize,
+        _config: &ConfigOptions,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>, DataFusionError> {
+        Ok(None)
+    }
+
+    fn execute(
+        &self,
+        _partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream, DataFusionError> {
+        let scan = self.scan.clone();
+        let schema = self.schema.clone();
+        let projection = self.projection.clone();
+        let stream = futures::stream::once(async move {
+            let batches = scan.record_batches(schema, projection);
+            futures::stream::iter(batches.map_or_else(
+                |err| vec![Err(err)],
+                |batches| batches.into_iter().map(Ok).collect(),
+            ))
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            futures::StreamExt::flatten(stream),
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    fn statistics(&self) -> Result<Statistics, DataFusionError> {
+        Ok(Statistics::new_unknown(&self.schema()))
+    }
+}
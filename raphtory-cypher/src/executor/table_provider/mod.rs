@@ -1,8 +1,12 @@
 use arrow_schema::SchemaRef;
 use datafusion::{physical_expr::EquivalenceProperties, physical_plan::PlanProperties};
 
+#[cfg(feature = "storage")]
 pub mod edge;
+pub mod graph_view;
+#[cfg(feature = "storage")]
 pub mod node;
+
 pub fn plan_properties(schema: SchemaRef, num_partitions: usize) -> PlanProperties {
     let eq_properties = EquivalenceProperties::new(schema);
     let partitioning = datafusion::physical_plan::Partitioning::UnknownPartitioning(num_partitions);
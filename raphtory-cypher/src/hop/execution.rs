@@ -1,6 +1,6 @@
 use std::{
     any::Any,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt,
     ops::Range,
     pin::Pin,
@@ -40,7 +40,10 @@ use futures::{Stream, StreamExt};
 use crate::take_record_batch;
 use pometry_storage::graph_fragment::TempColGraphFragment;
 use raphtory::{
-    core::{entities::VID, Direction},
+    core::{
+        entities::{EID, VID},
+        Direction,
+    },
     disk_graph::{
         prelude::{ArrayOps, BaseArrayOps, PrimitiveCol},
         DiskGraphStorage,
@@ -54,6 +57,8 @@ pub struct HopExec {
     graph: DiskGraphStorage,
     dir: Direction,
     input_col: usize,
+    right_col: String,
+    right_fields: Option<Vec<String>>,
     input: Arc<dyn ExecutionPlan>,
     layers: Vec<String>,
     right_schema: DFSchemaRef,
@@ -64,7 +69,10 @@ pub struct HopExec {
 }
 
 // we assume to be chaining the hops so we need to find the last input column
-fn find_last_input_col(hop: &HopPlan, input: &Arc<dyn ExecutionPlan>) -> usize {
+fn find_last_input_col(
+    hop: &HopPlan,
+    input: &Arc<dyn ExecutionPlan>,
+) -> Result<usize, DataFusionError> {
     let mut input_col = None;
 
     for (id, field) in input.schema().fields().iter().enumerate() {
@@ -72,17 +80,27 @@ fn find_last_input_col(hop: &HopPlan, input: &Arc<dyn ExecutionPlan>) -> usize {
             input_col = Some(id);
         }
     }
-    let input_col = input_col.expect("failed to find the input column in the input schema");
-    input_col
+    input_col.ok_or_else(|| {
+        DataFusionError::Plan(format!(
+            "failed to find the input column {} in the input schema",
+            hop.left_col
+        ))
+    })
 }
 
 impl HopExec {
-    pub fn new(hop: &HopPlan, physical_inputs: &[Arc<dyn ExecutionPlan>]) -> Self {
+    pub fn new(
+        hop: &HopPlan,
+        physical_inputs: &[Arc<dyn ExecutionPlan>],
+    ) -> Result<Self, DataFusionError> {
         let graph = hop.graph();
         let dir = hop.dir;
-        let input = physical_inputs[0].clone();
+        let input = physical_inputs
+            .first()
+            .ok_or_else(|| DataFusionError::Plan("Hop requires an input plan".to_string()))?
+            .clone();
 
-        let input_col = find_last_input_col(hop, &input);
+        let input_col = find_last_input_col(hop, &input)?;
 
         let out_schema: Arc<Schema> = Arc::new(hop.out_schema.as_ref().into());
         let props = plan_properties(
@@ -90,10 +108,12 @@ impl HopExec {
             input.properties().output_partitioning().partition_count(),
         );
 
-        Self {
+        Ok(Self {
             graph,
             dir,
             input_col,
+            right_col: hop.right_col.clone(),
+            right_fields: hop.right_fields.clone(),
             input,
             right_schema: hop.right_schema.clone(),
             layers: hop.right_layers.clone(),
@@ -101,7 +121,7 @@ impl HopExec {
             output_schema: out_schema,
             props,
             right_proj: hop.right_proj.clone(),
-        }
+        })
     }
 }
 
@@ -149,6 +169,8 @@ impl ExecutionPlan for HopExec {
             graph: self.graph.clone(),
             dir: self.dir,
             input_col: self.input_col,
+            right_col: self.right_col.clone(),
+            right_fields: self.right_fields.clone(),
             input: children[0].clone(),
             layers: self.layers.clone(),
             right_schema: self.right_schema.clone(),
@@ -170,6 +192,8 @@ impl ExecutionPlan for HopExec {
             self.graph.clone(),
             self.dir,
             self.input_col,
+            self.right_col.clone(),
+            self.right_fields.clone(),
             batch_size,
             self.layers.clone(),
             self.right_schema.clone(),
@@ -183,6 +207,8 @@ pub(crate) struct HopStream {
     graph: DiskGraphStorage,
     dir: Direction,
     input_col: usize,
+    right_col: String,
+    right_fields: Option<Vec<String>>,
     batch_size: usize,
     layers: Vec<String>,
     right_schema: DFSchemaRef,
@@ -197,6 +223,8 @@ impl HopStream {
         graph: DiskGraphStorage,
         dir: Direction,
         input_col: usize,
+        right_col: String,
+        right_fields: Option<Vec<String>>,
         batch_size: usize,
         layers: Vec<String>,
         right_schema: DFSchemaRef,
@@ -208,6 +236,8 @@ impl HopStream {
             graph,
             dir,
             input_col,
+            right_col,
+            right_fields,
             batch_size,
             layers,
             right_schema,
@@ -281,7 +311,10 @@ impl Stream for HopStream {
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let window_size: usize = self.batch_size;
 
+        let dir = self.dir;
         let input_col = self.input_col;
+        let right_col = self.right_col.clone();
+        let right_fields = self.right_fields.clone();
         let graph = self.graph.clone();
         let layers = self.layers.clone();
         let output_schema = self.output_schema.clone();
@@ -305,7 +338,10 @@ impl Stream for HopStream {
                 layer,
                 window_size,
                 last_node,
+                dir,
                 input_col,
+                &right_col,
+                right_fields.as_deref(),
                 &graph,
                 layers,
                 output_schema,
@@ -333,6 +369,31 @@ impl Stream for HopStream {
     }
 }
 
+/// The edges of `v_id` in the direction of the hop, the node at the other end and whether the edge
+/// goes out of `v_id`, starting at `from`
+///
+/// Hopping in both directions scans the outgoing edges and then the incoming ones, self loops are
+/// only returned once like in the undirected edge tables of the transpiler.
+fn hop_edges(
+    layer: &TempColGraphFragment,
+    dir: Direction,
+    v_id: VID,
+    from: usize,
+) -> impl Iterator<Item = (EID, VID, bool)> + '_ {
+    let out_edges = matches!(dir, Direction::OUT | Direction::BOTH)
+        .then(|| layer.nodes_storage().out_adj_list(v_id))
+        .into_iter()
+        .flatten()
+        .map(|(e_id, u_id)| (e_id, u_id, true));
+    let in_edges = matches!(dir, Direction::IN | Direction::BOTH)
+        .then(|| layer.nodes_storage().in_adj_list(v_id))
+        .into_iter()
+        .flatten()
+        .filter(move |(_, u_id)| dir != Direction::BOTH || *u_id != v_id)
+        .map(|(e_id, u_id)| (e_id, u_id, false));
+    out_edges.chain(in_edges).skip(from)
+}
+
 // FIXME: this will fail when prev_node (dst) is the same but the src node changes
 // because we're not resetting the edge and time positions
 fn produce_next_record(
@@ -345,7 +406,10 @@ fn produce_next_record(
 
     prev_node: &mut Option<VID>,
 
+    dir: Direction,
     input_col: usize,
+    right_col: &str,
+    right_fields: Option<&[String]>,
     graph: &DiskGraphStorage,
     layers: Vec<String>,
     output_schema: SchemaRef,
@@ -371,6 +435,8 @@ fn produce_next_record(
 
     let mut take_indices = Vec::with_capacity(max_record_rows);
     let mut edge_timestamps = Vec::with_capacity(max_record_rows);
+    let mut nbr_indices = Vec::with_capacity(max_record_rows);
+    let mut src_indices = Vec::with_capacity(max_record_rows);
     let mut dst_indices = Vec::with_capacity(max_record_rows);
 
     let mut edge_ids = Vec::with_capacity(max_record_rows);
@@ -432,10 +498,10 @@ fn produce_next_record(
     let min_layer_pos = *layer_pos;
     'top: for (layer_id, layer) in &layers[*layer_pos..] {
         for (col_id, v_id) in hop_col.into_iter().map(|n| VID(*n as usize)).enumerate() {
-            for (edge, u_id) in layer
-                .out_edges_from(v_id, *edge_pos)
-                .map(|(e_id, u_id)| (graph.edge(e_id), u_id))
+            for (edge, u_id, outgoing) in hop_edges(layer, dir, v_id, *edge_pos)
+                .map(|(e_id, u_id, outgoing)| (graph.edge(e_id), u_id, outgoing))
             {
+                let (src, dst) = if outgoing { (v_id, u_id) } else { (u_id, v_id) };
                 let slice = edge.timestamp_slice(*layer_id);
                 let time_slice = slice.slice(*time_pos..);
                 let start = time_slice.range().start;
@@ -443,7 +509,9 @@ fn produce_next_record(
                 for t in time_slice {
                     take_indices.push(col_id as u64);
                     edge_timestamps.push(t);
-                    dst_indices.push(u_id.0 as u64);
+                    nbr_indices.push(u_id.0 as u64);
+                    src_indices.push(src.0 as u64);
+                    dst_indices.push(dst.0 as u64);
                     edge_ids.push(end as u64);
                     layer_ids.push(*layer_id as u64);
 
@@ -519,12 +587,15 @@ fn produce_next_record(
     }
 
     let take_indices = UInt64Array::from(take_indices);
-    let left_rb = take_record_batch(&rb, &take_indices).expect("take failed");
+    let left_rb = match take_record_batch(&rb, &take_indices) {
+        Ok(left_rb) => left_rb,
+        Err(err) => return Some(Err(err.into())),
+    };
 
-    let src_ids = left_rb.column(input_col).clone();
+    let src_ids: ArrayRef = Arc::new(UInt64Array::from(src_indices));
+    let dst_ids: ArrayRef = Arc::new(UInt64Array::from(dst_indices));
 
     let edge_timestamps = Arc::new(Int64Array::from(edge_timestamps));
-    let dst_ids = Arc::new(UInt64Array::from(dst_indices));
     let edge_ids = Arc::new(UInt64Array::from(edge_ids));
     let layer_ids = Arc::new(UInt64Array::from(layer_ids));
 
@@ -541,7 +612,7 @@ fn produce_next_record(
         right_columns.push(builder.finish());
     }
 
-    let right_columns = if let Some(right_proj) = right_proj {
+    let right_columns: Vec<ArrayRef> = if let Some(right_proj) = right_proj {
         right_proj
             .iter()
             .map(|i| right_columns[*i].clone())
@@ -550,6 +621,38 @@ fn produce_next_record(
         right_columns
     };
 
+    // undirected edge lists add the orientation of the edge in the match, the node we hop from
+    // goes in the column the edge was joined on and its neighbour in the other one
+    let right_columns = match right_fields {
+        Some(right_fields) => {
+            let hop_ids = left_rb.column(input_col).clone();
+            let nbr_ids: ArrayRef = Arc::new(UInt64Array::from(nbr_indices));
+            let mut columns = right_schema
+                .fields()
+                .iter()
+                .map(|field| field.name().as_str())
+                .zip(right_columns)
+                .collect::<HashMap<_, _>>();
+            let other_col = if right_col == "_from" { "_to" } else { "_from" };
+            columns.insert(right_col, hop_ids);
+            columns.insert(other_col, nbr_ids);
+            match right_fields
+                .iter()
+                .map(|name| columns.get(name.as_str()).cloned())
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(columns) => columns,
+                None => {
+                    return Some(Err(DataFusionError::Plan(format!(
+                        "undirected hop can't produce the columns {:?}",
+                        right_fields
+                    ))))
+                }
+            }
+        }
+        None => right_columns,
+    };
+
     columns.extend(right_columns);
 
     Some(RecordBatch::try_new(output_schema, columns).map_err(Into::into))
@@ -563,7 +666,10 @@ impl RecordBatchStream for HopStream {
 
 #[cfg(test)]
 mod test {
-    use arrow::{compute::concat_batches, util::pretty::print_batches};
+    use arrow::{
+        compute::concat_batches,
+        util::pretty::{pretty_format_batches, print_batches},
+    };
     use arrow_array::{
         types::{Float64Type, Int64Type, UInt64Type},
         Float64Array, PrimitiveArray,
@@ -637,9 +743,64 @@ mod test {
             edges in (1..5usize).prop_map(|num_nodes| graph_gen_edges(num_nodes))
         ) {
             tokio::runtime::Runtime::new().unwrap().block_on(
-                check_random_hop(chunk_size, t_props_chunk_size, &edges)
+                check_random_hop("MATCH (a)-[e1]->(b)-[e2]->(c) RETURN count(*)", chunk_size, t_props_chunk_size, &edges)
             );
         }
+
+        #[test]
+        fn undirected_hop_proptest(
+            chunk_size in 1usize..23,
+            t_props_chunk_size in 1usize..11,
+            edges in (1..5usize).prop_map(|num_nodes| graph_gen_edges(num_nodes))
+        ) {
+            tokio::runtime::Runtime::new().unwrap().block_on(
+                check_random_hop("MATCH (a)-[e1]-(b)-[e2]-(c) RETURN count(*)", chunk_size, t_props_chunk_size, &edges)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn undirected_hop() {
+        let query = "MATCH (a)-[e1]-(b)-[e2]-(c) RETURN count(*)";
+        check_random_hop(query, 2, 2, &EDGES).await;
+        check_random_hop(query, 10, 10, &EDGES).await;
+    }
+
+    #[tokio::test]
+    async fn undirected_hop_with_self_loops() {
+        let edges = vec![
+            (0u64, 0u64, 0i64, 1.),
+            (0, 1, 1, 2.),
+            (1, 1, 2, 3.),
+            (1, 2, 3, 4.),
+        ];
+        let query =
+            "MATCH (a)-[e1]-(b)-[e2]-(c) RETURN a.gid, b.gid, c.gid, e1.src, e1.dst, e2.src, e2.dst";
+        let graph_dir = tempdir().unwrap();
+        let graph = DiskGraphStorage::make_simple_graph(graph_dir, &edges, 2, 2);
+
+        let mut rows = vec![];
+        for enable_hop in [false, true] {
+            let df = run_cypher(query, &graph, enable_hop).await.unwrap();
+            let schema = df.schema().inner().clone();
+            let rbs = df.collect().await.unwrap();
+            rows.push(concat_batches(&schema, &rbs).unwrap());
+        }
+        let [join, hop] = rows.as_slice() else {
+            unreachable!()
+        };
+        assert_eq!(sorted_rows(join), sorted_rows(hop));
+    }
+
+    fn sorted_rows(rb: &RecordBatch) -> Vec<String> {
+        let mut rows = pretty_format_batches(&[rb.clone()])
+            .unwrap()
+            .to_string()
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        rows.sort();
+        rows
     }
 
     fn graph_gen_edges(num_nodes: usize) -> Vec<(u64, u64, i64, f64)> {
@@ -662,6 +823,7 @@ mod test {
     }
 
     async fn check_random_hop(
+        query: &str,
         chunk_size: usize,
         t_props_chunk_size: usize,
         edges: &[(u64, u64, i64, f64)],
@@ -670,7 +832,6 @@ mod test {
         let graph =
             DiskGraphStorage::make_simple_graph(graph_dir, edges, chunk_size, t_props_chunk_size);
 
-        let query = "MATCH (a)-[e1]->(b)-[e2]->(c) RETURN count(*)";
         let df = run_cypher(query, &graph, false).await.unwrap();
 
         let rbs = df.collect().await.unwrap();
@@ -809,6 +970,7 @@ mod test {
             graph,
             Direction::OUT,
             3,
+            "src".to_string(),
             batch_size,
             vec!["_default".to_string()],
             table_schema.into(),
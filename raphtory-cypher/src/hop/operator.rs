@@ -13,6 +13,8 @@ pub struct HopPlan {
     input: Arc<LogicalPlan>,
     pub dir: Direction,
     pub left_col: String,
+    pub right_col: String, // the column of the edge list joined to left_col, src or dst (_from or _to if undirected)
+    pub right_fields: Option<Vec<String>>, // the columns of an undirected edge list, with its orientation
     pub out_schema: DFSchemaRef,
     pub right_schema: DFSchemaRef, // helps pick the columns from the edge list we're hopping onto
     pub right_layers: Vec<String>, // what layers are we hopping onto
@@ -70,6 +72,8 @@ impl HopPlan {
         left: &LogicalPlan,
         right: TableScan,
         left_col: String,
+        right_col: String,
+        right_fields: Option<Vec<String>>,
         on: Vec<(Expr, Expr)>,
     ) -> Self {
         Self {
@@ -78,6 +82,8 @@ impl HopPlan {
             dir,
             out_schema: schema.clone(),
             left_col: left_col.to_string(),
+            right_col,
+            right_fields,
             right_schema: right.projected_schema,
             right_layers: vec![right.table_name.to_string()],
             expressions: on.iter().cloned().collect(),
@@ -134,6 +140,8 @@ impl UserDefinedLogicalNodeCore for HopPlan {
             graph: self.graph.clone(),
             dir: self.dir,
             left_col: self.left_col.clone(),
+            right_col: self.right_col.clone(),
+            right_fields: self.right_fields.clone(),
             input: Arc::new(inputs[0].clone()),
             out_schema: self.out_schema.clone(),
             right_schema: self.right_schema.clone(),
@@ -7,7 +7,10 @@ use datafusion::{
     common::{tree_node::Transformed, Column},
     error::DataFusionError,
     execution::context::{QueryPlanner, SessionState},
-    logical_expr::{Expr, Extension, Join, LogicalPlan, UserDefinedLogicalNode},
+    logical_expr::{
+        BinaryExpr, Expr, Extension, Filter, Join, LogicalPlan, Operator, TableScan,
+        UserDefinedLogicalNode,
+    },
     optimizer::{optimizer::ApplyOrder, OptimizerConfig, OptimizerRule},
     physical_plan::ExecutionPlan,
     physical_planner::{DefaultPhysicalPlanner, ExtensionPlanner, PhysicalPlanner},
@@ -40,6 +43,7 @@ impl OptimizerRule for HopRule {
                 on,
                 left,
                 schema,
+                filter,
                 ..
             } = join;
 
@@ -47,7 +51,7 @@ impl OptimizerRule for HopRule {
                 return Ok(Transformed::no(plan));
             }
 
-            let (hop_from_col, _hop_to_col, direction) = if let (
+            let (hop_from_col, hop_to_col, direction) = if let (
                 Expr::Column(Column {
                     name: hop_from_col, ..
                 }),
@@ -56,7 +60,13 @@ impl OptimizerRule for HopRule {
                 }),
             ) = &on[0]
             {
-                let direction = match (hop_from_col.as_ref(), hop_to_col.as_ref()) {
+                // undirected edge lists are joined on their orientation columns
+                let endpoint = |col: &str| match col {
+                    "_from" => "src",
+                    "_to" => "dst",
+                    col => col,
+                };
+                let direction = match (endpoint(hop_from_col), endpoint(hop_to_col)) {
                     ("dst", "src") => Direction::OUT,
                     ("dst", "dst") => Direction::IN,
                     ("src", "src") => Direction::OUT,
@@ -68,20 +78,41 @@ impl OptimizerRule for HopRule {
                 return Ok(Transformed::no(plan));
             };
 
-            // simplest form Any -> TableScan
+            // simplest form Any -> TableScan, undirected relationships hop both ways over the scan
             if let (l_tbl, LogicalPlan::SubqueryAlias(r_tbl)) = (left.as_ref(), right.as_ref()) {
-                if let LogicalPlan::TableScan(r_tbl) = r_tbl.input.as_ref() {
+                let r_scan = match r_tbl.input.as_ref() {
+                    LogicalPlan::TableScan(r_scan) => Some((r_scan, direction, None)),
+                    r_plan => undirected_scan(r_plan).map(|r_scan| {
+                        let right_fields = r_tbl
+                            .schema
+                            .fields()
+                            .iter()
+                            .map(|field| field.name().clone())
+                            .collect();
+                        (r_scan, Direction::BOTH, Some(right_fields))
+                    }),
+                };
+                if let Some((r_scan, direction, right_fields)) = r_scan {
                     let plan = LogicalPlan::Extension(Extension {
                         node: Arc::new(HopPlan::from_table_scans(
                             self.graph.clone(),
                             direction,
                             schema.clone(),
                             l_tbl,
-                            r_tbl.clone(),
+                            r_scan.clone(),
                             hop_from_col.clone(),
+                            hop_to_col.clone(),
+                            right_fields,
                             on.clone(),
                         )),
                     });
+                    // the hop replaces the equi-join, the rest of the join condition still applies
+                    let plan = match filter {
+                        Some(filter) => {
+                            LogicalPlan::Filter(Filter::try_new(filter.clone(), Arc::new(plan))?)
+                        }
+                        None => plan,
+                    };
                     return Ok(Transformed::yes(plan));
                 }
             }
@@ -94,6 +125,60 @@ impl OptimizerRule for HopRule {
     }
 }
 
+/// The edges of an undirected relationship as laid out by the transpiler, the scan of the edge list
+/// oriented from src to dst UNION ALL the same scan oriented from dst to src without the self loops
+fn undirected_scan(plan: &LogicalPlan) -> Option<&TableScan> {
+    let LogicalPlan::Union(union) = plan else {
+        return None;
+    };
+    let [forward, reversed] = union.inputs.as_slice() else {
+        return None;
+    };
+    let scan = oriented_scan(forward)?;
+    let reversed_scan = reversed_scan(reversed)?;
+    (reversed_scan.table_name == scan.table_name).then_some(scan)
+}
+
+fn oriented_scan(plan: &LogicalPlan) -> Option<&TableScan> {
+    match plan {
+        LogicalPlan::SubqueryAlias(alias) => oriented_scan(&alias.input),
+        LogicalPlan::Projection(projection) => aliased_scan(&projection.input),
+        _ => None,
+    }
+}
+
+fn aliased_scan(plan: &LogicalPlan) -> Option<&TableScan> {
+    match plan {
+        LogicalPlan::SubqueryAlias(alias) => aliased_scan(&alias.input),
+        LogicalPlan::TableScan(scan) => Some(scan),
+        _ => None,
+    }
+}
+
+fn reversed_scan(plan: &LogicalPlan) -> Option<&TableScan> {
+    match plan {
+        LogicalPlan::SubqueryAlias(alias) => reversed_scan(&alias.input),
+        LogicalPlan::Projection(projection) => reversed_scan(&projection.input),
+        LogicalPlan::Filter(filter) if is_not_self_loop(&filter.predicate) => {
+            aliased_scan(&filter.input)
+        }
+        // the self loop filter can also be pushed down into the scan
+        LogicalPlan::TableScan(scan) if scan.filters.iter().any(is_not_self_loop) => Some(scan),
+        _ => None,
+    }
+}
+
+fn is_not_self_loop(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::BinaryExpr(BinaryExpr { left, op: Operator::NotEq, right })
+            if matches!(
+                (left.as_ref(), right.as_ref()),
+                (Expr::Column(l), Expr::Column(r)) if l.name == "src" && r.name == "dst"
+            )
+    )
+}
+
 pub struct HopQueryPlanner;
 
 #[async_trait]
@@ -128,7 +213,7 @@ impl ExtensionPlanner for HopPlanner {
         _session_state: &SessionState,
     ) -> Result<Option<Arc<dyn ExecutionPlan>>, DataFusionError> {
         if let Some(node) = node.as_any().downcast_ref::<HopPlan>() {
-            let exec_plan = HopExec::new(node, physical_inputs);
+            let exec_plan = HopExec::new(node, physical_inputs)?;
             Ok(Some(Arc::new(exec_plan)))
         } else {
             Ok(None)
@@ -1,16 +1,11 @@
-#[cfg(feature = "storage")]
 pub use cypher::*;
 
-#[cfg(feature = "storage")]
 pub mod executor;
 #[cfg(feature = "storage")]
 pub mod hop;
-#[cfg(feature = "storage")]
 pub mod parser;
-#[cfg(feature = "storage")]
 pub mod transpiler;
 
-#[cfg(feature = "storage")]
 mod cypher {
     use arrow::compute::take;
     use std::sync::Arc;
@@ -26,22 +21,32 @@ mod cypher {
             runtime_env::RuntimeEnv,
         },
         logical_expr::{create_udf, ColumnarValue, LogicalPlan, Volatility},
-        physical_plan::SendableRecordBatchStream,
     };
+    use raphtory::db::api::view::StaticGraphViewOps;
 
     use super::{
-        executor::{table_provider::edge::EdgeListTableProvider, ExecError},
+        executor::{
+            mutation::{CypherMutableGraph, UpdatePlan, UpdateStats},
+            table_provider::graph_view::GraphViewTables,
+            ExecError,
+        },
+        parser::ast::Query,
         *,
     };
-    use raphtory::disk_graph::DiskGraphStorage;
 
+    #[cfg(feature = "storage")]
     use crate::{
-        executor::table_provider::node::NodeTableProvider,
+        executor::table_provider::{edge::EdgeListTableProvider, node::NodeTableProvider},
         hop::rule::{HopQueryPlanner, HopRule},
     };
+    #[cfg(feature = "storage")]
+    use datafusion::physical_plan::SendableRecordBatchStream;
+    #[cfg(feature = "storage")]
+    use raphtory::disk_graph::DiskGraphStorage;
 
     pub use polars_arrow as arrow2;
 
+    #[cfg(feature = "storage")]
     pub async fn run_cypher(
         query: &str,
         g: &DiskGraphStorage,
@@ -52,6 +57,7 @@ mod cypher {
         Ok(df)
     }
 
+    #[cfg(feature = "storage")]
     pub async fn prepare_plan(
         query: &str,
         g: &DiskGraphStorage,
@@ -84,36 +90,9 @@ mod cypher {
         ctx.register_table("nodes", Arc::new(node_table_provider))?;
         let layer_names = graph.layer_names().to_vec();
 
-        ctx.register_udf(create_udf(
-            "type",
-            vec![DataType::UInt64],
-            DataType::Utf8.into(),
-            Volatility::Immutable,
-            Arc::new(move |cols| {
-                let layer_id_col = match &cols[0] {
-                    ColumnarValue::Array(a) => a.clone(),
-                    ColumnarValue::Scalar(a) => a.to_array()?,
-                };
-
-                let layer_id_col = layer_id_col
-                    .as_any()
-                    .downcast_ref::<UInt64Array>()
-                    .ok_or_else(|| {
-                        DataFusionError::Execution("Expected column of type u64".to_string())
-                    })?;
-
-                let mut type_col = builder::StringBuilder::new();
-                for layer_id in layer_id_col.values() {
-                    let layer_name = layer_names
-                        .get(*layer_id as usize)
-                        .ok_or_else(|| DataFusionError::Execution("Layer not found".to_string()))?;
-                    type_col.append_value(layer_name);
-                }
-                Ok(ColumnarValue::Array(Arc::new(type_col.finish())))
-            }),
-        ));
+        register_type_udf(&ctx, layer_names);
         ctx.refresh_catalogs().await?;
-        let query = transpiler::to_sql(query, g);
+        let query = transpiler::to_sql(query, g)?;
 
         let plan = ctx
             .state()
@@ -128,6 +107,7 @@ mod cypher {
         Ok((ctx, plan))
     }
 
+    #[cfg(feature = "storage")]
     pub async fn run_cypher_to_streams(
         query: &str,
         graph: &DiskGraphStorage,
@@ -137,6 +117,7 @@ mod cypher {
         Ok(stream)
     }
 
+    #[cfg(feature = "storage")]
     pub async fn run_sql(query: &str, graph: &DiskGraphStorage) -> Result<DataFrame, ExecError> {
         let ctx = SessionContext::new();
 
@@ -152,6 +133,155 @@ mod cypher {
         Ok(df)
     }
 
+    /// Run a Cypher query against any graph view, including windowed and layered views
+    ///
+    /// Every layer of the view becomes an edge table with one row per edge update in the view and
+    /// nodes are only included if they are part of the view.
+    pub async fn run_cypher_on_graph<G: StaticGraphViewOps>(
+        query: &str,
+        graph: &G,
+    ) -> Result<DataFrame, ExecError> {
+        run_cypher_on_graph_with_max_path_length(query, graph, transpiler::DEFAULT_MAX_PATH_LENGTH)
+            .await
+    }
+
+    /// Run a Cypher query against any graph view with variable length relationships of at most
+    /// `max_path_length` hops
+    pub async fn run_cypher_on_graph_with_max_path_length<G: StaticGraphViewOps>(
+        query: &str,
+        graph: &G,
+        max_path_length: usize,
+    ) -> Result<DataFrame, ExecError> {
+        let query = super::parser::parse_cypher(query)?;
+        let (ctx, plan) = prepare_query_plan_on_graph(query, graph, max_path_length).await?;
+        let df = ctx.execute_logical_plan(plan).await?;
+        Ok(df)
+    }
+
+    pub async fn prepare_plan_on_graph<G: StaticGraphViewOps>(
+        query: &str,
+        graph: &G,
+    ) -> Result<(SessionContext, LogicalPlan), ExecError> {
+        let query = super::parser::parse_cypher(query)?;
+        prepare_query_plan_on_graph(query, graph, transpiler::DEFAULT_MAX_PATH_LENGTH).await
+    }
+
+    async fn prepare_query_plan_on_graph<G: StaticGraphViewOps>(
+        query: Query,
+        graph: &G,
+        max_path_length: usize,
+    ) -> Result<(SessionContext, LogicalPlan), ExecError> {
+        let config = SessionConfig::from_env()?.with_information_schema(true);
+        let runtime = Arc::new(RuntimeEnv::default());
+        let state = SessionState::new_with_config_rt(config, runtime);
+        let ctx = SessionContext::new_with_state(state);
+
+        let tables = register_graph_tables(&ctx, graph)?;
+        let layer_names = graph
+            .edge_meta()
+            .layer_meta()
+            .get_keys()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        register_type_udf(&ctx, layer_names);
+        ctx.refresh_catalogs().await?;
+        let query = transpiler::to_sql_with_max_path_length(query, &tables, max_path_length)?;
+
+        let plan = ctx
+            .state()
+            .statement_to_plan(datafusion::sql::parser::Statement::Statement(Box::new(
+                query,
+            )))
+            .await?;
+        let opts = SQLOptions::new();
+        opts.verify_plan(&plan)?;
+
+        let plan = ctx.state().optimize(&plan)?;
+        Ok((ctx, plan))
+    }
+
+    /// Apply the CREATE, MERGE, SET and DELETE clauses of a Cypher query to a graph at time `t`
+    ///
+    /// The updates are applied once for every row matched by the MATCH clauses of the query, new nodes
+    /// are identified by their `id` property and the type of a new relationship is its layer.
+    /// DELETE needs a [`PersistentGraph`](raphtory::db::graph::views::deletion_graph::PersistentGraph).
+    pub async fn run_cypher_update<G: CypherMutableGraph + StaticGraphViewOps>(
+        query: &str,
+        graph: &G,
+        t: i64,
+    ) -> Result<UpdateStats, ExecError> {
+        let query = super::parser::parse_cypher(query)?;
+        let plan = UpdatePlan::new(query)?;
+        let batches = match plan.read_query() {
+            Some(read_query) => {
+                let (ctx, read_plan) = prepare_query_plan_on_graph(
+                    read_query,
+                    graph,
+                    transpiler::DEFAULT_MAX_PATH_LENGTH,
+                )
+                .await?;
+                ctx.execute_logical_plan(read_plan).await?.collect().await?
+            }
+            None => vec![],
+        };
+        plan.apply(graph, t, &batches)
+    }
+
+    pub async fn run_sql_on_graph<G: StaticGraphViewOps>(
+        query: &str,
+        graph: &G,
+    ) -> Result<DataFrame, ExecError> {
+        let ctx = SessionContext::new();
+        register_graph_tables(&ctx, graph)?;
+        let df = ctx.sql(query).await?;
+        Ok(df)
+    }
+
+    fn register_graph_tables<G: StaticGraphViewOps>(
+        ctx: &SessionContext,
+        graph: &G,
+    ) -> Result<GraphViewTables<G>, ExecError> {
+        let tables = GraphViewTables::new(graph.clone());
+        for layer in tables.layer_names() {
+            ctx.register_table(layer.as_str(), Arc::new(tables.edge_table(layer)?))?;
+        }
+        ctx.register_table("nodes", Arc::new(tables.node_table()))?;
+        Ok(tables)
+    }
+
+    /// `type(e)` maps the `layer_id` column of an edge table to the name of the layer
+    fn register_type_udf(ctx: &SessionContext, layer_names: Vec<String>) {
+        ctx.register_udf(create_udf(
+            "type",
+            vec![DataType::UInt64],
+            DataType::Utf8.into(),
+            Volatility::Immutable,
+            Arc::new(move |cols| {
+                let layer_id_col = match &cols[0] {
+                    ColumnarValue::Array(a) => a.clone(),
+                    ColumnarValue::Scalar(a) => a.to_array()?,
+                };
+
+                let layer_id_col = layer_id_col
+                    .as_any()
+                    .downcast_ref::<UInt64Array>()
+                    .ok_or_else(|| {
+                        DataFusionError::Execution("Expected column of type u64".to_string())
+                    })?;
+
+                let mut type_col = builder::StringBuilder::new();
+                for layer_id in layer_id_col.values() {
+                    let layer_name = layer_names
+                        .get(*layer_id as usize)
+                        .ok_or_else(|| DataFusionError::Execution("Layer not found".to_string()))?;
+                    type_col.append_value(layer_name);
+                }
+                Ok(ColumnarValue::Array(Arc::new(type_col.finish())))
+            }),
+        ));
+    }
+
     pub fn take_record_batch(
         record_batch: &RecordBatch,
         indices: &dyn Array,
@@ -164,7 +294,7 @@ mod cypher {
         RecordBatch::try_new(record_batch.schema(), columns)
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "storage"))]
     mod test {
         use arrow::compute::concat_batches;
         use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
@@ -457,6 +587,31 @@ mod cypher {
             assert_eq!(hop_rb, hop_join);
         }
 
+        #[tokio::test]
+        async fn temporal_var_length_paths() {
+            let graph_dir = tempdir().unwrap();
+            let graph = DiskGraphStorage::make_simple_graph(graph_dir, &EDGES, 100, 100);
+
+            // the time of the edges is in the rap_time column of the disk graph
+            let paths = run_to_rb(&graph, "MATCH (a)-[e*2]->(b) RETURN count(*)", false).await;
+            let temporal_paths = run_to_rb(
+                &graph,
+                "MATCH (a)-[e*2 TEMPORAL]->(b) RETURN count(*)",
+                false,
+            )
+            .await;
+
+            let count = |rb: &RecordBatch| {
+                rb.column(0)
+                    .as_any()
+                    .downcast_ref::<arrow_array::Int64Array>()
+                    .unwrap()
+                    .value(0)
+            };
+            assert_eq!(count(&paths), 13);
+            assert_eq!(count(&temporal_paths), 11);
+        }
+
         #[tokio::test]
         async fn three_hops_with_condition() {
             let graph_dir = tempdir().unwrap();
@@ -771,4 +926,514 @@ mod cypher {
             print_batches(&data).unwrap();
         }
     }
+
+    #[cfg(test)]
+    mod graph_view_test {
+        use arrow::compute::{cast, concat_batches};
+        use arrow_array::{Array, RecordBatch, StringArray};
+        use arrow_schema::DataType;
+        use itertools::Itertools;
+        use pretty_assertions::assert_eq;
+        use raphtory::{
+            db::{api::view::StaticGraphViewOps, graph::views::deletion_graph::PersistentGraph},
+            prelude::*,
+        };
+
+        use crate::{
+            executor::mutation::UpdateStats, run_cypher_on_graph,
+            run_cypher_on_graph_with_max_path_length, run_cypher_update, run_sql_on_graph,
+        };
+
+        fn make_graph() -> Graph {
+            let g = Graph::new();
+            for (id, name, age) in [(1, "Alice", 30i64), (2, "Bob", 25), (3, "Charlie", 35)] {
+                g.add_node(
+                    0,
+                    id,
+                    [("name", Prop::str(name)), ("age", Prop::I64(age))],
+                    None,
+                )
+                .unwrap();
+            }
+            g.add_edge(1, 1, 2, [("weight", 1.0)], Some("follows"))
+                .unwrap();
+            g.add_edge(2, 1, 2, [("weight", 2.0)], Some("follows"))
+                .unwrap();
+            g.add_edge(3, 2, 3, [("weight", 3.0)], Some("follows"))
+                .unwrap();
+            g.add_edge(4, 1, 3, [("weight", 4.0)], Some("likes"))
+                .unwrap();
+            g
+        }
+
+        async fn run<G: StaticGraphViewOps>(query: &str, graph: &G) -> RecordBatch {
+            let df = run_cypher_on_graph(query, graph).await.unwrap();
+            let schema = df.schema().inner().clone();
+            let batches = df.collect().await.unwrap();
+            concat_batches(&schema, &batches).unwrap()
+        }
+
+        /// every row of the batch with the values formatted as strings, sorted for comparison
+        fn rows(batch: &RecordBatch) -> Vec<Vec<String>> {
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|col| cast(col, &DataType::Utf8).unwrap())
+                .collect_vec();
+            (0..batch.num_rows())
+                .map(|row| {
+                    columns
+                        .iter()
+                        .map(|col| {
+                            let col = col.as_any().downcast_ref::<StringArray>().unwrap();
+                            if col.is_null(row) {
+                                "null".to_owned()
+                            } else {
+                                col.value(row).to_owned()
+                            }
+                        })
+                        .collect()
+                })
+                .sorted()
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn select_edges_with_node_properties() {
+            let g = make_graph();
+            let batch = run(
+                "MATCH (a)-[e:follows]->(b) RETURN a.name, e.time, e.weight, b.name",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![
+                    vec!["Alice", "1", "1.0", "Bob"],
+                    vec!["Alice", "2", "2.0", "Bob"],
+                    vec!["Bob", "3", "3.0", "Charlie"],
+                ]
+            );
+
+            let batch = run(
+                "MATCH ()-[e]->() WHERE e.time > 2 RETURN type(e), e.time",
+                &g,
+            )
+            .await;
+            assert_eq!(rows(&batch), vec![vec!["follows", "3"], vec!["likes", "4"]]);
+        }
+
+        #[tokio::test]
+        async fn select_with_filters_and_two_hops() {
+            let g = make_graph();
+            let batch = run(
+                "MATCH (a)-[e1]->(b)-[e2]->(c) WHERE a.age < 35 RETURN a.gid, b.gid, c.gid",
+                &g,
+            )
+            .await;
+            assert_eq!(rows(&batch), vec![vec!["1", "2", "3"], vec!["1", "2", "3"]]);
+
+            let batch = run("MATCH (n) WHERE n.age > 26 RETURN n.name", &g).await;
+            assert_eq!(rows(&batch), vec![vec!["Alice"], vec!["Charlie"]]);
+        }
+
+        #[tokio::test]
+        async fn select_from_windowed_and_layered_views() {
+            let g = make_graph();
+            let batch = run("MATCH ()-[e]->() RETURN e.time", &g.window(2, 4)).await;
+            assert_eq!(rows(&batch), vec![vec!["2"], vec!["3"]]);
+
+            let batch = run("MATCH (n) RETURN n.gid", &g.window(0, 1)).await;
+            assert_eq!(rows(&batch), vec![vec!["1"], vec!["2"], vec!["3"]]);
+
+            let layered = g.layers("likes").unwrap();
+            let batch = run("MATCH (a)-[e]->(b) RETURN a.name, b.name", &layered).await;
+            assert_eq!(rows(&batch), vec![vec!["Alice", "Charlie"]]);
+            assert!(
+                run_cypher_on_graph("MATCH ()-[e:follows]->() RETURN e", &layered)
+                    .await
+                    .is_err()
+            );
+        }
+
+        #[tokio::test]
+        async fn select_from_persistent_graph_and_string_ids() {
+            let g = PersistentGraph::new();
+            g.add_edge(1, "a", "b", [("weight", 1i64)], None).unwrap();
+            g.add_edge(5, "b", "c", [("weight", 2i64)], None).unwrap();
+            g.delete_edge(7, "a", "b", None).unwrap();
+
+            let batch = run("MATCH (a)-[e]->(b) RETURN a.gid, b.gid, e.weight", &g).await;
+            assert_eq!(rows(&batch), vec![vec!["a", "b", "1"], vec!["b", "c", "2"]]);
+
+            let batch = run("MATCH (a)-[e]->(b) RETURN a.gid", &g.window(8, 10)).await;
+            assert_eq!(rows(&batch), vec![vec!["b"]]);
+        }
+
+        #[tokio::test]
+        async fn run_sql_against_graph() {
+            let g = make_graph();
+            let df = run_sql_on_graph("SELECT count(*) FROM follows", &g)
+                .await
+                .unwrap();
+            let schema = df.schema().inner().clone();
+            let batch = concat_batches(&schema, &df.collect().await.unwrap()).unwrap();
+            assert_eq!(rows(&batch), vec![vec!["3"]]);
+        }
+
+        #[tokio::test]
+        async fn select_undirected_edges() {
+            let g = make_graph();
+            let batch = run(
+                "MATCH (a)-[e:follows]-(b) WHERE a.name = 'Bob' RETURN b.name, e.src, e.dst",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![
+                    vec!["Alice", "0", "1"],
+                    vec!["Alice", "0", "1"],
+                    vec!["Charlie", "1", "2"],
+                ]
+            );
+
+            let batch = run(
+                "MATCH (a)-[e1]-(b)<-[e2]-(c) WHERE a.name = 'Charlie' RETURN b.name, c.name",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![vec!["Bob", "Alice"], vec!["Bob", "Alice"]]
+            );
+
+            g.add_edge(5, 3, 3, NO_PROPS, Some("follows")).unwrap();
+            let batch = run(
+                "MATCH (a)-[e:follows]-(b) WHERE e.time = 5 RETURN a.name",
+                &g,
+            )
+            .await;
+            assert_eq!(rows(&batch), vec![vec!["Charlie"]]);
+
+            // returning the relationship returns the edge as it is stored
+            let batch = run(
+                "MATCH (a)-[e:follows]-(b) WHERE a.name = 'Charlie' RETURN e",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                batch
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().as_str())
+                    .collect::<Vec<_>>(),
+                vec!["id", "layer_id", "src", "dst", "time", "weight"]
+            );
+
+            // undirected paths follow the orientation of their edges
+            let batch = run(
+                "MATCH (a)-[e*2]-(b) WHERE a.name = 'Charlie' AND b.name = 'Alice' RETURN e.time",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![vec!["[3, 1]"], vec!["[3, 2]"], vec!["[5, 4]"]]
+            );
+        }
+
+        #[tokio::test]
+        async fn select_variable_length_paths() {
+            let g = make_graph();
+            let batch = run(
+                "MATCH (a)-[e*1..2]->(b) WHERE a.name = 'Alice' RETURN b.name, e.hops, e.time",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![
+                    vec!["Bob", "1", "[1]"],
+                    vec!["Bob", "1", "[2]"],
+                    vec!["Charlie", "1", "[4]"],
+                    vec!["Charlie", "2", "[1, 3]"],
+                    vec!["Charlie", "2", "[2, 3]"],
+                ]
+            );
+
+            // the hop into Bob at time 5 is too late to continue to Charlie at time 3
+            g.add_edge(5, 1, 2, NO_PROPS, Some("follows")).unwrap();
+            let batch = run(
+                "MATCH (a {name: 'Alice'})-[e:follows*2 TEMPORAL]->(b) RETURN b.name, e.time",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![vec!["Charlie", "[1, 3]"], vec!["Charlie", "[2, 3]"]]
+            );
+
+            let batch = run(
+                "MATCH (a)<-[e*1..3 TEMPORAL {weight: 3.0}]-(b) RETURN a.name, b.name",
+                &g,
+            )
+            .await;
+            assert_eq!(rows(&batch), vec![vec!["Charlie", "Bob"]]);
+
+            let batch = run(
+                "MATCH (a)-[e*2..2]-(b) WHERE a.name = 'Charlie' RETURN b.name, e.hops",
+                &g.layers("follows").unwrap(),
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![vec!["Alice", "2"], vec!["Alice", "2"], vec!["Alice", "2"]]
+            );
+        }
+
+        #[tokio::test]
+        async fn select_open_and_zero_length_paths() {
+            let g = make_graph();
+            let batch = run(
+                "MATCH (a)-[e*]->(b) WHERE a.name = 'Alice' RETURN b.name, e.hops",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![
+                    vec!["Bob", "1"],
+                    vec!["Bob", "1"],
+                    vec!["Charlie", "1"],
+                    vec!["Charlie", "2"],
+                    vec!["Charlie", "2"],
+                ]
+            );
+
+            let batch = run("MATCH (a)-[e*2..]->(b) RETURN a.name, b.name, e.time", &g).await;
+            assert_eq!(
+                rows(&batch),
+                vec![
+                    vec!["Alice", "Charlie", "[1, 3]"],
+                    vec!["Alice", "Charlie", "[2, 3]"],
+                ]
+            );
+
+            let batch = run(
+                "MATCH (a)-[e*0..1]->(b) WHERE a.name = 'Bob' RETURN b.name, e.hops, e.time",
+                &g,
+            )
+            .await;
+            assert_eq!(
+                rows(&batch),
+                vec![vec!["Bob", "0", "[]"], vec!["Charlie", "1", "[3]"]]
+            );
+
+            // the paths are at most DEFAULT_MAX_PATH_LENGTH hops long unless a longer maximum is given
+            for query in [
+                "MATCH (a)-[e*6..]->(b) RETURN a",
+                "MATCH (a)-[e*1..6]->(b) RETURN a",
+            ] {
+                assert!(run_cypher_on_graph(query, &g).await.is_err());
+                assert!(run_cypher_on_graph_with_max_path_length(query, &g, 6)
+                    .await
+                    .is_ok());
+            }
+        }
+
+        #[tokio::test]
+        async fn unsupported_queries_return_errors() {
+            let g = make_graph();
+            for query in [
+                "MATCH (a) WHERE a.name IN a.names RETURN a",
+                "MATCH (a) WHERE a.name CONTAINS a.other RETURN a",
+                "MATCH ()-[e]->() RETURN type(1)",
+                "MATCH (a) RETURN a.name STARTS WITH a.other",
+            ] {
+                assert!(
+                    run_cypher_on_graph(query, &g).await.is_err(),
+                    "expected an error for {query}"
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn create_merge_and_set() {
+            let g = make_graph();
+
+            let stats = run_cypher_update(
+                "CREATE (a:Person {id: 4, name: 'Dave'})-[e:follows {weight: 5.0}]->(b {id: 1})",
+                &g,
+                10,
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                stats,
+                UpdateStats {
+                    nodes_created: 1,
+                    edges_created: 1,
+                    properties_set: 2,
+                    ..Default::default()
+                }
+            );
+            let dave = g.node(4).unwrap();
+            assert_eq!(dave.node_type().as_deref(), Some("Person"));
+            assert_eq!(dave.properties().get("name"), Some(Prop::str("Dave")));
+            let edge = g.edge(4, 1).unwrap();
+            assert_eq!(edge.layer_names(), vec!["follows"]);
+            assert_eq!(edge.properties().get("weight"), Some(Prop::F64(5.0)));
+            assert_eq!(edge.latest_time(), Some(10));
+
+            let merge = "MATCH (a {name: 'Bob'}) MERGE (a)-[e:likes]->(b {id: 3}) ON CREATE SET e.weight = 1.0 ON MATCH SET e.weight = 2.0";
+            let stats = run_cypher_update(merge, &g, 11).await.unwrap();
+            assert_eq!(stats.edges_created, 1);
+            assert_eq!(
+                g.edge(2, 3)
+                    .unwrap()
+                    .layers("likes")
+                    .unwrap()
+                    .properties()
+                    .get("weight"),
+                Some(Prop::F64(1.0))
+            );
+            let stats = run_cypher_update(merge, &g, 12).await.unwrap();
+            assert_eq!(stats.edges_created, 0);
+            assert_eq!(
+                g.edge(2, 3)
+                    .unwrap()
+                    .layers("likes")
+                    .unwrap()
+                    .properties()
+                    .get("weight"),
+                Some(Prop::F64(2.0))
+            );
+
+            // updates are applied once for every matched node and use the matched values
+            let stats = run_cypher_update(
+                "MATCH (a)-[e:follows]->(b) SET b.followed_by = a.name, b:Followed",
+                &g,
+                13,
+            )
+            .await
+            .unwrap();
+            assert_eq!(stats.properties_set, 3);
+            assert_eq!(
+                g.node(2).unwrap().properties().get("followed_by"),
+                Some(Prop::str("Alice"))
+            );
+            assert_eq!(g.node(3).unwrap().node_type().as_deref(), Some("Followed"));
+
+            let batch = run("MATCH (a) WHERE a.age > 30 RETURN a.name", &g).await;
+            assert_eq!(rows(&batch), vec![vec!["Charlie"]]);
+            run_cypher_update("MATCH (a {name: 'Alice'}) SET a += {age: 40}", &g, 14)
+                .await
+                .unwrap();
+            let batch = run(
+                "MATCH (a) WHERE a.age > 30 RETURN a.name ORDER BY a.name",
+                &g,
+            )
+            .await;
+            assert_eq!(rows(&batch), vec![vec!["Alice"], vec!["Charlie"]]);
+
+            // float literals keep their type
+            run_cypher_update("MATCH (a {name: 'Bob'}) SET a.score = 0.1", &g, 15)
+                .await
+                .unwrap();
+            assert_eq!(
+                g.node(2).unwrap().properties().get("score"),
+                Some(Prop::F64(0.1))
+            );
+
+            // variables that look like the names of anonymous nodes are still bound
+            let stats = run_cypher_update(
+                "MATCH (n_7 {name: 'Alice'})-[e:likes]->() SET n_7.score = 1.0",
+                &g,
+                16,
+            )
+            .await
+            .unwrap();
+            assert_eq!(stats.properties_set, 1);
+            let batch = run(
+                "MATCH (n_7)-[e:likes]->(n_8) WHERE n_7.score = 1.0 RETURN n_7.name, n_8.name",
+                &g,
+            )
+            .await;
+            assert_eq!(rows(&batch), vec![vec!["Alice", "Charlie"]]);
+        }
+
+        #[tokio::test]
+        async fn delete_from_persistent_graph() {
+            let g = make_graph().persistent_graph();
+
+            let stats =
+                run_cypher_update("MATCH (a {name: 'Alice'})-[e:likes]->(b) DELETE e", &g, 10)
+                    .await
+                    .unwrap();
+            assert_eq!(stats.edges_deleted, 1);
+            assert!(g.at(20).edge(1, 3).is_none());
+            assert!(g.at(20).edge(1, 2).is_some());
+
+            assert!(
+                run_cypher_update("MATCH (a {name: 'Bob'}) DELETE a", &g, 11)
+                    .await
+                    .is_err()
+            );
+            let stats = run_cypher_update("MATCH (a {name: 'Bob'}) DETACH DELETE a", &g, 11)
+                .await
+                .unwrap();
+            assert_eq!(stats.nodes_deleted, 1);
+            assert!(g.at(20).edge(1, 2).is_none());
+            assert!(g.at(20).edge(2, 3).is_none());
+
+            // matching the deleted edges again does not delete anything
+            let batch = run("MATCH ()-[e:follows]->() RETURN e.src", &g.after(11)).await;
+            assert_eq!(batch.num_rows(), 0);
+
+            // only the edges alive when the node is deleted are checked
+            let stats = run_cypher_update("MATCH (a {name: 'Charlie'}) DELETE a", &g, 12)
+                .await
+                .unwrap();
+            assert_eq!(stats.nodes_deleted, 1);
+
+            // including the edges deleted by the same query
+            let g = make_graph().persistent_graph();
+            let stats = run_cypher_update("MATCH (a {name: 'Charlie'})-[e]-() DELETE e, a", &g, 10)
+                .await
+                .unwrap();
+            assert_eq!(stats.edges_deleted, 2);
+            assert_eq!(stats.nodes_deleted, 1);
+            assert!(g.at(20).node(3).is_none());
+        }
+
+        #[tokio::test]
+        async fn invalid_updates_return_errors() {
+            let g = make_graph();
+            for query in [
+                "MATCH (a {name: 'Alice'}) DELETE a",
+                "CREATE (a {name: 'Eve'})",
+                "CREATE (a {id: 5})-[e]-(b {id: 6})",
+                "MATCH (a) SET b.name = 'Eve'",
+                "MATCH (a) SET a.name = 'Eve' RETURN a",
+                "MATCH (a) RETURN a",
+                "CREATE (a {id: 'Eve'})",
+                "CREATE (a {id: 5, age: 'old'})",
+                "MATCH (a {name: 'Alice'}) CREATE (b {id: 5}) SET a.age = 'old'",
+                "MATCH (a {name: 'Alice'}) CREATE (b {id: 5}) SET a:Person SET a:Robot",
+                "MATCH (a {name: 'Alice'}) CREATE (b {id: 5})-[e:follows]->(a) SET e.weight = 1",
+            ] {
+                assert!(
+                    run_cypher_update(query, &g, 10).await.is_err(),
+                    "expected an error for {query}"
+                );
+            }
+            assert!(run_cypher_on_graph("CREATE (a {id: 5})", &g).await.is_err());
+            // failing queries leave the graph unchanged
+            assert_eq!(g.count_nodes(), 3);
+            assert_eq!(g.count_edges(), 3);
+            assert_eq!(g.node(1).unwrap().node_type(), None);
+        }
+    }
 }
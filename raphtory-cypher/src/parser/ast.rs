@@ -86,6 +86,10 @@ pub struct SingleQuery {
 pub enum Clause {
     Match(Match),
     Return(Return),
+    Create(Create),
+    Merge(Merge),
+    Set(Set),
+    Delete(Delete),
 }
 
 impl Clause {
@@ -110,16 +114,59 @@ impl Clause {
     }
 }
 
-#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Match {
     pub pattern: Pattern,
     pub where_clause: Option<Expr>,
 }
 
 #[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct Pattern(pub Vec<PatternPart>);
+pub struct Create {
+    pub pattern: Pattern,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Merge {
+    pub pattern: PatternPart,
+    pub on_create: Vec<SetItem>,
+    pub on_match: Vec<SetItem>,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Set {
+    pub items: Vec<SetItem>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SetItem {
+    /// `SET n.key = expr`
+    Property {
+        var_name: String,
+        key: String,
+        value: Expr,
+    },
+    /// `SET n += {key: expr, ...}`
+    Properties {
+        var_name: String,
+        props: HashMap<String, Expr>,
+    },
+    /// `SET n:Label`
+    Labels {
+        var_name: String,
+        labels: Vec<String>,
+    },
+}
 
 #[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Delete {
+    pub detach: bool,
+    pub exprs: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Pattern(pub Vec<PatternPart>);
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PatternPart {
     pub var: Option<String>,
     pub node: NodePattern,
@@ -151,11 +198,14 @@ impl PatternPart {
     }
 }
 
-#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct NodePattern {
     pub name: String,
     pub labels: Vec<String>,
     pub props: Option<HashMap<String, Expr>>,
+    /// The node has no variable in the query (`()`), its name is generated
+    #[serde(default)]
+    pub anonymous: bool,
 }
 
 impl NodePattern {
@@ -164,6 +214,7 @@ impl NodePattern {
             name: name.to_string(),
             labels: vec![],
             props: None,
+            anonymous: false,
         }
     }
 
@@ -172,16 +223,45 @@ impl NodePattern {
             name: name.to_string(),
             labels: labels.into_iter().map(|s| s.as_ref().to_string()).collect(),
             props: None,
+            anonymous: false,
         }
     }
 }
 
-#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct RelPattern {
     pub name: String,
     pub direction: Direction,
     pub rel_types: Vec<String>,
     pub props: Option<HashMap<String, Expr>>,
+    pub var_length: Option<VarLength>,
+}
+
+/// The range of a variable length relationship `-[e*min..max]->`
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VarLength {
+    pub min: usize,
+    pub max: Option<usize>,
+    /// Raphtory extension (`-[e*1..4 TEMPORAL]->`), successive hops must have non-decreasing timestamps
+    pub temporal: bool,
+}
+
+impl VarLength {
+    pub fn new(min: usize, max: Option<usize>) -> Self {
+        VarLength {
+            min,
+            max,
+            temporal: false,
+        }
+    }
+
+    pub fn temporal(min: usize, max: Option<usize>) -> Self {
+        VarLength {
+            min,
+            max,
+            temporal: true,
+        }
+    }
 }
 
 impl RelPattern {
@@ -191,6 +271,7 @@ impl RelPattern {
             direction: Direction::OUT,
             rel_types: vec![],
             props: None,
+            var_length: None,
         }
     }
 
@@ -200,6 +281,7 @@ impl RelPattern {
             direction: Direction::IN,
             rel_types: vec![],
             props: None,
+            var_length: None,
         }
     }
 
@@ -209,6 +291,7 @@ impl RelPattern {
             direction: Direction::IN,
             rel_types: labels.into_iter().map(|s| s.as_ref().to_string()).collect(),
             props: None,
+            var_length: None,
         }
     }
 
@@ -218,6 +301,7 @@ impl RelPattern {
             direction: Direction::OUT,
             rel_types: labels.into_iter().map(|s| s.as_ref().to_string()).collect(),
             props: None,
+            var_length: None,
         }
     }
 
@@ -227,6 +311,7 @@ impl RelPattern {
             direction: Direction::BOTH,
             rel_types: vec![],
             props: None,
+            var_length: None,
         }
     }
 }
@@ -32,6 +32,8 @@ pub enum ParseError {
     // parse float error
     #[error("Unable to parse float: {0}")]
     ParseFloat(#[from] std::num::ParseFloatError),
+    #[error("Edge layers have incompatible schemas: {0}")]
+    LayerSchemaMismatch(String),
 }
 
 fn unsupported<B>(msg: &str, rule: &Rule) -> Result<B, ParseError> {
@@ -103,10 +105,16 @@ pub fn parse_single_query(pair: Pair<Rule>) -> Result<SingleQuery, ParseError> {
                                 }
                             }
                         }
+                        Rule::UpdatingClause => {
+                            for pair in pair.into_inner() {
+                                clauses.push(parse_updating_clause(pair, &mut un_named_counter)?);
+                            }
+                        }
                         Rule::Return => {
                             let return_clause = parse_return(pair)?;
                             clauses.push(Clause::Return(return_clause));
                         }
+                        Rule::SP => {}
                         rule => return unsupported("parse_single_query 2", &rule),
                     }
                 }
@@ -145,6 +153,152 @@ pub fn parse_match(pair: Pair<Rule>, un_named_counter: &mut usize) -> Result<Mat
     })
 }
 
+fn parse_updating_clause(
+    pair: Pair<Rule>,
+    un_named_counter: &mut usize,
+) -> Result<Clause, ParseError> {
+    match pair.as_rule() {
+        Rule::Create => {
+            let mut pattern = Pattern::default();
+            for pair in pair.into_inner() {
+                match pair.as_rule() {
+                    Rule::CREATE | Rule::SP => {}
+                    Rule::Pattern => pattern = parse_pattern(pair, un_named_counter)?,
+                    rule => return unsupported("parse_create", &rule),
+                }
+            }
+            Ok(Clause::Create(Create { pattern }))
+        }
+        Rule::Merge => {
+            let mut merge = Merge::default();
+            for pair in pair.into_inner() {
+                match pair.as_rule() {
+                    Rule::MERGE | Rule::SP => {}
+                    Rule::PatternPart => {
+                        merge.pattern = parse_pattern_part(pair, un_named_counter)?
+                    }
+                    Rule::MergeAction => {
+                        let mut on_create = false;
+                        let mut items = vec![];
+                        for pair in pair.into_inner() {
+                            match pair.as_rule() {
+                                Rule::ON | Rule::MATCH | Rule::SP => {}
+                                Rule::CREATE => on_create = true,
+                                Rule::Set => items = parse_set(pair)?.items,
+                                rule => return unsupported("parse_merge_action", &rule),
+                            }
+                        }
+                        if on_create {
+                            merge.on_create.extend(items);
+                        } else {
+                            merge.on_match.extend(items);
+                        }
+                    }
+                    rule => return unsupported("parse_merge", &rule),
+                }
+            }
+            Ok(Clause::Merge(merge))
+        }
+        Rule::Set => Ok(Clause::Set(parse_set(pair)?)),
+        Rule::Delete => {
+            let mut delete = Delete::default();
+            for pair in pair.into_inner() {
+                match pair.as_rule() {
+                    Rule::DELETE | Rule::SP => {}
+                    Rule::DETACH => delete.detach = true,
+                    Rule::Expression => delete.exprs.push(parse_expr(pair.into_inner())?),
+                    rule => return unsupported("parse_delete", &rule),
+                }
+            }
+            Ok(Clause::Delete(delete))
+        }
+        Rule::Remove => Err(ParseError::Unsupported(
+            "REMOVE, properties and labels can't be removed from a temporal graph".to_string(),
+        )),
+        rule => unsupported("parse_updating_clause", &rule),
+    }
+}
+
+fn parse_set(pair: Pair<Rule>) -> Result<Set, ParseError> {
+    let mut items = vec![];
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::SET | Rule::SP => {}
+            Rule::SetItem => items.push(parse_set_item(pair)?),
+            rule => return unsupported("parse_set", &rule),
+        }
+    }
+    Ok(Set { items })
+}
+
+fn parse_set_item(pair: Pair<Rule>) -> Result<SetItem, ParseError> {
+    let text = pair.as_str();
+    let start = pair.as_span().start();
+    let mut inner = pair.into_inner().filter(|pair| pair.as_rule() != Rule::SP);
+    let target = inner
+        .next()
+        .ok_or_else(|| ParseError::SyntaxError(format!("invalid SET item {}", text)))?;
+    let value = inner.next();
+
+    match (target.as_rule(), value) {
+        (Rule::PropertyExpression, Some(value)) => {
+            let mut var_name = None;
+            let mut keys = vec![];
+            for pair in target.into_inner() {
+                match pair.as_rule() {
+                    Rule::Atom => var_name = Some(pair.as_str().trim().to_string()),
+                    Rule::PropertyLookup => keys.extend(
+                        pair.into_inner()
+                            .filter(|pair| pair.as_rule() == Rule::PropertyKeyName)
+                            .map(|pair| pair.as_str().to_string()),
+                    ),
+                    _ => {}
+                }
+            }
+            match (var_name, keys.as_slice()) {
+                (Some(var_name), [key]) => Ok(SetItem::Property {
+                    var_name,
+                    key: key.clone(),
+                    value: parse_expr(value.into_inner())?,
+                }),
+                _ => Err(ParseError::Unsupported(format!(
+                    "SET of nested properties {}",
+                    text
+                ))),
+            }
+        }
+        (Rule::Variable, Some(value)) if value.as_rule() == Rule::NodeLabels => {
+            Ok(SetItem::Labels {
+                var_name: parse_variable(target)?,
+                labels: value.into_inner().filter_map(parse_node_label).collect(),
+            })
+        }
+        (Rule::Variable, Some(value)) => {
+            let operator = &text[target.as_span().end() - start..value.as_span().start() - start];
+            let var_name = parse_variable(target)?;
+            if !operator.contains("+=") {
+                return Err(ParseError::Unsupported(format!(
+                    "SET {} = ..., properties can't be removed from a temporal graph, use SET {} += {{...}}",
+                    var_name, var_name
+                )));
+            }
+            let props = value
+                .into_inner()
+                .flatten()
+                .find(|pair| pair.as_rule() == Rule::MapLiteral)
+                .ok_or_else(|| {
+                    ParseError::Unsupported(format!("SET {} += expects a map literal", var_name))
+                })
+                .and_then(parse_map_literal)?;
+            Ok(SetItem::Properties { var_name, props })
+        }
+        _ => Err(ParseError::SyntaxError(format!(
+            "invalid SET item {}",
+            text
+        ))),
+    }
+}
+
 pub fn parse_return(pair: Pair<Rule>) -> Result<Return, ParseError> {
     let mut items = Vec::new();
     let mut all = false;
@@ -589,6 +743,16 @@ fn parse_rel_detail(
                 },
                 None => {}
             },
+            Rule::RangeLiteral => {
+                rel_pattern.var_length = Some(parse_range_literal(pair)?);
+            }
+            Rule::TEMPORAL => match rel_pattern.var_length.as_mut() {
+                Some(var_length) => var_length.temporal = true,
+                None => return Err(ParseError::SyntaxError(
+                    "TEMPORAL requires a variable length relationship e.g. -[e*1..3 TEMPORAL]->"
+                        .to_string(),
+                )),
+            },
             rule => return unsupported("parse_rel_detail", &rule),
         }
     }
@@ -601,6 +765,33 @@ fn parse_rel_detail(
     Ok(rel_pattern)
 }
 
+fn parse_range_literal(pair: Pair<'_, Rule>) -> Result<VarLength, ParseError> {
+    let mut lower = None;
+    let mut upper = None;
+    let mut is_range = false;
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::IntegerLiteral if is_range => upper = Some(pair.as_str().parse::<usize>()?),
+            Rule::IntegerLiteral => lower = Some(pair.as_str().parse::<usize>()?),
+            Rule::DOT_DOT => is_range = true,
+            Rule::SP => {}
+            rule => return unsupported("parse_range_literal", &rule),
+        }
+    }
+
+    // *n is exactly n hops, * and *n.. have no upper bound
+    let min = lower.unwrap_or(1);
+    let max = if is_range { upper } else { lower };
+    if max.is_some_and(|max| max < min) {
+        return Err(ParseError::SyntaxError(format!(
+            "invalid relationship range *{}..{}",
+            min,
+            max.unwrap_or_default()
+        )));
+    }
+    Ok(VarLength::new(min, max))
+}
+
 fn parse_rel_pattern(
     pair: Pair<'_, Rule>,
     un_named_counter: &mut usize,
@@ -664,6 +855,7 @@ fn parse_node_pattern(
 
     if node.name.is_empty() {
         node.name = format!("n_{}", un_named_counter);
+        node.anonymous = true;
         *un_named_counter += 1;
     }
 
@@ -1023,7 +1215,8 @@ mod test {
             Ok(NodePattern {
                 name: "n_0".to_string(),
                 labels: vec![],
-                props: None
+                props: None,
+                anonymous: true
             })
         );
     }
@@ -1041,7 +1234,8 @@ mod test {
             Ok(NodePattern {
                 name: "n".to_string(),
                 labels: vec![],
-                props: None
+                props: None,
+                anonymous: false
             })
         );
     }
@@ -1059,7 +1253,8 @@ mod test {
             Ok(NodePattern {
                 name: "n".to_string(),
                 labels: vec!["Person".to_string()],
-                props: None
+                props: None,
+                anonymous: false
             })
         );
     }
@@ -1084,7 +1279,8 @@ mod test {
                     )]
                     .into_iter()
                     .collect()
-                )
+                ),
+                anonymous: false
             })
         );
     }
@@ -1110,7 +1306,8 @@ mod test {
                     )]
                     .into_iter()
                     .collect()
-                )
+                ),
+                var_length: None,
             })
         );
     }
@@ -1129,7 +1326,8 @@ mod test {
                 name: "r".to_string(),
                 direction: Direction::OUT,
                 rel_types: vec!["KNOWS".to_string()],
-                props: None
+                props: None,
+                var_length: None,
             })
         );
     }
@@ -1161,6 +1359,91 @@ mod test {
         assert_eq!(rel, Ok(RelPattern::undirected("r")));
     }
 
+    #[test]
+    fn check_edge_pattern_var_length() {
+        let parse = |input: &str| {
+            let pairs = CypherParser::parse(Rule::RelationshipPattern, input).unwrap();
+            parse_rel_pattern(pairs.into_iter().next().unwrap(), &mut 0).map(|rel| rel.var_length)
+        };
+
+        assert_eq!(parse("-[r]->"), Ok(None));
+        assert_eq!(parse("-[r*]->"), Ok(Some(VarLength::new(1, None))));
+        assert_eq!(parse("-[r*3]->"), Ok(Some(VarLength::new(3, Some(3)))));
+        assert_eq!(parse("-[r*..3]->"), Ok(Some(VarLength::new(1, Some(3)))));
+        assert_eq!(parse("-[r*2..]->"), Ok(Some(VarLength::new(2, None))));
+        assert_eq!(
+            parse("-[r:KNOWS*1..4]-"),
+            Ok(Some(VarLength::new(1, Some(4))))
+        );
+        assert_eq!(
+            parse("<-[*1..4 temporal]-"),
+            Ok(Some(VarLength::temporal(1, Some(4))))
+        );
+        assert_eq!(
+            parse("-[r*1..4 TEMPORAL {weight: 1}]->"),
+            Ok(Some(VarLength::temporal(1, Some(4))))
+        );
+        assert!(parse("-[r*3..1]->").is_err());
+        assert!(parse("-[r TEMPORAL]->").is_err());
+    }
+
+    #[test]
+    fn parse_updating_clauses() {
+        let query = parse_cypher(
+            "MATCH (a {name: 'Alice'}) CREATE (a)-[e:follows {weight: 1.0}]->(b:Person {id: 4}) SET a.age = 31, b:Admin DETACH DELETE a",
+        )
+        .unwrap();
+        let clauses = query.clauses();
+        assert_eq!(clauses.len(), 4);
+        let Clause::Create(Create { pattern }) = &clauses[1] else {
+            panic!("expected CREATE, got {:?}", clauses[1]);
+        };
+        let (rel, node) = &pattern.0[0].rel_chain[0];
+        assert_eq!(rel.rel_types, vec!["follows".to_string()]);
+        assert_eq!(node.labels, vec!["Person".to_string()]);
+        assert_eq!(
+            clauses[2],
+            Clause::Set(Set {
+                items: vec![
+                    SetItem::Property {
+                        var_name: "a".to_string(),
+                        key: "age".to_string(),
+                        value: Expr::Literal(Literal::Int(31)),
+                    },
+                    SetItem::Labels {
+                        var_name: "b".to_string(),
+                        labels: vec!["Admin".to_string()],
+                    },
+                ]
+            })
+        );
+        assert_eq!(
+            clauses[3],
+            Clause::Delete(Delete {
+                detach: true,
+                exprs: vec![Expr::var("a", Vec::<String>::new())],
+            })
+        );
+
+        let query = parse_cypher(
+            "MERGE (a {id: 1}) ON CREATE SET a.new = true ON MATCH SET a += {seen: 2}",
+        )
+        .unwrap();
+        let Clause::Merge(merge) = &query.clauses()[0] else {
+            panic!("expected MERGE, got {:?}", query.clauses()[0]);
+        };
+        assert_eq!(merge.on_create.len(), 1);
+        assert_eq!(
+            merge.on_match,
+            vec![SetItem::Properties {
+                var_name: "a".to_string(),
+                props: [("seen".to_string(), Expr::Literal(Literal::Int(2)))].into(),
+            }]
+        );
+
+        assert!(parse_cypher("MATCH (a) REMOVE a.name").is_err());
+    }
+
     #[test]
     fn map_literal() {
         let input = "{a: 1, b: true}";
@@ -1677,19 +1960,22 @@ mod test {
                         NodePattern {
                             name: "a".to_string(),
                             labels: vec!["Person".to_string()],
-                            props: None
+                            props: None,
+                            anonymous: false
                         },
                         vec![(
                             RelPattern {
                                 name: "r".to_string(),
                                 direction: Direction::OUT,
                                 rel_types: vec!["KNOWS".to_string()],
-                                props: None
+                                props: None,
+                                var_length: None,
                             },
                             NodePattern {
                                 name: "b".to_string(),
                                 labels: vec!["Person".to_string()],
-                                props: None
+                                props: None,
+                                anonymous: false
                             }
                         )]
                     )]),
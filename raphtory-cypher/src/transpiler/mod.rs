@@ -1,9 +1,8 @@
-use std::{
-    collections::{HashMap, HashSet},
-    sync::Arc,
-};
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "storage")]
+use std::sync::Arc;
 
-use crate::parser::ast::*;
+use crate::parser::{ast::*, ParseError};
 
 use arrow_schema::{Fields, Schema};
 
@@ -14,9 +13,11 @@ use raphtory::{
         Direction,
     },
     db::{api::properties::internal::ConstPropertiesOps, graph::node::NodeView},
-    disk_graph::DiskGraphStorage,
     prelude::*,
 };
+
+#[cfg(feature = "storage")]
+use raphtory::disk_graph::DiskGraphStorage;
 use sqlparser::ast::{
     self as sql_ast, DuplicateTreatment, FunctionArgumentList, GroupByExpr, OrderByExpr, SetExpr,
     TableAlias, WildcardAdditionalOptions, With,
@@ -24,7 +25,53 @@ use sqlparser::ast::{
 
 mod exprs;
 
-pub fn to_sql(query: Query, graph: &DiskGraphStorage) -> sql_ast::Statement {
+/// The edge tables a query is transpiled against, one per layer
+pub trait EdgeTables {
+    /// Names of the layers, which are also the names of the edge tables
+    fn layer_names(&self) -> Vec<String>;
+
+    /// Columns of the edge table for `layer_name` or `None` if there is no such layer
+    fn layer_fields(&self, layer_name: &str) -> Option<Fields>;
+}
+
+#[cfg(feature = "storage")]
+impl EdgeTables for DiskGraphStorage {
+    fn layer_names(&self) -> Vec<String> {
+        self.as_ref().layer_names().to_vec()
+    }
+
+    fn layer_fields(&self, layer_name: &str) -> Option<Fields> {
+        let layer_id = self.as_ref().find_layer_id(layer_name)?;
+        full_layer_fields(self, layer_id)
+    }
+}
+
+/// The default maximum number of hops of variable length relationships (`-[e*]->`, `-[e*1..3]->`)
+pub const DEFAULT_MAX_PATH_LENGTH: usize = 5;
+
+pub fn to_sql(query: Query, graph: &impl EdgeTables) -> Result<sql_ast::Statement, ParseError> {
+    to_sql_with_max_path_length(query, graph, DEFAULT_MAX_PATH_LENGTH)
+}
+
+/// Transpile `query` with variable length relationships of at most `max_path_length` hops
+///
+/// Relationships without an upper bound are expanded up to `max_path_length` hops, queries with
+/// a larger upper bound are rejected.
+pub fn to_sql_with_max_path_length(
+    query: Query,
+    graph: &impl EdgeTables,
+    max_path_length: usize,
+) -> Result<sql_ast::Statement, ParseError> {
+    if query
+        .clauses()
+        .iter()
+        .any(|clause| !matches!(clause, Clause::Match(_) | Clause::Return(_)))
+    {
+        return Err(ParseError::Unsupported(
+            "updating clauses can't be transpiled to SQL, use run_cypher_update".to_string(),
+        ));
+    }
+
     let query = bind_unbound_pattern_filters(query);
     let query = unbind_unused_binds(query);
 
@@ -35,14 +82,14 @@ pub fn to_sql(query: Query, graph: &DiskGraphStorage) -> sql_ast::Statement {
         .map(|node_pat| node_pat.name.clone())
         .collect::<Vec<_>>();
 
-    let with = parse_rels_to_ctes(&query, graph);
-    sql_ast::Statement::Query(Box::new(sql_ast::Query {
+    let with = parse_rels_to_ctes(&query, graph, max_path_length)?;
+    Ok(sql_ast::Statement::Query(Box::new(sql_ast::Query {
         // WITH (common table expressions, or CTEs)
         with: Some(with),
         // SELECT or UNION / EXCEPT / INTERSECT
-        body: parse_select_body(&query, graph, &rel_binds, &node_binds),
+        body: parse_select_body(&query, graph, &rel_binds, &node_binds)?,
         // ORDER BY
-        order_by: parse_order_by(&query, &rel_binds, &node_binds),
+        order_by: parse_order_by(&query, &rel_binds, &node_binds)?,
         // `LIMIT { <N> | ALL }`
         limit: exprs::parse_limit(&query),
 
@@ -58,7 +105,7 @@ pub fn to_sql(query: Query, graph: &DiskGraphStorage) -> sql_ast::Statement {
         // `FOR JSON { AUTO | PATH } [ , INCLUDE_NULL_VALUES ]`
         // (MSSQL-specific)
         for_clause: None,
-    }))
+    })))
 }
 
 fn bind_unbound_pattern_filters(mut query: Query) -> Query {
@@ -68,6 +115,7 @@ fn bind_unbound_pattern_filters(mut query: Query) -> Query {
         c += 1;
         if node_pat.props.is_some() {
             node_pat.name = format!("a_{}", c);
+            node_pat.anonymous = false;
         }
     }
     query
@@ -79,8 +127,8 @@ fn unbind_unused_binds(mut query: Query) -> Query {
 
     let node_binds = query
         .node_patterns()
+        .filter(|node_pat| is_bound(node_pat))
         .map(|node_pat| node_pat.name.clone())
-        .filter(|name| is_bound_str(name))
         .collect::<HashSet<_>>();
 
     let nodes_in_return = query
@@ -134,7 +182,7 @@ fn unbind_unused_binds(mut query: Query) -> Query {
     query.node_patterns_mut().fold(
         (max_n, HashMap::new()),
         |(c, mut bind_table), node_pattern| {
-            if is_bound_str(&node_pattern.name)
+            if is_bound(node_pattern)
                 && !nodes_in_return.contains(&node_pattern.name)
                 && !nodes_in_where.contains(&node_pattern.name)
                 && !nodes_with_pattern_props.contains(&node_pattern.name)
@@ -146,10 +194,12 @@ fn unbind_unused_binds(mut query: Query) -> Query {
                         entry.insert(new_name.clone());
 
                         node_pattern.name = new_name;
+                        node_pattern.anonymous = true;
                         (c + 1, bind_table)
                     }
                     std::collections::hash_map::Entry::Occupied(entry) => {
                         node_pattern.name = entry.get().clone();
+                        node_pattern.anonymous = true;
                         (c, bind_table)
                     }
                 }
@@ -177,46 +227,38 @@ fn max_bind_count(query: &Query) -> usize {
         .unwrap_or(0)
 }
 
-fn parse_order_by(query: &Query, rel_binds: &[String], node_binds: &[String]) -> Vec<OrderByExpr> {
-    query
-        .clauses()
-        .into_iter()
-        .flat_map(|clause| match clause {
-            Clause::Return(Return {
-                order_by: Some(items),
-                ..
-            }) => items
-                .exprs
-                .iter()
-                .map(|(expr, asc)| {
-                    let sql_expr = cypher_to_sql_expr(expr, rel_binds, node_binds, false);
-                    OrderByExpr {
-                        expr: sql_expr,
-                        asc: *asc,
-                        nulls_first: None,
-                    }
-                })
-                .collect(),
-            _ => vec![],
-        })
-        .collect()
+fn parse_order_by(
+    query: &Query,
+    rel_binds: &[String],
+    node_binds: &[String],
+) -> Result<Vec<OrderByExpr>, ParseError> {
+    let mut order_by = vec![];
+    for clause in query.clauses() {
+        if let Clause::Return(Return {
+            order_by: Some(items),
+            ..
+        }) = clause
+        {
+            for (expr, asc) in items.exprs.iter() {
+                let sql_expr = cypher_to_sql_expr(expr, rel_binds, node_binds, false)?;
+                order_by.push(OrderByExpr {
+                    expr: sql_expr,
+                    asc: *asc,
+                    nulls_first: None,
+                });
+            }
+        }
+    }
+    Ok(order_by)
 }
 
 fn scan_edges_as_sql_cte(
     layer_names: &[impl AsRef<str>],
     name: &impl AsRef<str>,
-    graph: &DiskGraphStorage,
-) -> sql_ast::Cte {
-    // fetch and merge the schemas
-
-    let schemas = layer_names
-        .iter()
-        .filter_map(|layer| graph.as_ref().find_layer_id(layer.as_ref()))
-        .filter_map(|layer_id| full_layer_fields(graph, layer_id))
-        .map(Schema::new);
-
+    graph: &impl EdgeTables,
+) -> Result<sql_ast::Cte, ParseError> {
     // this is the schema that all layers must match, any missing columns will be filled with NULLs
-    let schema = Schema::try_merge(schemas).expect("failed to merge schemas");
+    let schema = merged_layer_schema(layer_names, graph)?;
 
     let union_query = layer_names
         .iter()
@@ -232,9 +274,11 @@ fn scan_edges_as_sql_cte(
         .sorted_by(|(null_count1, _), (null_count2, _)| null_count1.cmp(null_count2))
         .map(|(_, query)| query)
         .reduce(query_union)
-        .unwrap();
+        .ok_or_else(|| {
+            ParseError::Unsupported(format!("no layers to scan for {}", name.as_ref()))
+        })?;
 
-    sql_ast::Cte {
+    Ok(sql_ast::Cte {
         alias: TableAlias {
             name: sql_ast::Ident::new(name.as_ref()),
             columns: vec![],
@@ -242,10 +286,77 @@ fn scan_edges_as_sql_cte(
         query: union_query,
         from: None,
         materialized: None,
+    })
+}
+
+fn merged_layer_schema(
+    layer_names: &[impl AsRef<str>],
+    graph: &impl EdgeTables,
+) -> Result<Schema, ParseError> {
+    let schemas = layer_names
+        .iter()
+        .filter_map(|layer| graph.layer_fields(layer.as_ref()))
+        .map(Schema::new);
+
+    Schema::try_merge(schemas).map_err(|err| ParseError::LayerSchemaMismatch(err.to_string()))
+}
+
+/// The columns of an undirected relationship with the node the match goes from and the node it
+/// goes to, `src` and `dst` keep the endpoints of the edge
+const FROM_COL: &str = "_from";
+const TO_COL: &str = "_to";
+
+/// An undirected relationship matches every edge in both orientations, we model it as the
+/// directed scan oriented from src to dst UNION ALL the same scan oriented from dst to src
+/// (self loops are only returned once)
+fn undirected_edges_cte(name: &str, directed_name: &str) -> sql_ast::Cte {
+    let oriented = |from: &str, to: &str| {
+        vec![
+            sql_ast::SelectItem::Wildcard(WildcardAdditionalOptions::default()),
+            sql_ast::SelectItem::ExprWithAlias {
+                expr: sql_ast::Expr::Identifier(sql_ast::Ident::new(from)),
+                alias: sql_ast::Ident::new(FROM_COL),
+            },
+            sql_ast::SelectItem::ExprWithAlias {
+                expr: sql_ast::Expr::Identifier(sql_ast::Ident::new(to)),
+                alias: sql_ast::Ident::new(TO_COL),
+            },
+        ]
+    };
+
+    let not_self_loop = sql_ast::Expr::BinaryOp {
+        left: Box::new(sql_ast::Expr::Identifier(sql_ast::Ident::new("src"))),
+        op: sql_ast::BinaryOperator::NotEq,
+        right: Box::new(sql_ast::Expr::Identifier(sql_ast::Ident::new("dst"))),
+    };
+
+    let forward = select_query_with_projection(oriented("src", "dst"), directed_name, None);
+    let reversed =
+        select_query_with_projection(oriented("dst", "src"), directed_name, Some(not_self_loop));
+
+    sql_ast::Cte {
+        alias: TableAlias {
+            name: sql_ast::Ident::new(name),
+            columns: vec![],
+        },
+        query: query_union(forward, reversed),
+        from: None,
+        materialized: None,
+    }
+}
+
+/// The column of `rel` to join on for the `src` or `dst` end of the match, undirected
+/// relationships join on their orientation
+fn endpoint_col<'a>(undirected_rels: &HashSet<String>, rel: &str, col: &'a str) -> &'a str {
+    match col {
+        "src" if undirected_rels.contains(rel) => FROM_COL,
+        "dst" if undirected_rels.contains(rel) => TO_COL,
+        col => col,
     }
 }
 
 // TODO: this needs to match the schema from EdgeListTableProvider
+#[cfg(feature = "storage")]
 fn full_layer_fields(graph: &DiskGraphStorage, layer_id: usize) -> Option<Fields> {
     let dt = graph.as_ref().layer(layer_id).edges_props_data_type();
     let arr_dt: arrow_schema::DataType = dt.clone().into();
@@ -302,14 +413,10 @@ fn query_union(q1: Box<sql_ast::Query>, q2: Box<sql_ast::Query>) -> Box<sql_ast:
 
 fn select_scan_query(
     layer_name: &str,
-    graph: &DiskGraphStorage,
+    graph: &impl EdgeTables,
     total_schema: Option<&Schema>,
 ) -> (usize, Box<sql_ast::Query>) {
-    let layer_id = graph
-        .as_ref()
-        .find_layer_id(layer_name)
-        .expect("layer not found");
-    let layer_schema = full_layer_fields(graph, layer_id);
+    let layer_schema = graph.layer_fields(layer_name);
 
     let projection_with_priority = total_schema
         .zip(layer_schema)
@@ -348,13 +455,26 @@ fn select_scan_query(
 
     (
         null_count,
-        select_query_with_projection(projection, layer_name),
+        select_query_with_projection(projection, layer_name, None),
     )
 }
 
 fn select_query_with_projection(
     projection: Vec<sql_ast::SelectItem>,
     from_name: &str,
+    selection: Option<sql_ast::Expr>,
+) -> Box<sql_ast::Query> {
+    let from = sql_ast::TableWithJoins {
+        relation: table_from_name(from_name),
+        joins: vec![],
+    };
+    select_query(projection, from, selection)
+}
+
+fn select_query(
+    projection: Vec<sql_ast::SelectItem>,
+    from: sql_ast::TableWithJoins,
+    selection: Option<sql_ast::Expr>,
 ) -> Box<sql_ast::Query> {
     Box::new(sql_ast::Query {
         // WITH (common table expressions, or CTEs)
@@ -369,14 +489,11 @@ fn select_query_with_projection(
             // INTO
             into: None,
             // FROM
-            from: vec![sql_ast::TableWithJoins {
-                relation: table_from_name(from_name),
-                joins: vec![],
-            }],
+            from: vec![from],
             // LATERAL VIEWs
             lateral_views: vec![],
             // WHERE
-            selection: None,
+            selection,
             // GROUP BY
             group_by: GroupByExpr::Expressions(vec![]),
             // CLUSTER BY (Hive)
@@ -415,7 +532,11 @@ fn select_query_with_projection(
     })
 }
 
-fn parse_rels_to_ctes(query: &Query, graph: &DiskGraphStorage) -> With {
+fn parse_rels_to_ctes(
+    query: &Query,
+    graph: &impl EdgeTables,
+    max_path_length: usize,
+) -> Result<With, ParseError> {
     // each rel can become a CTE
     // inside the cte
     // if the pattern has no layers -[e]- and the graph has one layer then we just select * from the layer
@@ -427,16 +548,41 @@ fn parse_rels_to_ctes(query: &Query, graph: &DiskGraphStorage) -> With {
 
     let mut cte_tables = vec![];
 
-    let layer_names = graph.as_ref().layer_names();
+    let layer_names = graph.layer_names();
 
     for rel in query.rel_patterns() {
-        // rewrite the conditions in a nicer way
-        if rel.rel_types.is_empty() {
-            // select * from layer
-            cte_tables.push(scan_edges_as_sql_cte(layer_names, &rel.name, graph))
+        // no layers means select * from every layer, otherwise UNION ALL the layers of the relation pattern
+        let rel_layers = if rel.rel_types.is_empty() {
+            &layer_names
+        } else {
+            &rel.rel_types
+        };
+
+        // variable length relationships scan the edges into an intermediate table and build the paths on top
+        let edges_name = if rel.var_length.is_some() {
+            format!("{}_edges", rel.name)
         } else {
-            // UNION ALL for all the layers of the relation pattern
-            cte_tables.push(scan_edges_as_sql_cte(&rel.rel_types, &rel.name, graph))
+            rel.name.clone()
+        };
+
+        if rel.direction == Direction::BOTH {
+            // scan the edges once and then expose them in both orientations under the rel name
+            let directed_name = format!("{}_directed", rel.name);
+            cte_tables.push(scan_edges_as_sql_cte(rel_layers, &directed_name, graph)?);
+            cte_tables.push(undirected_edges_cte(&edges_name, &directed_name));
+        } else {
+            cte_tables.push(scan_edges_as_sql_cte(rel_layers, &edges_name, graph)?);
+        }
+
+        if let Some(var_length) = rel.var_length.as_ref() {
+            let time_col = edge_time_column(rel, rel_layers, graph)?;
+            cte_tables.push(var_length_paths_cte(
+                rel,
+                var_length,
+                &edges_name,
+                &time_col,
+                max_path_length,
+            )?);
         }
     }
 
@@ -458,10 +604,250 @@ fn parse_rels_to_ctes(query: &Query, graph: &DiskGraphStorage) -> With {
         }
     }
 
-    With {
+    Ok(With {
         recursive: false,
         cte_tables,
+    })
+}
+
+/// The name of the column with the time of the edge updates, the first one after `id`, `layer_id`, `src` and `dst`
+fn edge_time_column(
+    rel: &RelPattern,
+    layer_names: &[String],
+    graph: &impl EdgeTables,
+) -> Result<String, ParseError> {
+    let time_cols = layer_names
+        .iter()
+        .filter_map(|layer| graph.layer_fields(layer))
+        .filter_map(|fields| fields.get(4).map(|field| field.name().to_string()))
+        .unique()
+        .collect::<Vec<_>>();
+    match time_cols.as_slice() {
+        [time_col] => Ok(time_col.clone()),
+        [] => Err(ParseError::Unsupported(format!(
+            "relationship {} has no edges with a time column",
+            rel.name
+        ))),
+        _ => Err(ParseError::Unsupported(format!(
+            "variable length relationship {} over layers with different time columns {:?}",
+            rel.name, time_cols
+        ))),
+    }
+}
+
+/// A variable length relationship is expanded into one chain of joins per path length,
+/// every row is a path with the `src` of the first hop, the `dst` of the last hop, the number of `hops`
+/// and the `id`, `layer_id` and time of every hop as lists, the time list is named after the time column of the edges
+///
+/// Paths without an upper bound are expanded up to `max_path_length` hops, longer upper bounds are
+/// an error, and zero length paths go from every node to itself.
+fn var_length_paths_cte(
+    rel: &RelPattern,
+    var_length: &VarLength,
+    edges_name: &str,
+    time_col: &str,
+    max_path_length: usize,
+) -> Result<sql_ast::Cte, ParseError> {
+    let max = var_length.max.unwrap_or(max_path_length);
+    if max < var_length.min {
+        return Err(ParseError::Unsupported(match var_length.max {
+            Some(_) => format!("empty range for relationship {}", rel.name),
+            None => format!(
+                "relationship {} needs at least {} hops, more than the maximum path length {}",
+                rel.name, var_length.min, max_path_length
+            ),
+        }));
     }
+    if max > max_path_length {
+        return Err(ParseError::Unsupported(format!(
+            "relationship {} has up to {} hops, more than the maximum path length {}",
+            rel.name, max, max_path_length
+        )));
+    }
+
+    let zero_length = (var_length.min == 0).then(|| zero_length_paths_query(time_col));
+    let paths = (var_length.min.max(1)..=max)
+        .map(|hops| fixed_length_paths_query(rel, var_length.temporal, edges_name, time_col, hops))
+        .chain(zero_length.map(Ok))
+        .reduce(|q1, q2| Ok(query_union(q1?, q2?)))
+        .ok_or_else(|| {
+            ParseError::Unsupported(format!("empty range for relationship {}", rel.name))
+        })??;
+
+    Ok(sql_ast::Cte {
+        alias: TableAlias {
+            name: sql_ast::Ident::new(&rel.name),
+            columns: vec![],
+        },
+        query: paths,
+        from: None,
+        materialized: None,
+    })
+}
+
+/// The paths without hops, from every node to itself with empty lists of edges
+fn zero_length_paths_query(time_col: &str) -> Box<sql_ast::Query> {
+    let node_id = || sql_ast::Expr::Identifier(sql_ast::Ident::new("id"));
+    let empty_list = |col: &str| sql_ast::SelectItem::ExprWithAlias {
+        expr: sql_ast::Expr::Function(sql_ast::Function {
+            name: sql_ast::ObjectName(vec![sql_ast::Ident::new("make_array")]),
+            args: sql_ast::FunctionArguments::List(FunctionArgumentList {
+                args: vec![],
+                duplicate_treatment: None,
+                clauses: vec![],
+            }),
+            over: None,
+            filter: None,
+            null_treatment: None,
+            within_group: vec![],
+        }),
+        alias: sql_ast::Ident::new(col),
+    };
+    let projection = vec![
+        sql_ast::SelectItem::ExprWithAlias {
+            expr: node_id(),
+            alias: sql_ast::Ident::new("src"),
+        },
+        sql_ast::SelectItem::ExprWithAlias {
+            expr: node_id(),
+            alias: sql_ast::Ident::new("dst"),
+        },
+        sql_ast::SelectItem::ExprWithAlias {
+            expr: sql_ast::Expr::Value(sql_ast::Value::Number("0".to_string(), false)),
+            alias: sql_ast::Ident::new("hops"),
+        },
+        empty_list("id"),
+        empty_list("layer_id"),
+        empty_list(time_col),
+    ];
+    select_query_with_projection(projection, "nodes", None)
+}
+
+fn fixed_length_paths_query(
+    rel: &RelPattern,
+    temporal: bool,
+    edges_name: &str,
+    time_col: &str,
+    hops: usize,
+) -> Result<Box<sql_ast::Query>, ParseError> {
+    let hop_names = (1..=hops).map(|i| format!("h{}", i)).collect::<Vec<_>>();
+    let first = &hop_names[0];
+    let last = &hop_names[hops - 1];
+    // the hops of undirected paths follow the orientation of the edges in the match
+    let (from_col, to_col) = if rel.direction == Direction::BOTH {
+        (FROM_COL, TO_COL)
+    } else {
+        ("src", "dst")
+    };
+
+    let hop_column = |hop: &str, col: &str| {
+        sql_ast::Expr::CompoundIdentifier(vec![sql_ast::Ident::new(hop), sql_ast::Ident::new(col)])
+    };
+    let hop_list = |col: &str| sql_ast::SelectItem::ExprWithAlias {
+        expr: sql_ast::Expr::Function(sql_ast::Function {
+            name: sql_ast::ObjectName(vec![sql_ast::Ident::new("make_array")]),
+            args: sql_ast::FunctionArguments::List(FunctionArgumentList {
+                args: hop_names
+                    .iter()
+                    .map(|hop| {
+                        sql_ast::FunctionArg::Unnamed(sql_ast::FunctionArgExpr::Expr(hop_column(
+                            hop, col,
+                        )))
+                    })
+                    .collect(),
+                duplicate_treatment: None,
+                clauses: vec![],
+            }),
+            over: None,
+            filter: None,
+            null_treatment: None,
+            within_group: vec![],
+        }),
+        alias: sql_ast::Ident::new(col),
+    };
+
+    let projection = vec![
+        sql_ast::SelectItem::ExprWithAlias {
+            expr: hop_column(first, from_col),
+            alias: sql_ast::Ident::new("src"),
+        },
+        sql_ast::SelectItem::ExprWithAlias {
+            expr: hop_column(last, to_col),
+            alias: sql_ast::Ident::new("dst"),
+        },
+        sql_ast::SelectItem::ExprWithAlias {
+            expr: sql_ast::Expr::Value(sql_ast::Value::Number(hops.to_string(), false)),
+            alias: sql_ast::Ident::new("hops"),
+        },
+        hop_list("id"),
+        hop_list("layer_id"),
+        hop_list(time_col),
+    ];
+
+    let joins = hop_names
+        .iter()
+        .tuple_windows()
+        .map(|(prev, next)| sql_ast::Join {
+            relation: aliased_table(edges_name, next),
+            join_operator: sql_ast::JoinOperator::Inner(sql_ast::JoinConstraint::On(
+                sql_ast::Expr::BinaryOp {
+                    left: Box::new(hop_column(prev, to_col)),
+                    op: sql_ast::BinaryOperator::Eq,
+                    right: Box::new(hop_column(next, from_col)),
+                },
+            )),
+        })
+        .collect();
+
+    // the same edge can't be used twice in a path
+    let unique_edges = hop_names
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| Expr::Nested(Box::new(unique_edge_filter(a, b))));
+
+    let temporal_order =
+        hop_names
+            .iter()
+            .tuple_windows()
+            .filter(|_| temporal)
+            .map(|(prev, next)| Expr::BinOp {
+                op: BinOpType::Lte,
+                left: Box::new(Expr::var(prev, [time_col])),
+                right: Box::new(Expr::var(next, [time_col])),
+            });
+
+    // properties on the pattern must hold for every hop
+    let hop_props = hop_names.iter().flat_map(|hop| {
+        rel.props.iter().flat_map(move |props| {
+            props.iter().map(move |(prop, expr)| {
+                Expr::eq(
+                    Expr::Var {
+                        var_name: hop.clone(),
+                        attrs: vec![prop.clone()],
+                    },
+                    expr.clone(),
+                )
+            })
+        })
+    });
+
+    let selection = unique_edges
+        .chain(temporal_order)
+        .chain(hop_props)
+        .map(|expr| cypher_to_sql_expr(&expr, &[], &[], false))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .reduce(|a, b| sql_ast::Expr::BinaryOp {
+            left: Box::new(a),
+            op: sql_ast::BinaryOperator::And,
+            right: Box::new(b),
+        });
+
+    let from = sql_ast::TableWithJoins {
+        relation: aliased_table(edges_name, first),
+        joins,
+    };
+    Ok(select_query(projection, from, selection))
 }
 
 fn node_scan_cte(node: &NodePattern) -> sql_ast::Cte {
@@ -475,6 +861,7 @@ fn node_scan_cte(node: &NodePattern) -> sql_ast::Cte {
                 WildcardAdditionalOptions::default(),
             )],
             "nodes",
+            None,
         ),
         from: None,
         materialized: None,
@@ -483,20 +870,20 @@ fn node_scan_cte(node: &NodePattern) -> sql_ast::Cte {
 
 fn parse_select_body(
     query: &Query,
-    _graph: &DiskGraphStorage,
+    _graph: &impl EdgeTables,
     rel_binds: &[String],
     node_binds: &[String],
-) -> Box<SetExpr> {
+) -> Result<Box<SetExpr>, ParseError> {
     let order_by = vec![];
 
-    let (from_tables, rel_uniqueness_filters) = parse_tables_2(query);
+    let (from_tables, rel_uniqueness_filters) = parse_tables_2(query)?;
 
-    Box::new(SetExpr::Select(Box::new(sql_ast::Select {
+    Ok(Box::new(SetExpr::Select(Box::new(sql_ast::Select {
         distinct: None,
         // MSSQL syntax: `TOP (<N>) [ PERCENT ] [ WITH TIES ]`
         top: None,
         // projection expressions
-        projection: sql_projection(query, &rel_binds, &node_binds),
+        projection: sql_projection(query, &rel_binds, &node_binds)?,
         // INTO
         into: None,
         // FROM
@@ -504,7 +891,7 @@ fn parse_select_body(
         // LATERAL VIEWs
         lateral_views: vec![],
         // WHERE
-        selection: where_expr(query, rel_uniqueness_filters, &rel_binds, &node_binds),
+        selection: where_expr(query, rel_uniqueness_filters, &rel_binds, &node_binds)?,
         // GROUP BY
         group_by: GroupByExpr::Expressions(vec![]),
         // CLUSTER BY (Hive)
@@ -522,7 +909,7 @@ fn parse_select_body(
         value_table_mode: None,
         window_before_qualify: false,
         connect_by: None,
-    })))
+    }))))
 }
 
 fn rel_names(query: &Query) -> Vec<String> {
@@ -533,14 +920,14 @@ fn all_bound_nodes(query: &Query) -> impl Iterator<Item = &NodePattern> + '_ {
     query.node_patterns().filter(|&node_pat| is_bound(node_pat))
 }
 
-fn parse_tables_2(query: &Query) -> (Vec<sql_ast::TableWithJoins>, Vec<Expr>) {
+fn parse_tables_2(query: &Query) -> Result<(Vec<sql_ast::TableWithJoins>, Vec<Expr>), ParseError> {
     let mut joins = vec![];
     let graph = query_to_graph(query);
 
     let first = query
         .node_patterns()
         .next()
-        .expect("unexpected! no node patterns");
+        .ok_or_else(|| ParseError::Unsupported("query without node patterns".to_string()))?;
     // walk the graph in depth first fashion and add the nodes as joins
     let edge_counts = graph
         .nodes()
@@ -549,20 +936,46 @@ fn parse_tables_2(query: &Query) -> (Vec<sql_ast::TableWithJoins>, Vec<Expr>) {
         .count();
 
     if edge_counts > 0 {
-        let first_edge = query
-            .rel_patterns()
-            .next()
-            .expect("unexpected! no edge patterns");
+        let first_edge = query.rel_patterns().next().ok_or_else(|| {
+            ParseError::Unsupported("query without relationship patterns".to_string())
+        })?;
 
         let mut seen: HashSet<VID> = HashSet::new();
 
-        let mut stack = vec![graph
-            .node(first_edge.name.as_str())
-            .expect("edge not found")];
+        let mut stack = vec![graph.node(first_edge.name.as_str()).ok_or_else(|| {
+            ParseError::Unsupported(format!("relationship {} not found", first_edge.name))
+        })?];
 
-        let mut last_edge_dir = first_edge.direction;
+        // undirected relationships are exposed in both orientations by their CTE so they join like outgoing ones
+        let mut last_edge_out = first_edge.direction != Direction::IN;
         let mut last_edge: Option<NodeView<Graph>> = None;
 
+        // single undirected edges are joined on their orientation, paths go from src to dst
+        let undirected_rels = query
+            .rel_patterns()
+            .filter(|rel| rel.direction == Direction::BOTH && rel.var_length.is_none())
+            .map(|rel| rel.name.clone())
+            .collect::<HashSet<_>>();
+        let join = |left: &str, left_col: &str, right: &str, right_col: &str| {
+            make_sql_join(
+                left,
+                endpoint_col(&undirected_rels, left, left_col),
+                right,
+                endpoint_col(&undirected_rels, right, right_col),
+            )
+        };
+
+        // paths are lists of edges, the uniqueness filters between single edges don't apply to them
+        let var_length_rels = query
+            .rel_patterns()
+            .filter(|rel| rel.var_length.is_some())
+            .map(|rel| rel.name.clone())
+            .collect::<HashSet<_>>();
+
+        let bound_nodes = all_bound_nodes(query)
+            .map(|node| node.name.clone())
+            .collect::<HashSet<_>>();
+
         let mut additional_filters = vec![];
 
         while !stack.is_empty() {
@@ -578,59 +991,57 @@ fn parse_tables_2(query: &Query) -> (Vec<sql_ast::TableWithJoins>, Vec<Expr>) {
                 let edge = graph
                     .edge(&parent.name(), &n.name())
                     .or_else(|| graph.edge(&n.name(), &parent.name()))
-                    .expect("surprisingly edge not found!");
+                    .ok_or_else(|| {
+                        ParseError::Unsupported(format!(
+                            "no relationship between {} and {}",
+                            parent.name(),
+                            n.name()
+                        ))
+                    })?;
 
                 let dir = if (edge.src().name(), edge.dst().name()) == (parent.name(), n.name()) {
                     Dir::Out
-                } else if (edge.src().name(), edge.dst().name()) == (n.name(), parent.name()) {
-                    Dir::Into
                 } else {
-                    panic!("unexpected edge direction");
+                    Dir::Into
                 };
 
                 if let Some(Prop::Bool(out)) = n.get_const_prop(0) {
                     // this is an edge
-
-                    let current_edge_dir = if out { Direction::OUT } else { Direction::IN };
-                    if !is_bound_str(&parent.name()) {
+                    if !bound_nodes.contains(&parent.name()) {
                         if let Some(ref last_edge) = last_edge {
-                            let (from, to) = match (last_edge_dir, current_edge_dir) {
-                                (Direction::OUT, Direction::OUT) => ("dst", "src"),
-                                (Direction::OUT, Direction::IN) => ("dst", "dst"),
-                                (Direction::IN, Direction::OUT) => ("src", "src"),
-                                (Direction::IN, Direction::IN) => ("src", "dst"),
-                                _ => unimplemented!("both direction not supported"),
+                            let (from, to) = match (last_edge_out, out) {
+                                (true, true) => ("dst", "src"),
+                                (true, false) => ("dst", "dst"),
+                                (false, true) => ("src", "src"),
+                                (false, false) => ("src", "dst"),
                             };
 
-                            joins.push(make_sql_join(&last_edge.name(), from, &n.name(), to));
+                            joins.push(join(&last_edge.name(), from, &n.name(), to));
                         }
                     } else {
                         // parent is node
                         match dir {
-                            Dir::Out => {
-                                joins.push(make_sql_join(&parent.name(), "id", &n.name(), "src"))
-                            }
-                            Dir::Into => {
-                                joins.push(make_sql_join(&parent.name(), "id", &n.name(), "dst"))
-                            }
+                            Dir::Out => joins.push(join(&parent.name(), "id", &n.name(), "src")),
+                            Dir::Into => joins.push(join(&parent.name(), "id", &n.name(), "dst")),
                         }
                     }
 
                     if let Some(ref last_edge) = last_edge {
-                        additional_filters.push(unique_edge_filter(&last_edge.name(), &n.name()));
+                        if !var_length_rels.contains(&last_edge.name())
+                            && !var_length_rels.contains(&n.name())
+                        {
+                            additional_filters
+                                .push(unique_edge_filter(&last_edge.name(), &n.name()));
+                        }
                     }
                     child_edges.push(n.name());
-                    last_edge_dir = current_edge_dir;
+                    last_edge_out = out;
                 } else {
                     // node with edge parent
-                    if is_bound_str(&n.name()) {
+                    if bound_nodes.contains(&n.name()) {
                         match dir {
-                            Dir::Out => {
-                                joins.push(make_sql_join(&parent.name(), "dst", &n.name(), "id"))
-                            }
-                            Dir::Into => {
-                                joins.push(make_sql_join(&parent.name(), "src", &n.name(), "id"))
-                            }
+                            Dir::Out => joins.push(join(&parent.name(), "dst", &n.name(), "id")),
+                            Dir::Into => joins.push(join(&parent.name(), "src", &n.name(), "id")),
                         }
                     }
                 }
@@ -638,10 +1049,14 @@ fn parse_tables_2(query: &Query) -> (Vec<sql_ast::TableWithJoins>, Vec<Expr>) {
                 stack.push(n);
             }
 
-            let unique_edges = child_edges.iter().combinations(2).map(|perm_vec| {
-                let (a, b) = (perm_vec[0], perm_vec[1]);
-                unique_edge_filter(a, b)
-            });
+            let unique_edges = child_edges
+                .iter()
+                .filter(|name| !var_length_rels.contains(*name))
+                .combinations(2)
+                .map(|perm_vec| {
+                    let (a, b) = (perm_vec[0], perm_vec[1]);
+                    unique_edge_filter(a, b)
+                });
 
             additional_filters.extend(unique_edges);
 
@@ -655,14 +1070,14 @@ fn parse_tables_2(query: &Query) -> (Vec<sql_ast::TableWithJoins>, Vec<Expr>) {
             relation: table_from_name(&first_edge.name),
             joins,
         };
-        (vec![table], additional_filters)
+        Ok((vec![table], additional_filters))
     } else {
         // matching only one node
         let node_table = sql_ast::TableWithJoins {
             relation: table_from_name(&first.name),
             joins,
         };
-        (vec![node_table], vec![])
+        Ok((vec![node_table], vec![]))
     }
 }
 
@@ -708,7 +1123,8 @@ fn query_to_graph(query: &Query) -> Graph {
         ) in rel_chain
         {
             match direction {
-                Direction::OUT => {
+                // undirected relationships are laid out as outgoing, their CTE provides the reverse orientation
+                Direction::OUT | Direction::BOTH => {
                     graph
                         .add_edge(0, last_node.name.as_str(), edge.as_str(), NO_PROPS, None)
                         .expect("failed to add edge");
@@ -727,11 +1143,9 @@ fn query_to_graph(query: &Query) -> Graph {
                         .add_edge(0, edge.as_str(), last_node.name.as_str(), NO_PROPS, None)
                         .expect("failed to add node");
                 }
-                Direction::BOTH => unimplemented!("BOTH direction not supported"),
             }
 
-            assert_ne!(direction, &Direction::BOTH, "BOTH direction not supported");
-            let direction_flag = direction == &Direction::OUT;
+            let direction_flag = direction != &Direction::IN;
 
             let edge_node = graph.node(edge.as_str()).expect("edge not found");
             edge_node
@@ -746,11 +1160,7 @@ fn query_to_graph(query: &Query) -> Graph {
 }
 
 fn is_bound(node: &NodePattern) -> bool {
-    is_bound_str(&node.name)
-}
-
-fn is_bound_str(node: &str) -> bool {
-    !node.starts_with("n_")
+    !node.anonymous
 }
 
 fn make_sql_join(
@@ -777,6 +1187,20 @@ fn make_sql_join(
     }
 }
 
+fn aliased_table(name: &str, alias: &str) -> sql_ast::TableFactor {
+    sql_ast::TableFactor::Table {
+        name: sql_ast::ObjectName(vec![sql_ast::Ident::new(name.to_string())]),
+        alias: Some(TableAlias {
+            name: sql_ast::Ident::new(alias),
+            columns: vec![],
+        }),
+        args: None,
+        with_hints: vec![],
+        version: None,
+        partitions: vec![],
+    }
+}
+
 fn table_from_name(name: &str) -> sql_ast::TableFactor {
     sql_ast::TableFactor::Table {
         name: sql_ast::ObjectName(vec![sql_ast::Ident::new(name.to_string())]),
@@ -792,39 +1216,52 @@ fn sql_projection(
     query: &Query,
     rel_binds: &[String],
     node_binds: &[String],
-) -> Vec<sql_ast::SelectItem> {
+) -> Result<Vec<sql_ast::SelectItem>, ParseError> {
+    // the orientation columns of undirected relationships are only used to join them, returning
+    // the relationship returns the edge
+    let undirected_rels = query
+        .rel_patterns()
+        .filter(|rel| rel.direction == Direction::BOTH && rel.var_length.is_none())
+        .map(|rel| rel.name.clone())
+        .collect::<HashSet<_>>();
+    let wildcard_options = |undirected: bool| sql_ast::WildcardAdditionalOptions {
+        opt_exclude: undirected.then(|| {
+            sql_ast::ExcludeSelectItem::Multiple(vec![
+                sql_ast::Ident::new(FROM_COL),
+                sql_ast::Ident::new(TO_COL),
+            ])
+        }),
+        ..Default::default()
+    };
+
     query
         .clauses()
         .iter()
         .find(|clause| matches!(clause, Clause::Return(_)))
         .map(|clause| match clause {
-            Clause::Return(Return { all: true, .. }) => {
-                vec![sql_ast::SelectItem::Wildcard(
-                    WildcardAdditionalOptions::default(),
-                )]
-            }
+            Clause::Return(Return { all: true, .. }) => Ok(vec![sql_ast::SelectItem::Wildcard(
+                WildcardAdditionalOptions::default(),
+            )]),
             Clause::Return(Return { items, .. }) => items
                 .iter()
                 .map(|ret_i| {
-                    let expr = cypher_to_sql_expr(&ret_i.expr, rel_binds, node_binds, true);
-                    if let Some(name) = ret_i.as_name.as_ref() {
+                    let expr = cypher_to_sql_expr(&ret_i.expr, rel_binds, node_binds, true)?;
+                    Ok(if let Some(name) = ret_i.as_name.as_ref() {
                         sql_ast::SelectItem::ExprWithAlias {
                             expr,
                             alias: sql_ast::Ident::new(name),
                         }
                     } else if let sql_ast::Expr::QualifiedWildcard(name) = expr {
-                        sql_ast::SelectItem::QualifiedWildcard(
-                            name,
-                            sql_ast::WildcardAdditionalOptions::default(),
-                        )
+                        let undirected = undirected_rels.contains(&name.to_string());
+                        sql_ast::SelectItem::QualifiedWildcard(name, wildcard_options(undirected))
                     } else {
                         sql_ast::SelectItem::UnnamedExpr(expr)
-                    }
+                    })
                 })
                 .collect(),
             _ => unreachable!(),
         })
-        .unwrap_or_default()
+        .unwrap_or_else(|| Ok(vec![]))
 }
 
 fn where_expr(
@@ -832,7 +1269,7 @@ fn where_expr(
     rel_uniqueness_filters: Vec<Expr>,
     rel_binds: &[String],
     node_binds: &[String],
-) -> Option<sql_ast::Expr> {
+) -> Result<Option<sql_ast::Expr>, ParseError> {
     let rel_uniqueness_filters = rel_uniqueness_filters
         .iter()
         .map(|expr| cypher_to_sql_expr(expr, rel_binds, node_binds, false));
@@ -846,19 +1283,23 @@ fn where_expr(
                 pattern: Pattern(pat_parts),
                 ..
             }) => pat_parts.iter().flat_map(|part| {
-                part.rel_chain.iter().flat_map(|(rel, _)| {
-                    rel.props.iter().flat_map(|props| {
-                        props.iter().map(|(prop, expr)| {
-                            Expr::eq(
-                                Expr::Var {
-                                    var_name: rel.name.clone(),
-                                    attrs: vec![prop.clone()],
-                                },
-                                expr.clone(),
-                            )
+                // properties of variable length relationships are checked on every hop when building the paths
+                part.rel_chain
+                    .iter()
+                    .filter(|(rel, _)| rel.var_length.is_none())
+                    .flat_map(|(rel, _)| {
+                        rel.props.iter().flat_map(|props| {
+                            props.iter().map(|(prop, expr)| {
+                                Expr::eq(
+                                    Expr::Var {
+                                        var_name: rel.name.clone(),
+                                        attrs: vec![prop.clone()],
+                                    },
+                                    expr.clone(),
+                                )
+                            })
                         })
                     })
-                })
             }),
             _ => unreachable!(),
         })
@@ -889,15 +1330,17 @@ fn where_expr(
         _ => None,
     });
 
-    where_exprs
+    let exprs = where_exprs
         .chain(rel_exprs)
         .chain(rel_uniqueness_filters)
         .chain(node_exprs)
-        .reduce(|a, b| sql_ast::Expr::BinaryOp {
-            left: Box::new(a),
-            op: sql_ast::BinaryOperator::And,
-            right: Box::new(b),
-        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(exprs.into_iter().reduce(|a, b| sql_ast::Expr::BinaryOp {
+        left: Box::new(a),
+        op: sql_ast::BinaryOperator::And,
+        right: Box::new(b),
+    }))
 }
 
 fn cypher_unary_op_to_sql(op: &UnaryOpType) -> sql_ast::UnaryOperator {
@@ -907,8 +1350,8 @@ fn cypher_unary_op_to_sql(op: &UnaryOpType) -> sql_ast::UnaryOperator {
     }
 }
 
-fn cypher_binary_op_to_sql(op: &BinOpType) -> sql_ast::BinaryOperator {
-    match op {
+fn cypher_binary_op_to_sql(op: &BinOpType) -> Result<sql_ast::BinaryOperator, ParseError> {
+    Ok(match op {
         BinOpType::Add => sql_ast::BinaryOperator::Plus,
         BinOpType::Sub => sql_ast::BinaryOperator::Minus,
         BinOpType::Mul => sql_ast::BinaryOperator::Multiply,
@@ -923,9 +1366,13 @@ fn cypher_binary_op_to_sql(op: &BinOpType) -> sql_ast::BinaryOperator {
         BinOpType::And => sql_ast::BinaryOperator::And,
         BinOpType::Or => sql_ast::BinaryOperator::Or,
         BinOpType::Xor => sql_ast::BinaryOperator::Xor,
-        BinOpType::In => unimplemented!("IN operator handled in cypher_to_sql_expr"),
-        _ => unimplemented!("unsupported binary operator {:?}", op),
-    }
+        _ => {
+            return Err(ParseError::Unsupported(format!(
+                "binary operator {:?} in this position",
+                op
+            )))
+        }
+    })
 }
 
 fn cypher_to_sql_expr(
@@ -933,8 +1380,8 @@ fn cypher_to_sql_expr(
     rel_binds: &[String],
     node_binds: &[String],
     allow_wildcard_edges: bool,
-) -> sql_ast::Expr {
-    match expr {
+) -> Result<sql_ast::Expr, ParseError> {
+    let sql_expr = match expr {
         Expr::Var { var_name, attrs } => {
             if attrs.is_empty() {
                 if allow_wildcard_edges {
@@ -969,19 +1416,19 @@ fn cypher_to_sql_expr(
             op: BinOpType::Contains,
             left,
             right,
-        } => sql_like(right, left, |s| format!("%{}%", s), rel_binds, node_binds),
+        } => sql_like(right, left, |s| format!("%{}%", s), rel_binds, node_binds)?,
         // starts_with
         Expr::BinOp {
             op: BinOpType::StartsWith,
             left,
             right,
-        } => sql_like(right, left, |s| format!("{}%", s), rel_binds, node_binds),
+        } => sql_like(right, left, |s| format!("{}%", s), rel_binds, node_binds)?,
         // ends_with
         Expr::BinOp {
             op: BinOpType::EndsWith,
             left,
             right,
-        } => sql_like(right, left, |s| format!("%{}", s), rel_binds, node_binds),
+        } => sql_like(right, left, |s| format!("%{}", s), rel_binds, node_binds)?,
         // in
         Expr::BinOp {
             op: BinOpType::In,
@@ -999,23 +1446,28 @@ fn cypher_to_sql_expr(
                             false,
                         )
                     })
-                    .collect::<Vec<_>>(),
-                expr => unimplemented!("unexpected right hand side of IN operator {:?}", expr),
+                    .collect::<Result<Vec<_>, _>>()?,
+                expr => {
+                    return Err(ParseError::Unsupported(format!(
+                        "right hand side of IN operator {:?}, expected a list literal",
+                        expr
+                    )))
+                }
             };
             sql_ast::Expr::InList {
-                expr: Box::new(cypher_to_sql_expr(left, rel_binds, node_binds, false)),
+                expr: Box::new(cypher_to_sql_expr(left, rel_binds, node_binds, false)?),
                 list: sql_list,
                 negated: false,
             }
         }
         Expr::BinOp { op, left, right } => sql_ast::Expr::BinaryOp {
-            left: Box::new(cypher_to_sql_expr(left, rel_binds, node_binds, false)),
-            op: cypher_binary_op_to_sql(op),
-            right: Box::new(cypher_to_sql_expr(right, rel_binds, node_binds, false)),
+            left: Box::new(cypher_to_sql_expr(left, rel_binds, node_binds, false)?),
+            op: cypher_binary_op_to_sql(op)?,
+            right: Box::new(cypher_to_sql_expr(right, rel_binds, node_binds, false)?),
         },
         Expr::UnaryOp { op, expr } => sql_ast::Expr::UnaryOp {
             op: cypher_unary_op_to_sql(op),
-            expr: Box::new(cypher_to_sql_expr(expr, rel_binds, node_binds, false)),
+            expr: Box::new(cypher_to_sql_expr(expr, rel_binds, node_binds, false)?),
         },
         Expr::CountAll => {
             if let Some(bind) = rel_binds.first() {
@@ -1023,7 +1475,9 @@ fn cypher_to_sql_expr(
             } else if let Some(bind) = node_binds.first() {
                 sql_count_all(bind, "id")
             } else {
-                unimplemented!("can't find matching bind for count all")
+                return Err(ParseError::Unsupported(
+                    "count(*) without a matching bind".to_string(),
+                ));
             }
         }
 
@@ -1048,24 +1502,23 @@ fn cypher_to_sql_expr(
         } => {
             if name == "type" {
                 // turn this into type(e.layer_id)
-                let first_arg = args.first().expect(
-                    "type function must have one argument representing the bind of an edge",
-                );
-                match first_arg {
-                    Expr::Var { var_name, .. } => sql_function_ast(
+                match args.first() {
+                    Some(Expr::Var { var_name, .. }) => sql_function_ast(
                         name,
                         &vec![Expr::var(var_name, vec!["layer_id"])],
                         rel_binds,
                         node_binds,
                         distinct,
-                    ),
-                    expr => unimplemented!(
-                        "type function must have a variable as argument, found {:?}",
-                        expr
-                    ),
+                    )?,
+                    arg => {
+                        return Err(ParseError::Unsupported(format!(
+                            "type function must have the bind of an edge as argument, found {:?}",
+                            arg
+                        )))
+                    }
                 }
             } else {
-                sql_function_ast(name, args, rel_binds, node_binds, distinct)
+                sql_function_ast(name, args, rel_binds, node_binds, distinct)?
             }
         }
         Expr::Nested(expr) => sql_ast::Expr::Nested(Box::new(cypher_to_sql_expr(
@@ -1073,9 +1526,10 @@ fn cypher_to_sql_expr(
             rel_binds,
             node_binds,
             allow_wildcard_edges & true,
-        ))),
-        _ => unimplemented!("unsupported expression {:?}", expr),
-    }
+        )?)),
+        _ => return Err(ParseError::Unsupported(format!("expression {:?}", expr))),
+    };
+    Ok(sql_expr)
 }
 
 fn sql_count_all(table: &str, attr: &str) -> sql_ast::Expr {
@@ -1106,15 +1560,17 @@ fn sql_function_ast(
     rel_binds: &[String],
     node_binds: &[String],
     distinct: &bool,
-) -> sql_ast::Expr {
+) -> Result<sql_ast::Expr, ParseError> {
     let args = args
         .iter()
         .map(|arg| {
-            sql_ast::FunctionArg::Unnamed(sql_ast::FunctionArgExpr::Expr(cypher_to_sql_expr(
-                arg, rel_binds, node_binds, false,
-            )))
+            Ok(sql_ast::FunctionArg::Unnamed(
+                sql_ast::FunctionArgExpr::Expr(cypher_to_sql_expr(
+                    arg, rel_binds, node_binds, false,
+                )?),
+            ))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
     let duplicate_treatment = distinct.then(|| DuplicateTreatment::Distinct);
     let args = sql_ast::FunctionArguments::List(FunctionArgumentList {
@@ -1122,14 +1578,14 @@ fn sql_function_ast(
         duplicate_treatment,
         clauses: vec![],
     });
-    sql_ast::Expr::Function(sql_ast::Function {
+    Ok(sql_ast::Expr::Function(sql_ast::Function {
         name: sql_ast::ObjectName(vec![sql_ast::Ident::new(name)]),
         args,
         over: None,
         filter: None,
         null_treatment: None,
         within_group: vec![],
-    })
+    }))
 }
 
 fn sql_like(
@@ -1138,33 +1594,35 @@ fn sql_like(
     pattern: impl Fn(&String) -> String,
     rel_binds: &[String],
     node_binds: &[String],
-) -> sql_ast::Expr {
+) -> Result<sql_ast::Expr, ParseError> {
     match right {
-        Expr::Literal(Literal::Str(s)) => sql_ast::Expr::Like {
+        Expr::Literal(Literal::Str(s)) => Ok(sql_ast::Expr::Like {
             negated: false,
-            expr: Box::new(cypher_to_sql_expr(left, rel_binds, node_binds, false)),
+            expr: Box::new(cypher_to_sql_expr(left, rel_binds, node_binds, false)?),
             pattern: Box::new(sql_ast::Expr::Value(sql_ast::Value::SingleQuotedString(
                 pattern(s),
             ))),
             escape_char: None,
-        },
-        pattern => unimplemented!(
-            "unexpected right hand side of CONTAINS operator {:?}",
+        }),
+        pattern => Err(ParseError::Unsupported(format!(
+            "right hand side of string operator {:?}, expected a string literal",
             pattern
-        ),
+        ))),
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{parser, transpiler};
+    use crate::{
+        executor::table_provider::graph_view::GraphViewTables,
+        parser::{self, ParseError},
+        transpiler,
+    };
     use pretty_assertions::assert_eq;
     use raphtory::{
         db::{api::mutation::AdditionOps, graph::graph::Graph},
-        disk_graph::DiskGraphStorage,
         prelude::NO_PROPS,
     };
-    use tempfile::tempdir;
 
     #[test]
     fn count_all_nodes() {
@@ -1436,6 +1894,113 @@ mod test {
         );
     }
 
+    #[test]
+    fn hop_undirected_with_nodes() {
+        check_cypher_to_sql(
+            "MATCH (n1)-[e1]-(n2) RETURN n1.name, n2.name",
+            "WITH \
+             e1_directed AS (SELECT * FROM _default), \
+             e1 AS (SELECT *, src AS _from, dst AS _to FROM e1_directed \
+             UNION ALL \
+             SELECT *, dst AS _from, src AS _to FROM e1_directed WHERE src <> dst), \
+             n1 AS (SELECT * FROM nodes), \
+             n2 AS (SELECT * FROM nodes) \
+             SELECT n1.name, n2.name \
+             FROM e1 \
+             JOIN n1 ON e1._from = n1.id \
+             JOIN n2 ON e1._to = n2.id",
+        );
+    }
+
+    #[test]
+    fn hop_var_length_zero_hops() {
+        check_cypher_to_sql(
+            "MATCH (n1)-[e*0..1]->(n2) RETURN n1.name, n2.name",
+            "WITH \
+             e_edges AS (SELECT * FROM _default), \
+             e AS (\
+             SELECT h1.src AS src, h1.dst AS dst, 1 AS hops, \
+             make_array(h1.id) AS id, make_array(h1.layer_id) AS layer_id, make_array(h1.time) AS time \
+             FROM e_edges AS h1 \
+             UNION ALL \
+             SELECT id AS src, id AS dst, 0 AS hops, \
+             make_array() AS id, make_array() AS layer_id, make_array() AS time \
+             FROM nodes), \
+             n1 AS (SELECT * FROM nodes), \
+             n2 AS (SELECT * FROM nodes) \
+             SELECT n1.name, n2.name \
+             FROM e \
+             JOIN n1 ON e.src = n1.id \
+             JOIN n2 ON e.dst = n2.id",
+        );
+    }
+
+    #[test]
+    fn hop_var_length_max_path_length() {
+        let g = Graph::new();
+        g.add_edge(0, 0, 1, NO_PROPS, None).unwrap();
+        let to_sql = |query: &str, max_path_length: usize| {
+            transpiler::to_sql_with_max_path_length(
+                parser::parse_cypher(query).unwrap(),
+                &GraphViewTables::new(g.clone()),
+                max_path_length,
+            )
+            .map(|sql| sql.to_string())
+        };
+
+        let sql = to_sql("MATCH (n1)-[e*2..]->(n2) RETURN n2.name", 3).unwrap();
+        assert!(sql.contains("3 AS hops"));
+        assert!(!sql.contains("4 AS hops"));
+        assert_eq!(
+            to_sql("MATCH (n1)-[e*]->(n2) RETURN n2.name", 4).unwrap(),
+            to_sql("MATCH (n1)-[e*1..4]->(n2) RETURN n2.name", 4).unwrap()
+        );
+        assert!(to_sql("MATCH (n1)-[e*4..]->(n2) RETURN n2.name", 3).is_err());
+        assert!(to_sql("MATCH (n1)-[e*1..3]->(n2) RETURN n2.name", 3).is_ok());
+        assert!(to_sql("MATCH (n1)-[e*1..4]->(n2) RETURN n2.name", 3).is_err());
+        assert!(to_sql("MATCH (n1)-[e*1000]->(n2) RETURN n2.name", 3).is_err());
+    }
+
+    #[test]
+    fn hop_var_length_temporal() {
+        check_cypher_to_sql(
+            "MATCH (n1)-[e*1..2 TEMPORAL]->(n2) RETURN n1.name, e.hops, n2.name",
+            "WITH \
+             e_edges AS (SELECT * FROM _default), \
+             e AS (\
+             SELECT h1.src AS src, h1.dst AS dst, 1 AS hops, \
+             make_array(h1.id) AS id, make_array(h1.layer_id) AS layer_id, make_array(h1.time) AS time \
+             FROM e_edges AS h1 \
+             UNION ALL \
+             SELECT h1.src AS src, h2.dst AS dst, 2 AS hops, \
+             make_array(h1.id, h2.id) AS id, make_array(h1.layer_id, h2.layer_id) AS layer_id, make_array(h1.time, h2.time) AS time \
+             FROM e_edges AS h1 \
+             JOIN e_edges AS h2 ON h1.dst = h2.src \
+             WHERE (h1.id <> h2.id AND h1.layer_id = h2.layer_id OR h1.layer_id <> h2.layer_id) AND h1.time <= h2.time), \
+             n1 AS (SELECT * FROM nodes), \
+             n2 AS (SELECT * FROM nodes) \
+             SELECT n1.name, e.hops, n2.name \
+             FROM e \
+             JOIN n1 ON e.src = n1.id \
+             JOIN n2 ON e.dst = n2.id",
+        );
+    }
+
+    #[test]
+    fn unsupported_expressions_are_errors() {
+        let g = Graph::new();
+        for query in [
+            "MATCH (n) WHERE n.name IN n.names RETURN n",
+            "MATCH (n) WHERE n.name ENDS WITH n.suffix RETURN n",
+        ] {
+            let res = transpiler::to_sql(
+                parser::parse_cypher(query).unwrap(),
+                &GraphViewTables::new(g.clone()),
+            );
+            assert!(matches!(res, Err(ParseError::Unsupported(_))), "{query}");
+        }
+    }
+
     #[test]
     fn two_hops_on_separate_parts() {
         check_cypher_to_sql(
@@ -1650,16 +2215,25 @@ mod test {
         expected: &str,
         layers: LS,
     ) {
-        let query = parser::parse_cypher(query).unwrap();
-        let graph_dir = tempdir().unwrap();
         let g = Graph::new();
         for layer in layers {
             g.add_edge(0, 0, 0, NO_PROPS, Some(layer.as_ref()))
                 .expect("failed to add edge");
         }
-        let graph = DiskGraphStorage::from_graph(&g, graph_dir).unwrap();
-        let sql = transpiler::to_sql(query, &graph);
+        let sql = transpiler::to_sql(
+            parser::parse_cypher(query).unwrap(),
+            &GraphViewTables::new(g.clone()),
+        )
+        .unwrap();
         assert_eq!(sql.to_string(), expected.to_string());
+
+        #[cfg(feature = "storage")]
+        {
+            let graph_dir = tempfile::tempdir().unwrap();
+            let graph = raphtory::disk_graph::DiskGraphStorage::from_graph(&g, graph_dir).unwrap();
+            let sql = transpiler::to_sql(parser::parse_cypher(query).unwrap(), &graph).unwrap();
+            assert_eq!(sql.to_string(), expected.to_string());
+        }
     }
 
     fn check_cypher_to_sql(query: &str, expected: &str) {
@@ -0,0 +1,416 @@
+use crate::{
+    config::{
+        auth_config::{Access, AuthConfig, NamespacePermissions},
+        query_config::QueryConfig,
+    },
+    limits::execute_with_limits,
+};
+use async_graphql::{dynamic::Schema, http::ALL_WEBSOCKET_PROTOCOLS, Context, Data};
+use async_graphql_poem::{
+    GraphQLBatchRequest, GraphQLBatchResponse, GraphQLProtocol, GraphQLWebSocket,
+};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use poem::{
+    http::StatusCode, web::websocket::WebSocket, Endpoint, FromRequest, IntoResponse, Request,
+    Response, Result as PoemResult,
+};
+use serde::Deserialize;
+use std::{fs, path::Path, str::FromStr, sync::Arc};
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuthError {
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+    #[error("Invalid token: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+    #[error("Unsupported JWT algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("Failed to read JWT key file: {0}")]
+    KeyFile(#[from] std::io::Error),
+    #[error("Permission denied: {access} access is required for graph '{path}'")]
+    PermissionDenied { path: String, access: Access },
+}
+
+/// The permissions of the caller of a request
+///
+/// The most specific namespace containing a graph decides the access to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permissions {
+    namespaces: Option<NamespacePermissions>,
+}
+
+impl Permissions {
+    /// Read and write access to every graph, used when authentication is disabled
+    pub fn unrestricted() -> Self {
+        Self { namespaces: None }
+    }
+
+    pub fn new(namespaces: NamespacePermissions) -> Self {
+        let namespaces = namespaces
+            .into_iter()
+            .map(|(namespace, access)| {
+                let namespace = namespace.trim_matches('/');
+                let namespace = if namespace == "*" { "" } else { namespace };
+                (namespace.to_owned(), access)
+            })
+            .collect();
+        Self {
+            namespaces: Some(namespaces),
+        }
+    }
+
+    pub fn access(&self, path: &str) -> Option<Access> {
+        match &self.namespaces {
+            None => Some(Access::Write),
+            Some(namespaces) => {
+                let path = Path::new(path);
+                namespaces
+                    .iter()
+                    .filter(|(namespace, _)| path.starts_with(namespace))
+                    .max_by_key(|(namespace, _)| Path::new(namespace).components().count())
+                    .map(|(_, access)| *access)
+            }
+        }
+    }
+
+    pub fn can_read(&self, path: &str) -> bool {
+        self.access(path).is_some()
+    }
+
+    pub fn check(&self, path: &str, access: Access) -> Result<(), AuthError> {
+        if self.access(path) >= Some(access) {
+            Ok(())
+        } else {
+            Err(AuthError::PermissionDenied {
+                path: path.to_owned(),
+                access,
+            })
+        }
+    }
+}
+
+/// Check that the caller of the request has `access` to the graph at `path`
+///
+/// Requests that don't go through [`GraphQLEndpoint`] (e.g. a schema executed directly) are not restricted.
+pub(crate) fn require_access(
+    ctx: &Context<'_>,
+    path: &str,
+    access: Access,
+) -> Result<(), AuthError> {
+    match ctx.data_opt::<Permissions>() {
+        Some(permissions) => permissions.check(path, access),
+        None => Ok(()),
+    }
+}
+
+/// Permissions of the caller of the request, unrestricted when the request is not authenticated by the server
+pub(crate) fn permissions<'a>(ctx: &'a Context<'_>) -> Option<&'a Permissions> {
+    ctx.data_opt::<Permissions>()
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    #[serde(default)]
+    permissions: NamespacePermissions,
+}
+
+/// Resolves the credentials of a request into [`Permissions`]
+pub struct Authenticator {
+    enabled: bool,
+    api_keys: Vec<(String, Permissions)>,
+    jwt: Option<(DecodingKey, Validation)>,
+    anonymous: Permissions,
+}
+
+impl Authenticator {
+    pub fn new(config: &AuthConfig) -> Result<Self, AuthError> {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                (
+                    api_key.key.clone(),
+                    Permissions::new(api_key.permissions.clone()),
+                )
+            })
+            .collect();
+        let jwt = match &config.jwt {
+            Some(jwt) => {
+                let algorithm = Algorithm::from_str(&jwt.algorithm)
+                    .map_err(|_| AuthError::UnsupportedAlgorithm(jwt.algorithm.clone()))?;
+                let key = fs::read(&jwt.key_path)?;
+                let key = match algorithm {
+                    Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                        DecodingKey::from_secret(key.trim_ascii())
+                    }
+                    Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(&key)?,
+                    Algorithm::EdDSA => DecodingKey::from_ed_pem(&key)?,
+                    _ => DecodingKey::from_rsa_pem(&key)?,
+                };
+                Some((key, Validation::new(algorithm)))
+            }
+            None => None,
+        };
+        Ok(Self {
+            enabled: config.is_enabled(),
+            api_keys,
+            jwt,
+            anonymous: Permissions::new(config.anonymous_permissions.clone()),
+        })
+    }
+
+    /// Credentials are read from the `X-API-Key` header or from a bearer token in the
+    /// `Authorization` header, which can be an API key or a JWT
+    pub fn authenticate(&self, req: &Request) -> Result<Permissions, AuthError> {
+        self.authenticate_credentials(req.header("X-API-Key"), req.header("Authorization"))
+    }
+
+    fn authenticate_credentials(
+        &self,
+        api_key: Option<&str>,
+        authorization: Option<&str>,
+    ) -> Result<Permissions, AuthError> {
+        if !self.enabled {
+            return Ok(Permissions::unrestricted());
+        }
+        let bearer = authorization.and_then(|value| value.strip_prefix("Bearer "));
+        let api_key = api_key.or(bearer);
+        match (api_key, bearer) {
+            (None, _) => Ok(self.anonymous.clone()),
+            (Some(api_key), bearer) => match self.api_key_permissions(api_key) {
+                Some(permissions) => Ok(permissions),
+                None => match (bearer, &self.jwt) {
+                    (Some(token), Some((key, validation))) => {
+                        let claims = decode::<Claims>(token, key, validation)?.claims;
+                        Ok(Permissions::new(claims.permissions))
+                    }
+                    _ => Err(AuthError::InvalidCredentials),
+                },
+            },
+        }
+    }
+
+    fn api_key_permissions(&self, api_key: &str) -> Option<Permissions> {
+        // compare against every key in constant time to not leak valid prefixes
+        let mut found = None;
+        for (key, permissions) in self.api_keys.iter() {
+            if constant_time_eq(key.as_bytes(), api_key.as_bytes()) {
+                found = Some(permissions.clone());
+            }
+        }
+        found
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// GraphQL endpoint that authenticates every request before executing it
+pub(crate) struct GraphQLEndpoint {
+    schema: Schema,
+    authenticator: Arc<Authenticator>,
+    query_config: QueryConfig,
+}
+
+impl GraphQLEndpoint {
+    pub(crate) fn new(
+        schema: Schema,
+        authenticator: Arc<Authenticator>,
+        query_config: QueryConfig,
+    ) -> Self {
+        Self {
+            schema,
+            authenticator,
+            query_config,
+        }
+    }
+}
+
+impl Endpoint for GraphQLEndpoint {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> PoemResult<Self::Output> {
+        let permissions = match self.authenticator.authenticate(&req) {
+            Ok(permissions) => permissions,
+            Err(err) => {
+                return Ok(Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(err.to_string()))
+            }
+        };
+        let (req, mut body) = req.split();
+        let batch = GraphQLBatchRequest::from_request(&req, &mut body).await?;
+        let response =
+            execute_with_limits(&self.schema, batch.0.data(permissions), &self.query_config).await;
+        Ok(GraphQLBatchResponse(response).into_response())
+    }
+}
+
+/// WebSocket endpoint for GraphQL subscriptions
+///
+/// Browsers can't set headers on WebSocket connections, so the credentials can also be sent as
+/// `X-API-Key` or `Authorization` in the payload of the `connection_init` message, which take
+/// precedence over the headers of the upgrade request.
+pub(crate) struct GraphQLSubscriptionEndpoint {
+    schema: Schema,
+    authenticator: Arc<Authenticator>,
+}
+
+impl GraphQLSubscriptionEndpoint {
+    pub(crate) fn new(schema: Schema, authenticator: Arc<Authenticator>) -> Self {
+        Self {
+            schema,
+            authenticator,
+        }
+    }
+}
+
+impl Endpoint for GraphQLSubscriptionEndpoint {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> PoemResult<Self::Output> {
+        let from_headers = self.authenticator.authenticate(&req);
+        let (req, mut body) = req.split();
+        let websocket = WebSocket::from_request(&req, &mut body).await?;
+        let protocol = GraphQLProtocol::from_request(&req, &mut body).await?;
+        let schema = self.schema.clone();
+        let authenticator = self.authenticator.clone();
+
+        let on_connection_init = move |payload: serde_json::Value| async move {
+            let header = |name: &str| payload.get(name).and_then(|value| value.as_str());
+            let permissions = match (header("X-API-Key"), header("Authorization")) {
+                (None, None) => from_headers,
+                (api_key, authorization) => {
+                    authenticator.authenticate_credentials(api_key, authorization)
+                }
+            }?;
+            let mut data = Data::default();
+            data.insert(permissions);
+            Ok(data)
+        };
+        Ok(websocket
+            .protocols(ALL_WEBSOCKET_PROTOCOLS)
+            .on_upgrade(move |stream| {
+                GraphQLWebSocket::new(stream, schema, protocol)
+                    .on_connection_init(on_connection_init)
+                    .serve()
+            })
+            .into_response())
+    }
+}
+
+#[cfg(test)]
+mod auth_test {
+    use super::*;
+    use crate::config::auth_config::ApiKeyConfig;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+
+    fn request(header: Option<(&str, &str)>) -> Request {
+        let mut builder = Request::builder();
+        if let Some((name, value)) = header {
+            builder = builder.header(name, value);
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn most_specific_namespace_wins() {
+        let permissions = Permissions::new(
+            [
+                ("*".to_owned(), Access::Read),
+                ("team_a/".to_owned(), Access::Write),
+                ("team_a/archive".to_owned(), Access::Read),
+            ]
+            .into(),
+        );
+        assert_eq!(permissions.access("graph"), Some(Access::Read));
+        assert_eq!(permissions.access("team_a/graph"), Some(Access::Write));
+        assert_eq!(
+            permissions.access("team_a/archive/graph"),
+            Some(Access::Read)
+        );
+        assert_eq!(permissions.access("team_ab/graph"), Some(Access::Read));
+        assert!(permissions.check("team_a/graph", Access::Write).is_ok());
+        assert!(permissions.check("team_b/graph", Access::Write).is_err());
+
+        let permissions = Permissions::new([("team_a".to_owned(), Access::Read)].into());
+        assert!(!permissions.can_read("team_b/graph"));
+        assert!(permissions.can_read("team_a/graph"));
+        assert!(Permissions::unrestricted()
+            .check("anything", Access::Write)
+            .is_ok());
+    }
+
+    #[test]
+    fn authenticate_api_keys_and_jwt() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let key_path = tmp_dir.path().join("secret");
+        fs::write(&key_path, "very secret\n").unwrap();
+
+        let config = AuthConfig {
+            api_keys: vec![ApiKeyConfig {
+                key: "abc".to_owned(),
+                permissions: [("team_a".to_owned(), Access::Write)].into(),
+            }],
+            jwt: Some(crate::config::auth_config::JwtConfig {
+                key_path,
+                algorithm: "HS256".to_owned(),
+            }),
+            anonymous_permissions: [("public".to_owned(), Access::Read)].into(),
+        };
+        let authenticator = Authenticator::new(&config).unwrap();
+
+        let anonymous = authenticator.authenticate(&request(None)).unwrap();
+        assert!(anonymous.can_read("public/graph"));
+        assert!(!anonymous.can_read("team_a/graph"));
+
+        let api_key = authenticator
+            .authenticate(&request(Some(("X-API-Key", "abc"))))
+            .unwrap();
+        assert_eq!(api_key.access("team_a/graph"), Some(Access::Write));
+        let bearer = authenticator
+            .authenticate(&request(Some(("Authorization", "Bearer abc"))))
+            .unwrap();
+        assert_eq!(bearer, api_key);
+        assert!(authenticator
+            .authenticate(&request(Some(("X-API-Key", "abd"))))
+            .is_err());
+
+        let claims = json!({"exp": 10000000000u64, "permissions": {"team_b": "read"}});
+        let token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(b"very secret"),
+        )
+        .unwrap();
+        let jwt = authenticator
+            .authenticate(&request(Some((
+                "Authorization",
+                format!("Bearer {token}").as_str(),
+            ))))
+            .unwrap();
+        assert_eq!(jwt.access("team_b/graph"), Some(Access::Read));
+        assert_eq!(jwt.access("team_a/graph"), None);
+
+        let forged = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(b"not the secret"),
+        )
+        .unwrap();
+        assert!(authenticator
+            .authenticate(&request(Some((
+                "Authorization",
+                format!("Bearer {forged}").as_str(),
+            ))))
+            .is_err());
+
+        let unrestricted = Authenticator::new(&AuthConfig::default()).unwrap();
+        assert_eq!(
+            unrestricted.authenticate(&request(None)).unwrap(),
+            Permissions::unrestricted()
+        );
+    }
+}
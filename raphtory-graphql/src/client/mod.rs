@@ -0,0 +1,468 @@
+//! A client for a Raphtory GraphQL server
+//!
+//! ```no_run
+//! use raphtory::prelude::*;
+//! use raphtory_graphql::client::RaphtoryGraphQLClient;
+//!
+//! # async fn example() -> Result<(), raphtory_graphql::client::ClientError> {
+//! let client = RaphtoryGraphQLClient::new("http://localhost:1736");
+//! let graph = Graph::new();
+//! graph.add_edge(1, "ben", "hamza", NO_PROPS, None).unwrap();
+//! client.send_graph("friends", graph, false).await?;
+//!
+//! let remote = client.remote_graph("friends");
+//! remote.add_edge(2, "hamza", "lucas", NO_PROPS, None).await?;
+//! let graph = client.receive_graph("friends").await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{
+    model::GqlGraphType,
+    url_encode::{url_decode_graph, url_encode_graph, UrlDecodeError},
+};
+use raphtory::{core::utils::errors::GraphError, db::api::view::MaterializedGraph};
+use reqwest::{multipart, Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value as JsonValue};
+use std::path::Path;
+
+mod remote_graph;
+
+pub use remote_graph::{
+    RemoteBatchError, RemoteBatchResult, RemoteEdgeAddition, RemoteGraph, RemoteNodeAddition,
+    RemoteUpdate,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    #[error("Request to the server failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("The server rejected the credentials of the client: {0}")]
+    Unauthorised(String),
+    #[error(
+        "After sending query to the server:\n\t{query}\nGot the following errors:\n\t{errors}"
+    )]
+    Query { query: String, errors: String },
+    #[error("Error while reading server response for query:\n\t{query}\nGot:\n\t{response}")]
+    InvalidResponse { query: String, response: String },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+    #[error("Failed to decode the graph received from the server: {0}")]
+    Decode(#[from] UrlDecodeError),
+}
+
+/// A client for the queries and mutations of a Raphtory GraphQL server
+#[derive(Clone, Debug)]
+pub struct RaphtoryGraphQLClient {
+    url: String,
+    api_key: Option<String>,
+    token: Option<String>,
+    http: Client,
+}
+
+impl RaphtoryGraphQLClient {
+    /// Create a client for the server at `url`, no request is sent until the client is used
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            api_key: None,
+            token: None,
+            http: Client::new(),
+        }
+    }
+
+    /// Send an API key configured on the server in the `X-API-Key` header
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Send a JWT signed with the key configured on the server as a bearer token
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Check if the server is online
+    pub async fn is_server_online(&self) -> bool {
+        self.http
+            .get(&self.url)
+            .send()
+            .await
+            .is_ok_and(|response| response.status() == StatusCode::OK)
+    }
+
+    /// Make a GraphQL query against the server and return the `data` field of the response
+    pub async fn query(
+        &self,
+        query: &str,
+        variables: Map<String, JsonValue>,
+    ) -> Result<Map<String, JsonValue>, ClientError> {
+        let body = json!({
+            "query": query,
+            "variables": variables,
+        });
+        self.send(self.http.post(&self.url).json(&body), query)
+            .await
+    }
+
+    /// Make a GraphQL query against the server and deserialise the `data` field of the response
+    pub async fn query_as<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Map<String, JsonValue>,
+    ) -> Result<T, ClientError> {
+        let data = self.query(query, variables).await?;
+        Ok(serde_json::from_value(JsonValue::Object(data))?)
+    }
+
+    /// Send a graph to the server and store it at `path`
+    pub async fn send_graph(
+        &self,
+        path: &str,
+        graph: impl Into<MaterializedGraph>,
+        overwrite: bool,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            mutation SendGraph($path: String!, $graph: String!, $overwrite: Boolean!) {
+                sendGraph(path: $path, graph: $graph, overwrite: $overwrite)
+            }"#;
+        let variables = json!({
+            "path": path,
+            "graph": url_encode_graph(graph)?,
+            "overwrite": overwrite,
+        });
+        let data = self.query(query, into_map(variables)).await?;
+        field::<String>(query, data, "sendGraph")?;
+        Ok(())
+    }
+
+    /// Upload a graph saved as a zip file at `file_path` on the client and store it at `path`
+    pub async fn upload_graph(
+        &self,
+        path: &str,
+        file_path: impl AsRef<Path>,
+        overwrite: bool,
+    ) -> Result<(), ClientError> {
+        let query = "mutation UploadGraph($path: String!, $graph: Upload!, $overwrite: Boolean!) { uploadGraph(path: $path, graph: $graph, overwrite: $overwrite) }";
+        let file_path = file_path.as_ref();
+        let file = tokio::fs::read(file_path).await?;
+        let file_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "graph".to_owned());
+        let operations = json!({
+            "query": query,
+            "variables": {"path": path, "overwrite": overwrite, "graph": null},
+        });
+        let form = multipart::Form::new()
+            .text("operations", operations.to_string())
+            .text("map", r#"{"0": ["variables.graph"]}"#)
+            .part("0", multipart::Part::bytes(file).file_name(file_name));
+        let data = self
+            .send(self.http.post(&self.url).multipart(form), query)
+            .await?;
+        field::<String>(query, data, "uploadGraph")?;
+        Ok(())
+    }
+
+    /// Create an empty graph at `path`
+    pub async fn new_graph(&self, path: &str, graph_type: GqlGraphType) -> Result<(), ClientError> {
+        let query = r#"
+            mutation NewGraph($path: String!, $graphType: GqlGraphType!) {
+                newGraph(path: $path, graphType: $graphType)
+            }"#;
+        let graph_type = match graph_type {
+            GqlGraphType::Persistent => "PERSISTENT",
+            GqlGraphType::Event => "EVENT",
+        };
+        let variables = json!({"path": path, "graphType": graph_type});
+        self.mutate_graphs(query, variables, "newGraph").await
+    }
+
+    /// Copy the graph at `path` on the server to `new_path`
+    pub async fn copy_graph(&self, path: &str, new_path: &str) -> Result<(), ClientError> {
+        let query = r#"
+            mutation CopyGraph($path: String!, $newPath: String!) {
+                copyGraph(path: $path, newPath: $newPath)
+            }"#;
+        let variables = json!({"path": path, "newPath": new_path});
+        self.mutate_graphs(query, variables, "copyGraph").await
+    }
+
+    /// Move the graph at `path` on the server to `new_path`
+    pub async fn move_graph(&self, path: &str, new_path: &str) -> Result<(), ClientError> {
+        let query = r#"
+            mutation MoveGraph($path: String!, $newPath: String!) {
+                moveGraph(path: $path, newPath: $newPath)
+            }"#;
+        let variables = json!({"path": path, "newPath": new_path});
+        self.mutate_graphs(query, variables, "moveGraph").await
+    }
+
+    /// Delete the graph at `path` on the server
+    pub async fn delete_graph(&self, path: &str) -> Result<(), ClientError> {
+        let query = r#"
+            mutation DeleteGraph($path: String!) {
+                deleteGraph(path: $path)
+            }"#;
+        let variables = json!({"path": path});
+        self.mutate_graphs(query, variables, "deleteGraph").await
+    }
+
+    /// Download the graph at `path` on the server
+    pub async fn receive_graph(&self, path: &str) -> Result<MaterializedGraph, ClientError> {
+        let query = r#"
+            query ReceiveGraph($path: String!) {
+                receiveGraph(path: $path)
+            }"#;
+        let data = self.query(query, into_map(json!({"path": path}))).await?;
+        let graph: String = field(query, data, "receiveGraph")?;
+        Ok(url_decode_graph(graph)?)
+    }
+
+    /// A handle to apply updates to the graph at `path` on the server
+    pub fn remote_graph(&self, path: impl Into<String>) -> RemoteGraph {
+        RemoteGraph::new(path.into(), self.clone())
+    }
+
+    /// Add the credentials of the client to a request
+    fn authorise(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = match &self.api_key {
+            Some(api_key) => request.header("X-API-Key", api_key),
+            None => request,
+        };
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        query: &str,
+    ) -> Result<Map<String, JsonValue>, ClientError> {
+        let response = self.authorise(request).send().await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(ClientError::Unauthorised(response.text().await?));
+        }
+        let text = response.text().await?;
+        let invalid = || ClientError::InvalidResponse {
+            query: query.to_owned(),
+            response: text.clone(),
+        };
+        let mut response: Map<String, JsonValue> =
+            serde_json::from_str(&text).map_err(|_| invalid())?;
+        match response.remove("errors") {
+            Some(JsonValue::Array(errors)) if !errors.is_empty() => Err(ClientError::Query {
+                query: query.to_owned(),
+                errors: errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n\t"),
+            }),
+            _ => match response.remove("data") {
+                Some(JsonValue::Object(data)) => Ok(data),
+                _ => Err(invalid()),
+            },
+        }
+    }
+
+    /// Run a mutation of the stored graphs that returns `true` on success
+    async fn mutate_graphs(
+        &self,
+        query: &str,
+        variables: JsonValue,
+        name: &str,
+    ) -> Result<(), ClientError> {
+        let data = self.query(query, into_map(variables)).await?;
+        match field::<bool>(query, data, name)? {
+            true => Ok(()),
+            false => Err(ClientError::InvalidResponse {
+                query: query.to_owned(),
+                response: format!("{name} returned false"),
+            }),
+        }
+    }
+}
+
+fn into_map(variables: JsonValue) -> Map<String, JsonValue> {
+    match variables {
+        JsonValue::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Deserialise the field `name` of the `data` of a response
+fn field<T: DeserializeOwned>(
+    query: &str,
+    data: Map<String, JsonValue>,
+    name: &str,
+) -> Result<T, ClientError> {
+    data.get(name)
+        .and_then(|value| T::deserialize(value).ok())
+        .ok_or_else(|| ClientError::InvalidResponse {
+            query: query.to_owned(),
+            response: JsonValue::Object(data.clone()).to_string(),
+        })
+}
+
+#[cfg(test)]
+mod client_tests {
+    use super::{RaphtoryGraphQLClient, RemoteEdgeAddition, RemoteNodeAddition, RemoteUpdate};
+    use crate::{model::GqlGraphType, server::GraphServer};
+    use raphtory::{
+        db::graph::views::deletion_graph::PersistentGraph,
+        prelude::*,
+        serialise::{GraphFolder, StableEncode},
+    };
+    use serde::Deserialize;
+    use serde_json::{json, Map};
+    use tokio::time::{sleep, Duration};
+
+    #[derive(Deserialize)]
+    struct Count {
+        graph: CountGraph,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CountGraph {
+        count_nodes: usize,
+    }
+
+    #[tokio::test]
+    async fn test_client_and_remote_graph() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let server = GraphServer::new(tmp_dir.path().to_path_buf(), None, None)
+            .unwrap()
+            .start_with_port(1751)
+            .await
+            .unwrap();
+        let client = RaphtoryGraphQLClient::new("http://localhost:1751");
+        for _ in 0..50 {
+            if client.is_server_online().await {
+                break;
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        let graph = PersistentGraph::new();
+        graph.add_edge(1, "ben", "hamza", NO_PROPS, None).unwrap();
+        client
+            .send_graph("friends", graph.clone(), false)
+            .await
+            .unwrap();
+        assert!(client
+            .send_graph("friends", graph.clone(), false)
+            .await
+            .is_err());
+
+        let zip_dir = tempfile::tempdir().unwrap();
+        let zip_path = zip_dir.path().join("graph.zip");
+        graph.encode(GraphFolder::new_as_zip(&zip_path)).unwrap();
+        client
+            .upload_graph("uploaded", &zip_path, false)
+            .await
+            .unwrap();
+        client.copy_graph("uploaded", "copied").await.unwrap();
+        client.move_graph("copied", "moved").await.unwrap();
+        client.delete_graph("uploaded").await.unwrap();
+        client
+            .new_graph("empty", GqlGraphType::Persistent)
+            .await
+            .unwrap();
+
+        let remote = client.remote_graph("friends");
+        remote
+            .add_node(2, "lucas", [("age", Prop::I64(30))], Some("person"))
+            .await
+            .unwrap();
+        remote
+            .add_edge(3, "hamza", "lucas", [("weight", 0.5)], Some("follows"))
+            .await
+            .unwrap();
+        remote
+            .add_nodes(vec![RemoteNodeAddition::new("pedro")
+                .node_type("person")
+                .update(RemoteUpdate::new(4, [("age", Prop::I64(25))]))])
+            .await
+            .unwrap();
+        remote
+            .add_edges(vec![RemoteEdgeAddition::new("pedro", "ben")
+                .layer("follows")
+                .update(RemoteUpdate::new(5, NO_PROPS))])
+            .await
+            .unwrap();
+        remote.add_properties(6, [("version", 1i64)]).await.unwrap();
+        remote
+            .add_constant_properties([("name", "friends")])
+            .await
+            .unwrap();
+        remote
+            .update_constant_properties([("name", "people")])
+            .await
+            .unwrap();
+        remote.delete_edge(7, "ben", "hamza", None).await.unwrap();
+
+        let result = remote
+            .add_batch(
+                vec![RemoteNodeAddition::new("ben")
+                    .update(RemoteUpdate::new(8, [("age", "unknown")]))],
+                vec![],
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.errors[0].kind, "NODE");
+        assert_eq!(result.errors[0].index, 0);
+
+        let count: Count = client
+            .query_as(
+                "query Count($path: String!) { graph(path: $path) { countNodes } }",
+                Map::from_iter([("path".to_owned(), json!("friends"))]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(count.graph.count_nodes, 4);
+
+        let graph = client.receive_graph("friends").await.unwrap();
+        assert_eq!(graph.node("lucas").unwrap().node_type().unwrap(), "person");
+        assert_eq!(
+            graph
+                .edge("hamza", "lucas")
+                .unwrap()
+                .properties()
+                .get("weight"),
+            Some(Prop::F64(0.5))
+        );
+        assert_eq!(
+            graph.properties().constant().get("name"),
+            Some(Prop::str("people"))
+        );
+        assert_eq!(
+            graph
+                .properties()
+                .temporal()
+                .get("version")
+                .unwrap()
+                .latest(),
+            Some(Prop::I64(1))
+        );
+        let moved = client.receive_graph("moved").await.unwrap();
+        assert_eq!(moved.count_edges(), 1);
+        assert!(client.receive_graph("uploaded").await.is_err());
+
+        server.stop().await;
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{
+    client::{ClientError, RaphtoryGraphQLClient},
+    model::graph::property::prop_to_gql,
+};
+use raphtory::core::{utils::time::IntoTime, Prop};
+use raphtory_api::core::entities::GID;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Value as JsonValue};
+
+#[derive(Serialize, Clone, Debug)]
+struct PropertyInput {
+    key: String,
+    #[serde(serialize_with = "serialize_prop")]
+    value: Prop,
+}
+
+fn serialize_prop<S: Serializer>(prop: &Prop, serializer: S) -> Result<S::Ok, S::Error> {
+    prop_to_gql(prop).serialize(serializer)
+}
+
+fn property_inputs<K: AsRef<str>, V: Into<Prop>>(
+    properties: impl IntoIterator<Item = (K, V)>,
+) -> Vec<PropertyInput> {
+    properties
+        .into_iter()
+        .map(|(key, value)| PropertyInput {
+            key: key.as_ref().to_owned(),
+            value: value.into(),
+        })
+        .collect()
+}
+
+/// The temporal properties of a node or edge at `time`
+#[derive(Serialize, Clone, Debug)]
+pub struct RemoteUpdate {
+    time: i64,
+    properties: Vec<PropertyInput>,
+}
+
+impl RemoteUpdate {
+    pub fn new<K: AsRef<str>, V: Into<Prop>>(
+        time: impl IntoTime,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self {
+            time: time.into_time(),
+            properties: property_inputs(properties),
+        }
+    }
+}
+
+/// The updates of a node in a batch
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteNodeAddition {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node_type: Option<String>,
+    constant_properties: Vec<PropertyInput>,
+    updates: Vec<RemoteUpdate>,
+}
+
+impl RemoteNodeAddition {
+    pub fn new(name: impl Into<GID>) -> Self {
+        Self {
+            name: name.into().to_string(),
+            node_type: None,
+            constant_properties: vec![],
+            updates: vec![],
+        }
+    }
+
+    pub fn node_type(mut self, node_type: impl Into<String>) -> Self {
+        self.node_type = Some(node_type.into());
+        self
+    }
+
+    pub fn constant_properties<K: AsRef<str>, V: Into<Prop>>(
+        mut self,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        self.constant_properties.extend(property_inputs(properties));
+        self
+    }
+
+    pub fn update(mut self, update: RemoteUpdate) -> Self {
+        self.updates.push(update);
+        self
+    }
+}
+
+/// The updates of an edge in a batch
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteEdgeAddition {
+    src: String,
+    dst: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layer: Option<String>,
+    constant_properties: Vec<PropertyInput>,
+    updates: Vec<RemoteUpdate>,
+}
+
+impl RemoteEdgeAddition {
+    pub fn new(src: impl Into<GID>, dst: impl Into<GID>) -> Self {
+        Self {
+            src: src.into().to_string(),
+            dst: dst.into().to_string(),
+            layer: None,
+            constant_properties: vec![],
+            updates: vec![],
+        }
+    }
+
+    pub fn layer(mut self, layer: impl Into<String>) -> Self {
+        self.layer = Some(layer.into());
+        self
+    }
+
+    pub fn constant_properties<K: AsRef<str>, V: Into<Prop>>(
+        mut self,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        self.constant_properties.extend(property_inputs(properties));
+        self
+    }
+
+    pub fn update(mut self, update: RemoteUpdate) -> Self {
+        self.updates.push(update);
+        self
+    }
+}
+
+/// Why an item of a batch was rejected, `index` is the position of the item in the nodes or
+/// edges of the batch
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoteBatchError {
+    /// `NODE` or `EDGE`
+    pub kind: String,
+    pub index: usize,
+    pub message: String,
+}
+
+/// The outcome of [`RemoteGraph::add_batch`], nothing was applied if `success` is false
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoteBatchResult {
+    pub success: bool,
+    pub errors: Vec<RemoteBatchError>,
+}
+
+/// A graph stored on a server, updated through the mutations of the GraphQL API
+#[derive(Clone, Debug)]
+pub struct RemoteGraph {
+    path: String,
+    client: RaphtoryGraphQLClient,
+}
+
+impl RemoteGraph {
+    pub(crate) fn new(path: String, client: RaphtoryGraphQLClient) -> Self {
+        Self { path, client }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Add a node update, creating the node if it does not exist
+    pub async fn add_node<K: AsRef<str>, V: Into<Prop>>(
+        &self,
+        time: impl IntoTime,
+        name: impl Into<GID>,
+        properties: impl IntoIterator<Item = (K, V)>,
+        node_type: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query AddNode($path: String!, $time: Int!, $name: String!, $properties: [GqlPropInput!], $nodeType: String) {
+                updateGraph(path: $path) {
+                    addNode(time: $time, name: $name, properties: $properties, nodeType: $nodeType) {
+                        success
+                    }
+                }
+            }"#;
+        let variables = json!({
+            "time": time.into_time(),
+            "name": name.into().to_string(),
+            "properties": property_inputs(properties),
+            "nodeType": node_type,
+        });
+        self.update(query, variables).await
+    }
+
+    /// Add the updates of several nodes in a single request
+    pub async fn add_nodes(&self, nodes: Vec<RemoteNodeAddition>) -> Result<(), ClientError> {
+        let query = r#"
+            query AddNodes($path: String!, $nodes: [NodeAddition!]!) {
+                updateGraph(path: $path) {
+                    addNodes(nodes: $nodes)
+                }
+            }"#;
+        self.update(query, json!({ "nodes": nodes })).await
+    }
+
+    /// Add an edge update, creating the edge and its nodes if they do not exist
+    pub async fn add_edge<K: AsRef<str>, V: Into<Prop>>(
+        &self,
+        time: impl IntoTime,
+        src: impl Into<GID>,
+        dst: impl Into<GID>,
+        properties: impl IntoIterator<Item = (K, V)>,
+        layer: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query AddEdge($path: String!, $time: Int!, $src: String!, $dst: String!, $properties: [GqlPropInput!], $layer: String) {
+                updateGraph(path: $path) {
+                    addEdge(time: $time, src: $src, dst: $dst, properties: $properties, layer: $layer) {
+                        success
+                    }
+                }
+            }"#;
+        let variables = json!({
+            "time": time.into_time(),
+            "src": src.into().to_string(),
+            "dst": dst.into().to_string(),
+            "properties": property_inputs(properties),
+            "layer": layer,
+        });
+        self.update(query, variables).await
+    }
+
+    /// Add the updates of several edges in a single request
+    pub async fn add_edges(&self, edges: Vec<RemoteEdgeAddition>) -> Result<(), ClientError> {
+        let query = r#"
+            query AddEdges($path: String!, $edges: [EdgeAddition!]!) {
+                updateGraph(path: $path) {
+                    addEdges(edges: $edges)
+                }
+            }"#;
+        self.update(query, json!({ "edges": edges })).await
+    }
+
+    /// Add nodes and edges atomically, the server applies none of them if any is invalid
+    pub async fn add_batch(
+        &self,
+        nodes: Vec<RemoteNodeAddition>,
+        edges: Vec<RemoteEdgeAddition>,
+    ) -> Result<RemoteBatchResult, ClientError> {
+        let query = r#"
+            query AddBatch($path: String!, $nodes: [NodeAddition!], $edges: [EdgeAddition!]) {
+                updateGraph(path: $path) {
+                    addBatch(nodes: $nodes, edges: $edges) {
+                        success
+                        errors { kind index message }
+                    }
+                }
+            }"#;
+        let mut data = self
+            .query(query, json!({ "nodes": nodes, "edges": edges }))
+            .await?;
+        Ok(serde_json::from_value(
+            data["updateGraph"]["addBatch"].take(),
+        )?)
+    }
+
+    /// Add temporal properties to the graph
+    pub async fn add_properties<K: AsRef<str>, V: Into<Prop>>(
+        &self,
+        time: impl IntoTime,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query AddProperties($path: String!, $t: Int!, $properties: [GqlPropInput!]!) {
+                updateGraph(path: $path) {
+                    addProperties(t: $t, properties: $properties)
+                }
+            }"#;
+        let variables = json!({
+            "t": time.into_time(),
+            "properties": property_inputs(properties),
+        });
+        self.update(query, variables).await
+    }
+
+    /// Add constant properties to the graph, fails if a property already has a different value
+    pub async fn add_constant_properties<K: AsRef<str>, V: Into<Prop>>(
+        &self,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query AddConstantProperties($path: String!, $properties: [GqlPropInput!]!) {
+                updateGraph(path: $path) {
+                    addConstantProperties(properties: $properties)
+                }
+            }"#;
+        self.update(query, json!({ "properties": property_inputs(properties) }))
+            .await
+    }
+
+    /// Set constant properties of the graph, overwriting existing values
+    pub async fn update_constant_properties<K: AsRef<str>, V: Into<Prop>>(
+        &self,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query UpdateConstantProperties($path: String!, $properties: [GqlPropInput!]!) {
+                updateGraph(path: $path) {
+                    updateConstantProperties(properties: $properties)
+                }
+            }"#;
+        self.update(query, json!({ "properties": property_inputs(properties) }))
+            .await
+    }
+
+    /// Delete an edge at `time`, the edge is created if it does not exist
+    pub async fn delete_edge(
+        &self,
+        time: impl IntoTime,
+        src: impl Into<GID>,
+        dst: impl Into<GID>,
+        layer: Option<&str>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query DeleteEdge($path: String!, $time: Int!, $src: String!, $dst: String!, $layer: String) {
+                updateGraph(path: $path) {
+                    deleteEdge(time: $time, src: $src, dst: $dst, layer: $layer) {
+                        success
+                    }
+                }
+            }"#;
+        let variables = json!({
+            "time": time.into_time(),
+            "src": src.into().to_string(),
+            "dst": dst.into().to_string(),
+            "layer": layer,
+        });
+        self.update(query, variables).await
+    }
+
+    /// Delete a node at `time`
+    pub async fn delete_node(
+        &self,
+        time: impl IntoTime,
+        name: impl Into<GID>,
+    ) -> Result<(), ClientError> {
+        let query = r#"
+            query DeleteNode($path: String!, $time: Int!, $name: String!) {
+                updateGraph(path: $path) {
+                    deleteNode(time: $time, name: $name) {
+                        success
+                    }
+                }
+            }"#;
+        let variables = json!({
+            "time": time.into_time(),
+            "name": name.into().to_string(),
+        });
+        self.update(query, variables).await
+    }
+
+    /// Run an update of the graph through `updateGraph`, `path` is added to `variables`
+    async fn query(&self, query: &str, variables: JsonValue) -> Result<JsonValue, ClientError> {
+        let mut variables = super::into_map(variables);
+        variables.insert("path".to_owned(), json!(self.path));
+        let data = self.client.query(query, variables).await?;
+        Ok(JsonValue::Object(data))
+    }
+
+    async fn update(&self, query: &str, variables: JsonValue) -> Result<(), ClientError> {
+        self.query(query, variables).await?;
+        Ok(())
+    }
+}
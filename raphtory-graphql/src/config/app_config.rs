@@ -1,5 +1,9 @@
 use crate::config::{
-    cache_config::CacheConfig, log_config::LoggingConfig, otlp_config::TracingConfig,
+    auth_config::{ApiKeyConfig, AuthConfig, JwtConfig, NamespacePermissions},
+    cache_config::CacheConfig,
+    log_config::LoggingConfig,
+    otlp_config::TracingConfig,
+    query_config::QueryConfig,
 };
 use config::{Config, ConfigError, File};
 use serde::Deserialize;
@@ -10,6 +14,10 @@ pub struct AppConfig {
     pub logging: LoggingConfig,
     pub cache: CacheConfig,
     pub tracing: TracingConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub query: QueryConfig,
 }
 
 impl Default for AppConfig {
@@ -18,6 +26,8 @@ impl Default for AppConfig {
             logging: LoggingConfig::default(),
             cache: CacheConfig::default(),
             tracing: TracingConfig::default(),
+            auth: AuthConfig::default(),
+            query: QueryConfig::default(),
         }
     }
 }
@@ -26,6 +36,8 @@ pub struct AppConfigBuilder {
     logging: LoggingConfig,
     cache: CacheConfig,
     tracing: TracingConfig,
+    auth: AuthConfig,
+    query: QueryConfig,
 }
 
 impl AppConfigBuilder {
@@ -34,6 +46,8 @@ impl AppConfigBuilder {
             logging: LoggingConfig::default(),
             cache: CacheConfig::default(),
             tracing: TracingConfig::default(),
+            auth: AuthConfig::default(),
+            query: QueryConfig::default(),
         }
     }
 
@@ -42,6 +56,8 @@ impl AppConfigBuilder {
             logging: config.logging,
             cache: config.cache,
             tracing: config.tracing,
+            auth: config.auth,
+            query: config.query,
         }
     }
 
@@ -80,11 +96,61 @@ impl AppConfigBuilder {
         self
     }
 
+    pub fn with_auth(mut self, auth: AuthConfig) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    pub fn with_api_key(mut self, key: String, permissions: NamespacePermissions) -> Self {
+        self.auth.api_keys.push(ApiKeyConfig { key, permissions });
+        self
+    }
+
+    pub fn with_jwt(mut self, key_path: PathBuf, algorithm: String) -> Self {
+        self.auth.jwt = Some(JwtConfig {
+            key_path,
+            algorithm,
+        });
+        self
+    }
+
+    pub fn with_anonymous_permissions(mut self, permissions: NamespacePermissions) -> Self {
+        self.auth.anonymous_permissions = permissions;
+        self
+    }
+
+    pub fn with_max_query_depth(mut self, max_depth: usize) -> Self {
+        self.query.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_max_query_complexity(mut self, max_complexity: usize) -> Self {
+        self.query.max_complexity = Some(max_complexity);
+        self
+    }
+
+    pub fn with_query_timeout_seconds(mut self, timeout_seconds: u64) -> Self {
+        self.query.timeout_seconds = Some(timeout_seconds);
+        self
+    }
+
+    pub fn with_max_result_size(mut self, max_result_size: usize) -> Self {
+        self.query.max_result_size = Some(max_result_size);
+        self
+    }
+
+    pub fn with_query_limits(mut self, query: QueryConfig) -> Self {
+        self.query = query;
+        self
+    }
+
     pub fn build(self) -> AppConfig {
         AppConfig {
             logging: self.logging,
             cache: self.cache,
             tracing: self.tracing,
+            auth: self.auth,
+            query: self.query,
         }
     }
 }
@@ -140,5 +206,13 @@ pub fn load_config(
         app_config_builder = app_config_builder.with_cache_tti_seconds(cache_tti_seconds);
     }
 
+    if let Some(auth) = settings.get::<AuthConfig>("auth").ok() {
+        app_config_builder = app_config_builder.with_auth(auth);
+    }
+
+    if let Some(query) = settings.get::<QueryConfig>("query").ok() {
+        app_config_builder = app_config_builder.with_query_limits(query);
+    }
+
     Ok(app_config_builder.build())
 }
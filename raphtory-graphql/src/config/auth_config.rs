@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+};
+
+pub const DEFAULT_JWT_ALGORITHM: &'static str = "RS256";
+
+/// The access level granted on a graph namespace, write access includes read access
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    Read,
+    Write,
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Access::Read => write!(f, "read"),
+            Access::Write => write!(f, "write"),
+        }
+    }
+}
+
+impl FromStr for Access {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "read" => Ok(Access::Read),
+            "write" => Ok(Access::Write),
+            _ => Err(format!("Invalid access '{s}', expected 'read' or 'write'")),
+        }
+    }
+}
+
+/// Permissions granted per graph namespace, `""` or `"*"` is the root of the working directory
+pub type NamespacePermissions = BTreeMap<String, Access>;
+
+#[derive(Debug, Deserialize, PartialEq, Clone, Serialize)]
+pub struct ApiKeyConfig {
+    pub key: String,
+    pub permissions: NamespacePermissions,
+}
+
+/// JWT verification against a key stored on the server
+///
+/// The permissions are read from the `permissions` claim of the token which maps namespaces to
+/// `"read"` or `"write"`.
+#[derive(Debug, Deserialize, PartialEq, Clone, Serialize)]
+pub struct JwtConfig {
+    /// PEM encoded public key, or the shared secret for the HMAC algorithms
+    pub key_path: PathBuf,
+    #[serde(default = "default_jwt_algorithm")]
+    pub algorithm: String,
+}
+
+fn default_jwt_algorithm() -> String {
+    DEFAULT_JWT_ALGORITHM.to_owned()
+}
+
+/// Authentication is enabled as soon as an API key or a JWT key is configured
+///
+/// The `/metrics` route is then only served to callers with read access to the root namespace.
+#[derive(Debug, Deserialize, PartialEq, Clone, Serialize, Default)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub api_keys: Vec<ApiKeyConfig>,
+    pub jwt: Option<JwtConfig>,
+    /// Permissions of requests without credentials when authentication is enabled
+    pub anonymous_permissions: NamespacePermissions,
+}
+
+impl AuthConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty() || self.jwt.is_some()
+    }
+}
@@ -1,11 +1,16 @@
 pub mod app_config;
+pub mod auth_config;
 pub mod cache_config;
 pub mod log_config;
 pub mod otlp_config;
+pub mod query_config;
 
 #[cfg(test)]
 mod tests {
-    use crate::config::app_config::{load_config, AppConfigBuilder};
+    use crate::config::{
+        app_config::{load_config, AppConfigBuilder},
+        auth_config::Access,
+    };
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -37,6 +42,64 @@ mod tests {
         fs::remove_file(config_path).unwrap();
     }
 
+    #[test]
+    fn test_load_auth_config_from_toml() {
+        let config_toml = r#"
+            [auth]
+            anonymous_permissions = { public = "read" }
+
+            [[auth.api_keys]]
+            key = "abc"
+            permissions = { "*" = "read", team_a = "write" }
+
+            [auth.jwt]
+            key_path = "/keys/public.pem"
+        "#;
+        let config_path = PathBuf::from("test_auth_config.toml");
+        fs::write(&config_path, config_toml).unwrap();
+
+        let result = load_config(None, Some(config_path.clone()));
+        let expected_config = AppConfigBuilder::new()
+            .with_api_key(
+                "abc".to_string(),
+                [
+                    ("*".to_string(), Access::Read),
+                    ("team_a".to_string(), Access::Write),
+                ]
+                .into(),
+            )
+            .with_jwt(PathBuf::from("/keys/public.pem"), "RS256".to_string())
+            .with_anonymous_permissions([("public".to_string(), Access::Read)].into())
+            .build();
+
+        assert_eq!(result.unwrap(), expected_config);
+
+        fs::remove_file(config_path).unwrap();
+    }
+
+    #[test]
+    fn test_load_query_config_from_toml() {
+        let config_toml = r#"
+            [query]
+            max_depth = 10
+            max_complexity = 500
+            timeout_seconds = 30
+        "#;
+        let config_path = PathBuf::from("test_query_config.toml");
+        fs::write(&config_path, config_toml).unwrap();
+
+        let result = load_config(None, Some(config_path.clone()));
+        let expected_config = AppConfigBuilder::new()
+            .with_max_query_depth(10)
+            .with_max_query_complexity(500)
+            .with_query_timeout_seconds(30)
+            .build();
+
+        assert_eq!(result.unwrap(), expected_config);
+
+        fs::remove_file(config_path).unwrap();
+    }
+
     #[test]
     fn test_load_config_with_custom_cache() {
         let app_config = AppConfigBuilder::new()
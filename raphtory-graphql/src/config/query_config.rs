@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Limits on the queries run by the server, every limit that is not set is unbounded
+#[derive(Debug, Deserialize, PartialEq, Clone, Serialize, Default)]
+#[serde(default)]
+pub struct QueryConfig {
+    /// Maximum nesting of the fields of a query
+    pub max_depth: Option<usize>,
+    /// Maximum number of fields of a query, counting the fields of fragments where they are spread
+    pub max_complexity: Option<usize>,
+    /// Time after which a query is cancelled
+    pub timeout_seconds: Option<u64>,
+    /// Maximum size in bytes of the JSON response
+    pub max_result_size: Option<usize>,
+}
+
+impl QueryConfig {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_seconds.map(Duration::from_secs)
+    }
+}
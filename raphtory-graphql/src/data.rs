@@ -1,13 +1,19 @@
 use crate::{
     config::app_config::AppConfig,
-    graph::GraphWithVectors,
+    events::GraphEvents,
+    graph::{GraphWithVectors, GraphWriteLocks},
     model::plugins::query_plugin::QueryPlugin,
+    observability::metrics::{MeteredEmbedding, METRICS},
     paths::{ExistingGraphFolder, ValidGraphFolder},
+    snapshots::{Snapshot, SnapshotError, Snapshots},
 };
+use chrono::Utc;
 use moka::sync::Cache;
+use parking_lot::Mutex;
 use raphtory::{
     core::utils::errors::{GraphError, GraphResult},
     db::api::view::MaterializedGraph,
+    prelude::*,
     vectors::{
         embedding_cache::EmbeddingCache, embeddings::openai_embedding, template::DocumentTemplate,
         vectorisable::Vectorisable, vectorised_graph::VectorisedGraph, Embedding,
@@ -15,6 +21,7 @@ use raphtory::{
     },
 };
 use std::{
+    cell::Cell,
     collections::HashMap,
     fs,
     path::{Path, PathBuf, StripPrefixError},
@@ -37,6 +44,10 @@ pub struct Data {
     cache: Cache<PathBuf, GraphWithVectors>,
     pub(crate) index: bool,
     pub(crate) embedding_conf: Option<EmbeddingConf>,
+    pub(crate) events: GraphEvents,
+    /// Serialises the updates of the snapshot files
+    snapshots_lock: Arc<Mutex<()>>,
+    write_locks: GraphWriteLocks,
 }
 
 impl Data {
@@ -46,10 +57,17 @@ impl Data {
         let cache = Cache::<PathBuf, GraphWithVectors>::builder()
             .max_capacity(cache_configs.capacity)
             .time_to_idle(std::time::Duration::from_secs(cache_configs.tti_seconds))
-            .eviction_listener(|_, graph, _| {
-                graph
-                    .write_updates()
-                    .unwrap_or_else(|err| error!("Write on eviction failed: {err:?}"))
+            .eviction_listener(|_, graph, cause| {
+                METRICS
+                    .graph_cache_evictions
+                    .get(&format!("{cause:?}").to_lowercase())
+                    .inc();
+                // the file of an outdated graph was truncated by a restore, there is nothing to write
+                if !graph.is_outdated() {
+                    graph
+                        .write_updates()
+                        .unwrap_or_else(|err| error!("Write on eviction failed: {err:?}"))
+                }
                 // FIXME: don't have currently a way to know which embedding updates are pending
             })
             .build();
@@ -59,6 +77,9 @@ impl Data {
             cache,
             index: true,
             embedding_conf: Default::default(),
+            events: Default::default(),
+            snapshots_lock: Default::default(),
+            write_locks: Default::default(),
         }
     }
 
@@ -67,9 +88,22 @@ impl Data {
         path: &str,
     ) -> Result<(GraphWithVectors, ExistingGraphFolder), Arc<GraphError>> {
         let graph_folder = ExistingGraphFolder::try_from(self.work_dir.clone(), path)?;
-        self.cache
-            .try_get_with(path.into(), || self.read_graph_from_folder(&graph_folder))
-            .map(|graph| (graph, graph_folder))
+        let loaded = Cell::new(false);
+        let graph = self.cache.try_get_with(path.into(), || {
+            loaded.set(true);
+            self.read_graph_from_folder(&graph_folder)
+        });
+        match loaded.get() {
+            true => METRICS.graph_cache_misses.inc(),
+            false => METRICS.graph_cache_hits.inc(),
+        }
+        graph.map(|graph| (graph, graph_folder))
+    }
+
+    /// The number of graphs loaded in the cache
+    pub(crate) fn resident_graphs(&self) -> u64 {
+        self.cache.run_pending_tasks();
+        self.cache.entry_count()
     }
 
     pub async fn insert_graph(
@@ -98,6 +132,11 @@ impl Data {
             Err(_) => {
                 fs::create_dir_all(folder.get_base_path())?;
                 graph.cache(folder)?;
+                let write_lock = self.write_locks.get(path);
+                let version = write_lock.version();
+                let graph = graph
+                    .with_events(self.events.publisher(path))
+                    .with_write_lock(write_lock, version);
                 self.cache.insert(path.into(), graph);
                 Ok(())
             }
@@ -111,6 +150,91 @@ impl Data {
         Ok(())
     }
 
+    pub(crate) fn list_snapshots(&self, path: &str) -> Result<Vec<Snapshot>, SnapshotError> {
+        let folder = ExistingGraphFolder::try_from(self.work_dir.clone(), path)?;
+        Ok(Snapshots::read(&folder)?.iter().cloned().collect())
+    }
+
+    /// Record the current state of the graph at `path` under `name`
+    ///
+    /// Writes are held off until the snapshot is recorded so that it never captures a partially
+    /// applied batch of updates.
+    pub(crate) async fn create_snapshot(
+        &self,
+        path: &str,
+        name: &str,
+    ) -> Result<Snapshot, SnapshotError> {
+        let (graph, folder) = self.get_graph(path)?;
+        let _writes = graph.lock_writes().await?;
+        let _guard = self.snapshots_lock.lock();
+        let mut snapshots = Snapshots::read(&folder)?;
+        let snapshot = Snapshot {
+            name: name.to_owned(),
+            count_nodes: graph.graph.count_nodes(),
+            count_edges: graph.graph.count_edges(),
+            checkpoint: graph.graph.checkpoint()?,
+            created_at: Utc::now().timestamp_millis(),
+        };
+        snapshots.insert(snapshot.clone())?;
+        snapshots.write()?;
+        Ok(snapshot)
+    }
+
+    pub(crate) fn delete_snapshot(&self, path: &str, name: &str) -> Result<(), SnapshotError> {
+        let folder = ExistingGraphFolder::try_from(self.work_dir.clone(), path)?;
+        let _guard = self.snapshots_lock.lock();
+        let mut snapshots = Snapshots::read(&folder)?;
+        snapshots.remove(name)?;
+        snapshots.write()
+    }
+
+    /// The graph at `path` as it was when the snapshot `name` was created
+    pub(crate) fn read_snapshot(
+        &self,
+        path: &str,
+        name: &str,
+    ) -> Result<MaterializedGraph, SnapshotError> {
+        let folder = ExistingGraphFolder::try_from(self.work_dir.clone(), path)?;
+        let checkpoint = Snapshots::read(&folder)?.get(name)?.checkpoint;
+        Ok(MaterializedGraph::decode_checkpoint(folder, checkpoint)?)
+    }
+
+    /// Discard the updates applied to the graph at `path` since the snapshot `name` was created
+    ///
+    /// The snapshots created after `name` are deleted along with the updates.
+    pub(crate) async fn restore_snapshot(
+        &self,
+        path: &str,
+        name: &str,
+    ) -> Result<Snapshot, SnapshotError> {
+        let folder = ExistingGraphFolder::try_from(self.work_dir.clone(), path)?;
+        let write_lock = self.write_locks.get(path);
+        let snapshot = {
+            // the graph can be held by in-flight requests, no mutation can run until the copies
+            // loaded before the restore are marked as outdated
+            let _writes = write_lock.lock().await;
+            let _guard = self.snapshots_lock.lock();
+            let mut snapshots = Snapshots::read(&folder)?;
+            let snapshot = snapshots.get(name)?.clone();
+            folder.truncate_graph(snapshot.checkpoint)?;
+            snapshots.truncate(snapshot.checkpoint);
+            snapshots.write()?;
+            // bumped after the truncation as the graphs loaded from then on read the truncated file
+            write_lock.bump_version();
+            self.cache.invalidate(&PathBuf::from(path));
+            snapshot
+        };
+        // the embeddings include the discarded updates
+        let vectors_path = folder.get_vectors_path();
+        if vectors_path.exists() {
+            fs::remove_dir_all(&vectors_path)?;
+            if let Some(vectors) = self.vectorise_folder(&folder).await {
+                vectors.write_to_path(&vectors_path)?;
+            }
+        }
+        Ok(snapshot)
+    }
+
     pub async fn embed_query(&self, query: String) -> GraphResult<Embedding> {
         let embedding_function = self
             .embedding_conf
@@ -119,7 +243,10 @@ impl Data {
         let embedding = if let Some(embedding_function) = embedding_function {
             embedding_function.call(vec![query]).await?.remove(0)
         } else {
-            openai_embedding(vec![query]).await?.remove(0)
+            MeteredEmbedding(openai_embedding)
+                .call(vec![query])
+                .await?
+                .remove(0)
         };
         Ok(embedding)
     }
@@ -235,14 +362,21 @@ impl Data {
             .embedding_conf
             .as_ref()
             .map(|conf| conf.function.clone())
-            .unwrap_or(Arc::new(openai_embedding));
+            .unwrap_or(Arc::new(MeteredEmbedding(openai_embedding)));
         let cache = self
             .embedding_conf
             .as_ref()
             .map(|conf| conf.cache.clone())
             .unwrap_or(Arc::new(None));
 
-        GraphWithVectors::read_from_folder(folder, self.index, embedding, cache)
+        let path = folder.get_original_path_str();
+        let write_lock = self.write_locks.get(path);
+        // read before the graph so that a restore running meanwhile makes it outdated
+        let version = write_lock.version();
+        let graph = GraphWithVectors::read_from_folder(folder, self.index, embedding, cache)?;
+        Ok(graph
+            .with_events(self.events.publisher(path))
+            .with_write_lock(write_lock, version))
     }
 }
 
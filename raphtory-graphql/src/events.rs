@@ -0,0 +1,249 @@
+use dynamic_graphql::Enum;
+use parking_lot::Mutex;
+use raphtory::{
+    db::{
+        api::view::StaticGraphViewOps,
+        graph::{edge::EdgeView, node::NodeView},
+    },
+    prelude::{EdgeViewOps, NodeViewOps, Prop},
+};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::broadcast;
+
+/// Number of events buffered per graph before slow subscribers start missing updates
+pub(crate) const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphEventKind {
+    NodeAdded,
+    NodeUpdated,
+    NodeDeleted,
+    EdgeAdded,
+    EdgeUpdated,
+    EdgeDeleted,
+    GraphUpdated,
+}
+
+/// An update applied to a graph through the mutation API
+///
+/// `time` is `None` for updates that are not temporal, e.g., constant properties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphEvent {
+    pub kind: GraphEventKind,
+    pub time: Option<i64>,
+    pub node: Option<String>,
+    pub node_type: Option<String>,
+    pub src: Option<String>,
+    pub src_type: Option<String>,
+    pub dst: Option<String>,
+    pub dst_type: Option<String>,
+    pub layer: Option<String>,
+    pub properties: Vec<(String, Prop)>,
+}
+
+impl GraphEvent {
+    pub(crate) fn node<G: StaticGraphViewOps>(
+        kind: GraphEventKind,
+        time: Option<i64>,
+        node: &NodeView<G>,
+        properties: Vec<(String, Prop)>,
+    ) -> Self {
+        Self {
+            kind,
+            time,
+            node: Some(node.name()),
+            node_type: node.node_type().map(|t| t.to_string()),
+            src: None,
+            src_type: None,
+            dst: None,
+            dst_type: None,
+            layer: None,
+            properties,
+        }
+    }
+
+    /// If `layer` is not specified, the layer of the edge view is used when it is unique
+    pub(crate) fn edge<G: StaticGraphViewOps>(
+        kind: GraphEventKind,
+        time: Option<i64>,
+        edge: &EdgeView<G>,
+        layer: Option<&str>,
+        properties: Vec<(String, Prop)>,
+    ) -> Self {
+        let layer = match layer {
+            Some(layer) => Some(layer.to_owned()),
+            None => edge.layer_name().ok().map(|layer| layer.to_string()),
+        };
+        Self {
+            kind,
+            time,
+            node: None,
+            node_type: None,
+            src: Some(edge.src().name()),
+            src_type: edge.src().node_type().map(|t| t.to_string()),
+            dst: Some(edge.dst().name()),
+            dst_type: edge.dst().node_type().map(|t| t.to_string()),
+            layer,
+            properties,
+        }
+    }
+
+    pub(crate) fn graph(time: Option<i64>, properties: Vec<(String, Prop)>) -> Self {
+        Self {
+            kind: GraphEventKind::GraphUpdated,
+            time,
+            node: None,
+            node_type: None,
+            src: None,
+            src_type: None,
+            dst: None,
+            dst_type: None,
+            layer: None,
+            properties,
+        }
+    }
+}
+
+/// Filters of a subscription, every filter that is not set lets all the events through
+#[derive(Clone, Debug, Default)]
+pub struct GraphEventFilter {
+    /// Only edge events are layered, node and graph events are not affected
+    pub layers: Option<Vec<String>>,
+    /// Edge events match if the type of either of their endpoints matches
+    pub node_types: Option<Vec<String>>,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+}
+
+impl GraphEventFilter {
+    pub fn matches(&self, event: &GraphEvent) -> bool {
+        fn contains(values: &[String], value: &Option<String>) -> bool {
+            value.as_ref().is_some_and(|value| values.contains(value))
+        }
+
+        let is_edge = event.src.is_some();
+        let layer_matches = match &self.layers {
+            Some(layers) if is_edge => contains(layers, &event.layer),
+            _ => true,
+        };
+        let node_type_matches = match &self.node_types {
+            Some(types) if is_edge => {
+                contains(types, &event.src_type) || contains(types, &event.dst_type)
+            }
+            Some(types) if event.node.is_some() => contains(types, &event.node_type),
+            _ => true,
+        };
+        let time_matches = match event.time {
+            Some(t) => {
+                self.start.is_none_or(|start| t >= start) && self.end.is_none_or(|end| t < end)
+            }
+            None => true,
+        };
+        layer_matches && node_type_matches && time_matches
+    }
+}
+
+/// Broadcast channels of graph events, one per graph path
+///
+/// Channels are owned by [`Data`](crate::data::Data) rather than the cached graphs so subscriptions
+/// survive graphs being evicted from the cache and reloaded.
+#[derive(Clone, Default)]
+pub(crate) struct GraphEvents {
+    channels: Arc<Mutex<HashMap<String, broadcast::Sender<GraphEvent>>>>,
+}
+
+impl GraphEvents {
+    pub(crate) fn subscribe(&self, path: &str) -> broadcast::Receiver<GraphEvent> {
+        self.channels
+            .lock()
+            .entry(path.to_owned())
+            .or_insert_with(|| broadcast::channel(EVENT_CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    pub(crate) fn publish(&self, path: &str, event: GraphEvent) {
+        let mut channels = self.channels.lock();
+        if let Some(sender) = channels.get(path) {
+            if sender.send(event).is_err() {
+                // every subscriber is gone
+                channels.remove(path);
+            }
+        }
+    }
+
+    pub(crate) fn publisher(&self, path: &str) -> EventPublisher {
+        EventPublisher {
+            events: self.clone(),
+            path: path.to_owned(),
+        }
+    }
+}
+
+/// Publishes the events of a single graph
+#[derive(Clone)]
+pub(crate) struct EventPublisher {
+    events: GraphEvents,
+    path: String,
+}
+
+impl EventPublisher {
+    pub(crate) fn publish(&self, event: GraphEvent) {
+        self.events.publish(&self.path, event)
+    }
+}
+
+#[cfg(test)]
+mod events_test {
+    use super::*;
+
+    fn edge_event(layer: &str, time: i64) -> GraphEvent {
+        GraphEvent {
+            kind: GraphEventKind::EdgeAdded,
+            time: Some(time),
+            node: None,
+            node_type: None,
+            src: Some("a".to_owned()),
+            src_type: Some("person".to_owned()),
+            dst: Some("b".to_owned()),
+            dst_type: None,
+            layer: Some(layer.to_owned()),
+            properties: vec![],
+        }
+    }
+
+    #[test]
+    fn filter_events() {
+        let filter = GraphEventFilter {
+            layers: Some(vec!["follows".to_owned()]),
+            node_types: Some(vec!["person".to_owned()]),
+            start: Some(10),
+            end: Some(20),
+        };
+        assert!(filter.matches(&edge_event("follows", 10)));
+        assert!(!filter.matches(&edge_event("follows", 20)));
+        assert!(!filter.matches(&edge_event("likes", 15)));
+        assert!(filter.matches(&GraphEvent::graph(None, vec![])));
+
+        let mut node_event = GraphEvent::graph(Some(15), vec![]);
+        node_event.kind = GraphEventKind::NodeAdded;
+        node_event.node = Some("c".to_owned());
+        assert!(!filter.matches(&node_event));
+        node_event.node_type = Some("person".to_owned());
+        assert!(filter.matches(&node_event));
+    }
+
+    #[test]
+    fn channels_are_dropped_without_subscribers() {
+        let events = GraphEvents::default();
+        let publisher = events.publisher("g");
+        publisher.publish(GraphEvent::graph(Some(1), vec![]));
+
+        let mut receiver = events.subscribe("g");
+        publisher.publish(GraphEvent::graph(Some(2), vec![]));
+        assert_eq!(receiver.try_recv().unwrap().time, Some(2));
+
+        drop(receiver);
+        publisher.publish(GraphEvent::graph(Some(3), vec![]));
+        assert!(events.channels.lock().is_empty());
+    }
+}
@@ -1,4 +1,11 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 
 use once_cell::sync::OnceCell;
 #[cfg(feature = "storage")]
@@ -22,8 +29,49 @@ use raphtory::{
         embedding_cache::EmbeddingCache, vectorised_graph::VectorisedGraph, EmbeddingFunction,
     },
 };
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::{
+    events::{EventPublisher, GraphEvent},
+    observability::metrics::{result_label, METRICS},
+    paths::ExistingGraphFolder,
+};
 
-use crate::paths::ExistingGraphFolder;
+/// Serialises the mutations applied through the GraphQL API to the graph of a folder
+///
+/// The lock is shared by all the copies of the graph loaded from the folder. Restoring a snapshot
+/// truncates the graph file and bumps the version, the copies loaded before then reject their
+/// updates instead of appending them to the truncated file.
+#[derive(Default)]
+pub(crate) struct GraphWriteLock {
+    lock: Mutex<()>,
+    version: AtomicU64,
+}
+
+impl GraphWriteLock {
+    pub(crate) fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    pub(crate) async fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    /// Mark the copies of the graph loaded so far as outdated, called with the lock held
+    pub(crate) fn bump_version(&self) {
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// The write locks of the graphs by path
+#[derive(Clone, Default)]
+pub(crate) struct GraphWriteLocks(Arc<parking_lot::Mutex<HashMap<String, Arc<GraphWriteLock>>>>);
+
+impl GraphWriteLocks {
+    pub(crate) fn get(&self, path: &str) -> Arc<GraphWriteLock> {
+        self.0.lock().entry(path.to_owned()).or_default().clone()
+    }
+}
 
 #[derive(Clone)]
 pub struct GraphWithVectors {
@@ -31,6 +79,10 @@ pub struct GraphWithVectors {
     pub index: Option<IndexedGraph<MaterializedGraph>>,
     pub vectors: Option<VectorisedGraph<MaterializedGraph>>,
     folder: OnceCell<GraphFolder>,
+    events: Option<EventPublisher>,
+    write_lock: Arc<GraphWriteLock>,
+    /// The version of the write lock when the graph was loaded
+    version: u64,
 }
 
 impl GraphWithVectors {
@@ -44,6 +96,45 @@ impl GraphWithVectors {
             index,
             vectors,
             folder: Default::default(),
+            events: None,
+            write_lock: Default::default(),
+            version: 0,
+        }
+    }
+
+    /// Publish the updates applied through the GraphQL API to the subscribers of the graph
+    pub(crate) fn with_events(mut self, events: EventPublisher) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Share the write lock of the folder of the graph, `version` is read before the graph is
+    pub(crate) fn with_write_lock(mut self, write_lock: Arc<GraphWriteLock>, version: u64) -> Self {
+        self.write_lock = write_lock;
+        self.version = version;
+        self
+    }
+
+    /// Lock the graph for a mutation
+    ///
+    /// The guard is held until the updates are written so that the checks of a mutation still
+    /// hold when it is applied. Fails if a snapshot was restored since the graph was loaded.
+    pub(crate) async fn lock_writes(&self) -> Result<MutexGuard<'_, ()>, GraphError> {
+        let guard = self.write_lock.lock().await;
+        if self.is_outdated() {
+            return Err(GraphError::GraphRestored);
+        }
+        Ok(guard)
+    }
+
+    /// Whether a snapshot was restored since the graph was loaded
+    pub(crate) fn is_outdated(&self) -> bool {
+        self.write_lock.version() != self.version
+    }
+
+    pub(crate) fn publish(&self, event: GraphEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
         }
     }
 
@@ -84,8 +175,14 @@ impl GraphWithVectors {
     }
 
     pub(crate) fn write_updates(&self) -> Result<(), GraphError> {
-        self.graph.write_updates()?;
-        self.dump_vectors_to_disk()
+        let start = Instant::now();
+        let result = self
+            .graph
+            .write_updates()
+            .and_then(|_| self.dump_vectors_to_disk());
+        METRICS.graph_write_duration.observe(start.elapsed());
+        METRICS.graph_writes.get(result_label(&result)).inc();
+        result
     }
 
     fn dump_vectors_to_disk(&self) -> Result<(), GraphError> {
@@ -127,6 +224,9 @@ impl GraphWithVectors {
             index: index.then(|| graph.into()),
             vectors,
             folder: OnceCell::with_value(folder.clone().into()),
+            events: None,
+            write_lock: Default::default(),
+            version: 0,
         })
     }
 }
@@ -1,11 +1,16 @@
 pub use crate::server::GraphServer;
+pub mod auth;
+pub mod client;
 pub mod data;
+mod events;
 mod graph;
+mod limits;
 pub mod model;
 pub mod observability;
 mod paths;
 mod routes;
 pub mod server;
+mod snapshots;
 pub mod url_encode;
 
 pub mod config;
@@ -15,18 +20,23 @@ pub mod python;
 #[cfg(test)]
 mod graphql_test {
     use crate::{
-        config::app_config::AppConfig,
+        auth::Permissions,
+        config::{app_config::AppConfig, auth_config::Access, query_config::QueryConfig},
         data::{data_tests::save_graphs_to_work_dir, Data},
+        events::{GraphEvent, EVENT_CHANNEL_CAPACITY},
+        limits::{execute_with_limits, limit_schema},
         model::App,
         url_encode::{url_decode_graph, url_encode_graph},
     };
-    use async_graphql::UploadValue;
+    use async_graphql::{BatchRequest, BatchResponse, UploadValue};
     use serde_json::Value;
 
     use dynamic_graphql::{Request, Variables};
+    use futures_util::StreamExt;
     #[cfg(feature = "storage")]
     use raphtory::disk_graph::DiskGraphStorage;
     use raphtory::{
+        core::utils::errors::GraphError,
         db::{
             api::view::{IntoDynamic, MaterializedGraph},
             graph::views::deletion_graph::PersistentGraph,
@@ -1041,6 +1051,96 @@ mod graphql_test {
         );
     }
 
+    #[tokio::test]
+    async fn test_filter_nodes() {
+        let graph = Graph::new();
+        graph.add_node(1, 1, [("country", "UK")], None).unwrap();
+        graph.add_node(1, 2, [("country", "UK")], None).unwrap();
+        graph.add_node(1, 3, [("country", "US")], None).unwrap();
+        graph.add_node(3, 2, [("country", "US")], None).unwrap();
+        graph.add_edge(2, 1, 2, NO_PROPS, None).unwrap();
+        graph.add_edge(2, 2, 3, NO_PROPS, None).unwrap();
+
+        let graph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let req = r#"
+        {
+          graph(path: "graph") {
+            window(start: 0, end: 3) {
+              filterNodes(filter: {property: "country", operator: EQUAL, value: "UK"}) {
+                nodes {
+                  list {
+                    name
+                  }
+                }
+                edges {
+                  list {
+                    src {
+                      name
+                    }
+                    dst {
+                      name
+                    }
+                  }
+                }
+              }
+            }
+            filterNodes(filter: {property: "country", operator: ANY, value: ["US", "FR"]}) {
+              nodes {
+                list {
+                  name
+                }
+              }
+            }
+          }
+        }
+        "#;
+
+        let req = Request::new(req);
+        let res = schema.execute(req).await;
+        let data = res.data.into_json().unwrap();
+        assert_eq!(
+            data,
+            json!({
+                "graph": {
+                  "window": {
+                    "filterNodes": {
+                      "nodes": {
+                        "list": [{"name": "1"}, {"name": "2"}]
+                      },
+                      "edges": {
+                        "list": [{"src": {"name": "1"}, "dst": {"name": "2"}}]
+                      }
+                    }
+                  },
+                  "filterNodes": {
+                    "nodes": {
+                      "list": [{"name": "2"}, {"name": "3"}]
+                    }
+                  }
+                }
+            }),
+        );
+
+        let req = r#"
+        {
+          graph(path: "graph") {
+            filterNodes(filter: {property: "country", operator: EQUAL}) {
+              countNodes
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(Request::new(req)).await;
+        assert_eq!(res.errors.len(), 1);
+    }
+
     #[cfg(feature = "storage")]
     #[tokio::test]
     async fn test_disk_graph() {
@@ -1116,4 +1216,1233 @@ mod graphql_test {
             }),
         );
     }
+
+    #[tokio::test]
+    async fn test_rolling_and_expanding_windows() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        graph.add_edge(5, 1, 3, NO_PROPS, None).unwrap();
+        graph.add_edge(11, 1, 2, NO_PROPS, None).unwrap();
+        graph.add_edge(21, 2, 3, NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            rolling(window: 10) { start end countTemporalEdges }
+            expanding(step: 10) { end countTemporalEdges }
+            days: rolling(window: "1 day", step: "12 hours") { start end }
+            nodes {
+              rolling(window: 10, step: 5) { count }
+            }
+            edges {
+              expanding(step: 10) { start end }
+            }
+            node(name: "3") {
+              rolling(window: 10) { degree }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {
+                "rolling": [
+                    {"start": 1, "end": 11, "countTemporalEdges": 2},
+                    {"start": 11, "end": 21, "countTemporalEdges": 1},
+                    {"start": 21, "end": 31, "countTemporalEdges": 1},
+                ],
+                "expanding": [
+                    {"end": 11, "countTemporalEdges": 2},
+                    {"end": 21, "countTemporalEdges": 3},
+                    {"end": 31, "countTemporalEdges": 4},
+                ],
+                "days": [
+                    {"start": -43199999, "end": 43200001},
+                ],
+                "nodes": {
+                    "rolling": [{"count": 3}, {"count": 3}, {"count": 2}, {"count": 2}, {"count": 2}]
+                },
+                "edges": {
+                    "expanding": [
+                        {"start": null, "end": 11},
+                        {"start": null, "end": 21},
+                        {"start": null, "end": 31},
+                    ]
+                },
+                "node": {
+                    "rolling": [{"degree": 1}, {"degree": 0}, {"degree": 1}]
+                }
+            }})
+        );
+
+        let res = schema
+            .execute(r#"{ graph(path: "graph") { rolling(window: "1 fortnight") { start } } }"#)
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0]
+            .message
+            .contains("'fortnight' is not a valid unit"));
+
+        for query in [
+            r#"{ graph(path: "graph") { rolling(window: 0) { start } } }"#,
+            r#"{ graph(path: "graph") { nodes { expanding(step: "0 days") { count } } } }"#,
+            r#"{ graph(path: "graph") { edges { rolling(window: 5, step: 0) { count } } } }"#,
+        ] {
+            let res = schema.execute(query).await;
+            assert_eq!(res.errors.len(), 1);
+            assert!(res.errors[0].message.contains("Interval must be positive"));
+        }
+
+        let res = schema
+            .execute(
+                r#"{ graph(path: "graph") { window(start: 0, end: 20001) { rolling(window: 4) { start } } } }"#,
+            )
+            .await;
+        assert_eq!(res.errors, vec![]);
+        let res = schema
+            .execute(
+                r#"{ graph(path: "graph") { window(start: 0, end: 20001) { rolling(window: 1) { start } } } }"#,
+            )
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0]
+            .message
+            .contains("at most 10000 windows can be returned"));
+    }
+
+    #[tokio::test]
+    async fn test_sort_and_filter_nodes_and_edges() {
+        let graph: MaterializedGraph = Graph::new().into();
+        for (name, kind) in [("a", "bank"), ("b", "bank"), ("c", "bank"), ("d", "shop")] {
+            graph
+                .add_node(0, name, [("kind", kind)], Some("company"))
+                .unwrap();
+        }
+        graph.add_node(0, "e", NO_PROPS, Some("person")).unwrap();
+        graph
+            .add_edge(1, "a", "b", [("amount", 5i64)], None)
+            .unwrap();
+        graph
+            .add_edge(2, "a", "c", [("amount", 20i64)], None)
+            .unwrap();
+        graph
+            .add_edge(3, "a", "d", [("amount", 10i64)], None)
+            .unwrap();
+        graph.add_edge(4, "c", "e", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            nodes {
+              filter(filter: {properties: [{property: "kind", operator: EQUAL, value: "bank"}]}) {
+                top: sortBy(sortBys: [{key: DEGREE, reverse: true}], limit: 2) { ids }
+                sortBy(sortBys: [{key: DEGREE}, {key: NAME, reverse: true}]) {
+                  page(limit: 2, offset: 0) { name }
+                  window(start: 2, end: 5) { ids }
+                }
+              }
+              byType: filter(filter: {nodeTypes: ["person"], maxDegree: 1}) { ids }
+              byDegree: filter(filter: {minDegree: 2}) { count }
+            }
+            edges {
+              sortBy(sortBys: [{key: PROPERTY, property: "amount", reverse: true}]) {
+                list { src { name } dst { name } }
+              }
+              filter(filter: {properties: [{property: "amount", operator: GREATER_THAN, value: 5}], nodeTypes: ["company"]}) {
+                sortBy(sortBys: [{key: DST}], limit: 1) { list { dst { name } } }
+              }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {
+                "nodes": {
+                    "filter": {
+                        "top": {"ids": ["a", "c"]},
+                        "sortBy": {
+                            "page": [{"name": "b"}, {"name": "c"}],
+                            "window": {"ids": ["c", "a"]},
+                        },
+                    },
+                    "byType": {"ids": ["e"]},
+                    "byDegree": {"count": 2},
+                },
+                "edges": {
+                    "sortBy": {
+                        "list": [
+                            {"src": {"name": "a"}, "dst": {"name": "c"}},
+                            {"src": {"name": "a"}, "dst": {"name": "d"}},
+                            {"src": {"name": "a"}, "dst": {"name": "b"}},
+                            {"src": {"name": "c"}, "dst": {"name": "e"}},
+                        ]
+                    },
+                    "filter": {
+                        "sortBy": {"list": [{"dst": {"name": "c"}}]}
+                    },
+                },
+            }})
+        );
+
+        let res = schema
+            .execute(
+                r#"{ graph(path: "graph") { nodes { sortBy(sortBys: [{key: PROPERTY}]) { count } } } }"#,
+            )
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0]
+            .message
+            .contains("Sorting by Property requires a property"));
+    }
+
+    #[tokio::test]
+    async fn test_selected_edges_follow_views() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph
+            .add_edge(1, "a", "b", [("amount", 5i64)], Some("x"))
+            .unwrap();
+        graph
+            .add_edge(2, "a", "b", [("amount", 20i64)], Some("y"))
+            .unwrap();
+        graph
+            .add_edge(3, "c", "d", [("amount", 7i64)], None)
+            .unwrap();
+        graph.add_edge(4, "a", "c", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            nodes {
+              sortBy(sortBys: [{key: DEGREE}], limit: 2) { ids }
+            }
+            edges {
+              sortBy(sortBys: [{key: SRC}]) {
+                window(start: 3, end: 5) { list { src { name } } }
+                layer(name: "x") { list { src { name } } }
+              }
+              explodeLayers {
+                filter(filter: {properties: [{property: "amount", operator: GREATER_THAN, value: 10}]}) {
+                  list { layerName }
+                }
+              }
+              explode {
+                filter(filter: {properties: [{property: "amount", operator: GREATER_THAN, value: 6}]}) {
+                  list { time }
+                  layer(name: "y") { list { time } }
+                }
+              }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {
+                "nodes": {"sortBy": {"ids": ["b", "d"]}},
+                "edges": {
+                    "sortBy": {
+                        "window": {"list": [{"src": {"name": "a"}}, {"src": {"name": "c"}}]},
+                        "layer": {"list": [{"src": {"name": "a"}}]},
+                    },
+                    "explodeLayers": {"filter": {"list": [{"layerName": "y"}]}},
+                    "explode": {"filter": {
+                        "list": [{"time": 2}, {"time": 3}],
+                        "layer": {"list": [{"time": 2}]},
+                    }},
+                },
+            }})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_temporal_properties() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_node(1, 1, [("weight", 4i64)], None).unwrap();
+        graph.add_node(2, 1, [("weight", 1i64)], None).unwrap();
+        graph.add_node(3, 1, [("weight", 7i64)], None).unwrap();
+        graph.add_node(4, 1, [("weight", 1i64)], None).unwrap();
+        graph.add_node(1, 1, [("score", 0.5f64)], None).unwrap();
+        graph.add_node(1, 1, [("status", "open")], None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            node(name: "1") {
+              properties {
+                temporal {
+                  weight: get(key: "weight") {
+                    typedValues
+                    typedAt(t: 2)
+                    typedLatest
+                    sum
+                    mean
+                    min { time value }
+                    max { time value }
+                    count
+                  }
+                  score: get(key: "score") { typedLatest typedUnique }
+                  status: get(key: "status") { typedLatest sum mean min { value } }
+                }
+              }
+            }
+            window(start: 2, end: 4) {
+              node(name: "1") {
+                properties {
+                  temporal {
+                    get(key: "weight") { sum mean count }
+                  }
+                }
+              }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {
+                "node": {"properties": {"temporal": {
+                    "weight": {
+                        "typedValues": [4, 1, 7, 1],
+                        "typedAt": 1,
+                        "typedLatest": 1,
+                        "sum": 13,
+                        "mean": 3.25,
+                        "min": {"time": 2, "value": 1},
+                        "max": {"time": 3, "value": 7},
+                        "count": 4,
+                    },
+                    "score": {"typedLatest": 0.5, "typedUnique": [0.5]},
+                    "status": {"typedLatest": "open", "sum": "open", "mean": null, "min": {"value": "open"}},
+                }}},
+                "window": {"node": {"properties": {"temporal": {
+                    "get": {"sum": 8, "mean": 4.0, "count": 2}
+                }}}},
+            }})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exploded_edges_and_deletions() {
+        let graph = PersistentGraph::new();
+        graph
+            .add_edge(1, "a", "b", [("amount", 10i64)], None)
+            .unwrap();
+        graph
+            .add_edge(2, "a", "b", [("amount", 20i64)], Some("wire"))
+            .unwrap();
+        graph
+            .add_edge(3, "a", "b", [("amount", 30i64)], None)
+            .unwrap();
+        graph.delete_edge(4, "a", "b", None).unwrap();
+        graph.add_edge(5, "b", "c", NO_PROPS, None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            edge(src: "a", dst: "b") {
+              historyCounts
+              history(limit: 2, offset: 1)
+              deletions
+              isValid
+              default: layer(name: "_default") { isValid isDeleted }
+              exploded {
+                count
+                page(limit: 2, offset: 0) {
+                  time
+                  layerName
+                  properties { get(key: "amount") { value } }
+                }
+              }
+              explodedLayers { list { layerName } }
+              explodeLayers { layerName }
+            }
+            edges {
+              explode { count }
+              sortBy(sortBys: [{key: SRC, reverse: true}]) {
+                explode { list { src { name } time } }
+              }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {
+                "edge": {
+                    "historyCounts": 3,
+                    "history": [3],
+                    "deletions": [4],
+                    "isValid": true,
+                    "default": {"isValid": false, "isDeleted": true},
+                    "exploded": {
+                        "count": 3,
+                        "page": [
+                            {"time": 1, "layerName": "_default", "properties": {"get": {"value": 10}}},
+                            {"time": 2, "layerName": "wire", "properties": {"get": {"value": 20}}},
+                        ]
+                    },
+                    "explodedLayers": {"list": [{"layerName": "_default"}, {"layerName": "wire"}]},
+                    "explodeLayers": [{"layerName": "_default"}, {"layerName": "wire"}],
+                },
+                "edges": {
+                    "explode": {"count": 4},
+                    "sortBy": {"explode": {"list": [
+                        {"src": {"name": "b"}, "time": 5},
+                        {"src": {"name": "a"}, "time": 1},
+                        {"src": {"name": "a"}, "time": 2},
+                        {"src": {"name": "a"}, "time": 3},
+                    ]}},
+                },
+            }})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_atomic_batch() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph
+            .add_node(0, "a", [("weight", 1i64)], Some("bank"))
+            .unwrap()
+            .add_constant_properties([("country", "UK")])
+            .unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let invalid = r#"
+        {
+          updateGraph(path: "graph") {
+            addBatch(
+              nodes: [
+                {name: "b", updates: [{time: 1, properties: [{key: "weight", value: 2}]}]},
+                {name: "a", nodeType: "shop"},
+                {name: "c", updates: [{time: 1, properties: [{key: "weight", value: "heavy"}]}]},
+                {name: "a", constantProperties: [{key: "country", value: "FR"}]},
+                {name: "d", constantProperties: [{key: "country", value: "FR"}]},
+              ],
+              edges: [
+                {src: "a", dst: "b", updates: [{time: 2, properties: [{key: "amount", value: 1}]}]},
+                {src: "b", dst: "c", updates: [{time: 3, properties: [{key: "amount", value: 1.5}]}]},
+              ]
+            ) {
+              success
+              errors { kind index message }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(invalid).await;
+        assert_eq!(res.errors, vec![]);
+        let data = res.data.into_json().unwrap();
+        let result = &data["updateGraph"]["addBatch"];
+        assert_eq!(result["success"], false);
+        let errors: Vec<_> = result["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| {
+                (
+                    error["kind"].as_str().unwrap(),
+                    error["index"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("NODE", 1),
+                ("NODE", 2),
+                ("NODE", 3),
+                ("NODE", 4),
+                ("EDGE", 1)
+            ]
+        );
+        assert!(result["errors"][1]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Wrong type for property weight"));
+
+        let count = r#"{ graph(path: "graph") { nodes { count } edges { count } } }"#;
+        let res = schema.execute(count).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"nodes": {"count": 1}, "edges": {"count": 0}}})
+        );
+
+        let valid = r#"
+        {
+          updateGraph(path: "graph") {
+            addBatch(
+              nodes: [
+                {name: "b", nodeType: "shop", updates: [{time: 1, properties: [{key: "weight", value: 2}]}]},
+                {name: "a", constantProperties: [{key: "country", value: "UK"}]},
+              ],
+              edges: [
+                {src: "a", dst: "b", constantProperties: [{key: "kind", value: "wire"}], updates: [{time: 2}]},
+              ]
+            ) {
+              success
+              errors { message }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(valid).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"updateGraph": {"addBatch": {"success": true, "errors": []}}})
+        );
+
+        // the updates are persisted
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let query = r#"
+        {
+          graph(path: "graph") {
+            nodes { count }
+            node(name: "b") { nodeType }
+            edge(src: "a", dst: "b") { properties { get(key: "kind") { value } } }
+          }
+        }
+        "#;
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {
+                "nodes": {"count": 2},
+                "node": {"nodeType": "shop"},
+                "edge": {"properties": {"get": {"value": "wire"}}},
+            }})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_holds_write_lock() {
+        let graphs = HashMap::from([("graph".to_string(), Graph::new().into())]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let (graph, _) = data.get_graph("graph").unwrap();
+        let guard = graph.lock_writes().await.unwrap();
+        let batch = tokio::spawn(async move {
+            let query = r#"
+            {
+              updateGraph(path: "graph") {
+                addBatch(nodes: [
+                  {name: "a"},
+                  {name: "b", updates: [{time: 1, properties: [{key: "weight", value: 2}]}]},
+                ]) {
+                  success
+                  errors { index }
+                }
+              }
+            }
+            "#;
+            schema.execute(query).await
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!batch.is_finished());
+
+        // the batch is validated once the conflicting update is applied
+        graph
+            .graph
+            .add_node(0, "c", [("weight", "heavy")], None)
+            .unwrap();
+        drop(guard);
+        let res = batch.await.unwrap();
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"updateGraph": {"addBatch": {"success": false, "errors": [{"index": 1}]}}})
+        );
+        assert!(!graph.graph.has_node("a"));
+    }
+
+    #[tokio::test]
+    async fn test_graph_permissions() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_node(0, 1, NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([
+            ("team_a/graph".to_string(), graph.clone()),
+            ("team_b/graph".to_string(), graph.clone()),
+            ("team_c/graph".to_string(), graph),
+        ]);
+        let tmp_dir = tempdir().unwrap();
+        for namespace in ["team_a", "team_b", "team_c"] {
+            fs::create_dir(tmp_dir.path().join(namespace)).unwrap();
+        }
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let permissions = Permissions::new(
+            [
+                ("team_a".to_owned(), Access::Write),
+                ("team_b".to_owned(), Access::Read),
+            ]
+            .into(),
+        );
+        let execute =
+            |query: &str| schema.execute(Request::new(query.to_owned()).data(permissions.clone()));
+
+        let res = execute("{ graphs { path } }").await;
+        assert_eq!(res.errors, vec![]);
+        let mut paths: Vec<String> =
+            serde_json::from_value(res.data.into_json().unwrap()["graphs"]["path"].take()).unwrap();
+        paths.sort();
+        assert_eq!(paths, vec!["team_a/graph", "team_b/graph"]);
+
+        let res = execute(r#"{ graph(path: "team_b/graph") { countNodes } }"#).await;
+        assert_eq!(res.errors, vec![]);
+        let res = execute(r#"{ graph(path: "team_c/graph") { countNodes } }"#).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Permission denied: read access is required for graph 'team_c/graph'"
+        );
+        let res = execute(r#"{ receiveGraph(path: "team_c/graph") }"#).await;
+        assert_eq!(res.errors.len(), 1);
+
+        // writes need write access to the namespace of every graph involved
+        for mutation in [
+            r#"mutation { deleteGraph(path: "team_b/graph") }"#,
+            r#"mutation { newGraph(path: "team_b/new", graphType: EVENT) }"#,
+            r#"mutation { moveGraph(path: "team_b/graph", newPath: "team_a/moved") }"#,
+            r#"mutation { copyGraph(path: "team_c/graph", newPath: "team_a/copied") }"#,
+            r#"mutation { copyGraph(path: "team_a/graph", newPath: "team_b/copied") }"#,
+            r#"{ updateGraph(path: "team_b/graph") { addNode(time: 1, name: "2") { success } } }"#,
+        ] {
+            let res = execute(mutation).await;
+            assert_eq!(res.errors.len(), 1, "expected an error for {mutation}");
+            assert!(res.errors[0].message.starts_with("Permission denied"));
+        }
+        let res =
+            execute(r#"mutation { copyGraph(path: "team_b/graph", newPath: "team_a/copied") }"#)
+                .await;
+        assert_eq!(res.errors, vec![]);
+        let res = execute(r#"mutation { deleteGraph(path: "team_a/copied") }"#).await;
+        assert_eq!(res.errors, vec![]);
+
+        // requests executed without permissions are not restricted
+        let res = schema
+            .execute(Request::new(
+                r#"{ graph(path: "team_c/graph") { countNodes } }"#,
+            ))
+            .await;
+        assert_eq!(res.errors, vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_graph_updates_subscription() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_node(0, "a", NO_PROPS, Some("person")).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let subscription = r#"
+        subscription {
+          graphUpdates(path: "graph", layers: ["follows"], start: 0, end: 10) {
+            kind
+            time
+            node
+            nodeType
+            src
+            dst
+            layer
+            properties { key value }
+          }
+        }
+        "#;
+        let mut updates = schema.execute_stream(subscription);
+        // the subscription is registered when the stream is first polled
+        assert!(futures_util::poll!(updates.next()).is_pending());
+
+        let mutation = r#"
+        {
+          updateGraph(path: "graph") {
+            e1: addEdge(time: 1, src: "a", dst: "b", layer: "likes") { success }
+            e2: addEdge(time: 2, src: "a", dst: "b", layer: "follows", properties: [{key: "weight", value: 0.5}]) { success }
+            e3: addEdge(time: 10, src: "a", dst: "b", layer: "follows") { success }
+            addNode(time: 3, name: "c", nodeType: "person") { success }
+            addConstantProperties(properties: [{key: "name", value: "test"}])
+          }
+        }
+        "#;
+        let res = schema.execute(mutation).await;
+        assert_eq!(res.errors, vec![]);
+
+        let received: Vec<Value> = updates
+            .take(3)
+            .map(|res| res.data.into_json().unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            received[0],
+            json!({"graphUpdates": {
+                "kind": "EDGE_ADDED",
+                "time": 2,
+                "node": null,
+                "nodeType": null,
+                "src": "a",
+                "dst": "b",
+                "layer": "follows",
+                "properties": [{"key": "weight", "value": 0.5}]
+            }})
+        );
+        assert_eq!(
+            received[1],
+            json!({"graphUpdates": {
+                "kind": "NODE_ADDED",
+                "time": 3,
+                "node": "c",
+                "nodeType": "person",
+                "src": null,
+                "dst": null,
+                "layer": null,
+                "properties": []
+            }})
+        );
+        assert_eq!(received[2]["graphUpdates"]["kind"], "GRAPH_UPDATED");
+        assert_eq!(received[2]["graphUpdates"]["time"], Value::Null);
+
+        let res = schema
+            .execute_stream(
+                Request::new(r#"subscription { graphUpdates(path: "graph") { kind } }"#).data(
+                    Permissions::new([("other".to_owned(), Access::Read)].into()),
+                ),
+            )
+            .next()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.errors[0].message,
+            "Permission denied: read access is required for graph 'graph'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber() {
+        let graph: MaterializedGraph = Graph::new().into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let mut updates =
+            schema.execute_stream(r#"subscription { graphUpdates(path: "graph") { time } }"#);
+        assert!(futures_util::poll!(updates.next()).is_pending());
+        for time in 0..EVENT_CHANNEL_CAPACITY as i64 + 2 {
+            data.events
+                .publish("graph", GraphEvent::graph(Some(time), vec![]));
+        }
+
+        // the subscriber is told how many updates it missed and the stream ends
+        let res = updates.next().await.unwrap();
+        assert_eq!(
+            res.errors[0].message,
+            "Missed 2 graph updates, subscribe again to receive new updates"
+        );
+        assert_eq!(
+            res.errors[0].extensions.as_ref().unwrap().get("skipped"),
+            Some(&async_graphql::Value::from(2))
+        );
+        assert!(updates.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshots() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_edge(0, "a", "b", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let execute = |query: &'static str| {
+            let schema = &schema;
+            async move {
+                let res = schema.execute(query).await;
+                assert_eq!(res.errors, vec![], "{query}");
+                res.data.into_json().unwrap()
+            }
+        };
+
+        execute(r#"mutation { createSnapshot(path: "graph", name: "initial") { name } }"#).await;
+        execute(
+            r#"{
+              updateGraph(path: "graph") {
+                addEdge(time: 1, src: "b", dst: "c", properties: [{key: "weight", value: 1}]) { success }
+                addNode(time: 2, name: "a", properties: [{key: "age", value: 3}]) { success }
+              }
+            }"#,
+        )
+        .await;
+        let snapshot = execute(
+            r#"mutation { createSnapshot(path: "graph", name: "ingested") { name countNodes countEdges } }"#,
+        )
+        .await;
+        assert_eq!(
+            snapshot["createSnapshot"],
+            json!({"name": "ingested", "countNodes": 3, "countEdges": 2})
+        );
+        let res = schema
+            .execute(r#"mutation { createSnapshot(path: "graph", name: "initial") { name } }"#)
+            .await;
+        assert_eq!(res.errors[0].message, "Snapshot initial already exists");
+
+        // a bad ingestion after the last snapshot
+        execute(
+            r#"{
+              updateGraph(path: "graph") {
+                first: addEdge(time: 3, src: "x", dst: "y") { success }
+                second: addEdge(time: 4, src: "b", dst: "c", properties: [{key: "weight", value: 2}]) { success }
+              }
+            }"#,
+        )
+        .await;
+
+        let diff = execute(
+            r#"{
+              diffSnapshots(path: "graph", from: "initial", to: "ingested") {
+                addedNodes removedNodes updatedNodes
+                addedEdges { src dst } removedEdges { src dst } updatedEdges { src dst }
+              }
+            }"#,
+        )
+        .await;
+        assert_eq!(
+            diff["diffSnapshots"],
+            json!({
+                "addedNodes": ["c"],
+                "removedNodes": [],
+                "updatedNodes": ["a", "b"],
+                "addedEdges": [{"src": "b", "dst": "c"}],
+                "removedEdges": [],
+                "updatedEdges": []
+            })
+        );
+        let diff = execute(
+            r#"{
+              diffSnapshots(path: "graph", from: "ingested") {
+                addedNodes updatedNodes updatedEdges { src dst }
+              }
+            }"#,
+        )
+        .await;
+        assert_eq!(
+            diff["diffSnapshots"],
+            json!({
+                "addedNodes": ["x", "y"],
+                "updatedNodes": ["b", "c"],
+                "updatedEdges": [{"src": "b", "dst": "c"}]
+            })
+        );
+        let snapshot = execute(
+            r#"{ snapshot(path: "graph", name: "initial") { graph { countNodes countEdges } } }"#,
+        )
+        .await;
+        assert_eq!(
+            snapshot["snapshot"]["graph"],
+            json!({"countNodes": 2, "countEdges": 1})
+        );
+
+        execute(r#"mutation { restoreSnapshot(path: "graph", name: "ingested") }"#).await;
+        let graph = execute(
+            r#"{
+              graph(path: "graph") {
+                countNodes
+                edge(src: "b", dst: "c") { properties { temporal { get(key: "weight") { values } } } }
+              }
+            }"#,
+        )
+        .await;
+        assert_eq!(
+            graph["graph"],
+            json!({
+                "countNodes": 3,
+                "edge": {"properties": {"temporal": {"get": {"values": ["1"]}}}}
+            })
+        );
+
+        // the graph can still be updated after it was restored
+        execute(r#"{ updateGraph(path: "graph") { addNode(time: 5, name: "d") { success } } }"#)
+            .await;
+        execute(r#"mutation { restoreSnapshot(path: "graph", name: "initial") }"#).await;
+        let snapshots = execute(r#"{ listSnapshots(path: "graph") { name } }"#).await;
+        assert_eq!(snapshots["listSnapshots"], json!([{"name": "initial"}]));
+        let graph = execute(r#"{ graph(path: "graph") { countNodes } }"#).await;
+        assert_eq!(graph["graph"]["countNodes"], 2);
+
+        execute(r#"mutation { deleteSnapshot(path: "graph", name: "initial") }"#).await;
+        let res = schema
+            .execute(r#"mutation { restoreSnapshot(path: "graph", name: "initial") }"#)
+            .await;
+        assert_eq!(res.errors[0].message, "Snapshot initial does not exist");
+
+        // the restored graph is persisted
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let (graph, _) = data.get_graph("graph").unwrap();
+        assert_eq!(graph.graph.count_nodes(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_diff_node_deletions() {
+        let graph: MaterializedGraph = PersistentGraph::new().into();
+        graph.add_node(0, "a", NO_PROPS, None).unwrap();
+        graph.add_node(0, "b", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let execute = |query: &'static str| {
+            let schema = &schema;
+            async move {
+                let res = schema.execute(query).await;
+                assert_eq!(res.errors, vec![], "{query}");
+                res.data.into_json().unwrap()
+            }
+        };
+
+        execute(r#"mutation { createSnapshot(path: "graph", name: "before") { name } }"#).await;
+        execute(r#"{ updateGraph(path: "graph") { deleteNode(time: 1, name: "a") { success } } }"#)
+            .await;
+        execute(r#"mutation { createSnapshot(path: "graph", name: "after") { name } }"#).await;
+
+        let diff = execute(
+            r#"{
+              diffSnapshots(path: "graph", from: "before", to: "after") {
+                addedNodes removedNodes updatedNodes
+              }
+            }"#,
+        )
+        .await;
+        assert_eq!(
+            diff["diffSnapshots"],
+            json!({"addedNodes": [], "removedNodes": [], "updatedNodes": ["a"]})
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_snapshot_waits_for_writes() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_edge(0, "a", "b", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        // the snapshot waits for the mutations in flight
+        let (graph, _) = data.get_graph("graph").unwrap();
+        let writes = graph.lock_writes().await.unwrap();
+        let creating = tokio::spawn({
+            let schema = schema.clone();
+            async move {
+                schema
+                    .execute(r#"mutation { createSnapshot(path: "graph", name: "initial") { countEdges } }"#)
+                    .await
+            }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!creating.is_finished());
+        graph.graph.add_edge(1, "b", "c", NO_PROPS, None).unwrap();
+        graph.write_updates().unwrap();
+        drop(writes);
+        let res = creating.await.unwrap();
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap()["createSnapshot"]["countEdges"],
+            2
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mutations_during_restore() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_edge(0, "a", "b", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+        let restore = r#"mutation { restoreSnapshot(path: "graph", name: "initial") }"#;
+        let res = schema
+            .execute(r#"mutation { createSnapshot(path: "graph", name: "initial") { name } }"#)
+            .await;
+        assert_eq!(res.errors, vec![]);
+
+        // the restore waits for the mutations in flight
+        let (stale, _) = data.get_graph("graph").unwrap();
+        let writes = stale.lock_writes().await.unwrap();
+        let restoring = tokio::spawn({
+            let schema = schema.clone();
+            async move { schema.execute(restore).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!restoring.is_finished());
+        drop(writes);
+        assert_eq!(restoring.await.unwrap().errors, vec![]);
+        // the copy loaded before the restore can't append to the truncated file anymore
+        assert!(stale.lock_writes().await.is_err());
+
+        let mutations: Vec<_> = (0..20)
+            .map(|i| {
+                let schema = schema.clone();
+                tokio::spawn(async move {
+                    let query = format!(
+                        r#"{{ updateGraph(path: "graph") {{ addNode(time: 1, name: "n{i}") {{ success }} }} }}"#
+                    );
+                    schema.execute(query).await
+                })
+            })
+            .collect();
+        let restoring = tokio::spawn({
+            let schema = schema.clone();
+            async move { schema.execute(restore).await }
+        });
+        for mutation in mutations {
+            let res = mutation.await.unwrap();
+            for error in res.errors {
+                assert_eq!(error.message, GraphError::GraphRestored.to_string());
+            }
+        }
+        assert_eq!(restoring.await.unwrap().errors, vec![]);
+
+        // the graph file is still readable and holds the updates of the graph being served
+        let (served, _) = data.get_graph("graph").unwrap();
+        let persisted = Graph::decode(tmp_dir.path().join("graph")).unwrap();
+        let names = |graph: &MaterializedGraph| {
+            let mut names: Vec<_> = graph.nodes().name().collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(&served.graph), names(&persisted.into()));
+    }
+
+    #[tokio::test]
+    async fn test_query_limits() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph.add_edge(0, "a", "b", NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let query_config = QueryConfig {
+            max_depth: Some(4),
+            max_complexity: Some(7),
+            max_result_size: Some(40),
+            ..Default::default()
+        };
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = limit_schema(App::create_schema().data(data), &query_config)
+            .finish()
+            .unwrap();
+        let execute = |query: &'static str| {
+            let schema = &schema;
+            let query_config = &query_config;
+            async move {
+                match execute_with_limits(schema, Request::new(query).into(), query_config).await {
+                    BatchResponse::Single(res) => res,
+                    BatchResponse::Batch(_) => unreachable!(),
+                }
+            }
+        };
+
+        let res = execute(r#"{ graph(path: "graph") { countNodes } }"#).await;
+        assert_eq!(res.errors, vec![]);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countNodes": 2}})
+        );
+
+        let res = execute(
+            r#"{ graph(path: "graph") { node(name: "a") { edges { list { src { name } } } } } }"#,
+        )
+        .await;
+        assert_eq!(res.errors[0].message, "Query is nested too deep.");
+
+        let res = execute(
+            r#"{ graph(path: "graph") { name path countNodes countEdges countTemporalEdges earliestTime latestTime } }"#,
+        )
+        .await;
+        assert_eq!(res.errors[0].message, "Query is too complex.");
+
+        let res = execute(r#"{ graph(path: "graph") { nodes { list { name } } } }"#).await;
+        assert_eq!(
+            res.errors[0].message,
+            "The result of the query exceeds the maximum size of 40 bytes"
+        );
+    }
+
+    // the timer needs a worker that is not busy running the query
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_query_timeout() {
+        let graph: MaterializedGraph = Graph::new().into();
+        for src in 0..150 {
+            for dst in 0..150 {
+                graph.add_edge(0, src, dst, NO_PROPS, None).unwrap();
+            }
+        }
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let query_config = QueryConfig {
+            timeout_seconds: Some(1),
+            ..Default::default()
+        };
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = limit_schema(App::create_schema().data(data), &query_config)
+            .finish()
+            .unwrap();
+        let slow = r#"{ graph(path: "graph") { nodes { list { neighbours { list { neighbours { list { name } } } } } } } }"#;
+        let request = BatchRequest::Batch(vec![
+            Request::new(r#"{ graph(path: "graph") { countNodes } }"#),
+            Request::new(slow),
+        ]);
+        let start = std::time::Instant::now();
+        let response = execute_with_limits(&schema, request, &query_config).await;
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        match response {
+            BatchResponse::Batch(responses) => {
+                assert_eq!(responses.len(), 2);
+                for response in responses {
+                    assert_eq!(
+                        response.errors[0].message,
+                        "The query exceeded the timeout of 1 seconds"
+                    );
+                }
+            }
+            BatchResponse::Single(_) => panic!("a batch request gets a batch response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weighted_pagerank() {
+        let graph: MaterializedGraph = Graph::new().into();
+        graph
+            .add_edge(0, "a", "b", [("amount", 1.0)], None)
+            .unwrap();
+        graph
+            .add_edge(1, "a", "c", [("amount", 3.0)], None)
+            .unwrap();
+        graph
+            .add_edge(2, "b", "a", [("amount", 1.0)], None)
+            .unwrap();
+        graph
+            .add_edge(3, "c", "a", [("amount", 1.0)], None)
+            .unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            algorithms {
+              pagerank(iterCount: 1000, weight: "amount", seeds: ["a"]) {
+                name
+                rank
+              }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(Request::new(query)).await;
+        assert_eq!(res.errors, vec![]);
+        let data = res.data.into_json().unwrap();
+        let ranks: HashMap<String, f64> = data["graph"]["algorithms"]["pagerank"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rank| {
+                (
+                    rank["name"].as_str().unwrap().to_string(),
+                    rank["rank"].as_f64().unwrap(),
+                )
+            })
+            .collect();
+        // all random jumps land on a, which passes 3/4 of its score to c
+        assert!((ranks["a"] - 1.0 / 1.85).abs() < 1e-5);
+        assert!((ranks["c"] - 3.0 * ranks["b"]).abs() < 1e-5);
+
+        let query = r#"
+        {
+          graph(path: "graph") {
+            algorithms {
+              pagerank(iterCount: 10, weight: "amount", aggregation: "mean") {
+                name
+              }
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(Request::new(query)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Invalid weight aggregation mean, expected one of sum, latest or count"
+        );
+    }
 }
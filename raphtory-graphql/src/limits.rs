@@ -0,0 +1,108 @@
+use crate::{
+    config::query_config::QueryConfig,
+    model::plugins::cancellation::{CancellationExtension, QueryCancellation},
+};
+use async_graphql::{
+    dynamic::{Schema, SchemaBuilder},
+    BatchRequest, BatchResponse, Executor, Response, ServerError,
+};
+use std::io;
+
+#[derive(thiserror::Error, Debug)]
+pub enum QueryLimitError {
+    #[error("The query exceeded the timeout of {0} seconds")]
+    Timeout(u64),
+    #[error("The result of the query exceeds the maximum size of {0} bytes")]
+    ResultTooLarge(usize),
+}
+
+/// Depth and complexity are checked by the schema before a query is run, the queries that are
+/// cancelled stop before the next field they resolve
+pub(crate) fn limit_schema(schema: SchemaBuilder, config: &QueryConfig) -> SchemaBuilder {
+    let mut schema = schema.extension(CancellationExtension);
+    if let Some(max_depth) = config.max_depth {
+        schema = schema.limit_depth(max_depth);
+    }
+    if let Some(max_complexity) = config.max_complexity {
+        schema = schema.limit_complexity(max_complexity);
+    }
+    schema
+}
+
+/// Run a request, cancelling it if it times out or if the returned future is dropped
+///
+/// A request that fails a limit gets the error for each of its queries.
+pub(crate) async fn execute_with_limits(
+    schema: &Schema,
+    request: BatchRequest,
+    config: &QueryConfig,
+) -> BatchResponse {
+    let batch_size = match &request {
+        BatchRequest::Single(_) => None,
+        BatchRequest::Batch(requests) => Some(requests.len()),
+    };
+    let cancellation = QueryCancellation::default();
+    let _cancel_on_drop = cancellation.cancel_on_drop();
+    // the request runs in its own task so that the timeout fires while its resolvers are busy,
+    // the task stops once the request is cancelled
+    let execution = tokio::spawn({
+        let schema = schema.clone();
+        let request = request.data(cancellation);
+        async move { schema.execute_batch(request).await }
+    });
+    let response = match config.timeout_seconds {
+        Some(seconds) => match tokio::time::timeout(config.timeout().unwrap(), execution).await {
+            Ok(response) => response,
+            Err(_) => return limit_error(QueryLimitError::Timeout(seconds), batch_size),
+        },
+        None => execution.await,
+    };
+    let response = response.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()));
+    match config.max_result_size {
+        Some(max_size) if exceeds_size(&response, max_size) => {
+            limit_error(QueryLimitError::ResultTooLarge(max_size), batch_size)
+        }
+        _ => response,
+    }
+}
+
+fn limit_error(error: QueryLimitError, batch_size: Option<usize>) -> BatchResponse {
+    let response = || Response::from_errors(vec![ServerError::new(error.to_string(), None)]);
+    match batch_size {
+        None => BatchResponse::Single(response()),
+        Some(size) => BatchResponse::Batch((0..size).map(|_| response()).collect()),
+    }
+}
+
+/// Counts the bytes written, failing as soon as there are more than `max_size`
+struct SizeLimit {
+    size: usize,
+    max_size: usize,
+}
+
+impl io::Write for SizeLimit {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.size += buf.len();
+        if self.size > self.max_size {
+            Err(io::Error::other("size limit exceeded"))
+        } else {
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Only the data is counted so that errors are returned whatever their size
+fn exceeds_size(response: &BatchResponse, max_size: usize) -> bool {
+    let mut writer = SizeLimit { size: 0, max_size };
+    let responses = match response {
+        BatchResponse::Single(response) => std::slice::from_ref(response),
+        BatchResponse::Batch(responses) => responses.as_slice(),
+    };
+    responses
+        .iter()
+        .any(|response| serde_json::to_writer(&mut writer, &response.data).is_err())
+}
@@ -1,15 +1,21 @@
-use crate::model::plugins::{graph_algorithm_plugin::GraphAlgorithmPlugin, operation::Operation};
+use crate::model::plugins::{
+    cancellation::QueryCancellation,
+    graph_algorithm_plugin::GraphAlgorithmPlugin,
+    operation::{run_blocking, Operation},
+};
 use async_graphql::{
     dynamic::{FieldValue, ResolverContext, TypeRef},
     FieldResult,
 };
 use dynamic_graphql::{internal::TypeName, SimpleObject};
 use futures_util::future::BoxFuture;
-use itertools::Itertools;
 use ordered_float::OrderedFloat;
-use raphtory::algorithms::{
-    centrality::pagerank::unweighted_page_rank,
-    pathing::dijkstra::dijkstra_single_source_shortest_paths,
+use raphtory::{
+    algorithms::{
+        centrality::pagerank::{unweighted_page_rank, weighted_page_rank, WeightAggregation},
+        pathing::dijkstra::dijkstra_single_source_shortest_paths,
+    },
+    db::task::interrupt::with_interrupt,
 };
 use raphtory_api::core::Direction;
 
@@ -65,6 +71,10 @@ impl<'a> Operation<'a, GraphAlgorithmPlugin> for Pagerank {
             ("iterCount", TypeRef::named_nn(TypeRef::INT)), // _nn stands for not null
             ("threads", TypeRef::named(TypeRef::INT)),      // this one though might be null
             ("tol", TypeRef::named(TypeRef::FLOAT)),
+            ("weight", TypeRef::named(TypeRef::STRING)),
+            ("aggregation", TypeRef::named(TypeRef::STRING)),
+            ("seeds", TypeRef::named_nn_list(TypeRef::STRING)),
+            ("seedWeights", TypeRef::named_nn_list(TypeRef::FLOAT)),
         ]
     }
 
@@ -72,15 +82,16 @@ impl<'a> Operation<'a, GraphAlgorithmPlugin> for Pagerank {
         entry_point: &GraphAlgorithmPlugin,
         ctx: ResolverContext,
     ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
-        let result = apply_pagerank(entry_point, ctx);
-        Box::pin(async move { result })
+        let run = apply_pagerank(entry_point, ctx);
+        Box::pin(async move { run_blocking(run?).await })
     }
 }
 
-fn apply_pagerank<'b>(
+/// Parse the arguments and return the computation to run on the blocking thread pool
+fn apply_pagerank(
     entry_point: &GraphAlgorithmPlugin,
     ctx: ResolverContext,
-) -> FieldResult<Option<FieldValue<'b>>> {
+) -> FieldResult<impl FnOnce() -> FieldResult<Option<FieldValue<'static>>> + Send + 'static> {
     let iter_count = ctx.args.try_get("iterCount")?.u64()? as usize;
     let threads = ctx.args.get("threads").map(|v| v.u64()).transpose()?;
     let threads = threads.map(|v| v as usize);
@@ -90,20 +101,115 @@ fn apply_pagerank<'b>(
         .get("damping_factor")
         .map(|v| v.f64())
         .transpose()?;
-    let binding = unweighted_page_rank(
-        &entry_point.graph,
-        Some(iter_count),
-        threads,
-        tol,
-        true,
-        damping_factor,
+    let weight = ctx
+        .args
+        .get("weight")
+        .map(|v| v.string().map(str::to_owned))
+        .transpose()?;
+    let aggregation = ctx
+        .args
+        .get("aggregation")
+        .map(|v| v.string())
+        .transpose()?;
+    let aggregation = match aggregation {
+        Some(aggregation) => Some(aggregation.parse::<WeightAggregation>()?),
+        None => None,
+    };
+    let seeds = ctx
+        .args
+        .get("seeds")
+        .map(|v| {
+            v.list()?
+                .iter()
+                .map(|seed| seed.string().map(str::to_owned))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    let seed_weights = ctx
+        .args
+        .get("seedWeights")
+        .map(|v| {
+            v.list()?
+                .iter()
+                .map(|weight| weight.f64())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    let cancellation = QueryCancellation::from_context(ctx.ctx).cloned();
+    let graph = entry_point.graph.clone();
+    let weighted = weight.is_some() || aggregation.is_some() || seeds.is_some();
+    // seeds without weights all have the same weight
+    let personalisation: Option<Vec<(String, f64)>> = match (seeds, seed_weights) {
+        (Some(seeds), None) => Some(seeds.into_iter().map(|seed| (seed, 1.0)).collect()),
+        (Some(seeds), Some(weights)) if seeds.len() == weights.len() => {
+            Some(seeds.into_iter().zip(weights).collect())
+        }
+        (Some(_), Some(_)) => return Err("seeds and seedWeights must have the same length".into()),
+        (None, Some(_)) => return Err("seedWeights requires seeds".into()),
+        (None, None) => None,
+    };
+    Ok(move || {
+        check_cancellation(&cancellation)?;
+        let binding = run_interruptible(&cancellation, || {
+            if weighted {
+                weighted_page_rank(
+                    &graph,
+                    weight.as_deref(),
+                    aggregation.unwrap_or_default(),
+                    personalisation,
+                    Some(iter_count),
+                    threads,
+                    tol,
+                    true,
+                    damping_factor,
+                )
+            } else {
+                Ok(unweighted_page_rank(
+                    &graph,
+                    Some(iter_count),
+                    threads,
+                    tol,
+                    true,
+                    damping_factor,
+                ))
+            }
+        })??;
+        let result = binding
+            .get_all_with_names()
+            .into_iter()
+            .map(|pair| {
+                check_cancellation(&cancellation)?;
+                Ok(FieldValue::owned_any(PagerankOutput::from(pair)))
+            })
+            .collect::<FieldResult<Vec<_>>>()?;
+        Ok(Some(FieldValue::list(result)))
+    })
+}
+
+/// Run an algorithm that stops between its steps once the query is cancelled, the incomplete
+/// results of a cancelled query are dropped
+fn run_interruptible<R>(
+    cancellation: &Option<QueryCancellation>,
+    run: impl FnOnce() -> R,
+) -> FieldResult<R> {
+    let interrupt = cancellation.clone();
+    let result = with_interrupt(
+        move || {
+            interrupt
+                .as_ref()
+                .is_some_and(QueryCancellation::is_cancelled)
+        },
+        run,
     );
-    let result = binding
-        .get_all_with_names()
-        .into_iter()
-        .map(|pair| FieldValue::owned_any(PagerankOutput::from(pair)))
-        .collect_vec();
-    Ok(Some(FieldValue::list(result)))
+    check_cancellation(cancellation)?;
+    Ok(result)
+}
+
+fn check_cancellation(cancellation: &Option<QueryCancellation>) -> FieldResult<()> {
+    match cancellation {
+        Some(cancellation) => Ok(cancellation.check()?),
+        None => Ok(()),
+    }
 }
 
 pub(crate) struct ShortestPath;
@@ -139,15 +245,15 @@ impl<'a> Operation<'a, GraphAlgorithmPlugin> for ShortestPath {
         entry_point: &GraphAlgorithmPlugin,
         ctx: ResolverContext,
     ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
-        let result = apply_shortest_path(entry_point, ctx);
-        Box::pin(async move { result })
+        let run = apply_shortest_path(entry_point, ctx);
+        Box::pin(async move { run_blocking(run?).await })
     }
 }
 
-fn apply_shortest_path<'b>(
+fn apply_shortest_path(
     entry_point: &GraphAlgorithmPlugin,
     ctx: ResolverContext,
-) -> FieldResult<Option<FieldValue<'b>>> {
+) -> FieldResult<impl FnOnce() -> FieldResult<Option<FieldValue<'static>>> + Send + 'static> {
     let source = ctx.args.try_get("source")?.string()?;
     let targets = ctx.args.try_get("targets")?.list()?;
     let direction = match ctx.args.try_get("direction")?.string()? {
@@ -156,20 +262,29 @@ fn apply_shortest_path<'b>(
         "both" => Direction::BOTH,
         _ => return Err("Invalid direction".into()),
     };
+    let source = source.to_owned();
     let targets = targets
         .iter()
-        .map(|v| v.string())
-        .collect::<Result<Vec<&str>, _>>()?;
-    let binding =
-        dijkstra_single_source_shortest_paths(&entry_point.graph, source, targets, None, direction);
-    let result: Vec<FieldValue> = binding
-        .into_iter()
-        .flat_map(|pair| {
-            pair.into_iter()
-                .map(|(key, value)| ShortestPathOutput::from((key.to_string(), value.1)))
-        })
-        .map(FieldValue::owned_any)
-        .collect();
-
-    Ok(Some(FieldValue::list(result)))
+        .map(|v| v.string().map(str::to_owned))
+        .collect::<Result<Vec<_>, _>>()?;
+    let cancellation = QueryCancellation::from_context(ctx.ctx).cloned();
+    let graph = entry_point.graph.clone();
+    Ok(move || {
+        check_cancellation(&cancellation)?;
+        let binding = run_interruptible(&cancellation, || {
+            dijkstra_single_source_shortest_paths(&graph, source, targets, None, direction)
+        })?;
+        let result = binding
+            .into_iter()
+            .flat_map(|pair| {
+                pair.into_iter()
+                    .map(|(key, value)| ShortestPathOutput::from((key.to_string(), value.1)))
+            })
+            .map(|output| {
+                check_cancellation(&cancellation)?;
+                Ok(FieldValue::owned_any(output))
+            })
+            .collect::<FieldResult<Vec<_>>>()?;
+        Ok(Some(FieldValue::list(result)))
+    })
 }
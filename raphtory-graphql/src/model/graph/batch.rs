@@ -0,0 +1,278 @@
+use crate::model::graph::mutable_graph::{EdgeAddition, GqlPropInput, NodeAddition};
+use dynamic_graphql::{Enum, SimpleObject};
+use raphtory::{
+    core::{utils::errors::GraphError, PropType},
+    db::api::view::{internal::CoreGraphOps, MaterializedGraph},
+    prelude::*,
+};
+use raphtory_api::core::entities::{
+    properties::{props::Meta, PropError},
+    GID,
+};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchItemKind {
+    Node,
+    Edge,
+}
+
+/// Why an item of a batch was rejected, `index` is the position of the item in the `nodes` or
+/// `edges` of the batch
+#[derive(SimpleObject, Debug)]
+pub struct BatchItemError {
+    kind: BatchItemKind,
+    index: usize,
+    message: String,
+}
+
+/// The outcome of an atomic batch, nothing was applied if `success` is false
+#[derive(SimpleObject, Debug)]
+pub struct BatchResult {
+    pub(crate) success: bool,
+    pub(crate) errors: Vec<BatchItemError>,
+}
+
+/// Types of the properties that are new to the graph, fixed by the first item of the batch
+/// that sets them
+#[derive(Default)]
+struct BatchPropTypes {
+    temporal: HashMap<String, PropType>,
+    constant: HashMap<String, PropType>,
+}
+
+impl BatchPropTypes {
+    fn check(
+        &mut self,
+        meta: &Meta,
+        name: &str,
+        prop: &Prop,
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        let (mapper, batch_types) = if is_static {
+            (meta.const_prop_meta(), &mut self.constant)
+        } else {
+            (meta.temporal_prop_meta(), &mut self.temporal)
+        };
+        let actual = prop.dtype();
+        let expected = meta
+            .get_prop_id(name, is_static)
+            .and_then(|id| mapper.get_dtype(id))
+            .filter(|dtype| *dtype != PropType::Empty)
+            .or_else(|| batch_types.get(name).copied());
+        match expected {
+            Some(expected) if expected != actual => Err(PropError::PropertyTypeError {
+                name: name.to_owned(),
+                expected,
+                actual,
+            }
+            .into()),
+            Some(_) => Ok(()),
+            None => {
+                batch_types.insert(name.to_owned(), actual);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Checks a batch of additions against the graph and the earlier items of the batch without
+/// modifying the graph
+pub(crate) struct BatchValidator<'a> {
+    graph: &'a MaterializedGraph,
+    node_props: BatchPropTypes,
+    edge_props: BatchPropTypes,
+    /// Nodes and `(src, dst, layer)` edges created by earlier items
+    nodes: HashSet<String>,
+    edges: HashSet<(String, String, String)>,
+    /// Node types and constant values set by earlier items
+    node_types: HashMap<String, String>,
+    node_constants: HashMap<(String, String), Prop>,
+    edge_constants: HashMap<(String, String, String, String), Prop>,
+}
+
+impl<'a> BatchValidator<'a> {
+    pub(crate) fn new(graph: &'a MaterializedGraph) -> Self {
+        Self {
+            graph,
+            node_props: Default::default(),
+            edge_props: Default::default(),
+            nodes: Default::default(),
+            edges: Default::default(),
+            node_types: Default::default(),
+            node_constants: Default::default(),
+            edge_constants: Default::default(),
+        }
+    }
+
+    /// The errors of all the invalid items, the batch can be applied if there are none
+    pub(crate) fn validate(
+        mut self,
+        nodes: &[NodeAddition],
+        edges: &[EdgeAddition],
+    ) -> Vec<BatchItemError> {
+        let node_errors = nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| {
+                self.validate_node(node)
+                    .err()
+                    .map(|error| (BatchItemKind::Node, index, error))
+            })
+            .collect::<Vec<_>>();
+        let edge_errors = edges
+            .iter()
+            .enumerate()
+            .filter_map(|(index, edge)| {
+                self.validate_edge(edge)
+                    .err()
+                    .map(|error| (BatchItemKind::Edge, index, error))
+            })
+            .collect::<Vec<_>>();
+        node_errors
+            .into_iter()
+            .chain(edge_errors)
+            .map(|(kind, index, error)| BatchItemError {
+                kind,
+                index,
+                message: error.to_string(),
+            })
+            .collect()
+    }
+
+    fn validate_node(&mut self, node: &NodeAddition) -> Result<(), GraphError> {
+        let name = node.name.as_str();
+        let meta = self.graph.node_meta();
+        let updates = node.updates.as_deref().unwrap_or_default();
+        for prop in updates
+            .iter()
+            .flat_map(|update| properties(&update.properties))
+        {
+            self.node_props
+                .check(meta, &prop.key, &prop.value.0, false)?;
+        }
+        if !updates.is_empty() {
+            self.nodes.insert(name.to_owned());
+        }
+        let existing = self.graph.node(name);
+        let exists = existing.is_some() || self.nodes.contains(name);
+        let missing = || GraphError::NodeMissingError(GID::Str(name.to_owned()));
+
+        if let Some(node_type) = &node.node_type {
+            if !exists {
+                return Err(missing());
+            }
+            if node_type == "_default" {
+                return Err(GraphError::NodeTypeError(
+                    "_default type is not allowed to be used on nodes".to_owned(),
+                ));
+            }
+            let current = self.node_types.get(name).cloned().or_else(|| {
+                existing
+                    .as_ref()
+                    .and_then(|node| node.node_type())
+                    .map(|node_type| node_type.to_string())
+            });
+            match current {
+                Some(current) if current != *node_type => {
+                    return Err(GraphError::NodeTypeError(
+                        "Cannot change node type".to_owned(),
+                    ))
+                }
+                Some(_) => {}
+                None => {
+                    self.node_types.insert(name.to_owned(), node_type.clone());
+                }
+            }
+        }
+
+        let constants = properties(&node.constant_properties);
+        if !constants.is_empty() && !exists {
+            return Err(missing());
+        }
+        for prop in constants {
+            self.node_props
+                .check(meta, &prop.key, &prop.value.0, true)?;
+            let key = (name.to_owned(), prop.key.clone());
+            let old = self.node_constants.get(&key).cloned().or_else(|| {
+                existing
+                    .as_ref()
+                    .and_then(|node| node.properties().constant().get(&prop.key))
+            });
+            check_constant(&prop.key, old, &prop.value.0)?;
+            self.node_constants.insert(key, prop.value.0.clone());
+        }
+        Ok(())
+    }
+
+    fn validate_edge(&mut self, edge: &EdgeAddition) -> Result<(), GraphError> {
+        let src = edge.src.as_str();
+        let dst = edge.dst.as_str();
+        let layer = edge.layer.as_deref().unwrap_or("_default");
+        let meta = self.graph.edge_meta();
+        let updates = edge.updates.as_deref().unwrap_or_default();
+        for prop in updates
+            .iter()
+            .flat_map(|update| properties(&update.properties))
+        {
+            self.edge_props
+                .check(meta, &prop.key, &prop.value.0, false)?;
+        }
+        let key = (src.to_owned(), dst.to_owned(), layer.to_owned());
+        if !updates.is_empty() {
+            self.nodes.insert(src.to_owned());
+            self.nodes.insert(dst.to_owned());
+            self.edges.insert(key.clone());
+        }
+
+        let constants = properties(&edge.constant_properties);
+        if constants.is_empty() {
+            return Ok(());
+        }
+        let existing = self
+            .graph
+            .layers(layer)
+            .ok()
+            .and_then(|graph| graph.edge(src, dst));
+        if existing.is_none() && !self.edges.contains(&key) {
+            return Err(GraphError::EdgeMissingError {
+                src: GID::Str(src.to_owned()),
+                dst: GID::Str(dst.to_owned()),
+            });
+        }
+        for prop in constants {
+            self.edge_props
+                .check(meta, &prop.key, &prop.value.0, true)?;
+            let key = (
+                key.0.clone(),
+                key.1.clone(),
+                key.2.clone(),
+                prop.key.clone(),
+            );
+            let old = self.edge_constants.get(&key).cloned().or_else(|| {
+                existing
+                    .as_ref()
+                    .and_then(|edge| edge.properties().constant().get(&prop.key))
+            });
+            check_constant(&prop.key, old, &prop.value.0)?;
+            self.edge_constants.insert(key, prop.value.0.clone());
+        }
+        Ok(())
+    }
+}
+
+fn properties(properties: &Option<Vec<GqlPropInput>>) -> &[GqlPropInput] {
+    properties.as_deref().unwrap_or_default()
+}
+
+/// Constant properties can be set again but not changed
+fn check_constant(name: &str, old: Option<Prop>, new: &Prop) -> Result<(), GraphError> {
+    match old {
+        Some(old) if old != *new => Err(GraphError::ConstantPropertyMutationError {
+            name: name.into(),
+            old,
+            new: new.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::model::graph::{node::Node, property::GqlProperties};
+use crate::model::graph::{edges::GqlEdges, node::Node, property::GqlProperties};
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
@@ -170,12 +170,30 @@ impl Edge {
             .collect_vec()
     }
 
-    async fn history(&self) -> Vec<i64> {
-        self.ee.history()
+    /// One edge per update, with its own `time`, `layerName` and `properties`, as a paginated
+    /// collection of edges
+    async fn exploded(&self) -> GqlEdges {
+        GqlEdges::new(self.ee.explode())
     }
 
-    async fn deletions(&self) -> Vec<i64> {
-        self.ee.deletions()
+    /// One edge per layer of the edge, as a paginated collection of edges
+    async fn exploded_layers(&self) -> GqlEdges {
+        GqlEdges::new(self.ee.explode_layers())
+    }
+
+    /// The times of the updates of the edge, `limit` and `offset` select a page of them
+    async fn history(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<i64> {
+        paginate(self.ee.history(), limit, offset)
+    }
+
+    /// The number of updates of the edge
+    async fn history_counts(&self) -> usize {
+        self.ee.history_counts()
+    }
+
+    /// The times the edge was deleted, `limit` and `offset` select a page of them
+    async fn deletions(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<i64> {
+        paginate(self.ee.deletions(), limit, offset)
     }
 
     async fn is_valid(&self) -> bool {
@@ -198,3 +216,14 @@ impl Edge {
         self.ee.nbr().into()
     }
 }
+
+/// Page `offset` of size `limit` of the values, all the values if `limit` is not set
+fn paginate<T>(values: Vec<T>, limit: Option<usize>, offset: Option<usize>) -> Vec<T> {
+    match limit {
+        Some(limit) => {
+            let start = offset.unwrap_or(0) * limit;
+            values.into_iter().skip(start).take(limit).collect()
+        }
+        None => values,
+    }
+}
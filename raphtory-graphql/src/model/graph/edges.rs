@@ -1,29 +1,107 @@
-use crate::model::graph::edge::Edge;
+use crate::model::graph::{
+    edge::Edge,
+    filtering::{
+        compare_sort_values, EdgeFilterInput, EdgeSortBy, EdgeSortKey, ResolvedSortBy, SortKey,
+    },
+    interval::{collect_windows, GqlInterval},
+};
+use async_graphql::Error;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::{
-    db::{api::view::DynamicGraph, graph::edges::Edges},
-    prelude::{LayerOps, TimeOps},
+    core::entities::edges::edge_ref::EdgeRef,
+    db::{
+        api::view::{internal::OneHopFilter, DynamicGraph},
+        graph::{edge::EdgeView, edges::Edges},
+    },
+    prelude::{EdgeViewOps, GraphViewOps, LayerOps, NodeViewOps, Prop, TimeOps},
 };
+use std::sync::Arc;
 
 #[derive(ResolvedObject)]
 pub(crate) struct GqlEdges {
     pub(crate) ee: Edges<'static, DynamicGraph>,
+    /// The edges selected by `sortBy` or `filter` in order, later windows and layers change the
+    /// views of the selected edges and drop the ones that are not part of them but do not change
+    /// the order
+    selection: Option<Arc<[EdgeRef]>>,
 }
 
 impl GqlEdges {
     fn update<E: Into<Edges<'static, DynamicGraph>>>(&self, edges: E) -> Self {
-        Self::new(edges)
+        Self {
+            ee: edges.into(),
+            selection: self.selection.clone(),
+        }
+    }
+
+    fn select(&self, selection: Vec<EdgeRef>) -> Self {
+        Self {
+            ee: self.ee.clone(),
+            selection: Some(selection.into()),
+        }
     }
 }
 
 impl GqlEdges {
     pub(crate) fn new<E: Into<Edges<'static, DynamicGraph>>>(edges: E) -> Self {
-        Self { ee: edges.into() }
+        Self {
+            ee: edges.into(),
+            selection: None,
+        }
+    }
+
+    fn iter_views(&self) -> Box<dyn Iterator<Item = EdgeView<DynamicGraph>> + '_> {
+        match &self.selection {
+            Some(selection) => {
+                let base_graph = self.ee.base_graph();
+                let graph = self.ee.current_filter();
+                let iter = selection
+                    .iter()
+                    .filter(|&&edge| contains_edge(graph, edge))
+                    .map(|&edge| EdgeView {
+                        base_graph: base_graph.clone(),
+                        graph: graph.clone(),
+                        edge,
+                    });
+                Box::new(iter)
+            }
+            None => Box::new(self.ee.iter().map(|edge| edge.cloned())),
+        }
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let iter = self.ee.iter().map(Edge::from_ref);
-        Box::new(iter)
+        Box::new(self.iter_views().map(Edge::from))
+    }
+}
+
+/// Whether `edge` is part of `graph`, exploded edges also need their layer and update to be part
+/// of it
+fn contains_edge<'graph, G: GraphViewOps<'graph>>(graph: &G, edge: EdgeRef) -> bool {
+    match graph.edge(edge.src(), edge.dst()) {
+        None => false,
+        Some(view) => match (edge.time(), edge.layer()) {
+            (Some(_), _) => view
+                .explode()
+                .iter()
+                .any(|e| e.edge.time() == edge.time() && e.edge.layer() == edge.layer()),
+            (None, Some(layer)) => view
+                .explode_layers()
+                .iter()
+                .any(|e| e.edge.layer() == Some(layer)),
+            (None, None) => true,
+        },
+    }
+}
+
+fn sort_value(edge: &EdgeView<DynamicGraph>, key: &SortKey<EdgeSortKey>) -> Option<Prop> {
+    match key {
+        SortKey::Key(EdgeSortKey::Src) => Some(Prop::str(edge.src().name())),
+        SortKey::Key(EdgeSortKey::Dst) => Some(Prop::str(edge.dst().name())),
+        SortKey::Key(EdgeSortKey::EarliestTime) => edge.earliest_time().map(Prop::I64),
+        SortKey::Key(EdgeSortKey::LatestTime) => edge.latest_time().map(Prop::I64),
+        // resolved to `SortKey::Property` which carries the name of the property
+        SortKey::Key(EdgeSortKey::Property) => None,
+        SortKey::Property(name) => edge.properties().get(name),
     }
 }
 
@@ -87,6 +165,105 @@ impl GqlEdges {
         self.update(self.ee.shrink_end(end))
     }
 
+    /// Return a list of windows of size `window` moving forward by `step`, which defaults to `window`,
+    /// over the time range of the edges
+    async fn rolling(
+        &self,
+        window: GqlInterval,
+        step: Option<GqlInterval>,
+    ) -> Result<Vec<Self>, Error> {
+        collect_windows(
+            self.ee
+                .rolling(window, step)?
+                .map(|edges| self.update(edges)),
+        )
+    }
+
+    /// Return a list of windows over the time range of the edges, all starting at the start of the
+    /// edges and growing by `step`
+    async fn expanding(&self, step: GqlInterval) -> Result<Vec<Self>, Error> {
+        collect_windows(self.ee.expanding(step)?.map(|edges| self.update(edges)))
+    }
+
+    /// One edge per update of each edge, the edges selected by `sortBy` or `filter` are exploded
+    /// in order
+    async fn explode(&self) -> Self {
+        match self.selection {
+            Some(_) => self.select(
+                self.iter_views()
+                    .flat_map(|edge| edge.explode())
+                    .map(|edge| edge.edge)
+                    .collect(),
+            ),
+            None => self.update(self.ee.explode()),
+        }
+    }
+
+    /// One edge per layer of each edge, the edges selected by `sortBy` or `filter` are exploded in
+    /// order
+    async fn explode_layers(&self) -> Self {
+        match self.selection {
+            Some(_) => self.select(
+                self.iter_views()
+                    .flat_map(|edge| edge.explode_layers())
+                    .map(|edge| edge.edge)
+                    .collect(),
+            ),
+            None => self.update(self.ee.explode_layers()),
+        }
+    }
+
+    /// Sort the edges by the values of `sortBys` in turn, only the first `limit` edges are kept if
+    /// it is set
+    ///
+    /// Edges with a missing value are sorted last and ties keep their previous order.
+    async fn sort_by(
+        &self,
+        sort_bys: Vec<EdgeSortBy>,
+        limit: Option<usize>,
+    ) -> Result<Self, Error> {
+        let sort_bys = sort_bys
+            .into_iter()
+            .map(ResolvedSortBy::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut values: Vec<_> = self
+            .iter_views()
+            .map(|edge| {
+                let values: Vec<_> = sort_bys
+                    .iter()
+                    .map(|sort_by| sort_value(&edge, &sort_by.key))
+                    .collect();
+                (edge.edge, values)
+            })
+            .collect();
+        values.sort_by(|(_, left), (_, right)| compare_sort_values(&sort_bys, left, right));
+        if let Some(limit) = limit {
+            values.truncate(limit);
+        }
+        Ok(self.select(values.into_iter().map(|(edge, _)| edge).collect()))
+    }
+
+    /// Keep the edges that pass all the filters that are set
+    ///
+    /// Property filters use the latest value of the property of each edge in the current view,
+    /// exploded edges use the value of their own update and layer.
+    async fn filter(&self, mut filter: EdgeFilterInput) -> Result<Self, Error> {
+        let property_filter = filter.property_filter()?;
+        let selection = self
+            .iter_views()
+            .filter(|edge| {
+                filter.matches_types(
+                    edge.src().node_type().as_deref(),
+                    edge.dst().node_type().as_deref(),
+                ) && property_filter
+                    .as_ref()
+                    .is_none_or(|f| f.matches(|name| edge.properties().get(name)))
+            })
+            .map(|edge| edge.edge)
+            .collect();
+        Ok(self.select(selection))
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -0,0 +1,276 @@
+use crate::model::graph::property::GqlPropValue;
+use async_graphql::Error;
+use dynamic_graphql::{Enum, InputObject};
+use raphtory::{core::Prop, prelude::PropertyFilter};
+use std::cmp::Ordering;
+
+#[derive(Enum, Copy, Clone, Debug)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+    GreaterThan,
+    LessThan,
+    IsNone,
+    IsSome,
+    Any,
+    NotAny,
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct PropertyFilterInput {
+    property: String,
+    operator: Operator,
+    value: Option<GqlPropValue>,
+}
+
+impl PropertyFilterInput {
+    fn value(self) -> Result<(String, Prop), Error> {
+        match self.value {
+            Some(GqlPropValue(value)) => Ok((self.property, value)),
+            None => Err(Error::new(format!(
+                "Operator {:?} requires a value",
+                self.operator
+            ))),
+        }
+    }
+
+    fn values(self) -> Result<(String, Vec<Prop>), Error> {
+        let operator = self.operator;
+        match self.value()? {
+            (property, Prop::List(values)) => Ok((property, values.iter().cloned().collect())),
+            _ => Err(Error::new(format!(
+                "Operator {operator:?} requires a list of values"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<PropertyFilterInput> for PropertyFilter {
+    type Error = Error;
+
+    fn try_from(input: PropertyFilterInput) -> Result<Self, Self::Error> {
+        let filter = match input.operator {
+            Operator::Equal => {
+                let (name, value) = input.value()?;
+                PropertyFilter::eq(name, value)
+            }
+            Operator::NotEqual => {
+                let (name, value) = input.value()?;
+                PropertyFilter::ne(name, value)
+            }
+            Operator::GreaterThanOrEqual => {
+                let (name, value) = input.value()?;
+                PropertyFilter::ge(name, value)
+            }
+            Operator::LessThanOrEqual => {
+                let (name, value) = input.value()?;
+                PropertyFilter::le(name, value)
+            }
+            Operator::GreaterThan => {
+                let (name, value) = input.value()?;
+                PropertyFilter::gt(name, value)
+            }
+            Operator::LessThan => {
+                let (name, value) = input.value()?;
+                PropertyFilter::lt(name, value)
+            }
+            Operator::IsNone => PropertyFilter::is_none(input.property),
+            Operator::IsSome => PropertyFilter::is_some(input.property),
+            Operator::Any => {
+                let (name, values) = input.values()?;
+                PropertyFilter::any(name, values)
+            }
+            Operator::NotAny => {
+                let (name, values) = input.values()?;
+                PropertyFilter::not_any(name, values)
+            }
+        };
+        Ok(filter)
+    }
+}
+
+/// Combine a list of property filters into a single filter that requires all of them to pass
+pub(crate) fn all_properties(filters: Vec<PropertyFilterInput>) -> Result<PropertyFilter, Error> {
+    let filters = filters
+        .into_iter()
+        .map(PropertyFilter::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(PropertyFilter::all_of(filters))
+}
+
+/// Filters on nodes, every filter that is set needs to pass
+#[derive(InputObject, Clone, Debug)]
+pub struct NodeFilterInput {
+    properties: Option<Vec<PropertyFilterInput>>,
+    node_types: Option<Vec<String>>,
+    min_degree: Option<usize>,
+    max_degree: Option<usize>,
+}
+
+impl NodeFilterInput {
+    pub(crate) fn property_filter(&mut self) -> Result<Option<PropertyFilter>, Error> {
+        self.properties.take().map(all_properties).transpose()
+    }
+
+    pub(crate) fn matches_type(&self, node_type: Option<&str>) -> bool {
+        match &self.node_types {
+            Some(types) => node_type.is_some_and(|node_type| types.iter().any(|t| t == node_type)),
+            None => true,
+        }
+    }
+
+    pub(crate) fn matches_degree(&self, degree: usize) -> bool {
+        self.min_degree.is_none_or(|min| degree >= min)
+            && self.max_degree.is_none_or(|max| degree <= max)
+    }
+}
+
+/// Filters on edges, every filter that is set needs to pass
+#[derive(InputObject, Clone, Debug)]
+pub struct EdgeFilterInput {
+    properties: Option<Vec<PropertyFilterInput>>,
+    /// Keep edges where the type of either the source or the destination is in the list
+    node_types: Option<Vec<String>>,
+}
+
+impl EdgeFilterInput {
+    pub(crate) fn property_filter(&mut self) -> Result<Option<PropertyFilter>, Error> {
+        self.properties.take().map(all_properties).transpose()
+    }
+
+    pub(crate) fn matches_types(&self, src_type: Option<&str>, dst_type: Option<&str>) -> bool {
+        match &self.node_types {
+            Some(types) => [src_type, dst_type]
+                .into_iter()
+                .flatten()
+                .any(|node_type| types.iter().any(|t| t == node_type)),
+            None => true,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NodeSortKey {
+    Id,
+    Name,
+    Degree,
+    EarliestTime,
+    LatestTime,
+    Property,
+}
+
+/// Sort nodes by `key` in ascending order unless `reverse` is set, `property` names the property
+/// to sort by for the `PROPERTY` key
+#[derive(InputObject, Clone, Debug)]
+pub struct NodeSortBy {
+    pub(crate) key: NodeSortKey,
+    pub(crate) property: Option<String>,
+    pub(crate) reverse: Option<bool>,
+}
+
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeSortKey {
+    Src,
+    Dst,
+    EarliestTime,
+    LatestTime,
+    Property,
+}
+
+/// Sort edges by `key` in ascending order unless `reverse` is set, `property` names the property
+/// to sort by for the `PROPERTY` key
+#[derive(InputObject, Clone, Debug)]
+pub struct EdgeSortBy {
+    pub(crate) key: EdgeSortKey,
+    pub(crate) property: Option<String>,
+    pub(crate) reverse: Option<bool>,
+}
+
+/// A sort key that is resolved to the values of every node or edge before sorting
+pub(crate) enum SortKey<K> {
+    Key(K),
+    Property(String),
+}
+
+pub(crate) struct ResolvedSortBy<K> {
+    pub(crate) key: SortKey<K>,
+    pub(crate) reverse: bool,
+}
+
+fn resolve_sort_by<K: Copy + PartialEq + std::fmt::Debug>(
+    key: K,
+    property_key: K,
+    property: Option<String>,
+    reverse: Option<bool>,
+) -> Result<ResolvedSortBy<K>, Error> {
+    let key = if key == property_key {
+        match property {
+            Some(property) => SortKey::Property(property),
+            None => {
+                return Err(Error::new(format!(
+                    "Sorting by {key:?} requires a property"
+                )))
+            }
+        }
+    } else {
+        SortKey::Key(key)
+    };
+    Ok(ResolvedSortBy {
+        key,
+        reverse: reverse.unwrap_or(false),
+    })
+}
+
+impl TryFrom<NodeSortBy> for ResolvedSortBy<NodeSortKey> {
+    type Error = Error;
+
+    fn try_from(sort_by: NodeSortBy) -> Result<Self, Self::Error> {
+        resolve_sort_by(
+            sort_by.key,
+            NodeSortKey::Property,
+            sort_by.property,
+            sort_by.reverse,
+        )
+    }
+}
+
+impl TryFrom<EdgeSortBy> for ResolvedSortBy<EdgeSortKey> {
+    type Error = Error;
+
+    fn try_from(sort_by: EdgeSortBy) -> Result<Self, Self::Error> {
+        resolve_sort_by(
+            sort_by.key,
+            EdgeSortKey::Property,
+            sort_by.property,
+            sort_by.reverse,
+        )
+    }
+}
+
+/// Compare the sort values of two entities, missing values are always sorted last
+pub(crate) fn compare_sort_values<K>(
+    sort_bys: &[ResolvedSortBy<K>],
+    left: &[Option<Prop>],
+    right: &[Option<Prop>],
+) -> Ordering {
+    sort_bys
+        .iter()
+        .zip(left.iter().zip(right))
+        .map(|(sort_by, (left, right))| match (left, right) {
+            (Some(left), Some(right)) => {
+                let ordering = left.partial_cmp(right).unwrap_or(Ordering::Equal);
+                if sort_by.reverse {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
@@ -2,14 +2,20 @@ use crate::{
     data::Data,
     model::{
         graph::{
-            edge::Edge, edges::GqlEdges, node::Node, nodes::GqlNodes, property::GqlProperties,
+            edge::Edge,
+            edges::GqlEdges,
+            filtering::PropertyFilterInput,
+            interval::{collect_windows, GqlInterval},
+            node::Node,
+            nodes::GqlNodes,
+            property::GqlProperties,
         },
         plugins::graph_algorithm_plugin::GraphAlgorithmPlugin,
         schema::graph_schema::GraphSchema,
     },
     paths::ExistingGraphFolder,
 };
-use async_graphql::Context;
+use async_graphql::{Context, Result};
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
@@ -148,6 +154,25 @@ impl GqlGraph {
         )
     }
 
+    /// Restrict the graph to nodes whose latest property value in the current view passes the filter
+    async fn filter_nodes(&self, filter: PropertyFilterInput) -> Result<GqlGraph> {
+        let filter: PropertyFilter = filter.try_into()?;
+        let index = self
+            .index
+            .as_ref()
+            .map(|index| {
+                index
+                    .filter_nodes(filter.clone())
+                    .map(|g| g.into_dynamic_indexed())
+            })
+            .transpose()?;
+        Ok(Self {
+            path: self.path.clone(),
+            graph: self.graph.filter_nodes(filter)?.into_dynamic(),
+            index,
+        })
+    }
+
     /// Return a graph containing only the activity between `start` and `end` measured as milliseconds from epoch
 
     async fn window(&self, start: i64, end: i64) -> GqlGraph {
@@ -193,6 +218,42 @@ impl GqlGraph {
         self.apply(|g| g.shrink_end(end), |g| g.shrink_end(end))
     }
 
+    /// Return a list of windows of size `window` moving forward by `step`, which defaults to `window`,
+    /// over the time range of the graph
+    async fn rolling(
+        &self,
+        window: GqlInterval,
+        step: Option<GqlInterval>,
+    ) -> Result<Vec<GqlGraph>> {
+        let graphs = self.graph.rolling(window.clone(), step.clone())?;
+        match &self.index {
+            Some(index) => collect_windows(
+                graphs
+                    .zip(index.rolling(window, step)?)
+                    .map(|(graph, index)| GqlGraph::new(self.path.clone(), graph, Some(index))),
+            ),
+            None => collect_windows(graphs.map(|graph| {
+                GqlGraph::new(self.path.clone(), graph, None::<IndexedGraph<DynamicGraph>>)
+            })),
+        }
+    }
+
+    /// Return a list of windows over the time range of the graph, all starting at the start of the
+    /// graph and growing by `step`
+    async fn expanding(&self, step: GqlInterval) -> Result<Vec<GqlGraph>> {
+        let graphs = self.graph.expanding(step.clone())?;
+        match &self.index {
+            Some(index) => collect_windows(
+                graphs
+                    .zip(index.expanding(step)?)
+                    .map(|(graph, index)| GqlGraph::new(self.path.clone(), graph, Some(index))),
+            ),
+            None => collect_windows(graphs.map(|graph| {
+                GqlGraph::new(self.path.clone(), graph, None::<IndexedGraph<DynamicGraph>>)
+            })),
+        }
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -0,0 +1,56 @@
+use async_graphql::{Error, Value as GqlValue};
+use dynamic_graphql::{Scalar, ScalarValue};
+use raphtory::core::utils::time::{error::ParseTimeError, Interval};
+
+/// The maximum number of windows returned by `rolling` and `expanding`
+pub(crate) const MAX_WINDOWS: usize = 10_000;
+
+/// A time interval given either as a number of milliseconds or as a string like `"1 day"`, it
+/// needs to be positive
+#[derive(Clone, Debug, Scalar)]
+pub struct GqlInterval(GqlValue, Interval);
+
+impl ScalarValue for GqlInterval {
+    fn from_value(value: GqlValue) -> Result<GqlInterval, Error> {
+        let interval = match &value {
+            GqlValue::Number(n) => match n.as_i64() {
+                Some(n) => Interval::try_from(n)?,
+                None => {
+                    return Err(Error::new(
+                        "Interval must be a whole number of milliseconds",
+                    ))
+                }
+            },
+            GqlValue::String(s) => Interval::try_from(s.as_str())?,
+            _ => return Err(Error::new("Interval must be an integer or a string")),
+        };
+        if 0i64 + interval <= 0 {
+            return Err(Error::new("Interval must be positive"));
+        }
+        Ok(GqlInterval(value, interval))
+    }
+
+    fn to_value(&self) -> GqlValue {
+        self.0.clone()
+    }
+}
+
+impl TryFrom<GqlInterval> for Interval {
+    type Error = ParseTimeError;
+
+    fn try_from(value: GqlInterval) -> Result<Self, Self::Error> {
+        Ok(value.1)
+    }
+}
+
+/// Collect the windows of `rolling` or `expanding`, failing if there are more than `MAX_WINDOWS`
+pub(crate) fn collect_windows<T>(windows: impl Iterator<Item = T>) -> Result<Vec<T>, Error> {
+    let windows: Vec<_> = windows.take(MAX_WINDOWS + 1).collect();
+    if windows.len() > MAX_WINDOWS {
+        Err(Error::new(format!(
+            "Too many windows, at most {MAX_WINDOWS} windows can be returned"
+        )))
+    } else {
+        Ok(windows)
+    }
+}
@@ -1,10 +1,14 @@
+pub(crate) mod batch;
 pub(crate) mod edge;
 mod edges;
+pub(crate) mod filtering;
 pub(crate) mod graph;
 pub(crate) mod graphs;
+pub(crate) mod interval;
 pub(crate) mod mutable_graph;
 pub(crate) mod node;
 mod nodes;
 mod path_from_node;
 pub(crate) mod property;
+pub(crate) mod snapshot;
 pub(crate) mod vectorised_graph;
@@ -1,6 +1,13 @@
 use crate::{
+    events::{GraphEvent, GraphEventKind},
     graph::{GraphWithVectors, UpdateEmbeddings},
-    model::graph::{edge::Edge, graph::GqlGraph, node::Node, property::GqlPropValue},
+    model::graph::{
+        batch::{BatchResult, BatchValidator},
+        edge::Edge,
+        graph::GqlGraph,
+        node::Node,
+        property::GqlPropValue,
+    },
     paths::ExistingGraphFolder,
 };
 use dynamic_graphql::{InputObject, ResolvedObject, ResolvedObjectFields};
@@ -13,31 +20,31 @@ use raphtory_api::core::storage::arc_str::OptionAsStr;
 
 #[derive(InputObject)]
 pub struct GqlPropInput {
-    key: String,
-    value: GqlPropValue,
+    pub(crate) key: String,
+    pub(crate) value: GqlPropValue,
 }
 
 #[derive(InputObject)]
 pub struct TPropInput {
-    time: i64,
-    properties: Option<Vec<GqlPropInput>>,
+    pub(crate) time: i64,
+    pub(crate) properties: Option<Vec<GqlPropInput>>,
 }
 
 #[derive(InputObject)]
 pub struct NodeAddition {
-    name: String,
-    node_type: Option<String>,
-    constant_properties: Option<Vec<GqlPropInput>>,
-    updates: Option<Vec<TPropInput>>,
+    pub(crate) name: String,
+    pub(crate) node_type: Option<String>,
+    pub(crate) constant_properties: Option<Vec<GqlPropInput>>,
+    pub(crate) updates: Option<Vec<TPropInput>>,
 }
 
 #[derive(InputObject)]
 pub struct EdgeAddition {
-    src: String,
-    dst: String,
-    layer: Option<String>,
-    constant_properties: Option<Vec<GqlPropInput>>,
-    updates: Option<Vec<TPropInput>>,
+    pub(crate) src: String,
+    pub(crate) dst: String,
+    pub(crate) layer: Option<String>,
+    pub(crate) constant_properties: Option<Vec<GqlPropInput>>,
+    pub(crate) updates: Option<Vec<TPropInput>>,
 }
 
 #[derive(ResolvedObject)]
@@ -55,8 +62,8 @@ impl GqlMutableGraph {
     }
 }
 
-fn as_properties(properties: Vec<GqlPropInput>) -> impl Iterator<Item = (String, Prop)> {
-    properties.into_iter().map(|p| (p.key, p.value.0))
+fn as_properties(properties: Vec<GqlPropInput>) -> Vec<(String, Prop)> {
+    properties.into_iter().map(|p| (p.key, p.value.0)).collect()
 }
 
 #[ResolvedObjectFields]
@@ -85,43 +92,36 @@ impl GqlMutableGraph {
         properties: Option<Vec<GqlPropInput>>,
         node_type: Option<String>,
     ) -> Result<GqlMutableNode, GraphError> {
-        let node = self.graph.add_node(
-            time,
-            &name,
-            as_properties(properties.unwrap_or(vec![])),
-            node_type.as_str(),
-        )?;
+        let _guard = self.graph.lock_writes().await?;
+        let properties = as_properties(properties.unwrap_or(vec![]));
+        let node = self
+            .graph
+            .add_node(time, &name, properties.clone(), node_type.as_str())?;
         node.update_embeddings().await?;
         self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::node(
+            GraphEventKind::NodeAdded,
+            Some(time),
+            &node,
+            properties,
+        ));
         Ok(node.into())
     }
 
     /// Add a batch of nodes
+    ///
+    /// Nodes are added one by one, the ones before a failing node stay in the graph. Use
+    /// `addBatch` to add all of them or none.
     async fn add_nodes(&self, nodes: Vec<NodeAddition>) -> Result<bool, GraphError> {
+        let _guard = self.graph.lock_writes().await?;
+        let mut events = vec![];
         for node in nodes {
-            let name = node.name.as_str();
-
-            for prop in node.updates.unwrap_or(vec![]) {
-                self.graph.add_node(
-                    prop.time,
-                    name,
-                    as_properties(prop.properties.unwrap_or(vec![])),
-                    None,
-                )?;
-            }
-            if let Some(node_type) = node.node_type.as_str() {
-                self.get_node_view(name)?.set_node_type(node_type)?;
-            }
-            let constant_props = node.constant_properties.unwrap_or(vec![]);
-            if !constant_props.is_empty() {
-                self.get_node_view(name)?
-                    .add_constant_properties(as_properties(constant_props))?;
-            }
-            if let Ok(node) = self.get_node_view(name) {
-                node.update_embeddings().await; // FIXME: ideally this should call the embedding function just once!!
-            }
+            self.apply_node(node, &mut events).await?;
         }
         self.graph.write_updates()?;
+        events
+            .into_iter()
+            .for_each(|event| self.graph.publish(event));
         Ok(true)
     }
 
@@ -140,46 +140,80 @@ impl GqlMutableGraph {
         properties: Option<Vec<GqlPropInput>>,
         layer: Option<String>,
     ) -> Result<GqlMutableEdge, GraphError> {
-        let edge = self.graph.add_edge(
-            time,
-            src,
-            dst,
-            as_properties(properties.unwrap_or(vec![])),
-            layer.as_str(),
-        )?;
+        let _guard = self.graph.lock_writes().await?;
+        let properties = as_properties(properties.unwrap_or(vec![]));
+        let edge = self
+            .graph
+            .add_edge(time, src, dst, properties.clone(), layer.as_str())?;
         edge.update_embeddings().await;
         self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::edge(
+            GraphEventKind::EdgeAdded,
+            Some(time),
+            &edge,
+            layer.as_str(),
+            properties,
+        ));
         Ok(edge.into())
     }
 
     /// Add a batch of edges
+    ///
+    /// Edges are added one by one, the ones before a failing edge stay in the graph. Use
+    /// `addBatch` to add all of them or none.
     async fn add_edges(&self, edges: Vec<EdgeAddition>) -> Result<bool, GraphError> {
+        let _guard = self.graph.lock_writes().await?;
+        let mut events = vec![];
         for edge in edges {
-            let src = edge.src.as_str();
-            let dst = edge.dst.as_str();
-            let layer = edge.layer.as_str();
-            for prop in edge.updates.unwrap_or(vec![]) {
-                self.graph.add_edge(
-                    prop.time,
-                    src,
-                    dst,
-                    as_properties(prop.properties.unwrap_or(vec![])),
-                    layer,
-                )?;
-            }
-            let constant_props = edge.constant_properties.unwrap_or(vec![]);
-            if !constant_props.is_empty() {
-                self.get_edge_view(src, dst)?
-                    .add_constant_properties(as_properties(constant_props), layer)?;
-            }
-            if let Ok(edge) = self.get_edge_view(src, dst) {
-                edge.update_embeddings().await; // FIXME: ideally this should call the embedding function just once!!
-            }
+            self.apply_edge(edge, &mut events).await?;
         }
         self.graph.write_updates()?;
+        events
+            .into_iter()
+            .for_each(|event| self.graph.publish(event));
         Ok(true)
     }
 
+    /// Add a batch of nodes and edges atomically
+    ///
+    /// Every item is validated against the graph first (property types, node types and constant
+    /// properties). If any item is invalid, nothing is applied and the errors of all the invalid
+    /// items are returned. Otherwise the nodes are added followed by the edges and the updates
+    /// are persisted in a single write. No other mutation of the graph can run between the
+    /// validation and the write.
+    async fn add_batch(
+        &self,
+        nodes: Option<Vec<NodeAddition>>,
+        edges: Option<Vec<EdgeAddition>>,
+    ) -> Result<BatchResult, GraphError> {
+        let _guard = self.graph.lock_writes().await?;
+        let nodes = nodes.unwrap_or(vec![]);
+        let edges = edges.unwrap_or(vec![]);
+        let errors = BatchValidator::new(&self.graph.graph).validate(&nodes, &edges);
+        if !errors.is_empty() {
+            return Ok(BatchResult {
+                success: false,
+                errors,
+            });
+        }
+
+        let mut events = vec![];
+        for node in nodes {
+            self.apply_node(node, &mut events).await?;
+        }
+        for edge in edges {
+            self.apply_edge(edge, &mut events).await?;
+        }
+        self.graph.write_updates()?;
+        events
+            .into_iter()
+            .for_each(|event| self.graph.publish(event));
+        Ok(BatchResult {
+            success: true,
+            errors: vec![],
+        })
+    }
+
     /// Mark an edge as deleted (creates the edge if it did not exist)
 
     async fn delete_edge(
@@ -189,21 +223,47 @@ impl GqlMutableGraph {
         dst: String,
         layer: Option<String>,
     ) -> Result<GqlMutableEdge, GraphError> {
+        let _guard = self.graph.lock_writes().await?;
         let edge = self.graph.delete_edge(time, src, dst, layer.as_str())?;
         edge.update_embeddings().await;
         self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::edge(
+            GraphEventKind::EdgeDeleted,
+            Some(time),
+            &edge,
+            layer.as_str(),
+            vec![],
+        ));
         Ok(edge.into())
     }
 
+    /// Mark a node as deleted (creates the node if it did not exist)
+    async fn delete_node(&self, time: i64, name: String) -> Result<GqlMutableNode, GraphError> {
+        let _guard = self.graph.lock_writes().await?;
+        let node = self.graph.delete_node(time, name)?;
+        node.update_embeddings().await?;
+        self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::node(
+            GraphEventKind::NodeDeleted,
+            Some(time),
+            &node,
+            vec![],
+        ));
+        Ok(node.into())
+    }
+
     /// Add temporal properties to graph
     async fn add_properties(
         &self,
         t: i64,
         properties: Vec<GqlPropInput>,
     ) -> Result<bool, GraphError> {
-        self.graph.add_properties(t, as_properties(properties))?;
+        let _guard = self.graph.lock_writes().await?;
+        let properties = as_properties(properties);
+        self.graph.add_properties(t, properties.clone())?;
         self.update_graph_embeddings().await;
         self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::graph(Some(t), properties));
         Ok(true)
     }
 
@@ -212,10 +272,12 @@ impl GqlMutableGraph {
         &self,
         properties: Vec<GqlPropInput>,
     ) -> Result<bool, GraphError> {
-        self.graph
-            .add_constant_properties(as_properties(properties))?;
+        let _guard = self.graph.lock_writes().await?;
+        let properties = as_properties(properties);
+        self.graph.add_constant_properties(properties.clone())?;
         self.update_graph_embeddings().await;
         self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::graph(None, properties));
         Ok(true)
     }
 
@@ -224,15 +286,100 @@ impl GqlMutableGraph {
         &self,
         properties: Vec<GqlPropInput>,
     ) -> Result<bool, GraphError> {
-        self.graph
-            .update_constant_properties(as_properties(properties))?;
+        let _guard = self.graph.lock_writes().await?;
+        let properties = as_properties(properties);
+        self.graph.update_constant_properties(properties.clone())?;
         self.update_graph_embeddings().await;
         self.graph.write_updates()?;
+        self.graph.publish(GraphEvent::graph(None, properties));
         Ok(true)
     }
 }
 
 impl GqlMutableGraph {
+    async fn apply_node(
+        &self,
+        node: NodeAddition,
+        events: &mut Vec<GraphEvent>,
+    ) -> Result<(), GraphError> {
+        let name = node.name.as_str();
+
+        let mut updates = vec![];
+        for prop in node.updates.unwrap_or(vec![]) {
+            let properties = as_properties(prop.properties.unwrap_or(vec![]));
+            self.graph
+                .add_node(prop.time, name, properties.clone(), None)?;
+            updates.push((prop.time, properties));
+        }
+        if let Some(node_type) = node.node_type.as_str() {
+            self.get_node_view(name)?.set_node_type(node_type)?;
+        }
+        let constant_props = as_properties(node.constant_properties.unwrap_or(vec![]));
+        if !constant_props.is_empty() {
+            self.get_node_view(name)?
+                .add_constant_properties(constant_props.clone())?;
+        }
+        if let Ok(node) = self.get_node_view(name) {
+            node.update_embeddings().await; // FIXME: ideally this should call the embedding function just once!!
+            events.extend(updates.into_iter().map(|(time, properties)| {
+                GraphEvent::node(GraphEventKind::NodeAdded, Some(time), &node, properties)
+            }));
+            if !constant_props.is_empty() {
+                events.push(GraphEvent::node(
+                    GraphEventKind::NodeUpdated,
+                    None,
+                    &node,
+                    constant_props,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn apply_edge(
+        &self,
+        edge: EdgeAddition,
+        events: &mut Vec<GraphEvent>,
+    ) -> Result<(), GraphError> {
+        let src = edge.src.as_str();
+        let dst = edge.dst.as_str();
+        let layer = edge.layer.as_str();
+        let mut updates = vec![];
+        for prop in edge.updates.unwrap_or(vec![]) {
+            let properties = as_properties(prop.properties.unwrap_or(vec![]));
+            self.graph
+                .add_edge(prop.time, src, dst, properties.clone(), layer)?;
+            updates.push((prop.time, properties));
+        }
+        let constant_props = as_properties(edge.constant_properties.unwrap_or(vec![]));
+        if !constant_props.is_empty() {
+            self.get_edge_view(src, dst)?
+                .add_constant_properties(constant_props.clone(), layer)?;
+        }
+        if let Ok(edge) = self.get_edge_view(src, dst) {
+            edge.update_embeddings().await; // FIXME: ideally this should call the embedding function just once!!
+            events.extend(updates.into_iter().map(|(time, properties)| {
+                GraphEvent::edge(
+                    GraphEventKind::EdgeAdded,
+                    Some(time),
+                    &edge,
+                    Some(layer.unwrap_or("_default")),
+                    properties,
+                )
+            }));
+            if !constant_props.is_empty() {
+                events.push(GraphEvent::edge(
+                    GraphEventKind::EdgeUpdated,
+                    None,
+                    &edge,
+                    Some(layer.unwrap_or("_default")),
+                    constant_props,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     async fn update_graph_embeddings(&self) {
         self.graph
             .update_graph_embeddings(Some(self.path.get_original_path_str().to_owned()))
@@ -287,18 +434,22 @@ impl GqlMutableNode {
         &self,
         properties: Vec<GqlPropInput>,
     ) -> Result<bool, GraphError> {
-        self.node
-            .add_constant_properties(as_properties(properties))?;
+        let _guard = self.node.graph.lock_writes().await?;
+        let properties = as_properties(properties);
+        self.node.add_constant_properties(properties.clone())?;
         self.node.update_embeddings().await;
         self.node.graph.write_updates()?;
+        self.publish(GraphEventKind::NodeUpdated, None, properties);
         Ok(true)
     }
 
     /// Set the node type (errors if the node already has a non-default type)
     async fn set_node_type(&self, new_type: String) -> Result<bool, GraphError> {
+        let _guard = self.node.graph.lock_writes().await?;
         self.node.set_node_type(&new_type)?;
         self.node.update_embeddings().await;
         self.node.graph.write_updates()?;
+        self.publish(GraphEventKind::NodeUpdated, None, vec![]);
         Ok(true)
     }
 
@@ -307,10 +458,12 @@ impl GqlMutableNode {
         &self,
         properties: Vec<GqlPropInput>,
     ) -> Result<bool, GraphError> {
-        self.node
-            .update_constant_properties(as_properties(properties))?;
+        let _guard = self.node.graph.lock_writes().await?;
+        let properties = as_properties(properties);
+        self.node.update_constant_properties(properties.clone())?;
         self.node.update_embeddings().await;
         self.node.graph.write_updates()?;
+        self.publish(GraphEventKind::NodeUpdated, None, properties);
         Ok(true)
     }
 
@@ -320,12 +473,32 @@ impl GqlMutableNode {
         time: i64,
         properties: Option<Vec<GqlPropInput>>,
     ) -> Result<bool, GraphError> {
-        self.node
-            .add_updates(time, as_properties(properties.unwrap_or(vec![])))?;
+        let _guard = self.node.graph.lock_writes().await?;
+        let properties = as_properties(properties.unwrap_or(vec![]));
+        self.node.add_updates(time, properties.clone())?;
         self.node.update_embeddings().await;
         self.node.graph.write_updates()?;
+        self.publish(GraphEventKind::NodeUpdated, Some(time), properties);
         Ok(true)
     }
+
+    /// Mark the node as deleted at time `time` (all incident edges that are alive at `time` are deleted as well)
+    async fn delete(&self, time: i64) -> Result<bool, GraphError> {
+        let _guard = self.node.graph.lock_writes().await?;
+        self.node.delete(time)?;
+        self.node.update_embeddings().await?;
+        self.node.graph.write_updates()?;
+        self.publish(GraphEventKind::NodeDeleted, Some(time), vec![]);
+        Ok(true)
+    }
+}
+
+impl GqlMutableNode {
+    fn publish(&self, kind: GraphEventKind, time: Option<i64>, properties: Vec<(String, Prop)>) {
+        self.node
+            .graph
+            .publish(GraphEvent::node(kind, time, &self.node, properties));
+    }
 }
 
 #[derive(ResolvedObject)]
@@ -363,9 +536,11 @@ impl GqlMutableEdge {
 
     /// Mark the edge as deleted at time `time`
     async fn delete(&self, time: i64, layer: Option<String>) -> Result<bool, GraphError> {
+        let _guard = self.edge.graph.lock_writes().await?;
         self.edge.delete(time, layer.as_str())?;
         self.edge.update_embeddings().await;
         self.edge.graph.write_updates()?;
+        self.publish(GraphEventKind::EdgeDeleted, Some(time), layer, vec![]);
         Ok(true)
     }
 
@@ -378,10 +553,13 @@ impl GqlMutableEdge {
         properties: Vec<GqlPropInput>,
         layer: Option<String>,
     ) -> Result<bool, GraphError> {
+        let _guard = self.edge.graph.lock_writes().await?;
+        let properties = as_properties(properties);
         self.edge
-            .add_constant_properties(as_properties(properties), layer.as_str())?;
+            .add_constant_properties(properties.clone(), layer.as_str())?;
         self.edge.update_embeddings().await;
         self.edge.graph.write_updates()?;
+        self.publish(GraphEventKind::EdgeUpdated, None, layer, properties);
         Ok(true)
     }
 
@@ -394,10 +572,13 @@ impl GqlMutableEdge {
         properties: Vec<GqlPropInput>,
         layer: Option<String>,
     ) -> Result<bool, GraphError> {
+        let _guard = self.edge.graph.lock_writes().await?;
+        let properties = as_properties(properties);
         self.edge
-            .update_constant_properties(as_properties(properties), layer.as_str())?;
+            .update_constant_properties(properties.clone(), layer.as_str())?;
         self.edge.update_embeddings().await;
         self.edge.graph.write_updates()?;
+        self.publish(GraphEventKind::EdgeUpdated, None, layer, properties);
         Ok(true)
     }
 
@@ -411,13 +592,31 @@ impl GqlMutableEdge {
         properties: Option<Vec<GqlPropInput>>,
         layer: Option<String>,
     ) -> Result<bool, GraphError> {
-        self.edge.add_updates(
-            time,
-            as_properties(properties.unwrap_or(vec![])),
-            layer.as_str(),
-        )?;
+        let _guard = self.edge.graph.lock_writes().await?;
+        let properties = as_properties(properties.unwrap_or(vec![]));
+        self.edge
+            .add_updates(time, properties.clone(), layer.as_str())?;
         self.edge.update_embeddings().await;
         self.edge.graph.write_updates()?;
+        self.publish(GraphEventKind::EdgeUpdated, Some(time), layer, properties);
         Ok(true)
     }
 }
+
+impl GqlMutableEdge {
+    fn publish(
+        &self,
+        kind: GraphEventKind,
+        time: Option<i64>,
+        layer: Option<String>,
+        properties: Vec<(String, Prop)>,
+    ) {
+        self.edge.graph.publish(GraphEvent::edge(
+            kind,
+            time,
+            &self.edge,
+            layer.as_str(),
+            properties,
+        ));
+    }
+}
@@ -1,6 +1,10 @@
 use crate::model::graph::{
-    edges::GqlEdges, path_from_node::GqlPathFromNode, property::GqlProperties,
+    edges::GqlEdges,
+    interval::{collect_windows, GqlInterval},
+    path_from_node::GqlPathFromNode,
+    property::GqlProperties,
 };
+use async_graphql::Error;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::{
     algorithms::components::{in_component, out_component},
@@ -98,6 +102,22 @@ impl Node {
         self.vv.shrink_end(end).into()
     }
 
+    /// Return a list of windows of size `window` moving forward by `step`, which defaults to `window`,
+    /// over the time range of the node
+    async fn rolling(
+        &self,
+        window: GqlInterval,
+        step: Option<GqlInterval>,
+    ) -> Result<Vec<Node>, Error> {
+        collect_windows(self.vv.rolling(window, step)?.map(|node| node.into()))
+    }
+
+    /// Return a list of windows over the time range of the node, all starting at the start of the
+    /// node and growing by `step`
+    async fn expanding(&self, step: GqlInterval) -> Result<Vec<Node>, Error> {
+        collect_windows(self.vv.expanding(step)?.map(|node| node.into()))
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -1,29 +1,94 @@
-use crate::model::graph::node::Node;
+use crate::model::graph::{
+    filtering::{
+        compare_sort_values, NodeFilterInput, NodeSortBy, NodeSortKey, ResolvedSortBy, SortKey,
+    },
+    interval::{collect_windows, GqlInterval},
+    node::Node,
+};
+use async_graphql::Error;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::{
-    db::{api::view::DynamicGraph, graph::nodes::Nodes},
+    core::entities::VID,
+    db::{
+        api::{
+            state::NodeStateOps,
+            view::{internal::OneHopFilter, DynamicGraph, NodePropertyFilterOps},
+        },
+        graph::{node::NodeView, nodes::Nodes},
+    },
     prelude::*,
 };
+use raphtory_api::core::entities::GID;
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
 
 #[derive(ResolvedObject)]
 pub(crate) struct GqlNodes {
     pub(crate) nn: Nodes<'static, DynamicGraph>,
+    /// The nodes selected by `sortBy` or `filter` in order, later windows and layers change the
+    /// views of the selected nodes and drop the ones that are not part of them but do not change
+    /// the order
+    selection: Option<Arc<[VID]>>,
 }
 
 impl GqlNodes {
     fn update<N: Into<Nodes<'static, DynamicGraph>>>(&self, nodes: N) -> Self {
-        GqlNodes::new(nodes)
+        Self {
+            nn: nodes.into(),
+            selection: self.selection.clone(),
+        }
+    }
+
+    fn select(&self, selection: Vec<VID>) -> Self {
+        Self {
+            nn: self.nn.clone(),
+            selection: Some(selection.into()),
+        }
     }
 }
 
 impl GqlNodes {
     pub(crate) fn new<N: Into<Nodes<'static, DynamicGraph>>>(nodes: N) -> Self {
-        Self { nn: nodes.into() }
+        Self {
+            nn: nodes.into(),
+            selection: None,
+        }
+    }
+
+    fn iter_views(&self) -> Box<dyn Iterator<Item = NodeView<DynamicGraph>> + '_> {
+        match &self.selection {
+            Some(selection) => {
+                let base_graph = self.nn.base_graph();
+                let graph = self.nn.current_filter();
+                let iter = selection
+                    .iter()
+                    .filter(|&&vid| graph.has_node(vid))
+                    .map(|&vid| {
+                        NodeView::new_one_hop_filtered(base_graph.clone(), graph.clone(), vid)
+                    });
+                Box::new(iter)
+            }
+            None => self.nn.iter_owned(),
+        }
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Node> + '_> {
-        let iter = self.nn.iter_owned().map(Node::from);
-        Box::new(iter)
+        Box::new(self.iter_views().map(Node::from))
+    }
+}
+
+fn sort_value(node: &NodeView<DynamicGraph>, key: &SortKey<NodeSortKey>) -> Option<Prop> {
+    match key {
+        SortKey::Key(NodeSortKey::Id) => match node.id() {
+            GID::U64(id) => Some(Prop::U64(id)),
+            GID::Str(id) => Some(Prop::str(id)),
+        },
+        SortKey::Key(NodeSortKey::Name) => Some(Prop::str(node.name())),
+        SortKey::Key(NodeSortKey::Degree) => Some(Prop::U64(node.degree() as u64)),
+        SortKey::Key(NodeSortKey::EarliestTime) => node.earliest_time().map(Prop::I64),
+        SortKey::Key(NodeSortKey::LatestTime) => node.latest_time().map(Prop::I64),
+        // resolved to `SortKey::Property` which carries the name of the property
+        SortKey::Key(NodeSortKey::Property) => None,
+        SortKey::Property(name) => node.properties().get(name),
     }
 }
 
@@ -89,10 +154,99 @@ impl GqlNodes {
         self.update(self.nn.shrink_end(end))
     }
 
+    /// Return a list of windows of size `window` moving forward by `step`, which defaults to `window`,
+    /// over the time range of the nodes
+    async fn rolling(
+        &self,
+        window: GqlInterval,
+        step: Option<GqlInterval>,
+    ) -> Result<Vec<Self>, Error> {
+        collect_windows(
+            self.nn
+                .rolling(window, step)?
+                .map(|nodes| self.update(nodes)),
+        )
+    }
+
+    /// Return a list of windows over the time range of the nodes, all starting at the start of the
+    /// nodes and growing by `step`
+    async fn expanding(&self, step: GqlInterval) -> Result<Vec<Self>, Error> {
+        collect_windows(self.nn.expanding(step)?.map(|nodes| self.update(nodes)))
+    }
+
     async fn type_filter(&self, node_types: Vec<String>) -> Self {
         self.update(self.nn.type_filter(&node_types))
     }
 
+    /// Sort the nodes by the values of `sortBys` in turn, only the first `limit` nodes are kept if
+    /// it is set
+    ///
+    /// Nodes with a missing value are sorted last and ties keep their previous order.
+    async fn sort_by(
+        &self,
+        sort_bys: Vec<NodeSortBy>,
+        limit: Option<usize>,
+    ) -> Result<Self, Error> {
+        let sort_bys = sort_bys
+            .into_iter()
+            .map(ResolvedSortBy::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        // the sort values and the current position of the selected nodes, ties are broken by the
+        // position so they keep their previous order
+        let sort_values: HashMap<GID, (usize, Vec<Option<Prop>>)> = self
+            .iter_views()
+            .enumerate()
+            .map(|(position, node)| {
+                let values = sort_bys
+                    .iter()
+                    .map(|sort_by| sort_value(&node, &sort_by.key))
+                    .collect();
+                (node.id(), (position, values))
+            })
+            .collect();
+        let cmp = |left: &GID, right: &GID| match (sort_values.get(left), sort_values.get(right)) {
+            (Some((left_pos, left)), Some((right_pos, right))) => {
+                compare_sort_values(&sort_bys, left, right).then(left_pos.cmp(right_pos))
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        let ids = self.nn.id();
+        let sorted = match limit {
+            Some(limit) => ids.bottom_k_by(cmp, limit),
+            None => ids.sort_by_values_by(cmp),
+        };
+        let selection = sorted
+            .iter()
+            .filter(|(_, id)| sort_values.contains_key(*id))
+            .map(|(node, _)| node.node)
+            .collect();
+        Ok(self.select(selection))
+    }
+
+    /// Keep the nodes that pass all the filters that are set
+    ///
+    /// Property filters use the latest value of the property in the current view.
+    async fn filter(&self, mut filter: NodeFilterInput) -> Result<Self, Error> {
+        let filtered_graph = match filter.property_filter()? {
+            Some(property_filter) => Some(self.nn.current_filter().filter_nodes(property_filter)?),
+            None => None,
+        };
+        let selection = self
+            .iter_views()
+            .filter(|node| {
+                filter.matches_type(node.node_type().as_deref())
+                    && filter.matches_degree(node.degree())
+                    && filtered_graph
+                        .as_ref()
+                        .is_none_or(|graph| graph.has_node(node.node))
+            })
+            .map(|node| node.node)
+            .collect();
+        Ok(self.select(selection))
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -123,6 +277,6 @@ impl GqlNodes {
     }
 
     async fn ids(&self) -> Vec<String> {
-        self.nn.name().collect()
+        self.iter_views().map(|node| node.name()).collect()
     }
 }
@@ -52,7 +52,7 @@ fn gql_to_prop(value: GqlValue) -> Result<Prop, Error> {
     }
 }
 
-fn prop_to_gql(prop: &Prop) -> GqlValue {
+pub(crate) fn prop_to_gql(prop: &Prop) -> GqlValue {
     match prop {
         Prop::Str(s) => GqlValue::String(s.to_string()),
         Prop::U8(u) => GqlValue::Number(Number::from(*u)),
@@ -186,6 +186,48 @@ impl GqlTemporalProp {
             .collect_vec()
     }
 
+    /// The values of the property in the current view with their types preserved
+    async fn typed_values(&self) -> Vec<GqlPropValue> {
+        self.prop.values().map(GqlPropValue).collect()
+    }
+
+    async fn typed_at(&self, t: i64) -> Option<GqlPropValue> {
+        self.prop.at(t).map(GqlPropValue)
+    }
+
+    async fn typed_latest(&self) -> Option<GqlPropValue> {
+        self.prop.latest().map(GqlPropValue)
+    }
+
+    async fn typed_unique(&self) -> Vec<GqlPropValue> {
+        self.prop.unique().into_iter().map(GqlPropValue).collect()
+    }
+
+    /// The sum of the values in the current view, null if there are none or they can't be added
+    async fn sum(&self) -> Option<GqlPropValue> {
+        self.prop.sum().map(GqlPropValue)
+    }
+
+    /// The mean of the values in the current view, null if there are none or they are not numeric
+    async fn mean(&self) -> Option<f64> {
+        self.prop.mean().and_then(|mean| mean.as_f64())
+    }
+
+    /// The smallest value in the current view and the time it was set
+    async fn min(&self) -> Option<GqlPropTuple> {
+        self.prop.min().map(|min| min.into())
+    }
+
+    /// The largest value in the current view and the time it was set
+    async fn max(&self) -> Option<GqlPropTuple> {
+        self.prop.max().map(|max| max.into())
+    }
+
+    /// The number of updates to the property in the current view
+    async fn count(&self) -> usize {
+        self.prop.count()
+    }
+
     async fn ordered_dedupe(&self, latest_time: bool) -> Vec<GqlPropTuple> {
         self.prop
             .ordered_dedupe(latest_time)
@@ -0,0 +1,191 @@
+use crate::{
+    data::Data, model::graph::graph::GqlGraph, paths::ExistingGraphFolder, snapshots::Snapshot,
+};
+use async_graphql::Context;
+use dynamic_graphql::{ResolvedObject, ResolvedObjectFields, Result, SimpleObject};
+use raphtory::{
+    core::storage::timeindex::TimeIndexOps,
+    db::api::{
+        properties::{internal::PropertiesOps, Properties},
+        storage::graph::nodes::node_storage_ops::NodeStorageOps,
+        view::{internal::CoreGraphOps, MaterializedGraph},
+    },
+    prelude::*,
+    search::IndexedGraph,
+};
+use raphtory_api::core::storage::arc_str::ArcStr;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlSnapshot {
+    path: String,
+    snapshot: Snapshot,
+}
+
+impl GqlSnapshot {
+    pub(crate) fn new(path: String, snapshot: Snapshot) -> Self {
+        Self { path, snapshot }
+    }
+}
+
+#[ResolvedObjectFields]
+impl GqlSnapshot {
+    async fn name(&self) -> String {
+        self.snapshot.name.clone()
+    }
+
+    /// Milliseconds since the epoch
+    async fn created_at(&self) -> i64 {
+        self.snapshot.created_at
+    }
+
+    async fn count_nodes(&self) -> usize {
+        self.snapshot.count_nodes
+    }
+
+    async fn count_edges(&self) -> usize {
+        self.snapshot.count_edges
+    }
+
+    /// The graph as it was when the snapshot was created
+    async fn graph<'a>(&self, ctx: &Context<'a>) -> Result<GqlGraph> {
+        let data = ctx.data_unchecked::<Data>();
+        let folder = ExistingGraphFolder::try_from(data.work_dir.clone(), &self.path)?;
+        let graph = data.read_snapshot(&self.path, &self.snapshot.name)?;
+        Ok(GqlGraph::new(
+            folder,
+            graph,
+            None::<IndexedGraph<MaterializedGraph>>,
+        ))
+    }
+}
+
+#[derive(SimpleObject, Debug, PartialEq)]
+pub(crate) struct SnapshotEdge {
+    src: String,
+    dst: String,
+}
+
+/// The nodes and edges that differ between two versions of a graph, updated nodes and edges
+/// exist in both versions but have different updates, properties or types
+#[derive(SimpleObject, Debug, Default)]
+pub(crate) struct SnapshotDiff {
+    added_nodes: Vec<String>,
+    removed_nodes: Vec<String>,
+    updated_nodes: Vec<String>,
+    added_edges: Vec<SnapshotEdge>,
+    removed_edges: Vec<SnapshotEdge>,
+    updated_edges: Vec<SnapshotEdge>,
+}
+
+type PropUpdates = (Vec<(ArcStr, Prop)>, Vec<(ArcStr, Vec<(i64, Prop)>)>);
+
+fn prop_updates<P: PropertiesOps + Clone>(properties: Properties<P>) -> PropUpdates {
+    let mut constant = properties.constant().iter().collect::<Vec<_>>();
+    constant.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut temporal = properties
+        .temporal()
+        .iter()
+        .map(|(key, prop)| (key, prop.iter().collect()))
+        .collect::<Vec<_>>();
+    temporal.sort_by(|(a, _), (b, _)| a.cmp(b));
+    (constant, temporal)
+}
+
+#[derive(PartialEq)]
+struct NodeUpdates {
+    history: Vec<i64>,
+    deletions: Vec<i64>,
+    node_type: Option<ArcStr>,
+    properties: PropUpdates,
+}
+
+#[derive(PartialEq)]
+struct EdgeUpdates {
+    history: Vec<i64>,
+    deletions: Vec<i64>,
+    layers: Vec<ArcStr>,
+    properties: PropUpdates,
+}
+
+fn node_updates(graph: &MaterializedGraph) -> HashMap<String, NodeUpdates> {
+    graph
+        .nodes()
+        .iter()
+        .map(|node| {
+            let updates = NodeUpdates {
+                history: node.history(),
+                deletions: graph
+                    .core_node_entry(node.node)
+                    .deletions()
+                    .iter()
+                    .collect(),
+                node_type: node.node_type(),
+                properties: prop_updates(node.properties()),
+            };
+            (node.name(), updates)
+        })
+        .collect()
+}
+
+fn edge_updates(graph: &MaterializedGraph) -> BTreeMap<(String, String), EdgeUpdates> {
+    graph
+        .edges()
+        .iter()
+        .map(|edge| {
+            let mut layers = edge.layer_names();
+            layers.sort();
+            let updates = EdgeUpdates {
+                history: edge.history(),
+                deletions: edge.deletions(),
+                layers,
+                properties: prop_updates(edge.properties()),
+            };
+            ((edge.src().name(), edge.dst().name()), updates)
+        })
+        .collect()
+}
+
+impl SnapshotDiff {
+    pub(crate) fn new(from: &MaterializedGraph, to: &MaterializedGraph) -> Self {
+        let mut diff = Self::default();
+
+        let from_nodes = node_updates(from);
+        let to_nodes = node_updates(to);
+        for (name, updates) in &to_nodes {
+            match from_nodes.get(name) {
+                None => diff.added_nodes.push(name.clone()),
+                Some(old) if old != updates => diff.updated_nodes.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed_nodes = from_nodes
+            .keys()
+            .filter(|name| !to_nodes.contains_key(*name))
+            .cloned()
+            .collect();
+        diff.added_nodes.sort();
+        diff.removed_nodes.sort();
+        diff.updated_nodes.sort();
+
+        let from_edges = edge_updates(from);
+        let to_edges = edge_updates(to);
+        let edge = |(src, dst): &(String, String)| SnapshotEdge {
+            src: src.clone(),
+            dst: dst.clone(),
+        };
+        for (key, updates) in &to_edges {
+            match from_edges.get(key) {
+                None => diff.added_edges.push(edge(key)),
+                Some(old) if old != updates => diff.updated_edges.push(edge(key)),
+                Some(_) => {}
+            }
+        }
+        diff.removed_edges = from_edges
+            .keys()
+            .filter(|key| !to_edges.contains_key(*key))
+            .map(edge)
+            .collect();
+        diff
+    }
+}
@@ -1,23 +1,29 @@
 use crate::{
+    auth::{permissions, require_access},
+    config::auth_config::Access,
     data::Data,
     model::{
         graph::{
-            graph::GqlGraph, graphs::GqlGraphs, mutable_graph::GqlMutableGraph,
+            graph::GqlGraph,
+            graphs::GqlGraphs,
+            mutable_graph::GqlMutableGraph,
+            snapshot::{GqlSnapshot, SnapshotDiff},
             vectorised_graph::GqlVectorisedGraph,
         },
         plugins::{mutation_plugin::MutationPlugin, query_plugin::QueryPlugin},
+        subscription::{subscription_root, GqlGraphEvent, SUBSCRIPTION_ROOT},
     },
+    snapshots::SnapshotError,
     url_encode::{url_decode_graph, url_encode_graph},
 };
 use async_graphql::Context;
 use dynamic_graphql::{
-    App, Enum, Mutation, MutationFields, MutationRoot, ResolvedObject, ResolvedObjectFields,
-    Result, Upload,
+    dynamic::SchemaBuilder, internal::Registry, App, Enum, Mutation, MutationFields, MutationRoot,
+    ResolvedObject, ResolvedObjectFields, Result, Upload,
 };
 #[cfg(feature = "storage")]
 use raphtory::db::api::{storage::graph::storage_ops::GraphStorage, view::internal::CoreGraphOps};
 use raphtory::{
-    core::utils::errors::GraphError,
     db::{api::view::MaterializedGraph, graph::views::deletion_graph::PersistentGraph},
     prelude::*,
 };
@@ -25,7 +31,6 @@ use std::{
     error::Error,
     fmt::{Display, Formatter},
     io::Read,
-    sync::Arc,
 };
 use zip::ZipArchive;
 
@@ -33,6 +38,7 @@ pub mod algorithms;
 pub(crate) mod graph;
 pub mod plugins;
 pub(crate) mod schema;
+pub(crate) mod subscription;
 
 #[derive(Debug)]
 pub struct MissingGraph;
@@ -77,6 +83,7 @@ impl QueryRoot {
 
     /// Returns a graph
     async fn graph<'a>(ctx: &Context<'a>, path: &str) -> Result<GqlGraph> {
+        require_access(ctx, path, Access::Read)?;
         let data = ctx.data_unchecked::<Data>();
         Ok(data
             .get_graph(path)
@@ -84,6 +91,7 @@ impl QueryRoot {
     }
 
     async fn update_graph<'a>(ctx: &Context<'a>, path: String) -> Result<GqlMutableGraph> {
+        require_access(ctx, &path, Access::Write)?;
         let data = ctx.data_unchecked::<Data>();
         let graph = data
             .get_graph(path.as_ref())
@@ -92,6 +100,7 @@ impl QueryRoot {
     }
 
     async fn vectorised_graph<'a>(ctx: &Context<'a>, path: &str) -> Option<GqlVectorisedGraph> {
+        require_access(ctx, path, Access::Read).ok()?;
         let data = ctx.data_unchecked::<Data>();
         let g = data.get_graph(path).ok()?.0.vectors?;
         Some(g.into())
@@ -99,22 +108,68 @@ impl QueryRoot {
 
     async fn graphs<'a>(ctx: &Context<'a>) -> Result<GqlGraphs> {
         let data = ctx.data_unchecked::<Data>();
-        let paths = data.get_all_graph_folders();
+        let mut paths = data.get_all_graph_folders();
+        if let Some(permissions) = permissions(ctx) {
+            paths.retain(|folder| permissions.can_read(folder.get_original_path_str()));
+        }
         Ok(GqlGraphs::new(paths))
     }
 
-    async fn plugins<'a>(ctx: &Context<'a>) -> QueryPlugin {
+    /// Plugins search across all the graphs and need read access to the whole working directory
+    async fn plugins<'a>(ctx: &Context<'a>) -> Result<QueryPlugin> {
+        require_access(ctx, "", Access::Read)?;
         let data = ctx.data_unchecked::<Data>();
-        data.get_global_plugins()
+        Ok(data.get_global_plugins())
     }
 
-    async fn receive_graph<'a>(ctx: &Context<'a>, path: String) -> Result<String, Arc<GraphError>> {
+    async fn receive_graph<'a>(ctx: &Context<'a>, path: String) -> Result<String> {
         let path = path.as_ref();
+        require_access(ctx, path, Access::Read)?;
         let data = ctx.data_unchecked::<Data>();
         let g = data.get_graph(path)?.0.graph.clone();
         let res = url_encode_graph(g)?;
         Ok(res)
     }
+
+    /// The snapshots of the graph at `path` in the order they were created
+    async fn list_snapshots<'a>(ctx: &Context<'a>, path: String) -> Result<Vec<GqlSnapshot>> {
+        require_access(ctx, &path, Access::Read)?;
+        let data = ctx.data_unchecked::<Data>();
+        Ok(data
+            .list_snapshots(&path)?
+            .into_iter()
+            .map(|snapshot| GqlSnapshot::new(path.clone(), snapshot))
+            .collect())
+    }
+
+    async fn snapshot<'a>(ctx: &Context<'a>, path: String, name: String) -> Result<GqlSnapshot> {
+        require_access(ctx, &path, Access::Read)?;
+        let data = ctx.data_unchecked::<Data>();
+        let snapshot = data
+            .list_snapshots(&path)?
+            .into_iter()
+            .find(|snapshot| snapshot.name == name)
+            .ok_or(SnapshotError::NotFound(name))?;
+        Ok(GqlSnapshot::new(path, snapshot))
+    }
+
+    /// Compare the snapshot `from` of the graph at `path` to the snapshot `to`, or to the current
+    /// graph if `to` is not set
+    async fn diff_snapshots<'a>(
+        ctx: &Context<'a>,
+        path: String,
+        from: String,
+        to: Option<String>,
+    ) -> Result<SnapshotDiff> {
+        require_access(ctx, &path, Access::Read)?;
+        let data = ctx.data_unchecked::<Data>();
+        let from = data.read_snapshot(&path, &from)?;
+        let to = match to {
+            Some(to) => data.read_snapshot(&path, &to)?,
+            None => data.get_graph(&path)?.0.graph,
+        };
+        Ok(SnapshotDiff::new(&from, &to))
+    }
 }
 
 #[derive(MutationRoot)]
@@ -125,12 +180,15 @@ pub(crate) struct Mut(MutRoot);
 
 #[MutationFields]
 impl Mut {
-    async fn plugins<'a>(_ctx: &Context<'a>) -> MutationPlugin {
-        MutationPlugin::default()
+    /// Mutation plugins are not tied to a graph and need write access to the whole working directory
+    async fn plugins<'a>(ctx: &Context<'a>) -> Result<MutationPlugin> {
+        require_access(ctx, "", Access::Write)?;
+        Ok(MutationPlugin::default())
     }
 
     // If namespace is not provided, it will be set to the current working directory.
     async fn delete_graph<'a>(ctx: &Context<'a>, path: String) -> Result<bool> {
+        require_access(ctx, &path, Access::Write)?;
         let data = ctx.data_unchecked::<Data>();
         data.delete_graph(&path)?;
         Ok(true)
@@ -141,6 +199,7 @@ impl Mut {
         path: String,
         graph_type: GqlGraphType,
     ) -> Result<bool> {
+        require_access(ctx, &path, Access::Write)?;
         let data = ctx.data_unchecked::<Data>();
         let graph = match graph_type {
             GqlGraphType::Persistent => PersistentGraph::new().materialize()?,
@@ -153,6 +212,7 @@ impl Mut {
     // If namespace is not provided, it will be set to the current working directory.
     // This applies to both the graph namespace and new graph namespace.
     async fn move_graph<'a>(ctx: &Context<'a>, path: &str, new_path: &str) -> Result<bool> {
+        require_access(ctx, path, Access::Write)?;
         Self::copy_graph(ctx, path, new_path).await?;
         let data = ctx.data_unchecked::<Data>();
         data.delete_graph(path)?;
@@ -165,6 +225,8 @@ impl Mut {
         // doing this in a more efficient way is not trivial, this at least is correct
         // there are questions like, maybe the new vectorised graph have different rules
         // for the templates or if it needs to be vectorised at all
+        require_access(ctx, path, Access::Read)?;
+        require_access(ctx, new_path, Access::Write)?;
         let data = ctx.data_unchecked::<Data>();
         let graph = data.get_graph(path)?.0.graph.materialize()?;
 
@@ -177,6 +239,34 @@ impl Mut {
         Ok(true)
     }
 
+    /// Record the current state of the graph at `path` as the snapshot `name`
+    async fn create_snapshot<'a>(
+        ctx: &Context<'a>,
+        path: String,
+        name: String,
+    ) -> Result<GqlSnapshot> {
+        require_access(ctx, &path, Access::Write)?;
+        let data = ctx.data_unchecked::<Data>();
+        let snapshot = data.create_snapshot(&path, &name).await?;
+        Ok(GqlSnapshot::new(path, snapshot))
+    }
+
+    /// Roll the graph at `path` back to the snapshot `name`, discarding the updates applied since
+    /// and the snapshots created after it
+    async fn restore_snapshot<'a>(ctx: &Context<'a>, path: String, name: String) -> Result<bool> {
+        require_access(ctx, &path, Access::Write)?;
+        let data = ctx.data_unchecked::<Data>();
+        data.restore_snapshot(&path, &name).await?;
+        Ok(true)
+    }
+
+    async fn delete_snapshot<'a>(ctx: &Context<'a>, path: String, name: String) -> Result<bool> {
+        require_access(ctx, &path, Access::Write)?;
+        let data = ctx.data_unchecked::<Data>();
+        data.delete_snapshot(&path, &name)?;
+        Ok(true)
+    }
+
     /// Use GQL multipart upload to send new graphs to server
     ///
     /// Returns::
@@ -187,6 +277,7 @@ impl Mut {
         graph: Upload,
         overwrite: bool,
     ) -> Result<String> {
+        require_access(ctx, &path, Access::Write)?;
         let data = ctx.data_unchecked::<Data>();
         let graph = {
             let in_file = graph.value(ctx)?.content;
@@ -213,6 +304,7 @@ impl Mut {
         graph: String,
         overwrite: bool,
     ) -> Result<String> {
+        require_access(ctx, path, Access::Write)?;
         let data = ctx.data_unchecked::<Data>();
         let g: MaterializedGraph = url_decode_graph(graph)?;
         if overwrite {
@@ -224,4 +316,18 @@ impl Mut {
 }
 
 #[derive(App)]
-pub struct App(QueryRoot, MutRoot, Mut);
+struct Roots(QueryRoot, MutRoot, Mut, GqlGraphEvent);
+
+pub struct App;
+
+impl App {
+    /// `dynamic_graphql` can't derive a subscription root, so it is registered alongside the
+    /// derived query and mutation roots
+    pub fn create_schema() -> SchemaBuilder {
+        Registry::new()
+            .register::<Roots>()
+            .set_subscription(SUBSCRIPTION_ROOT)
+            .register_type(subscription_root())
+            .create_schema()
+    }
+}
@@ -0,0 +1,134 @@
+use async_graphql::{
+    async_trait,
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo},
+    Context, ServerError, ServerResult, Value,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::sync::Notify;
+
+#[derive(thiserror::Error, Debug)]
+#[error("The query was cancelled")]
+pub struct QueryCancelled;
+
+#[derive(Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Cancelled when the query that is running times out or its client disconnects
+///
+/// The server adds one to the data of every request. Queries are stopped before the next field
+/// they resolve and operations at their next await point, plugins that run synchronously for a
+/// long time should poll [`Self::is_cancelled`] or [`Self::check`], or run their algorithms with
+/// [`with_interrupt`](raphtory::db::task::interrupt::with_interrupt), to stop early.
+#[derive(Clone, Default)]
+pub struct QueryCancellation {
+    state: Arc<CancellationState>,
+}
+
+impl QueryCancellation {
+    /// The cancellation of the query being resolved, if it was run by the server
+    pub fn from_context<'a>(ctx: &Context<'a>) -> Option<&'a QueryCancellation> {
+        ctx.data_opt::<QueryCancellation>()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    pub fn check(&self) -> Result<(), QueryCancelled> {
+        match self.is_cancelled() {
+            true => Err(QueryCancelled),
+            false => Ok(()),
+        }
+    }
+
+    /// Wait until the query is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.state.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+        self.state.notify.notify_waiters();
+    }
+
+    /// Cancel the query when the guard is dropped, e.g., when the request handler is dropped
+    /// because the client disconnected
+    pub(crate) fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+}
+
+pub(crate) struct CancelOnDrop(QueryCancellation);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel()
+    }
+}
+
+/// Stops a cancelled query before the next field it resolves
+///
+/// Most resolvers don't yield, a query that runs for a long time is made of many of them and is
+/// stopped between two of them.
+pub(crate) struct CancellationExtension;
+
+impl ExtensionFactory for CancellationExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(CancellationExtension)
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for CancellationExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if let Some(cancellation) = ctx.data_opt::<QueryCancellation>() {
+            cancellation
+                .check()
+                .map_err(|err| ServerError::new(err.to_string(), None))?;
+        }
+        next.run(ctx, info).await
+    }
+}
+
+#[cfg(test)]
+mod cancellation_test {
+    use super::QueryCancellation;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancel_on_drop_wakes_waiters() {
+        let cancellation = QueryCancellation::default();
+        let waiter = tokio::spawn({
+            let cancellation = cancellation.clone();
+            async move { cancellation.cancelled().await }
+        });
+        assert!(cancellation.check().is_ok());
+
+        let guard = cancellation.cancel_on_drop();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(cancellation.is_cancelled());
+        assert!(cancellation.check().is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod cancellation;
 pub mod entry_point;
 pub mod graph_algorithm_plugin;
 pub mod mutation_entry_point;
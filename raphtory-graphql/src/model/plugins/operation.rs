@@ -1,4 +1,7 @@
-use crate::model::plugins::mutation_plugin::MutationPlugin;
+use crate::model::plugins::{
+    cancellation::{QueryCancellation, QueryCancelled},
+    mutation_plugin::MutationPlugin,
+};
 use async_graphql::{
     dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, TypeRef},
     FieldResult,
@@ -23,7 +26,16 @@ pub trait Operation<'a, A: Send + Sync + 'static> {
         let mut field = Field::new(name, Self::output_type(), |ctx| {
             FieldFuture::new(async move {
                 let entry_point: &A = ctx.parent_value.downcast_ref().unwrap();
-                Self::apply(&entry_point, ctx).await
+                match QueryCancellation::from_context(ctx.ctx).cloned() {
+                    Some(cancellation) => {
+                        cancellation.check()?;
+                        tokio::select! {
+                            result = Self::apply(entry_point, ctx) => result,
+                            _ = cancellation.cancelled() => Err(QueryCancelled.into()),
+                        }
+                    }
+                    None => Self::apply(entry_point, ctx).await,
+                }
             })
         });
 
@@ -35,6 +47,19 @@ pub trait Operation<'a, A: Send + Sync + 'static> {
     }
 }
 
+/// Run the synchronous part of an operation on the blocking thread pool
+///
+/// The future returned by [`Operation::apply`] then yields while the operation runs, so that the
+/// query can time out or be cancelled meanwhile. The operation itself keeps running until it
+/// returns, it should stop early once the query is cancelled, e.g., by running its algorithms
+/// with [`with_interrupt`](raphtory::db::task::interrupt::with_interrupt).
+pub(crate) async fn run_blocking<'b, F>(run: F) -> FieldResult<Option<FieldValue<'b>>>
+where
+    F: FnOnce() -> FieldResult<Option<FieldValue<'static>>> + Send + 'static,
+{
+    tokio::task::spawn_blocking(run).await?
+}
+
 pub(crate) struct NoOpMutation;
 
 impl<'a> Operation<'a, MutationPlugin> for NoOpMutation {
@@ -0,0 +1,142 @@
+use crate::{
+    auth::require_access,
+    config::auth_config::Access,
+    data::Data,
+    events::{GraphEvent, GraphEventFilter, GraphEventKind},
+    model::graph::property::GqlProp,
+};
+use async_graphql::{
+    dynamic::{
+        FieldValue, InputValue, Subscription, SubscriptionField, SubscriptionFieldFuture, TypeRef,
+    },
+    ErrorExtensions,
+};
+use dynamic_graphql::{internal::TypeName, ResolvedObject, ResolvedObjectFields};
+use futures_util::{future::ready, stream, StreamExt};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+pub(crate) const SUBSCRIPTION_ROOT: &str = "SubscriptionRoot";
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlGraphEvent {
+    event: GraphEvent,
+}
+
+impl From<GraphEvent> for GqlGraphEvent {
+    fn from(event: GraphEvent) -> Self {
+        Self { event }
+    }
+}
+
+#[ResolvedObjectFields]
+impl GqlGraphEvent {
+    async fn kind(&self) -> GraphEventKind {
+        self.event.kind
+    }
+
+    /// The time of the update, null for updates that are not temporal
+    async fn time(&self) -> Option<i64> {
+        self.event.time
+    }
+
+    /// The name of the node for node events
+    async fn node(&self) -> Option<String> {
+        self.event.node.clone()
+    }
+
+    async fn node_type(&self) -> Option<String> {
+        self.event.node_type.clone()
+    }
+
+    /// The name of the source node for edge events
+    async fn src(&self) -> Option<String> {
+        self.event.src.clone()
+    }
+
+    /// The name of the destination node for edge events
+    async fn dst(&self) -> Option<String> {
+        self.event.dst.clone()
+    }
+
+    async fn layer(&self) -> Option<String> {
+        self.event.layer.clone()
+    }
+
+    /// The properties added or updated
+    async fn properties(&self) -> Vec<GqlProp> {
+        self.event
+            .properties
+            .iter()
+            .cloned()
+            .map(|prop| prop.into())
+            .collect()
+    }
+}
+
+/// The subscription root, `dynamic_graphql` can't derive subscriptions so it is built by hand
+pub(crate) fn subscription_root() -> Subscription {
+    let graph_updates = SubscriptionField::new(
+        "graphUpdates",
+        TypeRef::named_nn(GqlGraphEvent::get_type_name()),
+        |ctx| {
+            SubscriptionFieldFuture::new(async move {
+                let path = ctx.args.try_get("path")?.string()?.to_owned();
+                let strings = |name: &str| {
+                    ctx.args
+                        .get(name)
+                        .map(|value| {
+                            value
+                                .list()?
+                                .iter()
+                                .map(|value| value.string().map(|s| s.to_owned()))
+                                .collect()
+                        })
+                        .transpose()
+                };
+                let filter = GraphEventFilter {
+                    layers: strings("layers")?,
+                    node_types: strings("nodeTypes")?,
+                    start: ctx.args.get("start").map(|t| t.i64()).transpose()?,
+                    end: ctx.args.get("end").map(|t| t.i64()).transpose()?,
+                };
+                require_access(ctx.ctx, &path, Access::Read)?;
+                let data = ctx.ctx.data_unchecked::<Data>();
+                data.get_graph(&path)?;
+                let receiver = data.events.subscribe(&path);
+
+                let events = stream::unfold(Some(receiver), |receiver| async move {
+                    let mut receiver = receiver?;
+                    match receiver.recv().await {
+                        Ok(event) => Some((Ok(event), Some(receiver))),
+                        // the subscriber can't tell which updates it missed, so report them and end
+                        // the stream rather than carrying on with gaps
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!("Subscriber to graph updates missed {skipped} events");
+                            Some((Err(skipped), None))
+                        }
+                        Err(RecvError::Closed) => None,
+                    }
+                });
+                Ok(events
+                    .filter(move |event| {
+                        ready(event.as_ref().map_or(true, |event| filter.matches(event)))
+                    })
+                    .map(|event| match event {
+                        Ok(event) => Ok(FieldValue::owned_any(GqlGraphEvent::from(event))),
+                        Err(skipped) => Err(async_graphql::Error::new(format!(
+                            "Missed {skipped} graph updates, subscribe again to receive new updates"
+                        ))
+                        .extend_with(|_, extensions| extensions.set("skipped", skipped))),
+                    }))
+            })
+        },
+    )
+    .description("Stream the updates applied to the graph at `path` through mutations, events without a time are not filtered by `start` and `end`. The stream ends with an error if the subscriber falls too far behind and misses updates.")
+    .argument(InputValue::new("path", TypeRef::named_nn(TypeRef::STRING)))
+    .argument(InputValue::new("layers", TypeRef::named_nn_list(TypeRef::STRING)))
+    .argument(InputValue::new("nodeTypes", TypeRef::named_nn_list(TypeRef::STRING)))
+    .argument(InputValue::new("start", TypeRef::named(TypeRef::INT)))
+    .argument(InputValue::new("end", TypeRef::named(TypeRef::INT)));
+    Subscription::new(SUBSCRIPTION_ROOT).field(graph_updates)
+}
@@ -0,0 +1,432 @@
+use async_graphql::{
+    async_trait,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextResolve,
+        ResolveInfo,
+    },
+    parser::types::ExecutableDocument,
+    QueryPathSegment, Response, ServerResult, Value, Variables,
+};
+use futures_util::future::BoxFuture;
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use raphtory::vectors::{Embedding, EmbeddingFunction, EmbeddingResult};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The metrics of the server, reported in the Prometheus text format by the `/metrics` route
+pub(crate) static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+/// Upper bounds in seconds of the buckets of the duration histograms
+const DURATION_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0,
+];
+
+#[derive(Default)]
+pub(crate) struct Counter(AtomicU64);
+
+impl Counter {
+    pub(crate) fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Histogram {
+    buckets: [AtomicU64; DURATION_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    pub(crate) fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        if let Some(bucket) = DURATION_BUCKETS.iter().position(|le| seconds <= *le) {
+            self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let with_le = |le: &str| match labels {
+            "" => format!("le=\"{le}\""),
+            labels => format!("{labels},le=\"{le}\""),
+        };
+        let mut cumulative = 0;
+        for (le, bucket) in DURATION_BUCKETS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{}}} {cumulative}",
+                with_le(&le.to_string())
+            );
+        }
+        let count = self.count();
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6;
+        let _ = writeln!(out, "{name}_bucket{{{}}} {count}", with_le("+Inf"));
+        let labels = match labels {
+            "" => String::new(),
+            labels => format!("{{{labels}}}"),
+        };
+        let _ = writeln!(out, "{name}_sum{labels} {sum}");
+        let _ = writeln!(out, "{name}_count{labels} {count}");
+    }
+}
+
+/// Metrics of the same kind distinguished by the value of a single label
+pub(crate) struct Family<M> {
+    label: &'static str,
+    metrics: RwLock<BTreeMap<String, Arc<M>>>,
+}
+
+impl<M: Default> Family<M> {
+    fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            metrics: Default::default(),
+        }
+    }
+
+    pub(crate) fn get(&self, value: &str) -> Arc<M> {
+        if let Some(metric) = self.metrics.read().get(value) {
+            return metric.clone();
+        }
+        self.metrics
+            .write()
+            .entry(value.to_owned())
+            .or_default()
+            .clone()
+    }
+
+    fn labelled(&self) -> Vec<(String, Arc<M>)> {
+        self.metrics
+            .read()
+            .iter()
+            .map(|(value, metric)| {
+                (
+                    format!("{}=\"{}\"", self.label, escape(value)),
+                    metric.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Histograms of the resolvers distinguished by the type and the name of their field
+///
+/// The histograms are looked up by type first so that no key is allocated once a field is known.
+#[derive(Default)]
+pub(crate) struct FieldHistograms {
+    metrics: RwLock<BTreeMap<String, BTreeMap<String, Arc<Histogram>>>>,
+}
+
+impl FieldHistograms {
+    pub(crate) fn get(&self, parent_type: &str, name: &str) -> Arc<Histogram> {
+        if let Some(metric) = self
+            .metrics
+            .read()
+            .get(parent_type)
+            .and_then(|fields| fields.get(name))
+        {
+            return metric.clone();
+        }
+        self.metrics
+            .write()
+            .entry(parent_type.to_owned())
+            .or_default()
+            .entry(name.to_owned())
+            .or_default()
+            .clone()
+    }
+
+    fn labelled(&self) -> Vec<(String, Arc<Histogram>)> {
+        self.metrics
+            .read()
+            .iter()
+            .flat_map(|(parent_type, fields)| {
+                fields.iter().map(move |(name, metric)| {
+                    (
+                        format!("field=\"{}.{}\"", escape(parent_type), escape(name)),
+                        metric.clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+pub(crate) struct Metrics {
+    pub(crate) requests: Family<Counter>,
+    pub(crate) request_errors: Family<Counter>,
+    pub(crate) request_duration: Family<Histogram>,
+    pub(crate) resolver_duration: FieldHistograms,
+    pub(crate) graph_cache_hits: Counter,
+    pub(crate) graph_cache_misses: Counter,
+    pub(crate) graph_cache_evictions: Family<Counter>,
+    pub(crate) graph_writes: Family<Counter>,
+    pub(crate) graph_write_duration: Histogram,
+    pub(crate) embedding_calls: Family<Counter>,
+    pub(crate) embedding_duration: Histogram,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            requests: Family::new("operation"),
+            request_errors: Family::new("operation"),
+            request_duration: Family::new("operation"),
+            resolver_duration: Default::default(),
+            graph_cache_hits: Default::default(),
+            graph_cache_misses: Default::default(),
+            graph_cache_evictions: Family::new("cause"),
+            graph_writes: Family::new("result"),
+            graph_write_duration: Default::default(),
+            embedding_calls: Family::new("result"),
+            embedding_duration: Default::default(),
+        }
+    }
+}
+
+/// The label of the outcome of a write or an embedding call
+pub(crate) fn result_label<T, E>(result: &Result<T, E>) -> &'static str {
+    match result {
+        Ok(_) => "ok",
+        Err(_) => "error",
+    }
+}
+
+impl Metrics {
+    /// Render the metrics in the Prometheus text exposition format
+    pub(crate) fn render(&self, resident_graphs: u64) -> String {
+        let mut out = String::new();
+        render_counters(
+            &mut out,
+            "raphtory_graphql_requests_total",
+            "GraphQL requests executed",
+            &self.requests,
+        );
+        render_counters(
+            &mut out,
+            "raphtory_graphql_request_errors_total",
+            "GraphQL requests that returned errors",
+            &self.request_errors,
+        );
+        render_histograms(
+            &mut out,
+            "raphtory_graphql_request_duration_seconds",
+            "Time taken to execute GraphQL requests",
+            &self.request_duration,
+        );
+        render_header(
+            &mut out,
+            "raphtory_graphql_resolver_duration_seconds",
+            "Time taken by the resolvers of the GraphQL fields resolved once per request",
+            "histogram",
+        );
+        for (labels, histogram) in self.resolver_duration.labelled() {
+            histogram.render(
+                &mut out,
+                "raphtory_graphql_resolver_duration_seconds",
+                &labels,
+            );
+        }
+        render_header(
+            &mut out,
+            "raphtory_graphql_resident_graphs",
+            "Graphs loaded in the cache",
+            "gauge",
+        );
+        let _ = writeln!(out, "raphtory_graphql_resident_graphs {resident_graphs}");
+        render_counter(
+            &mut out,
+            "raphtory_graphql_graph_cache_hits_total",
+            "Graph loads served from the cache",
+            &self.graph_cache_hits,
+        );
+        render_counter(
+            &mut out,
+            "raphtory_graphql_graph_cache_misses_total",
+            "Graph loads that read the graph from disk",
+            &self.graph_cache_misses,
+        );
+        render_counters(
+            &mut out,
+            "raphtory_graphql_graph_cache_evictions_total",
+            "Graphs removed from the cache",
+            &self.graph_cache_evictions,
+        );
+        render_counters(
+            &mut out,
+            "raphtory_graphql_graph_writes_total",
+            "Flushes of the pending updates of graphs to disk",
+            &self.graph_writes,
+        );
+        render_header(
+            &mut out,
+            "raphtory_graphql_graph_write_duration_seconds",
+            "Time taken to flush the pending updates of graphs to disk",
+            "histogram",
+        );
+        self.graph_write_duration.render(
+            &mut out,
+            "raphtory_graphql_graph_write_duration_seconds",
+            "",
+        );
+        render_counters(
+            &mut out,
+            "raphtory_graphql_embedding_calls_total",
+            "Calls to the embedding function",
+            &self.embedding_calls,
+        );
+        render_header(
+            &mut out,
+            "raphtory_graphql_embedding_duration_seconds",
+            "Time taken by the calls to the embedding function",
+            "histogram",
+        );
+        self.embedding_duration
+            .render(&mut out, "raphtory_graphql_embedding_duration_seconds", "");
+        out
+    }
+}
+
+fn render_header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn render_counter(out: &mut String, name: &str, help: &str, counter: &Counter) {
+    render_header(out, name, help, "counter");
+    let _ = writeln!(out, "{name} {}", counter.get());
+}
+
+fn render_counters(out: &mut String, name: &str, help: &str, family: &Family<Counter>) {
+    render_header(out, name, help, "counter");
+    for (labels, counter) in family.labelled() {
+        let _ = writeln!(out, "{name}{{{labels}}} {}", counter.get());
+    }
+}
+
+fn render_histograms(out: &mut String, name: &str, help: &str, family: &Family<Histogram>) {
+    render_header(out, name, help, "histogram");
+    for (labels, histogram) in family.labelled() {
+        histogram.render(out, name, &labels);
+    }
+}
+
+/// Records the requests and resolver timings of the schema in [`METRICS`]
+pub(crate) struct MetricsExtension;
+
+impl ExtensionFactory for MetricsExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RequestMetrics::default())
+    }
+}
+
+#[derive(Default)]
+struct RequestMetrics {
+    operation: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for RequestMetrics {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        // requests nearly always contain a single operation, the first one is used otherwise
+        if let Some((_, operation)) = document.operations.iter().next() {
+            *self.operation.lock() = Some(operation.node.ty.to_string());
+        }
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let start = Instant::now();
+        let response = next.run(ctx, operation_name).await;
+        let operation = self.operation.lock().clone().unwrap_or_default();
+        METRICS.requests.get(&operation).inc();
+        if response.is_err() {
+            METRICS.request_errors.get(&operation).inc();
+        }
+        METRICS
+            .request_duration
+            .get(&operation)
+            .observe(start.elapsed());
+        response
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        // the fields below a list are resolved once per item, timing them would cost more than
+        // resolving most of them so only the fields resolved once per request are timed
+        let in_list = info
+            .path_node
+            .parents()
+            .any(|node| matches!(node.segment, QueryPathSegment::Index(_)));
+        if info.is_for_introspection || in_list {
+            return next.run(ctx, info).await;
+        }
+        let (parent_type, name) = (info.parent_type, info.name);
+        let start = Instant::now();
+        let result = next.run(ctx, info).await;
+        METRICS
+            .resolver_duration
+            .get(parent_type, name)
+            .observe(start.elapsed());
+        result
+    }
+}
+
+/// Records the calls to the wrapped embedding function in [`METRICS`]
+pub(crate) struct MeteredEmbedding<F>(pub(crate) F);
+
+impl<F: EmbeddingFunction> EmbeddingFunction for MeteredEmbedding<F> {
+    fn call(&self, texts: Vec<String>) -> BoxFuture<'static, EmbeddingResult<Vec<Embedding>>> {
+        let call = self.0.call(texts);
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = call.await;
+            METRICS.embedding_duration.observe(start.elapsed());
+            METRICS.embedding_calls.get(result_label(&result)).inc();
+            result
+        })
+    }
+}
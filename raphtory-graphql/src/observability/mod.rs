@@ -1 +1,2 @@
+pub(crate) mod metrics;
 pub mod open_telemetry;
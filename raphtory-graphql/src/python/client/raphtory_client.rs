@@ -1,100 +1,74 @@
 use crate::{
+    client::{ClientError, RaphtoryGraphQLClient},
+    model::GqlGraphType,
     python::{
-        client::remote_graph::PyRemoteGraph, encode_graph, server::is_online,
-        translate_from_python, translate_map_to_python,
+        client::remote_graph::PyRemoteGraph, server::is_online, translate_from_python,
+        translate_map_to_python,
     },
-    url_encode::url_decode_graph,
 };
-use pyo3::{
-    exceptions::{PyException, PyValueError},
-    prelude::*,
-};
-use raphtory::{
-    db::api::view::MaterializedGraph,
-    python::utils::{errors::adapt_err_value, execute_async_task},
-};
-use reqwest::{multipart, multipart::Part, Client};
-use serde_json::{json, Value as JsonValue};
-use std::{collections::HashMap, fs::File, io::Read, path::Path};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use raphtory::{db::api::view::MaterializedGraph, python::utils::execute_async_task};
+use serde_json::Value as JsonValue;
+use std::{collections::HashMap, future::Future};
 use tracing::debug;
 
 /// A client for handling GraphQL operations in the context of Raphtory.
 #[derive(Clone)]
 #[pyclass(name = "RaphtoryClient")]
 pub struct PyRaphtoryClient {
-    pub(crate) url: String,
+    client: RaphtoryGraphQLClient,
 }
 
 impl PyRaphtoryClient {
-    pub(crate) fn query_with_json_variables(
+    /// Run a request of the client, blocking until it completes
+    fn execute<T, F>(
         &self,
-        query: String,
-        variables: HashMap<String, JsonValue>,
-    ) -> PyResult<HashMap<String, JsonValue>> {
-        let client = self.clone();
-        let (graphql_query, graphql_result) = execute_async_task(move || async move {
-            client.send_graphql_query(query, variables).await
-        })?;
-        let mut graphql_result = graphql_result;
-        match graphql_result.remove("data") {
-            Some(JsonValue::Object(data)) => Ok(data.into_iter().collect()),
-            _ => match graphql_result.remove("errors") {
-                Some(JsonValue::Array(errors)) => {
-                    let formatted_errors = errors
-                        .iter()
-                        .map(|err| format!("{}", err))
-                        .collect::<Vec<_>>()
-                        .join("\n\t");
-
-                    Err(PyException::new_err(format!(
-                        "After sending query to the server:\n\t{}\nGot the following errors:\n\t{}",
-                        graphql_query.to_string(),
-                        formatted_errors
-                    )))
-                }
-                _ => Err(PyException::new_err(format!(
-                    "Error while reading server response for query:\n\t{graphql_query}"
-                ))),
-            },
-        }
+        request: impl FnOnce(RaphtoryGraphQLClient) -> F + Send + 'static,
+    ) -> PyResult<T>
+    where
+        T: Send + 'static,
+        F: Future<Output = Result<T, ClientError>> + 'static,
+    {
+        let client = self.client.clone();
+        Ok(execute_async_task(move || request(client))?)
     }
 
-    /// Returns the query sent and the response
-    async fn send_graphql_query(
+    pub(crate) fn query_with_json_variables(
         &self,
         query: String,
         variables: HashMap<String, JsonValue>,
-    ) -> PyResult<(JsonValue, HashMap<String, JsonValue>)> {
-        let client = Client::new();
-
-        let request_body = json!({
-            "query": query,
-            "variables": variables
-        });
-
-        let response = client
-            .post(&self.url)
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|err| adapt_err_value(&err))?;
-
-        response
-            .json()
-            .await
-            .map_err(|err| adapt_err_value(&err))
-            .map(|json| (request_body, json))
+    ) -> PyResult<HashMap<String, JsonValue>> {
+        let data = self.execute(move |client| async move {
+            client.query(&query, variables.into_iter().collect()).await
+        })?;
+        Ok(data.into_iter().collect())
     }
 }
 
 #[pymethods]
 impl PyRaphtoryClient {
+    /// Arguments:
+    ///   * `url`: the url of the server.
+    ///   * `api_key`: an API key configured on the server, sent in the `X-API-Key` header.
+    ///   * `token`: a JWT signed with the key configured on the server, sent as a bearer token.
     #[new]
-    pub(crate) fn new(url: String) -> PyResult<Self> {
+    #[pyo3(signature = (url, api_key = None, token = None))]
+    pub(crate) fn new(
+        url: String,
+        api_key: Option<String>,
+        token: Option<String>,
+    ) -> PyResult<Self> {
         match reqwest::blocking::get(url.clone()) {
             Ok(response) => {
                 if response.status() == 200 {
-                    Ok(Self { url })
+                    let mut client = RaphtoryGraphQLClient::new(url);
+                    if let Some(api_key) = api_key {
+                        client = client.with_api_key(api_key);
+                    }
+                    if let Some(token) = token {
+                        client = client.with_token(token);
+                    }
+                    Ok(Self { client })
                 } else {
                     Err(PyValueError::new_err(format!(
                         "Could not connect to the given server - response {}",
@@ -114,7 +88,7 @@ impl PyRaphtoryClient {
     /// Returns:
     ///    Returns true if server is online otherwise false.
     fn is_server_online(&self) -> PyResult<bool> {
-        Ok(is_online(&self.url))
+        Ok(is_online(self.client.url()))
     }
 
     /// Make a graphQL query against the server.
@@ -153,32 +127,11 @@ impl PyRaphtoryClient {
     ///    The `data` field from the graphQL response after executing the mutation.
     #[pyo3(signature = (path, graph, overwrite = false))]
     fn send_graph(&self, path: String, graph: MaterializedGraph, overwrite: bool) -> PyResult<()> {
-        let encoded_graph = encode_graph(graph)?;
-
-        let query = r#"
-            mutation SendGraph($path: String!, $graph: String!, $overwrite: Boolean!) {
-                sendGraph(path: $path, graph: $graph, overwrite: $overwrite)
-            }
-        "#
-        .to_owned();
-        let variables = [
-            ("path".to_owned(), json!(path)),
-            ("graph".to_owned(), json!(encoded_graph)),
-            ("overwrite".to_owned(), json!(overwrite)),
-        ];
-
-        let data = self.query_with_json_variables(query, variables.into())?;
-
-        match data.get("sendGraph") {
-            Some(JsonValue::String(name)) => {
-                debug!("Sent graph '{name}' to the server");
-                Ok(())
-            }
-            _ => Err(PyException::new_err(format!(
-                "Error Sending Graph. Got response {:?}",
-                data
-            ))),
-        }
+        self.execute(move |client| async move {
+            client.send_graph(&path, graph, overwrite).await?;
+            debug!("Sent graph '{path}' to the server");
+            Ok(())
+        })
     }
 
     /// Upload graph file from a path `file_path` on the client
@@ -192,74 +145,9 @@ impl PyRaphtoryClient {
     ///    The `data` field from the graphQL response after executing the mutation.
     #[pyo3(signature = (path, file_path, overwrite = false))]
     fn upload_graph(&self, path: String, file_path: String, overwrite: bool) -> PyResult<()> {
-        let remote_client = self.clone();
-        execute_async_task(move || async move {
-            let client = Client::new();
-
-            let mut file =
-                File::open(Path::new(&file_path)).map_err(|err| adapt_err_value(&err))?;
-
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)
-                .map_err(|err| adapt_err_value(&err))?;
-
-            let variables = format!(
-                r#""path": "{}", "overwrite": {}, "graph": null"#,
-                path, overwrite
-            );
-
-            let operations = format!(
-                r#"{{
-            "query": "mutation UploadGraph($path: String!, $graph: Upload!, $overwrite: Boolean!) {{ uploadGraph(path: $path, graph: $graph, overwrite: $overwrite) }}",
-            "variables": {{ {} }}
-        }}"#,
-                variables
-            );
-
-            let form = multipart::Form::new()
-                .text("operations", operations)
-                .text("map", r#"{"0": ["variables.graph"]}"#)
-                .part("0", Part::bytes(buffer).file_name(file_path.clone()));
-
-            let response = client
-                .post(&remote_client.url)
-                .multipart(form)
-                .send()
-                .await
-                .map_err(|err| adapt_err_value(&err))?;
-
-            let status = response.status();
-            let text = response.text().await.map_err(|err| adapt_err_value(&err))?;
-
-            if !status.is_success() {
-                return Err(PyException::new_err(format!(
-                    "Error Uploading Graph. Status: {}. Response: {}",
-                    status, text
-                )));
-            }
-
-            let mut data: HashMap<String, JsonValue> =
-                serde_json::from_str(&text).map_err(|err| {
-                    PyException::new_err(format!(
-                        "Failed to parse JSON response: {}. Response text: {}",
-                        err, text
-                    ))
-                })?;
-
-            match data.remove("data") {
-                Some(JsonValue::Object(_)) => Ok(()),
-                _ => match data.remove("errors") {
-                    Some(JsonValue::Array(errors)) => Err(PyException::new_err(format!(
-                        "Error Uploading Graph. Got errors:\n\t{:#?}",
-                        errors
-                    ))),
-                    _ => Err(PyException::new_err(format!(
-                        "Error Uploading Graph. Unexpected response: {}",
-                        text
-                    ))),
-                },
-            }
-        })
+        self.execute(
+            move |client| async move { client.upload_graph(&path, file_path, overwrite).await },
+        )
     }
 
     /// Copy graph from a path `path` on the server to a `new_path` on the server
@@ -272,28 +160,7 @@ impl PyRaphtoryClient {
     ///    Copy status as boolean
     #[pyo3(signature = (path, new_path))]
     fn copy_graph(&self, path: String, new_path: String) -> PyResult<()> {
-        let query = r#"
-            mutation CopyGraph($path: String!, $newPath: String!) {
-              copyGraph(
-                path: $path,
-                newPath: $newPath,
-              )
-            }"#
-        .to_owned();
-
-        let variables = [
-            ("path".to_owned(), json!(path)),
-            ("newPath".to_owned(), json!(new_path)),
-        ];
-
-        let data = self.query_with_json_variables(query.clone(), variables.into())?;
-        match data.get("copyGraph") {
-            Some(JsonValue::Bool(res)) => Ok((*res).clone()),
-            _ => Err(PyException::new_err(format!(
-                "Error while reading server response for query:\n\t{query}\nGot data:\n\t'{data:?}'"
-            ))),
-        }?;
-        Ok(())
+        self.execute(move |client| async move { client.copy_graph(&path, &new_path).await })
     }
 
     /// Move graph from a path `path` on the server to a `new_path` on the server
@@ -306,28 +173,7 @@ impl PyRaphtoryClient {
     ///    Move status as boolean
     #[pyo3(signature = (path, new_path))]
     fn move_graph(&self, path: String, new_path: String) -> PyResult<()> {
-        let query = r#"
-            mutation MoveGraph($path: String!, $newPath: String!) {
-              moveGraph(
-                path: $path,
-                newPath: $newPath,
-              )
-            }"#
-        .to_owned();
-
-        let variables = [
-            ("path".to_owned(), json!(path)),
-            ("newPath".to_owned(), json!(new_path)),
-        ];
-
-        let data = self.query_with_json_variables(query.clone(), variables.into())?;
-        match data.get("moveGraph") {
-            Some(JsonValue::Bool(res)) => Ok((*res).clone()),
-            _ => Err(PyException::new_err(format!(
-                "Error while reading server response for query:\n\t{query}\nGot data:\n\t'{data:?}'"
-            ))),
-        }?;
-        Ok(())
+        self.execute(move |client| async move { client.move_graph(&path, &new_path).await })
     }
 
     /// Delete graph from a path `path` on the server
@@ -339,24 +185,7 @@ impl PyRaphtoryClient {
     ///    Delete status as boolean
     #[pyo3(signature = (path))]
     fn delete_graph(&self, path: String) -> PyResult<()> {
-        let query = r#"
-            mutation DeleteGraph($path: String!) {
-              deleteGraph(
-                path: $path,
-              )
-            }"#
-        .to_owned();
-
-        let variables = [("path".to_owned(), json!(path))];
-
-        let data = self.query_with_json_variables(query.clone(), variables.into())?;
-        match data.get("deleteGraph") {
-            Some(JsonValue::Bool(res)) => Ok((*res).clone()),
-            _ => Err(PyException::new_err(format!(
-                "Error while reading server response for query:\n\t{query}\nGot data:\n\t'{data:?}'"
-            ))),
-        }?;
-        Ok(())
+        self.execute(move |client| async move { client.delete_graph(&path).await })
     }
 
     /// Receive graph from a path `path` on the server
@@ -367,22 +196,7 @@ impl PyRaphtoryClient {
     /// Returns:
     ///    Graph as string
     fn receive_graph(&self, path: String) -> PyResult<MaterializedGraph> {
-        let query = r#"
-            query ReceiveGraph($path: String!) {
-                receiveGraph(path: $path)
-            }"#
-        .to_owned();
-        let variables = [("path".to_owned(), json!(path))];
-        let data = self.query_with_json_variables(query.clone(), variables.into())?;
-        match data.get("receiveGraph") {
-            Some(JsonValue::String(graph)) => {
-                let mat_graph = url_decode_graph(graph)?;
-                Ok(mat_graph)
-            }
-            _ => Err(PyException::new_err(format!(
-                "Error while reading server response for query:\n\t{query}\nGot data:\n\t'{data:?}'"
-            ))),
-        }
+        self.execute(move |client| async move { client.receive_graph(&path).await })
     }
 
     /// Create a new Graph on the server at `path`
@@ -395,26 +209,16 @@ impl PyRaphtoryClient {
     ///    None
     ///
     fn new_graph(&self, path: String, graph_type: String) -> PyResult<()> {
-        let query = r#"
-            mutation NewGraph($path: String!) {
-              newGraph(
-                path: $path,
-                graphType: EVENT
-              )
-            }"#
-        .to_owned();
-        let query = query.replace("EVENT", &*graph_type);
-
-        let variables = [("path".to_owned(), json!(path))];
-
-        let data = self.query_with_json_variables(query.clone(), variables.into())?;
-        match data.get("newGraph") {
-            Some(JsonValue::Bool(res)) => Ok((*res).clone()),
-            _ => Err(PyException::new_err(format!(
-                "Error while reading server response for query:\n\t{query}\nGot data:\n\t'{data:?}'"
-            ))),
-        }?;
-        Ok(())
+        let graph_type = match graph_type.as_str() {
+            "EVENT" => GqlGraphType::Event,
+            "PERSISTENT" => GqlGraphType::Persistent,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid graph type '{graph_type}', expected EVENT or PERSISTENT"
+                )))
+            }
+        };
+        self.execute(move |client| async move { client.new_graph(&path, graph_type).await })
     }
 
     /// Get a RemoteGraph reference to a graph on the server at `path`
@@ -420,4 +420,44 @@ impl PyRemoteGraph {
             dst.to_string(),
         ))
     }
+
+    /// Deletes a node in the remote graph, given the timestamp and the node id.
+    /// All edges incident to the node that are alive at the time of the deletion are deleted as well.
+    ///
+    /// Arguments:
+    ///   timestamp (int): The timestamp of the deletion.
+    ///   id (str|int): The id of the node.
+    ///
+    /// Returns:
+    ///   RemoteNode
+    pub fn delete_node(
+        &self,
+        py: Python,
+        timestamp: PyTime,
+        id: GID,
+    ) -> Result<PyRemoteNode, GraphError> {
+        let template = r#"
+        {
+            updateGraph(path: "{{ path }}") {
+                deleteNode(time: {{ time }}, name: "{{ name }}") {
+                    success
+                }
+            }
+        }
+        "#;
+
+        let query_context = context! {
+            path => self.path,
+            time => timestamp.into_time(),
+            name => id.to_string(),
+        };
+
+        let query = build_query(template, query_context)?;
+        let _ = &self.client.query(py, query, None)?;
+        Ok(PyRemoteNode::new(
+            self.path.clone(),
+            self.client.clone(),
+            id.to_string(),
+        ))
+    }
 }
@@ -1,4 +1,7 @@
-use crate::url_encode::{url_encode_graph, UrlDecodeError};
+use crate::{
+    client::ClientError,
+    url_encode::{url_encode_graph, UrlDecodeError},
+};
 use async_graphql::{dynamic::ValueAccessor, Value as GraphqlValue};
 use pyo3::{
     exceptions::{PyTypeError, PyValueError},
@@ -122,3 +125,9 @@ impl From<UrlDecodeError> for PyErr {
         adapt_err_value(&value)
     }
 }
+
+impl From<ClientError> for PyErr {
+    fn from(value: ClientError) -> Self {
+        adapt_err_value(&value)
+    }
+}
@@ -35,7 +35,7 @@ pub(crate) fn wait_server(running_server: &mut Option<ServerHandler>) -> PyResul
         .map_err(|e| adapt_err_value(&e))
 }
 
-pub(crate) fn is_online(url: &String) -> bool {
+pub(crate) fn is_online(url: &str) -> bool {
     reqwest::blocking::get(url)
         .map(|response| response.status().as_u16() == 200)
         .unwrap_or(false)
@@ -82,11 +82,24 @@ impl PyRunningGraphServer {
 
 #[pymethods]
 impl PyRunningGraphServer {
-    pub(crate) fn get_client(&self) -> PyResult<PyRaphtoryClient> {
+    /// Get a client for the server
+    ///
+    /// Arguments:
+    ///   api_key (str, optional): an API key configured on the server.
+    ///   token (str, optional): a JWT signed with the key configured on the server.
+    ///
+    /// Returns:
+    ///   RaphtoryClient: A client for the server
+    #[pyo3(signature = (api_key = None, token = None))]
+    pub(crate) fn get_client(
+        &self,
+        api_key: Option<String>,
+        token: Option<String>,
+    ) -> PyResult<PyRaphtoryClient> {
         self.apply_if_alive(|handler| {
             let port = handler.port;
             let url = format!("http://localhost:{port}");
-            Ok(PyRaphtoryClient::new(url)?)
+            Ok(PyRaphtoryClient::new(url, api_key, token)?)
         })
     }
 
@@ -1,5 +1,8 @@
 use crate::{
-    config::app_config::AppConfigBuilder,
+    config::{
+        app_config::AppConfigBuilder,
+        auth_config::{Access, NamespacePermissions, DEFAULT_JWT_ALGORITHM},
+    },
     model::{
         algorithms::document::GqlDocument,
         plugins::{entry_point::EntryPoint, query_plugin::QueryPlugin},
@@ -17,7 +20,7 @@ use async_graphql::dynamic::{Field, FieldFuture, FieldValue, InputValue, Object,
 use dynamic_graphql::internal::{Registry, TypeName};
 use itertools::intersperse;
 use pyo3::{
-    exceptions::{PyAttributeError, PyException},
+    exceptions::{PyAttributeError, PyException, PyValueError},
     pyclass, pymethods,
     types::{IntoPyDict, PyFunction, PyList},
     IntoPy, Py, PyObject, PyRefMut, PyResult, Python,
@@ -38,6 +41,16 @@ impl IntoPy<PyObject> for GraphServer {
     }
 }
 
+fn permissions_from_python(permissions: HashMap<String, String>) -> PyResult<NamespacePermissions> {
+    permissions
+        .into_iter()
+        .map(|(namespace, access)| {
+            let access = access.parse::<Access>().map_err(PyValueError::new_err)?;
+            Ok((namespace, access))
+        })
+        .collect()
+}
+
 fn template_from_python(
     graph_template: Option<String>,
     node_template: Option<String>,
@@ -150,7 +163,7 @@ impl PyGraphServer {
 impl PyGraphServer {
     #[new]
     #[pyo3(
-        signature = (work_dir, cache_capacity = None, cache_tti_seconds = None, log_level = None, tracing=None, otlp_agent_host=None, otlp_agent_port=None, otlp_tracing_service_name=None, config_path = None)
+        signature = (work_dir, cache_capacity = None, cache_tti_seconds = None, log_level = None, tracing=None, otlp_agent_host=None, otlp_agent_port=None, otlp_tracing_service_name=None, config_path = None, api_keys = None, jwt_key_path = None, jwt_algorithm = None, anonymous_permissions = None, max_query_depth = None, max_query_complexity = None, query_timeout_seconds = None, max_result_size = None)
     )]
     fn py_new(
         work_dir: PathBuf,
@@ -162,6 +175,14 @@ impl PyGraphServer {
         otlp_agent_port: Option<String>,
         otlp_tracing_service_name: Option<String>,
         config_path: Option<PathBuf>,
+        api_keys: Option<HashMap<String, HashMap<String, String>>>,
+        jwt_key_path: Option<PathBuf>,
+        jwt_algorithm: Option<String>,
+        anonymous_permissions: Option<HashMap<String, String>>,
+        max_query_depth: Option<usize>,
+        max_query_complexity: Option<usize>,
+        query_timeout_seconds: Option<u64>,
+        max_result_size: Option<usize>,
     ) -> PyResult<Self> {
         let mut app_config_builder = AppConfigBuilder::new();
         if let Some(log_level) = log_level {
@@ -186,6 +207,31 @@ impl PyGraphServer {
         if let Some(cache_tti_seconds) = cache_tti_seconds {
             app_config_builder = app_config_builder.with_cache_tti_seconds(cache_tti_seconds);
         }
+        for (key, permissions) in api_keys.into_iter().flatten() {
+            app_config_builder =
+                app_config_builder.with_api_key(key, permissions_from_python(permissions)?);
+        }
+        if let Some(jwt_key_path) = jwt_key_path {
+            let algorithm = jwt_algorithm.unwrap_or_else(|| DEFAULT_JWT_ALGORITHM.to_owned());
+            app_config_builder = app_config_builder.with_jwt(jwt_key_path, algorithm);
+        }
+        if let Some(anonymous_permissions) = anonymous_permissions {
+            app_config_builder = app_config_builder
+                .with_anonymous_permissions(permissions_from_python(anonymous_permissions)?);
+        }
+        if let Some(max_query_depth) = max_query_depth {
+            app_config_builder = app_config_builder.with_max_query_depth(max_query_depth);
+        }
+        if let Some(max_query_complexity) = max_query_complexity {
+            app_config_builder = app_config_builder.with_max_query_complexity(max_query_complexity);
+        }
+        if let Some(query_timeout_seconds) = query_timeout_seconds {
+            app_config_builder =
+                app_config_builder.with_query_timeout_seconds(query_timeout_seconds);
+        }
+        if let Some(max_result_size) = max_result_size {
+            app_config_builder = app_config_builder.with_max_result_size(max_result_size);
+        }
         let app_config = Some(app_config_builder.build());
 
         let server = GraphServer::new(work_dir, app_config, config_path)?;
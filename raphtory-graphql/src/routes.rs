@@ -1,11 +1,15 @@
+use crate::{
+    auth::Authenticator, config::auth_config::Access, data::Data, observability::metrics::METRICS,
+};
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
 use poem::{
     handler,
     http::StatusCode,
-    web::{Html, Json},
-    IntoResponse,
+    web::{Data as PoemData, Html, Json},
+    IntoResponse, Request, Response,
 };
 use serde::Serialize;
+use std::sync::Arc;
 
 #[derive(Serialize)]
 struct Health {
@@ -18,6 +22,28 @@ pub(crate) async fn health() -> impl IntoResponse {
     (StatusCode::OK, Json(health))
 }
 
+/// The metrics reveal the operations run on the server, so when authentication is enabled they
+/// are only served to callers with read access to the whole working directory
+#[handler]
+pub(crate) async fn metrics(
+    req: &Request,
+    data: PoemData<&Data>,
+    authenticator: PoemData<&Arc<Authenticator>>,
+) -> Response {
+    if let Err(err) = authenticator
+        .authenticate(req)
+        .and_then(|permissions| permissions.check("", Access::Read))
+    {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(err.to_string());
+    }
+    METRICS
+        .render(data.resident_graphs())
+        .with_content_type("text/plain; version=0.0.4")
+        .into_response()
+}
+
 #[handler]
 pub(crate) async fn ui() -> impl IntoResponse {
     Html(include_str!("../resources/index.html"))
@@ -1,17 +1,21 @@
 #![allow(dead_code)]
 
 use crate::{
+    auth::{AuthError, Authenticator, GraphQLEndpoint, GraphQLSubscriptionEndpoint},
     config::app_config::{load_config, AppConfig},
     data::{Data, EmbeddingConf},
+    limits::limit_schema,
     model::{
         plugins::{entry_point::EntryPoint, operation::Operation},
         App,
     },
-    observability::open_telemetry::OpenTelemetry,
-    routes::{health, ui},
+    observability::{
+        metrics::{MeteredEmbedding, MetricsExtension},
+        open_telemetry::OpenTelemetry,
+    },
+    routes::{health, metrics, ui},
     server::ServerError::SchemaError,
 };
-use async_graphql_poem::GraphQL;
 use config::ConfigError;
 use opentelemetry::trace::TracerProvider;
 use opentelemetry_sdk::trace::{Tracer, TracerProvider as TP};
@@ -68,6 +72,8 @@ pub enum ServerError {
     SchemaError(String),
     #[error("Failed to create endpoints: {0}")]
     EndpointError(String),
+    #[error("Failed to set up authentication: {0}")]
+    AuthError(#[from] AuthError),
 }
 
 impl From<ServerError> for io::Error {
@@ -112,7 +118,7 @@ impl GraphServer {
     ) -> Self {
         let cache = Some(PathBuf::from(cache).into()).into();
         self.data.embedding_conf = Some(EmbeddingConf {
-            function: Arc::new(embedding),
+            function: Arc::new(MeteredEmbedding(embedding)),
             cache,
             global_template,
             individual_templates: Default::default(),
@@ -232,9 +238,12 @@ impl GraphServer {
         self,
         tracer: Option<Tracer>,
     ) -> Result<CorsEndpoint<CookieJarManagerEndpoint<Route>>, ServerError> {
+        let authenticator = Arc::new(Authenticator::new(&self.config.auth)?);
         let schema_builder = App::create_schema();
-        let schema_builder = schema_builder.data(self.data);
-        let schema = schema_builder;
+        let schema_builder = schema_builder
+            .data(self.data.clone())
+            .extension(MetricsExtension);
+        let schema = limit_schema(schema_builder, &self.config.query);
         let schema = if let Some(t) = tracer {
             schema.extension(OpenTelemetry::new(t)).finish()
         } else {
@@ -243,11 +252,29 @@ impl GraphServer {
         .map_err(|e| SchemaError(e.to_string()))?;
 
         let app = Route::new()
-            .at("/", get(ui).post(GraphQL::new(schema)))
+            .at(
+                "/",
+                get(ui).post(GraphQLEndpoint::new(
+                    schema.clone(),
+                    authenticator.clone(),
+                    self.config.query.clone(),
+                )),
+            )
+            .at(
+                "/ws",
+                get(GraphQLSubscriptionEndpoint::new(
+                    schema,
+                    authenticator.clone(),
+                )),
+            )
             .at("/graph", get(ui))
             .at("/search", get(ui))
             .at("/playground", get(ui))
             .at("/health", get(health))
+            .at(
+                "/metrics",
+                get(metrics.data(self.data).data(authenticator.clone())),
+            )
             .with(CookieJarManager::new())
             .with(Cors::new());
         Ok(app)
@@ -334,7 +361,10 @@ mod server_tests {
 
     use std::path::Path;
 
-    use crate::server::GraphServer;
+    use crate::{
+        config::{app_config::AppConfigBuilder, auth_config::Access},
+        server::GraphServer,
+    };
     use chrono::prelude::*;
     use raphtory::{
         prelude::{AdditionOps, Graph, StableEncode, NO_PROPS},
@@ -386,4 +416,71 @@ mod server_tests {
         sleep(Duration::from_secs(5)).await;
         handler.await.unwrap().stop().await
     }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let graph = Graph::new();
+        graph.add_edge(0, "a", "b", NO_PROPS, None).unwrap();
+        graph.encode(tmp_dir.path().join("g")).unwrap();
+
+        let server = GraphServer::new(tmp_dir.path().to_path_buf(), None, None).unwrap();
+        let running = server.start_with_port(1752).await.unwrap();
+        let client = reqwest::Client::new();
+        let response = client
+            .post("http://localhost:1752")
+            .json(&serde_json::json!({
+                "query": r#"{ graph(path: "g") { countNodes nodes { list { name } } } }"#
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let metrics = client
+            .get("http://localhost:1752/metrics")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(metrics.contains("# TYPE raphtory_graphql_requests_total counter"));
+        assert!(metrics.contains(r#"raphtory_graphql_requests_total{operation="query"}"#));
+        assert!(metrics.contains(
+            r#"raphtory_graphql_resolver_duration_seconds_count{field="GqlGraph.countNodes"}"#
+        ));
+        // fields of list items are not timed
+        assert!(!metrics.contains(r#"field="GqlNode.name""#));
+        assert!(metrics.contains("raphtory_graphql_resident_graphs 1"));
+        assert!(metrics.contains("# TYPE raphtory_graphql_graph_write_duration_seconds histogram"));
+        running.stop().await
+    }
+
+    #[tokio::test]
+    async fn test_metrics_require_auth() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let config = AppConfigBuilder::new()
+            .with_api_key("reader".to_owned(), [("*".to_owned(), Access::Read)].into())
+            .with_api_key(
+                "team_a".to_owned(),
+                [("team_a".to_owned(), Access::Write)].into(),
+            )
+            .build();
+        let server = GraphServer::new(tmp_dir.path().to_path_buf(), Some(config), None).unwrap();
+        let running = server.start_with_port(1753).await.unwrap();
+        let client = reqwest::Client::new();
+        let status = |api_key: Option<&'static str>| {
+            let mut request = client.get("http://localhost:1753/metrics");
+            if let Some(api_key) = api_key {
+                request = request.header("X-API-Key", api_key);
+            }
+            async move { request.send().await.unwrap().status() }
+        };
+        assert_eq!(status(None).await, 401);
+        assert_eq!(status(Some("team_a")).await, 401);
+        assert_eq!(status(Some("wrong")).await, 401);
+        assert!(status(Some("reader")).await.is_success());
+        running.stop().await
+    }
 }
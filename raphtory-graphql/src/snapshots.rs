@@ -0,0 +1,106 @@
+use crate::paths::ExistingGraphFolder;
+use raphtory::core::utils::errors::GraphError;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, sync::Arc};
+
+/// Named checkpoints are stored next to the graph file so they are deleted along with the graph
+const SNAPSHOTS_FILE_NAME: &str = ".snapshots";
+
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotError {
+    #[error("Snapshot {0} already exists")]
+    AlreadyExists(String),
+    #[error("Snapshot {0} does not exist")]
+    NotFound(String),
+    #[error("Snapshots are not supported for disk graphs")]
+    DiskGraph,
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+    #[error(transparent)]
+    GraphLoad(#[from] Arc<GraphError>),
+    #[error("Failed to read or write snapshots: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid snapshots file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A named checkpoint of the incremental graph file, see
+/// [`CacheOps::checkpoint`](raphtory::serialise::CacheOps::checkpoint)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct Snapshot {
+    pub(crate) name: String,
+    pub(crate) checkpoint: u64,
+    /// Milliseconds since the epoch
+    pub(crate) created_at: i64,
+    pub(crate) count_nodes: usize,
+    pub(crate) count_edges: usize,
+}
+
+/// The snapshots of a graph ordered by creation
+pub(crate) struct Snapshots {
+    folder: ExistingGraphFolder,
+    snapshots: Vec<Snapshot>,
+}
+
+impl Snapshots {
+    pub(crate) fn read(folder: &ExistingGraphFolder) -> Result<Self, SnapshotError> {
+        if folder.get_graph_path().is_dir() {
+            return Err(SnapshotError::DiskGraph);
+        }
+        let snapshots = match fs::read(snapshots_path(folder)) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => vec![],
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Self {
+            folder: folder.clone(),
+            snapshots,
+        })
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Result<&Snapshot, SnapshotError> {
+        self.snapshots
+            .iter()
+            .find(|snapshot| snapshot.name == name)
+            .ok_or_else(|| SnapshotError::NotFound(name.to_owned()))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Snapshot> {
+        self.snapshots.iter()
+    }
+
+    pub(crate) fn insert(&mut self, snapshot: Snapshot) -> Result<(), SnapshotError> {
+        if self.get(&snapshot.name).is_ok() {
+            return Err(SnapshotError::AlreadyExists(snapshot.name));
+        }
+        self.snapshots.push(snapshot);
+        Ok(())
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> Result<Snapshot, SnapshotError> {
+        let index = self
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.name == name)
+            .ok_or_else(|| SnapshotError::NotFound(name.to_owned()))?;
+        Ok(self.snapshots.remove(index))
+    }
+
+    /// Drop the snapshots of updates discarded by restoring the graph to `checkpoint`
+    pub(crate) fn truncate(&mut self, checkpoint: u64) {
+        self.snapshots
+            .retain(|snapshot| snapshot.checkpoint <= checkpoint);
+    }
+
+    pub(crate) fn write(&self) -> Result<(), SnapshotError> {
+        let path = snapshots_path(&self.folder);
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&self.snapshots)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+fn snapshots_path(folder: &ExistingGraphFolder) -> std::path::PathBuf {
+    folder.get_base_path().join(SNAPSHOTS_FILE_NAME)
+}
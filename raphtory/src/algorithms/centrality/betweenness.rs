@@ -1,11 +1,226 @@
 use crate::{
-    algorithms::algorithm_result::AlgorithmResult,
-    core::entities::VID,
-    db::graph::node::NodeView,
-    prelude::{GraphViewOps, NodeViewOps},
+    algorithms::{
+        algorithm_result::AlgorithmResult,
+        centrality::edge_weights::{neighbour_weights, EdgeWeightError},
+    },
+    core::{entities::VID, Direction},
+    db::{
+        api::view::{GraphViewOps, StaticGraphViewOps},
+        task::{custom_pool, POOL},
+    },
+    prelude::*,
 };
 use ordered_float::OrderedFloat;
-use std::collections::{HashMap, VecDeque};
+use rayon::prelude::*;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+/// The out-edges of every node in compressed form, the edges of node `v` are
+/// `offsets[v]..offsets[v + 1]`
+struct Adjacency {
+    offsets: Vec<usize>,
+    sources: Vec<VID>,
+    targets: Vec<VID>,
+    weights: Vec<f64>,
+}
+
+impl Adjacency {
+    fn new<'graph, G: GraphViewOps<'graph>>(
+        g: &'graph G,
+        weight: Option<&str>,
+    ) -> Result<Self, EdgeWeightError> {
+        let neighbours = neighbour_weights(g, weight, Direction::OUT, f64::min)?;
+        let mut adjacency = Adjacency {
+            offsets: Vec::with_capacity(neighbours.len() + 1),
+            sources: vec![],
+            targets: vec![],
+            weights: vec![],
+        };
+        for (v, nbrs) in neighbours.into_iter().enumerate() {
+            adjacency.offsets.push(adjacency.targets.len());
+            for (nbr, w) in nbrs {
+                // self-loops are never on a shortest path
+                if nbr.0 != v {
+                    adjacency.sources.push(VID(v));
+                    adjacency.targets.push(nbr);
+                    adjacency.weights.push(w);
+                }
+            }
+        }
+        adjacency.offsets.push(adjacency.targets.len());
+        Ok(adjacency)
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn edges(&self, v: VID) -> std::ops::Range<usize> {
+        self.offsets[v.0]..self.offsets[v.0 + 1]
+    }
+}
+
+/// The shortest path state of a single source, reused by all the sources handled by a thread
+struct Scratch {
+    dist: Vec<f64>,
+    sigma: Vec<f64>,
+    delta: Vec<f64>,
+    settled: Vec<bool>,
+    /// the edges into each node on shortest paths from the source
+    preds: Vec<Vec<usize>>,
+    /// the nodes in order of non-decreasing distance from the source
+    stack: Vec<VID>,
+}
+
+impl Scratch {
+    fn new(n: usize) -> Self {
+        Self {
+            dist: vec![f64::INFINITY; n],
+            sigma: vec![0.0; n],
+            delta: vec![0.0; n],
+            settled: vec![false; n],
+            preds: vec![vec![]; n],
+            stack: vec![],
+        }
+    }
+
+    fn reset(&mut self) {
+        for v in self.stack.drain(..) {
+            self.dist[v.0] = f64::INFINITY;
+            self.sigma[v.0] = 0.0;
+            self.delta[v.0] = 0.0;
+            self.settled[v.0] = false;
+            self.preds[v.0].clear();
+        }
+    }
+
+    fn bfs(&mut self, adj: &Adjacency, source: VID) {
+        self.dist[source.0] = 0.0;
+        self.sigma[source.0] = 1.0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            self.stack.push(v);
+            for e in adj.edges(v) {
+                let w = adj.targets[e];
+                if self.dist[w.0].is_infinite() {
+                    self.dist[w.0] = self.dist[v.0] + 1.0;
+                    queue.push_back(w);
+                }
+                if self.dist[w.0] == self.dist[v.0] + 1.0 {
+                    self.sigma[w.0] += self.sigma[v.0];
+                    self.preds[w.0].push(e);
+                }
+            }
+        }
+    }
+
+    fn dijkstra(&mut self, adj: &Adjacency, source: VID) {
+        self.dist[source.0] = 0.0;
+        self.sigma[source.0] = 1.0;
+        let mut heap = BinaryHeap::from([Reverse((OrderedFloat(0.0), source))]);
+        while let Some(Reverse((OrderedFloat(d), v))) = heap.pop() {
+            if self.settled[v.0] || d > self.dist[v.0] {
+                continue;
+            }
+            self.settled[v.0] = true;
+            self.stack.push(v);
+            for e in adj.edges(v) {
+                let w = adj.targets[e];
+                if self.settled[w.0] {
+                    continue;
+                }
+                let next = d + adj.weights[e];
+                if next < self.dist[w.0] {
+                    self.dist[w.0] = next;
+                    self.sigma[w.0] = self.sigma[v.0];
+                    self.preds[w.0].clear();
+                    self.preds[w.0].push(e);
+                    heap.push(Reverse((OrderedFloat(next), w)));
+                } else if next == self.dist[w.0] {
+                    self.sigma[w.0] += self.sigma[v.0];
+                    self.preds[w.0].push(e);
+                }
+            }
+        }
+    }
+}
+
+/// The betweenness of the nodes and edges summed over the sources handled by a thread
+struct Accumulator {
+    nodes: Vec<f64>,
+    edges: Vec<f64>,
+}
+
+impl Accumulator {
+    fn merge(mut self, other: Self) -> Self {
+        for (l, r) in self.nodes.iter_mut().zip(other.nodes) {
+            *l += r;
+        }
+        for (l, r) in self.edges.iter_mut().zip(other.edges) {
+            *l += r;
+        }
+        self
+    }
+}
+
+/// Brandes' algorithm from each of the first `k` nodes in parallel, with Dijkstra's algorithm for
+/// the shortest paths if the edges are weighted
+fn brandes<'graph, G: GraphViewOps<'graph>>(
+    g: &'graph G,
+    weight: Option<&str>,
+    k: Option<usize>,
+    threads: Option<usize>,
+) -> Result<(Adjacency, Accumulator), EdgeWeightError> {
+    let adj = Adjacency::new(g, weight)?;
+    let n = adj.num_nodes();
+    let m = adj.targets.len();
+    let sources: Vec<VID> = g
+        .nodes()
+        .iter()
+        .map(|node| node.node)
+        .take(k.unwrap_or(usize::MAX))
+        .collect();
+
+    let pool = threads.map(custom_pool).unwrap_or_else(|| POOL.clone());
+    let empty = || Accumulator {
+        nodes: vec![0.0; n],
+        edges: vec![0.0; m],
+    };
+    let acc = pool.install(|| {
+        sources
+            .par_iter()
+            .fold(
+                || (Scratch::new(n), empty()),
+                |(mut scratch, mut acc), source| {
+                    if weight.is_some() {
+                        scratch.dijkstra(&adj, *source);
+                    } else {
+                        scratch.bfs(&adj, *source);
+                    }
+                    // accumulate the dependencies from the furthest nodes back to the source
+                    for &w in scratch.stack.iter().rev() {
+                        let coeff = (1.0 + scratch.delta[w.0]) / scratch.sigma[w.0];
+                        for &e in &scratch.preds[w.0] {
+                            let v = adj.sources[e];
+                            let c = scratch.sigma[v.0] * coeff;
+                            acc.edges[e] += c;
+                            scratch.delta[v.0] += c;
+                        }
+                        if w != *source {
+                            acc.nodes[w.0] += scratch.delta[w.0];
+                        }
+                    }
+                    scratch.reset();
+                    (scratch, acc)
+                },
+            )
+            .map(|(_, acc)| acc)
+            .reduce_with(Accumulator::merge)
+    });
+    Ok((adj, acc.unwrap_or_else(empty)))
+}
 
 /// Computes the betweenness centrality for nodes in a given graph.
 ///
@@ -23,99 +238,99 @@ pub fn betweenness_centrality<'graph, G: GraphViewOps<'graph>>(
     k: Option<usize>,
     normalized: bool,
 ) -> AlgorithmResult<G, f64, OrderedFloat<f64>> {
-    // Initialize a hashmap to store betweenness centrality values.
-    let mut betweenness: HashMap<usize, f64> = HashMap::new();
-
-    // Get the nodes and the total number of nodes in the graph.
-    let nodes = g.nodes();
-    let n = g.count_nodes();
-    let k_sample = k.unwrap_or(n);
-
-    // Main loop over each node to compute betweenness centrality.
-    for node in nodes.iter().take(k_sample) {
-        let mut stack = Vec::new();
-        let mut predecessors: HashMap<usize, Vec<usize>> = HashMap::new();
-        let mut sigma: HashMap<usize, f64> = HashMap::new();
-        let mut dist: HashMap<usize, i64> = HashMap::new();
-        let mut queue = VecDeque::new();
-
-        // Initialize distance and sigma values for each node.
-        for node in nodes.iter() {
-            dist.insert(node.node.0, -1);
-            sigma.insert(node.node.0, 0.0);
-        }
-        dist.insert(node.node.0, 0);
-        sigma.insert(node.node.0, 1.0);
-        queue.push_back(node.node.0);
-
-        // BFS loop to find shortest paths.
-        while let Some(current_node_id) = queue.pop_front() {
-            stack.push(current_node_id);
-            for neighbor in
-                NodeView::new_internal(g.clone(), VID::from(current_node_id)).out_neighbours()
-            {
-                // Path discovery
-                if dist[&neighbor.node.0] < 0 {
-                    queue.push_back(neighbor.node.0);
-                    dist.insert(neighbor.node.0, dist[&current_node_id] + 1);
-                }
-                // Path counting
-                if dist[&neighbor.node.0] == dist[&current_node_id] + 1 {
-                    sigma.insert(
-                        neighbor.node.0,
-                        sigma[&neighbor.node.0] + sigma[&current_node_id],
-                    );
-                    predecessors
-                        .entry(neighbor.node.0)
-                        .or_default()
-                        .push(current_node_id);
-                }
-            }
-        }
-
-        let mut delta: HashMap<usize, f64> = HashMap::new();
-        for node in nodes.iter() {
-            delta.insert(node.node.0, 0.0);
-        }
-
-        // Accumulation
-        while let Some(w) = stack.pop() {
-            for v in predecessors.get(&w).unwrap_or(&Vec::new()) {
-                let coeff = (sigma[v] / sigma[&w]) * (1.0 + delta[&w]);
-                let new_delta_v = delta[v] + coeff;
-                delta.insert(*v, new_delta_v);
-            }
-            if w != node.node.0 {
-                let updated_betweenness = betweenness.entry(w).or_insert(0.0);
-                *updated_betweenness += delta[&w];
-            }
-        }
-    }
+    weighted_betweenness_centrality(g, None, k, normalized, None)
+        .expect("edges without a weight property have length 1")
+}
 
-    // Normalization
-    if normalized {
-        let factor = 1.0 / ((n as f64 - 1.0) * (n as f64 - 2.0));
-        for node in nodes.iter() {
-            betweenness
-                .entry(node.node.0)
-                .and_modify(|v| *v *= factor)
-                .or_insert(0.0f64);
-        }
+/// Computes the betweenness centrality for nodes in a given graph with weighted edges.
+///
+/// The shortest paths from the sources are computed in parallel using Brandes' algorithm.
+///
+/// # Parameters
+///
+/// - `g`: A reference to the graph.
+/// - `weight`: The name of the edge property with the lengths of the edges. Edges have length 1 if not set or if they don't have the property.
+/// - `k`: An `Option<usize>` specifying the number of nodes to consider for the centrality computation. Defaults to all nodes if `None`.
+/// - `normalized`: If `true` normalize the centrality values.
+/// - `threads`: The number of threads to use, defaults to the global thread pool.
+///
+/// # Returns
+///
+/// Returns an `AlgorithmResult` containing the betweenness centrality of each node, or an error
+/// if a weight is not a non-negative number.
+pub fn weighted_betweenness_centrality<'graph, G: GraphViewOps<'graph>>(
+    g: &'graph G,
+    weight: Option<&str>,
+    k: Option<usize>,
+    normalized: bool,
+    threads: Option<usize>,
+) -> Result<AlgorithmResult<G, f64, OrderedFloat<f64>>, EdgeWeightError> {
+    let (_, acc) = brandes(g, weight, k, threads)?;
+    let n = g.count_nodes() as f64;
+    let factor = if normalized && n > 2.0 {
+        1.0 / ((n - 1.0) * (n - 2.0))
     } else {
-        for node in nodes.iter() {
-            betweenness.entry(node.node.0).or_insert(0.0f64);
-        }
-    }
+        1.0
+    };
+    let betweenness: HashMap<usize, f64> = g
+        .nodes()
+        .iter()
+        .map(|node| (node.node.0, acc.nodes[node.node.0] * factor))
+        .collect();
 
     // Construct and return the AlgorithmResult
     let results_type = std::any::type_name::<f64>();
-    AlgorithmResult::new(g.clone(), "Betweenness", results_type, betweenness)
+    Ok(AlgorithmResult::new(
+        g.clone(),
+        "Betweenness",
+        results_type,
+        betweenness,
+    ))
+}
+
+/// Computes the betweenness centrality for edges in a given graph, the number of shortest paths
+/// passing through each edge.
+///
+/// # Parameters
+///
+/// - `g`: A reference to the graph.
+/// - `weight`: The name of the edge property with the lengths of the edges. Edges have length 1 if not set or if they don't have the property.
+/// - `k`: An `Option<usize>` specifying the number of nodes to consider for the centrality computation. Defaults to all nodes if `None`.
+/// - `normalized`: If `true` normalize the centrality values.
+/// - `threads`: The number of threads to use, defaults to the global thread pool.
+///
+/// # Returns
+///
+/// Returns a `HashMap` from the names of the source and destination of each edge to its
+/// betweenness centrality, or an error if a weight is not a non-negative number.
+pub fn edge_betweenness_centrality<G: StaticGraphViewOps>(
+    g: &G,
+    weight: Option<&str>,
+    k: Option<usize>,
+    normalized: bool,
+    threads: Option<usize>,
+) -> Result<HashMap<(String, String), f64>, EdgeWeightError> {
+    let (adj, acc) = brandes(g, weight, k, threads)?;
+    let n = g.count_nodes() as f64;
+    let factor = if normalized && n > 1.0 {
+        1.0 / (n * (n - 1.0))
+    } else {
+        1.0
+    };
+    let name = |vid: VID| g.node(vid).map(|node| node.name()).unwrap_or_default();
+    Ok(adj
+        .sources
+        .iter()
+        .zip(&adj.targets)
+        .zip(acc.edges)
+        .map(|((src, dst), betweenness)| ((name(*src), name(*dst)), betweenness * factor))
+        .collect())
 }
 
 #[cfg(test)]
 mod betweenness_centrality_test {
     use super::*;
-    use crate::{prelude::*, test_storage};
+    use crate::test_storage;
 
     #[test]
     fn test_betweenness_centrality() {
@@ -159,6 +374,52 @@ mod betweenness_centrality_test {
             expected.insert("6".to_string(), 0.0);
             let res = betweenness_centrality(graph, None, true);
             assert_eq!(res.get_all_with_names(), expected);
+
+            let res = weighted_betweenness_centrality(graph, None, None, true, Some(2)).unwrap();
+            assert_eq!(res.get_all_with_names(), expected);
+        });
+    }
+
+    #[test]
+    fn test_weighted_and_edge_betweenness_centrality() {
+        let graph = Graph::new();
+        for (src, dst, length) in [
+            (1, 2, 1.0),
+            (1, 3, 4.0),
+            (2, 3, 2.0),
+            (3, 4, 1.0),
+            (4, 1, 3.0),
+            (5, 4, 1.0),
+        ] {
+            graph
+                .add_edge(0, src, dst, [("length", length)], None)
+                .unwrap();
+        }
+
+        test_storage!(&graph, |graph| {
+            // expected values computed with networkx
+            let res =
+                weighted_betweenness_centrality(graph, Some("length"), None, false, None).unwrap();
+            let expected: HashMap<String, f64> =
+                [("1", 5.0), ("2", 4.0), ("3", 3.0), ("4", 6.0), ("5", 0.0)]
+                    .into_iter()
+                    .map(|(node, value)| (node.to_string(), value))
+                    .collect();
+            assert_eq!(res.get_all_with_names(), expected);
+
+            let res = edge_betweenness_centrality(graph, None, None, false, None).unwrap();
+            let expected: HashMap<(String, String), f64> = [
+                (("1", "2"), 4.0),
+                (("1", "3"), 4.0),
+                (("2", "3"), 3.0),
+                (("3", "4"), 6.0),
+                (("4", "1"), 9.0),
+                (("5", "4"), 4.0),
+            ]
+            .into_iter()
+            .map(|((src, dst), value)| ((src.to_string(), dst.to_string()), value))
+            .collect();
+            assert_eq!(res, expected);
         });
     }
 }
@@ -0,0 +1,313 @@
+//! # Closeness and Harmonic Centrality
+//!
+//! Both measures are computed from the shortest path distances to each node from every other
+//! node, i.e., along the incoming paths of a directed graph. Edges have length 1 unless a weight
+//! property is given, and unreachable nodes are ignored.
+//!
+//! On large graphs the distances can be estimated from a random sample of source nodes, the sums
+//! over the sampled sources are scaled up to the size of the graph.
+use crate::{
+    algorithms::{
+        algorithm_result::AlgorithmResult,
+        centrality::edge_weights::{neighbour_weights, EdgeWeightError},
+    },
+    core::{entities::VID, Direction},
+    db::api::view::StaticGraphViewOps,
+};
+use ordered_float::OrderedFloat;
+use rand::{prelude::StdRng, seq::SliceRandom, SeedableRng};
+use rayon::prelude::*;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+#[derive(Clone, Debug, Default)]
+struct Reach {
+    reached: f64,
+    distance: f64,
+    harmonic: f64,
+}
+
+/// The shortest path distances from `source` to the nodes it reaches, excluding itself
+fn distances(neighbours: &[Vec<(VID, f64)>], source: VID, weighted: bool) -> Vec<(VID, f64)> {
+    let mut dist = vec![f64::INFINITY; neighbours.len()];
+    let mut reached = vec![];
+    dist[source.0] = 0.0;
+    if weighted {
+        let mut heap = BinaryHeap::from([Reverse((OrderedFloat(0.0), source))]);
+        while let Some(Reverse((OrderedFloat(d), node))) = heap.pop() {
+            if d > dist[node.0] {
+                continue;
+            }
+            if node != source {
+                reached.push((node, d));
+            }
+            for (nbr, w) in &neighbours[node.0] {
+                let next = d + w;
+                if next < dist[nbr.0] {
+                    dist[nbr.0] = next;
+                    heap.push(Reverse((OrderedFloat(next), *nbr)));
+                }
+            }
+        }
+    } else {
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            let d = dist[node.0];
+            for (nbr, _) in &neighbours[node.0] {
+                if dist[nbr.0].is_infinite() {
+                    dist[nbr.0] = d + 1.0;
+                    reached.push((*nbr, d + 1.0));
+                    queue.push_back(*nbr);
+                }
+            }
+        }
+    }
+    reached
+}
+
+/// Accumulate the distances to every node from all the nodes, or from `sample` random nodes,
+/// scaled up to the number of nodes of the graph
+fn reach<G: StaticGraphViewOps>(
+    g: &G,
+    weight: Option<&str>,
+    directed: bool,
+    sample: Option<usize>,
+    rng_seed: Option<u64>,
+) -> Result<Vec<Reach>, EdgeWeightError> {
+    let dir = if directed {
+        Direction::OUT
+    } else {
+        Direction::BOTH
+    };
+    let neighbours = neighbour_weights(g, weight, dir, f64::min)?;
+    let mut sources: Vec<VID> = g.nodes().iter().map(|node| node.node).collect();
+    let n = sources.len();
+    if let Some(k) = sample.filter(|k| *k < n) {
+        let mut rng = match rng_seed {
+            None => StdRng::from_entropy(),
+            Some(seed) => StdRng::seed_from_u64(seed),
+        };
+        sources.shuffle(&mut rng);
+        sources.truncate(k);
+    }
+    let scale = n as f64 / sources.len().max(1) as f64;
+
+    let mut reach = sources
+        .par_iter()
+        .fold(
+            || vec![Reach::default(); neighbours.len()],
+            |mut reach, source| {
+                for (node, d) in distances(&neighbours, *source, weight.is_some()) {
+                    let node_reach = &mut reach[node.0];
+                    node_reach.reached += 1.0;
+                    node_reach.distance += d;
+                    if d > 0.0 {
+                        node_reach.harmonic += 1.0 / d;
+                    }
+                }
+                reach
+            },
+        )
+        .reduce_with(|mut left, right| {
+            for (l, r) in left.iter_mut().zip(right) {
+                l.reached += r.reached;
+                l.distance += r.distance;
+                l.harmonic += r.harmonic;
+            }
+            left
+        })
+        .unwrap_or_default();
+    for node_reach in reach.iter_mut() {
+        node_reach.reached *= scale;
+        node_reach.distance *= scale;
+        node_reach.harmonic *= scale;
+    }
+    Ok(reach)
+}
+
+/// Computes the closeness centrality of the nodes of a graph.
+///
+/// The closeness of a node `u` reached from `r` other nodes at a total distance `d` is
+/// `(r / d) * (r / (n - 1))`, the inverse of the average distance scaled by the fraction of the
+/// graph reaching `u` (Wasserman and Faust). Nodes that are not reached have a closeness of 0.
+///
+/// # Arguments
+///
+/// - `g`: A reference to the graph.
+/// - `weight`: The name of the edge property with the lengths of the edges. Edges have length 1 if not set or if they don't have the property.
+/// - `directed`: If `false` the direction of the edges is ignored.
+/// - `sample`: Estimate the distances from this number of randomly sampled source nodes instead of all of them.
+/// - `rng_seed`: The seed of the sampling of the source nodes.
+///
+/// # Returns
+///
+/// Returns an `AlgorithmResult` containing the closeness centrality of each node, or an error
+/// if a weight is not a non-negative number.
+pub fn closeness_centrality<G: StaticGraphViewOps>(
+    g: &G,
+    weight: Option<&str>,
+    directed: bool,
+    sample: Option<usize>,
+    rng_seed: Option<u64>,
+) -> Result<AlgorithmResult<G, f64, OrderedFloat<f64>>, EdgeWeightError> {
+    let reach = reach(g, weight, directed, sample, rng_seed)?;
+    let n = g.count_nodes() as f64;
+    let closeness: HashMap<usize, f64> = g
+        .nodes()
+        .iter()
+        .map(|node| {
+            let Reach {
+                reached, distance, ..
+            } = reach[node.node.0];
+            let closeness = if distance > 0.0 {
+                (reached / distance) * (reached / (n - 1.0))
+            } else {
+                0.0
+            };
+            (node.node.0, closeness)
+        })
+        .collect();
+    let results_type = std::any::type_name::<f64>();
+    Ok(AlgorithmResult::new(
+        g.clone(),
+        "Closeness",
+        results_type,
+        closeness,
+    ))
+}
+
+/// Computes the harmonic centrality of the nodes of a graph, the sum of the inverse distances to
+/// a node from all other nodes.
+///
+/// # Arguments
+///
+/// - `g`: A reference to the graph.
+/// - `weight`: The name of the edge property with the lengths of the edges. Edges have length 1 if not set or if they don't have the property.
+/// - `directed`: If `false` the direction of the edges is ignored.
+/// - `sample`: Estimate the distances from this number of randomly sampled source nodes instead of all of them.
+/// - `rng_seed`: The seed of the sampling of the source nodes.
+///
+/// # Returns
+///
+/// Returns an `AlgorithmResult` containing the harmonic centrality of each node, or an error
+/// if a weight is not a non-negative number.
+pub fn harmonic_centrality<G: StaticGraphViewOps>(
+    g: &G,
+    weight: Option<&str>,
+    directed: bool,
+    sample: Option<usize>,
+    rng_seed: Option<u64>,
+) -> Result<AlgorithmResult<G, f64, OrderedFloat<f64>>, EdgeWeightError> {
+    let reach = reach(g, weight, directed, sample, rng_seed)?;
+    let harmonic: HashMap<usize, f64> = g
+        .nodes()
+        .iter()
+        .map(|node| (node.node.0, reach[node.node.0].harmonic))
+        .collect();
+    let results_type = std::any::type_name::<f64>();
+    Ok(AlgorithmResult::new(
+        g.clone(),
+        "Harmonic",
+        results_type,
+        harmonic,
+    ))
+}
+
+#[cfg(test)]
+mod closeness_centrality_test {
+    use super::*;
+    use crate::{
+        algorithms::centrality::{
+            edge_weights::edge_weights_test::weighted_test_graph,
+            pagerank::page_rank_tests::assert_eq_f64,
+        },
+        prelude::*,
+        test_storage,
+    };
+
+    #[test]
+    fn test_closeness_centrality() {
+        let graph = weighted_test_graph("length");
+        test_storage!(&graph, |graph| {
+            let expected = [
+                ("1", 0.5),
+                ("2", 0.444444),
+                ("3", 0.571429),
+                ("4", 0.666667),
+                ("5", 0.0),
+            ];
+            let result = closeness_centrality(graph, None, true, None, None).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 4);
+            }
+
+            let expected = [
+                ("1", 0.235294),
+                ("2", 0.266667),
+                ("3", 0.222222),
+                ("4", 0.444444),
+                ("5", 0.0),
+            ];
+            let result = closeness_centrality(graph, Some("length"), true, None, None).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 4);
+            }
+
+            let expected = [
+                ("1", 0.8),
+                ("2", 0.571429),
+                ("3", 0.8),
+                ("4", 0.8),
+                ("5", 0.5),
+            ];
+            let result = closeness_centrality(graph, None, false, None, None).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 4);
+            }
+        });
+    }
+
+    #[test]
+    fn test_harmonic_centrality() {
+        let graph = weighted_test_graph("length");
+        test_storage!(&graph, |graph| {
+            let expected = [
+                ("1", 2.333333),
+                ("2", 2.166667),
+                ("3", 2.833333),
+                ("4", 3.0),
+                ("5", 0.0),
+            ];
+            let result = harmonic_centrality(graph, None, true, None, None).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 4);
+            }
+
+            // sampling all the nodes is exact
+            let sampled = harmonic_centrality(graph, None, true, Some(5), Some(42)).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(sampled.get(node), Some(&value), 4);
+            }
+            // a sample is scaled up to the size of the graph, every node has an out-neighbour
+            let sampled = harmonic_centrality(graph, None, true, Some(1), Some(42)).unwrap();
+            let max = sampled.get_all_values().into_iter().fold(0.0, f64::max);
+            assert_eq!(max, 5.0);
+        });
+    }
+
+    #[test]
+    fn test_invalid_weight() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, [("length", -1.0)], None).unwrap();
+        assert_eq!(
+            closeness_centrality(&graph, Some("length"), true, None, None).map(|_| ()),
+            Err(EdgeWeightError::InvalidWeight {
+                value: "-1".to_string(),
+                src: "1".to_string(),
+                dst: "2".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Weighted adjacency lists shared by the centrality algorithms.
+use crate::{
+    core::{entities::VID, Direction},
+    db::graph::edge::EdgeView,
+    prelude::*,
+};
+use std::collections::BTreeMap;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum EdgeWeightError {
+    #[error("Weight {value} of edge {src} -> {dst} is not a non-negative number")]
+    InvalidWeight {
+        value: String,
+        src: String,
+        dst: String,
+    },
+}
+
+/// The latest value of the weight property of the edge, edges without the property have weight 1
+fn edge_weight<'graph, G: GraphViewOps<'graph>>(
+    edge: &EdgeView<G, G>,
+    weight: Option<&str>,
+) -> Result<f64, EdgeWeightError> {
+    match weight.and_then(|name| edge.properties().get(name)) {
+        None => Ok(1.0),
+        Some(value) => match value.as_f64() {
+            Some(weight) if weight >= 0.0 && weight.is_finite() => Ok(weight),
+            _ => Err(EdgeWeightError::InvalidWeight {
+                value: value.to_string(),
+                src: edge.src().name(),
+                dst: edge.dst().name(),
+            }),
+        },
+    }
+}
+
+/// The neighbours of every node in `dir` with the weights of the edges, indexed by node id.
+///
+/// With `Direction::BOTH` a pair of nodes with an edge in each direction is a single neighbour,
+/// the weights of the two edges are combined with `combine`.
+pub(crate) fn neighbour_weights<'graph, G: GraphViewOps<'graph>>(
+    g: &'graph G,
+    weight: Option<&str>,
+    dir: Direction,
+    combine: fn(f64, f64) -> f64,
+) -> Result<Vec<Vec<(VID, f64)>>, EdgeWeightError> {
+    let mut neighbours = vec![vec![]; g.unfiltered_num_nodes()];
+    for node in g.nodes() {
+        let edges = match dir {
+            Direction::OUT => node.out_edges(),
+            Direction::IN => node.in_edges(),
+            Direction::BOTH => node.edges(),
+        };
+        let mut weights = BTreeMap::new();
+        for edge in edges {
+            let w = edge_weight(&edge, weight)?;
+            weights
+                .entry(edge.nbr().node)
+                .and_modify(|current| *current = combine(*current, w))
+                .or_insert(w);
+        }
+        neighbours[node.node.0] = weights.into_iter().collect();
+    }
+    Ok(neighbours)
+}
+
+#[cfg(test)]
+pub(crate) mod edge_weights_test {
+    use crate::prelude::*;
+
+    /// A small directed graph with the weights in `property`, shared by the tests of the
+    /// centrality algorithms (the expected values in those tests are computed with networkx)
+    pub(crate) fn weighted_test_graph(property: &str) -> Graph {
+        let graph = Graph::new();
+        for (src, dst, weight) in [
+            (1, 2, 1.0),
+            (1, 3, 4.0),
+            (2, 3, 2.0),
+            (3, 4, 1.0),
+            (4, 1, 3.0),
+            (5, 4, 1.0),
+        ] {
+            graph
+                .add_edge(0, src, dst, [(property, weight)], None)
+                .unwrap();
+        }
+        graph
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    algorithms::{
+        algorithm_result::AlgorithmResult,
+        centrality::edge_weights::{neighbour_weights, EdgeWeightError},
+    },
+    core::{
+        entities::VID,
+        state::{accumulator_id::accumulators, compute_state::ComputeStateVec},
+        Direction,
+    },
+    db::{
+        api::view::StaticGraphViewOps,
+        task::{
+            context::Context,
+            node::eval_node::EvalNodeView,
+            task::{ATask, Job, Step},
+            task_runner::TaskRunner,
+        },
+    },
+};
+use num_traits::abs;
+use ordered_float::OrderedFloat;
+use std::collections::HashMap;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum PowerIterationError {
+    #[error(transparent)]
+    InvalidWeight(#[from] EdgeWeightError),
+    #[error(
+        "Power iteration failed to converge to tolerance {tol} within {iterations} iterations"
+    )]
+    NotConverged { iterations: usize, tol: f64 },
+    #[error("Power iteration diverged, the scores are not finite")]
+    Diverged,
+}
+
+/// Check the scores of the last iteration of a power iteration
+///
+/// `diff` is the sum of the absolute differences of the scores to the previous iteration.
+pub(crate) fn check_convergence<'a>(
+    scores: impl IntoIterator<Item = &'a f64>,
+    diff: f64,
+    n: usize,
+    iterations: usize,
+    tol: f64,
+) -> Result<(), PowerIterationError> {
+    if !diff.is_finite() || scores.into_iter().any(|score| !score.is_finite()) {
+        Err(PowerIterationError::Diverged)
+    } else if diff > tol * n as f64 {
+        Err(PowerIterationError::NotConverged { iterations, tol })
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct EigenvectorState {
+    score: f64,
+    diff: f64,
+}
+
+/// Eigenvector Centrality Algorithm:
+/// The score of a node is proportional to the sum of the scores of the nodes pointing at it,
+/// computed by power iteration on the adjacency matrix (shifted by the identity so that it also
+/// converges on bipartite graphs). The scores are normalised to unit L2 norm.
+///
+/// Arguments:
+///
+/// * `g`: A GraphView object
+/// * `weight`: The name of the edge property with the weights, edges have weight 1 if not set or if they don't have the property
+/// * `directed`: If false the direction of the edges is ignored, and the weights of the edges in each direction between two nodes are summed
+/// * `iter_count`: Number of iterations to run the algorithm for
+/// * `threads`: Number of threads to use for parallel execution
+/// * `tol`: The tolerance value for convergence
+///
+/// Result:
+///
+/// * An AlgorithmResult object containing the mapping from node ID to the eigenvector centrality of the node
+/// * An error if the scores have not converged after `iter_count` iterations
+///
+pub fn eigenvector_centrality<G: StaticGraphViewOps>(
+    g: &G,
+    weight: Option<&str>,
+    directed: bool,
+    iter_count: Option<usize>,
+    threads: Option<usize>,
+    tol: Option<f64>,
+) -> Result<AlgorithmResult<G, f64, OrderedFloat<f64>>, PowerIterationError> {
+    let n = g.count_nodes();
+    let dir = if directed {
+        Direction::IN
+    } else {
+        Direction::BOTH
+    };
+    let in_weights = neighbour_weights(g, weight, dir, |a, b| a + b)?;
+
+    let mut ctx: Context<G, ComputeStateVec> = g.into();
+
+    let tol: f64 = tol.unwrap_or(0.000001f64);
+    let iter_count = iter_count.unwrap_or(100);
+
+    let squared_norm = accumulators::sum::<f64>(0);
+    let total_diff = accumulators::sum::<f64>(1);
+    ctx.global_agg_reset(squared_norm);
+    ctx.global_agg_reset(total_diff);
+
+    let step1 = ATask::new(move |s: &mut EvalNodeView<G, EigenvectorState>| {
+        let graph = s.graph();
+        let received: f64 = in_weights[s.node.0]
+            .iter()
+            .filter_map(|(src, weight)| Some(graph.node(*src)?.prev().score * weight))
+            .sum();
+        let score = s.prev().score + received;
+        s.get_mut().score = score;
+        s.global_update(&squared_norm, score * score);
+        Step::Continue
+    });
+
+    let step2 = ATask::new(move |s: &mut EvalNodeView<G, EigenvectorState>| {
+        let norm = s
+            .read_global_state(&squared_norm)
+            .unwrap_or_default()
+            .sqrt();
+        if norm > 0.0 {
+            s.get_mut().score /= norm;
+        }
+        let diff = abs(s.get().score - s.prev().score);
+        s.get_mut().diff = diff;
+        s.global_update(&total_diff, diff);
+        Step::Continue
+    });
+
+    let step3 = Job::Check(Box::new(move |state| {
+        let diff = state.read(&total_diff);
+        if diff.is_finite() && diff > tol * n as f64 {
+            Step::Continue
+        } else {
+            Step::Done
+        }
+    }));
+
+    let mut runner: TaskRunner<G, _> = TaskRunner::new(ctx);
+
+    let initial = EigenvectorState {
+        score: 1f64 / n as f64,
+        diff: 0.0,
+    };
+    let (out, diff): (HashMap<usize, f64>, f64) = runner.run(
+        vec![],
+        vec![Job::new(step1), Job::new(step2), step3],
+        Some(vec![initial; g.unfiltered_num_nodes()]),
+        |_, _, _, local| {
+            let nodes = g.nodes();
+            let diff = nodes.iter().map(|node| local[node.node.0].diff).sum();
+            let out = nodes
+                .iter()
+                .map(|node| {
+                    let VID(i) = node.node;
+                    (i, local[i].score)
+                })
+                .collect();
+            (out, diff)
+        },
+        threads,
+        iter_count,
+        None,
+        None,
+    );
+
+    check_convergence(out.values(), diff, n, iter_count, tol)?;
+
+    let results_type = std::any::type_name::<f64>();
+    Ok(AlgorithmResult::new(
+        g.clone(),
+        "Eigenvector",
+        results_type,
+        out,
+    ))
+}
+
+#[cfg(test)]
+mod eigenvector_centrality_test {
+    use super::*;
+    use crate::{
+        algorithms::centrality::{
+            edge_weights::edge_weights_test::weighted_test_graph,
+            pagerank::page_rank_tests::assert_eq_f64,
+        },
+        test_storage,
+    };
+
+    #[test]
+    fn test_eigenvector_centrality() {
+        let graph = weighted_test_graph("weight");
+
+        test_storage!(&graph, |graph| {
+            let expected = [
+                ("1", 0.537077),
+                ("2", 0.406693),
+                ("3", 0.537077),
+                ("4", 0.474750),
+                ("5", 0.179750),
+            ];
+            let result =
+                eigenvector_centrality(graph, None, false, Some(1000), None, Some(1e-9)).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 3);
+            }
+
+            let expected = [
+                ("1", 0.632535),
+                ("2", 0.293387),
+                ("3", 0.579029),
+                ("4", 0.416990),
+                ("5", 0.068323),
+            ];
+            let result =
+                eigenvector_centrality(graph, Some("weight"), false, Some(1000), None, Some(1e-9))
+                    .unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 3);
+            }
+
+            // nothing points at 5 and its score vanishes
+            let expected = [
+                ("1", 0.431049),
+                ("2", 0.353105),
+                ("3", 0.642359),
+                ("4", 0.526202),
+                ("5", 0.0),
+            ];
+            let result =
+                eigenvector_centrality(graph, None, true, Some(1000), None, Some(1e-9)).unwrap();
+            for (node, value) in expected {
+                assert_eq_f64(result.get(node), Some(&value), 3);
+            }
+        });
+    }
+
+    #[test]
+    fn test_eigenvector_centrality_not_converged() {
+        let graph = weighted_test_graph("weight");
+        assert_eq!(
+            eigenvector_centrality(&graph, None, false, Some(3), None, Some(1e-9)).map(|_| ()),
+            Err(PowerIterationError::NotConverged {
+                iterations: 3,
+                tol: 1e-9
+            })
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    algorithms::{
+        algorithm_result::AlgorithmResult,
+        centrality::{
+            edge_weights::neighbour_weights,
+            eigenvector::{check_convergence, PowerIterationError},
+        },
+    },
+    core::{
+        entities::VID,
+        state::{accumulator_id::accumulators, compute_state::ComputeStateVec},
+        Direction,
+    },
+    db::{
+        api::view::StaticGraphViewOps,
+        task::{
+            context::Context,
+            node::eval_node::EvalNodeView,
+            task::{ATask, Job, Step},
+            task_runner::TaskRunner,
+        },
+    },
+};
+use num_traits::abs;
+use ordered_float::OrderedFloat;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default)]
+struct KatzState {
+    score: f64,
+    diff: f64,
+}
+
+/// Katz Centrality Algorithm:
+/// The score of a node is `alpha` times the sum of the scores of the nodes pointing at it plus
+/// `beta`, i.e., the number of walks ending at the node attenuated by `alpha` per step. The
+/// iteration only converges if `alpha` is smaller than the inverse of the largest eigenvalue of
+/// the adjacency matrix.
+///
+/// Arguments:
+///
+/// * `g`: A GraphView object
+/// * `weight`: The name of the edge property with the weights, edges have weight 1 if not set or if they don't have the property
+/// * `directed`: If false the direction of the edges is ignored, and the weights of the edges in each direction between two nodes are summed
+/// * `alpha`: The attenuation factor, defaults to 0.1
+/// * `beta`: The score every node receives regardless of its neighbours, defaults to 1
+/// * `normalized`: If true the scores are normalised to unit L2 norm
+/// * `iter_count`: Number of iterations to run the algorithm for
+/// * `threads`: Number of threads to use for parallel execution
+/// * `tol`: The tolerance value for convergence
+///
+/// Result:
+///
+/// * An AlgorithmResult object containing the mapping from node ID to the Katz centrality of the node
+/// * An error if the scores have not converged after `iter_count` iterations, e.g., because `alpha` is too large
+///
+#[allow(clippy::too_many_arguments)]
+pub fn katz_centrality<G: StaticGraphViewOps>(
+    g: &G,
+    weight: Option<&str>,
+    directed: bool,
+    alpha: Option<f64>,
+    beta: Option<f64>,
+    normalized: bool,
+    iter_count: Option<usize>,
+    threads: Option<usize>,
+    tol: Option<f64>,
+) -> Result<AlgorithmResult<G, f64, OrderedFloat<f64>>, PowerIterationError> {
+    let n = g.count_nodes();
+    let dir = if directed {
+        Direction::IN
+    } else {
+        Direction::BOTH
+    };
+    let in_weights = neighbour_weights(g, weight, dir, |a, b| a + b)?;
+
+    let mut ctx: Context<G, ComputeStateVec> = g.into();
+
+    let alpha = alpha.unwrap_or(0.1);
+    let beta = beta.unwrap_or(1.0);
+    let tol: f64 = tol.unwrap_or(0.000001f64);
+    let iter_count = iter_count.unwrap_or(1000);
+
+    let total_diff = accumulators::sum::<f64>(0);
+    ctx.global_agg_reset(total_diff);
+
+    let step1 = ATask::new(move |s: &mut EvalNodeView<G, KatzState>| {
+        let graph = s.graph();
+        let received: f64 = in_weights[s.node.0]
+            .iter()
+            .filter_map(|(src, weight)| Some(graph.node(*src)?.prev().score * weight))
+            .sum();
+        let score = alpha * received + beta;
+        let diff = abs(score - s.prev().score);
+        *s.get_mut() = KatzState { score, diff };
+        s.global_update(&total_diff, diff);
+        Step::Continue
+    });
+
+    let step2 = Job::Check(Box::new(move |state| {
+        let diff = state.read(&total_diff);
+        if diff.is_finite() && diff > tol * n as f64 {
+            Step::Continue
+        } else {
+            Step::Done
+        }
+    }));
+
+    let mut runner: TaskRunner<G, _> = TaskRunner::new(ctx);
+
+    let (mut out, diff): (HashMap<usize, f64>, f64) = runner.run(
+        vec![],
+        vec![Job::new(step1), step2],
+        Some(vec![KatzState::default(); g.unfiltered_num_nodes()]),
+        |_, _, _, local| {
+            let nodes = g.nodes();
+            let diff = nodes.iter().map(|node| local[node.node.0].diff).sum();
+            let out = nodes
+                .iter()
+                .map(|node| {
+                    let VID(i) = node.node;
+                    (i, local[i].score)
+                })
+                .collect();
+            (out, diff)
+        },
+        threads,
+        iter_count,
+        None,
+        None,
+    );
+
+    check_convergence(out.values(), diff, n, iter_count, tol)?;
+
+    if normalized {
+        let norm = out.values().map(|score| score * score).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            out.values_mut().for_each(|score| *score /= norm);
+        }
+    }
+
+    let results_type = std::any::type_name::<f64>();
+    Ok(AlgorithmResult::new(g.clone(), "Katz", results_type, out))
+}
+
+#[cfg(test)]
+mod katz_centrality_test {
+    use super::*;
+    use crate::{
+        algorithms::centrality::{
+            edge_weights::edge_weights_test::weighted_test_graph,
+            pagerank::page_rank_tests::assert_eq_f64,
+        },
+        prelude::*,
+        test_storage,
+    };
+
+    #[test]
+    fn test_katz_centrality() {
+        let graph = weighted_test_graph("weight");
+
+        test_storage!(&graph, |graph| {
+            let katz = |weight, directed, alpha, normalized| {
+                katz_centrality(
+                    graph, weight, directed, alpha, None, normalized, None, None, None,
+                )
+                .unwrap()
+            };
+            let cases = [
+                (
+                    katz(None, true, None, true),
+                    [0.440604, 0.436673, 0.480341, 0.479908, 0.392613],
+                ),
+                (
+                    katz(Some("weight"), true, Some(0.05), true),
+                    [0.457078, 0.414340, 0.524336, 0.437278, 0.391486],
+                ),
+                (
+                    katz(None, false, None, true),
+                    [0.473291, 0.430660, 0.473291, 0.468949, 0.382896],
+                ),
+                (
+                    katz(None, true, None, false),
+                    [1.122234, 1.112223, 1.223446, 1.222345, 1.0],
+                ),
+            ];
+            for (result, expected) in cases {
+                for (node, value) in ["1", "2", "3", "4", "5"].into_iter().zip(expected) {
+                    assert_eq_f64(result.get(node), Some(&value), 4);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_katz_centrality_divergence() {
+        // the largest eigenvalue of a star with 150 leaves is sqrt(150) > 1 / alpha
+        let graph = Graph::new();
+        for leaf in 1..=150 {
+            graph.add_edge(0, 0, leaf, NO_PROPS, None).unwrap();
+        }
+
+        test_storage!(&graph, |graph| {
+            let katz = |alpha| {
+                katz_centrality(graph, None, false, alpha, None, true, None, None, None).map(|_| ())
+            };
+            assert_eq!(
+                katz(None),
+                Err(PowerIterationError::NotConverged {
+                    iterations: 1000,
+                    tol: 0.000001
+                })
+            );
+            assert_eq!(katz(Some(1.0)), Err(PowerIterationError::Diverged));
+            assert!(katz(Some(0.05)).is_ok());
+        });
+    }
+}
@@ -1,4 +1,8 @@
 pub mod betweenness;
+pub mod closeness;
 pub mod degree_centrality;
+pub mod edge_weights;
+pub mod eigenvector;
 pub mod hits;
+pub mod katz;
 pub mod pagerank;
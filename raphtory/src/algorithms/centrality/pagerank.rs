@@ -1,21 +1,27 @@
 use crate::{
     algorithms::algorithm_result::AlgorithmResult,
     core::{
-        entities::VID,
+        entities::{
+            nodes::node_ref::{AsNodeRef, NodeRef},
+            VID,
+        },
         state::{accumulator_id::accumulators, compute_state::ComputeStateVec},
     },
     db::{
-        api::view::{NodeViewOps, StaticGraphViewOps},
+        api::view::StaticGraphViewOps,
+        graph::edge::EdgeView,
         task::{
             context::Context,
+            node::eval_node::EvalNodeView,
             task::{ATask, Job, Step},
             task_runner::TaskRunner,
         },
     },
+    prelude::*,
 };
 use num_traits::abs;
 use ordered_float::OrderedFloat;
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 #[derive(Clone, Debug, Default)]
 struct PageRankState {
@@ -182,6 +188,288 @@ pub fn unweighted_page_rank<G: StaticGraphViewOps>(
     AlgorithmResult::new(g.clone(), "Pagerank", results_type, out)
 }
 
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum PageRankError {
+    #[error("Invalid weight aggregation {0}, expected one of sum, latest or count")]
+    InvalidAggregation(String),
+    #[error("Weight {value} of edge {src} -> {dst} is not a non-negative number")]
+    InvalidWeight {
+        value: String,
+        src: String,
+        dst: String,
+    },
+    #[error("Node {0} in the personalisation does not exist")]
+    InvalidNode(String),
+    #[error("The personalisation of node {node} is {value}, expected a non-negative number")]
+    InvalidPersonalisation { node: String, value: f64 },
+    #[error("The personalisation has no positive values")]
+    EmptyPersonalisation,
+}
+
+/// How the values of the weight property of an edge are combined into the weight of the edge
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WeightAggregation {
+    /// The sum of the values of all the updates, e.g., the total amount transferred
+    #[default]
+    Sum,
+    /// The latest value
+    Latest,
+    /// The number of updates of the edge, the weight property is not read
+    Count,
+}
+
+impl FromStr for WeightAggregation {
+    type Err = PageRankError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(WeightAggregation::Sum),
+            "latest" => Ok(WeightAggregation::Latest),
+            "count" => Ok(WeightAggregation::Count),
+            _ => Err(PageRankError::InvalidAggregation(s.to_owned())),
+        }
+    }
+}
+
+fn edge_weight<G: StaticGraphViewOps>(
+    edge: &EdgeView<G, G>,
+    weight: Option<&str>,
+    aggregation: WeightAggregation,
+) -> Result<f64, PageRankError> {
+    let invalid = |value: String| PageRankError::InvalidWeight {
+        value,
+        src: edge.src().name(),
+        dst: edge.dst().name(),
+    };
+    let to_weight = |value: Prop| match value.as_f64() {
+        Some(weight) if weight >= 0.0 => Ok(weight),
+        _ => Err(invalid(value.to_string())),
+    };
+    let weight = match (aggregation, weight) {
+        (WeightAggregation::Count, _) => edge.history().len() as f64,
+        (_, None) => 1.0,
+        (WeightAggregation::Latest, Some(name)) => match edge.properties().get(name) {
+            Some(value) => to_weight(value)?,
+            None => 0.0,
+        },
+        (WeightAggregation::Sum, Some(name)) => match edge.properties().temporal().get(name) {
+            Some(values) => values.values().map(to_weight).sum::<Result<f64, _>>()?,
+            None => match edge.properties().constant().get(name) {
+                Some(value) => to_weight(value)?,
+                None => 0.0,
+            },
+        },
+    };
+    if weight.is_finite() {
+        Ok(weight)
+    } else {
+        Err(invalid(weight.to_string()))
+    }
+}
+
+/// The teleport probability of every node, uniform if there is no personalisation
+fn teleport_probabilities<G: StaticGraphViewOps, T: AsNodeRef>(
+    g: &G,
+    personalisation: Option<Vec<(T, f64)>>,
+) -> Result<Vec<f64>, PageRankError> {
+    let mut teleport = vec![0f64; g.unfiltered_num_nodes()];
+    match personalisation {
+        None => {
+            let n = g.count_nodes() as f64;
+            for node in g.nodes() {
+                teleport[node.node.0] = 1.0 / n;
+            }
+        }
+        Some(personalisation) => {
+            for (node, value) in personalisation {
+                let name = match node.as_node_ref() {
+                    NodeRef::External(gid) => gid.to_string(),
+                    NodeRef::Internal(vid) => format!("{vid:?}"),
+                };
+                let node = g.node(node).ok_or(PageRankError::InvalidNode(name))?;
+                if !(value >= 0.0 && value.is_finite()) {
+                    return Err(PageRankError::InvalidPersonalisation {
+                        node: node.name(),
+                        value,
+                    });
+                }
+                teleport[node.node.0] += value;
+            }
+            let total: f64 = teleport.iter().sum();
+            if total <= 0.0 {
+                return Err(PageRankError::EmptyPersonalisation);
+            }
+            teleport.iter_mut().for_each(|p| *p /= total);
+        }
+    }
+    Ok(teleport)
+}
+
+#[derive(Clone, Debug, Default)]
+struct WeightedPageRankState {
+    score: f64,
+    teleport: f64,
+    out_weight: f64,
+}
+
+/// Weighted and personalised PageRank Algorithm:
+/// Nodes pass their score to their out-neighbours in proportion to the weights of the edges, and
+/// the random surfer teleports to the nodes of the personalisation, e.g., a set of seed nodes.
+/// Nodes without outgoing weight distribute their score following the personalisation as well.
+///
+/// Arguments:
+///
+/// * `g`: A GraphView object
+/// * `weight`: The name of the edge property with the weights, all edges have the same weight if not set
+/// * `aggregation`: How the values of the weight property of an edge are combined
+/// * `personalisation`: The teleport weight of each node, teleports are uniform if not set. Give a weight of 1 to a set of seed nodes to rank the nodes seeded from them
+/// * `iter_count`: Number of iterations to run the algorithm for
+/// * `threads`: Number of threads to use for parallel execution
+/// * `tol`: The tolerance value for convergence
+/// * `use_l2_norm`: Whether to use L2 norm for convergence
+/// * `damping_factor`: Probability of likelihood the spread will continue
+///
+/// Result:
+///
+/// * An AlgorithmResult object containing the mapping from node ID to the PageRank score of the node
+///
+#[allow(clippy::too_many_arguments)]
+pub fn weighted_page_rank<G: StaticGraphViewOps, T: AsNodeRef>(
+    g: &G,
+    weight: Option<&str>,
+    aggregation: WeightAggregation,
+    personalisation: Option<Vec<(T, f64)>>,
+    iter_count: Option<usize>,
+    threads: Option<usize>,
+    tol: Option<f64>,
+    use_l2_norm: bool,
+    damping_factor: Option<f64>,
+) -> Result<AlgorithmResult<G, f64, OrderedFloat<f64>>, PageRankError> {
+    let n = g.count_nodes();
+    let teleport = teleport_probabilities(g, personalisation)?;
+
+    let mut out_weights = vec![0f64; g.unfiltered_num_nodes()];
+    let mut in_weights: Vec<Vec<(VID, f64)>> = vec![vec![]; g.unfiltered_num_nodes()];
+    for node in g.nodes() {
+        let edges = node
+            .out_edges()
+            .into_iter()
+            .map(|edge| Ok((edge.dst().node, edge_weight(&edge, weight, aggregation)?)))
+            .collect::<Result<Vec<_>, PageRankError>>()?;
+        let total: f64 = edges.iter().map(|(_, weight)| weight).sum();
+        out_weights[node.node.0] = total;
+        if total > 0.0 {
+            for (dst, weight) in edges {
+                in_weights[dst.0].push((node.node, weight / total));
+            }
+        }
+    }
+
+    let mut ctx: Context<G, ComputeStateVec> = g.into();
+
+    let tol: f64 = tol.unwrap_or(0.000001f64);
+    let damp = damping_factor.unwrap_or(0.85);
+    let iter_count = iter_count.unwrap_or(20);
+
+    let max_diff = accumulators::sum::<f64>(2);
+    let total_sink_contribution = accumulators::sum::<f64>(4);
+    ctx.global_agg_reset(max_diff);
+    ctx.global_agg_reset(total_sink_contribution);
+
+    let step1 = ATask::new(move |s| {
+        let node = s.node.0;
+        let state: &mut WeightedPageRankState = s.get_mut();
+        state.out_weight = out_weights[node];
+        state.teleport = teleport[node];
+        Step::Continue
+    });
+
+    let step2 = ATask::new(move |s: &mut EvalNodeView<G, WeightedPageRankState>| {
+        let graph = s.graph();
+        let score: f64 = in_weights[s.node.0]
+            .iter()
+            .filter_map(|(src, weight)| Some(graph.node(*src)?.prev().score * weight))
+            .sum();
+        let state: &mut WeightedPageRankState = s.get_mut();
+        state.score = damp * score + (1f64 - damp) * state.teleport;
+        Step::Continue
+    });
+
+    let step3 = ATask::new(move |s| {
+        let state: &WeightedPageRankState = s.get();
+        if state.out_weight == 0.0 {
+            let ts_contrib = damp * s.prev().score;
+            s.global_update(&total_sink_contribution, ts_contrib);
+        }
+        Step::Continue
+    });
+
+    let step4 = ATask::new(move |s| {
+        let total_sink_contribution = s
+            .read_global_state(&total_sink_contribution)
+            .unwrap_or_default();
+        let state: &mut WeightedPageRankState = s.get_mut();
+        state.score += total_sink_contribution * state.teleport;
+
+        let curr = state.score;
+        let prev = s.prev().score;
+        let md = if use_l2_norm {
+            f64::powi(abs(prev - curr), 2)
+        } else {
+            abs(prev - curr)
+        };
+        s.global_update(&max_diff, md);
+        Step::Continue
+    });
+
+    let step5 = Job::Check(Box::new(move |state| {
+        let max_diff_val = state.read(&max_diff);
+        let cont = if use_l2_norm {
+            f64::sqrt(max_diff_val) > tol * n as f64
+        } else {
+            max_diff_val > tol * n as f64
+        };
+        if cont {
+            Step::Continue
+        } else {
+            Step::Done
+        }
+    }));
+
+    let mut runner: TaskRunner<G, _> = TaskRunner::new(ctx);
+
+    let initial = WeightedPageRankState {
+        score: 1f64 / n as f64,
+        ..Default::default()
+    };
+    let out: HashMap<usize, f64> = runner.run(
+        vec![Job::new(step1)],
+        vec![Job::new(step2), Job::new(step3), Job::new(step4), step5],
+        Some(vec![initial; g.unfiltered_num_nodes()]),
+        |_, _, _, local| {
+            g.nodes()
+                .iter()
+                .map(|node| {
+                    let VID(i) = node.node;
+                    (i, local[i].score)
+                })
+                .collect()
+        },
+        threads,
+        iter_count,
+        None,
+        None,
+    );
+
+    let results_type = std::any::type_name::<f64>();
+    Ok(AlgorithmResult::new(
+        g.clone(),
+        "Weighted Pagerank",
+        results_type,
+        out,
+    ))
+}
+
 #[cfg(test)]
 pub mod page_rank_tests {
     use super::*;
@@ -352,6 +640,120 @@ pub mod page_rank_tests {
         });
     }
 
+    #[test]
+    fn weighted_page_rank_matches_unweighted_with_equal_weights() {
+        let graph = load_graph();
+        test_storage!(&graph, |graph| {
+            let unweighted = unweighted_page_rank(graph, Some(1000), Some(1), None, true, None);
+            let weighted = weighted_page_rank(
+                graph,
+                None,
+                WeightAggregation::Sum,
+                None::<Vec<(u64, f64)>>,
+                Some(1000),
+                Some(1),
+                None,
+                true,
+                None,
+            )
+            .unwrap();
+            for node in ["1", "2", "3", "4"] {
+                assert_eq_f64(weighted.get(node), unweighted.get(node), 5);
+            }
+        });
+    }
+
+    #[test]
+    fn weighted_and_personalised_page_rank() {
+        let graph = Graph::new();
+        for (t, src, dst, amount) in [
+            (0, 1, 2, 1.0),
+            (1, 1, 3, 5.0),
+            (2, 1, 3, 4.0),
+            (3, 2, 1, 1.0),
+            (4, 3, 1, 1.0),
+            (5, 4, 1, 1.0),
+        ] {
+            graph
+                .add_edge(t, src, dst, [("amount", amount)], None)
+                .unwrap();
+        }
+
+        test_storage!(&graph, |graph| {
+            let rank = |aggregation, personalisation: Option<Vec<(u64, f64)>>| {
+                weighted_page_rank(
+                    graph,
+                    Some("amount"),
+                    aggregation,
+                    personalisation,
+                    Some(1000),
+                    Some(1),
+                    None,
+                    true,
+                    None,
+                )
+                .unwrap()
+            };
+
+            // 1 passes 9/10 of its score to 3 and 1/10 to 2
+            let result = rank(WeightAggregation::Sum, None);
+            let (two, three) = (result.get("2").unwrap(), result.get("3").unwrap());
+            assert_eq_f64(Some((three - 0.0375) / (two - 0.0375)), Some(9.0), 5);
+
+            // the latest amount of 1 -> 3 is 4, the edge was updated twice
+            let result = rank(WeightAggregation::Latest, None);
+            let (two, three) = (result.get("2").unwrap(), result.get("3").unwrap());
+            assert_eq_f64(Some((three - 0.0375) / (two - 0.0375)), Some(4.0), 5);
+            let result = rank(WeightAggregation::Count, None);
+            let (two, three) = (result.get("2").unwrap(), result.get("3").unwrap());
+            assert_eq_f64(Some((three - 0.0375) / (two - 0.0375)), Some(2.0), 5);
+
+            // 4 is only reached by teleporting
+            let result = rank(WeightAggregation::Sum, Some(vec![(1, 1.0)]));
+            assert_eq_f64(result.get("4"), Some(&0.0), 5);
+            let total: f64 = result.get_all_values().iter().sum();
+            assert_eq_f64(Some(total), Some(1.0), 5);
+            let result = rank(WeightAggregation::Sum, Some(vec![(4, 1.0)]));
+            assert!(result.get("4").unwrap() > &0.15);
+        });
+    }
+
+    #[test]
+    fn weighted_page_rank_errors() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, [("amount", -1.0)], None).unwrap();
+        let rank = |weight, personalisation: Option<Vec<(u64, f64)>>| {
+            weighted_page_rank(
+                &graph,
+                weight,
+                WeightAggregation::Sum,
+                personalisation,
+                None,
+                None,
+                None,
+                true,
+                None,
+            )
+            .map(|_| ())
+        };
+        assert!(matches!(
+            rank(Some("amount"), None),
+            Err(PageRankError::InvalidWeight { .. })
+        ));
+        assert_eq!(
+            rank(None, Some(vec![(3, 1.0)])),
+            Err(PageRankError::InvalidNode("3".to_string()))
+        );
+        assert_eq!(
+            rank(None, Some(vec![(1, 0.0)])),
+            Err(PageRankError::EmptyPersonalisation)
+        );
+        assert_eq!(
+            "mean".parse::<WeightAggregation>(),
+            Err(PageRankError::InvalidAggregation("mean".to_string()))
+        );
+    }
+
     pub fn assert_eq_f64<T: Borrow<f64> + PartialEq + std::fmt::Debug>(
         a: Option<T>,
         b: Option<T>,
@@ -0,0 +1,438 @@
+//! # Leiden community detection
+//!
+//! The Leiden algorithm (Traag, Waltman and van Eck, 2019) improves on Louvain by refining the
+//! communities found by local moving before aggregating the graph. Only nodes of the same
+//! community are merged during refinement, such that every community in the result is
+//! connected.
+//!
+//! Communities can be tracked over time by running the algorithm on a sequence of windows, each
+//! window starting from the communities found in the previous one.
+use crate::{
+    algorithms::{
+        algorithm_result::AlgorithmResult,
+        community_detection::modularity::{
+            ModularityFunction, Partition, RefinableModularityFunction,
+        },
+    },
+    core::entities::VID,
+    db::api::view::{TimeOps, WindowSet},
+    prelude::{GraphViewOps, NodeViewOps},
+};
+use rand::{prelude::StdRng, seq::SliceRandom, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// The end of a window and the community of each node present in the window
+pub type WindowCommunities = (Option<i64>, HashMap<String, usize>);
+
+/// Move nodes to the neighbouring community that improves modularity the most until no move
+/// improves it by more than `tol`
+fn move_nodes<M: ModularityFunction>(state: &mut M, rng: &mut StdRng, tol: f64) {
+    let mut nodes: Vec<_> = state.nodes().collect();
+    let mut inner_moved = true;
+    while inner_moved {
+        inner_moved = false;
+        nodes.shuffle(rng);
+        for v in nodes.iter() {
+            if let Some((best_c, delta)) = state
+                .candidate_moves(v)
+                .map(|c| (c, state.move_delta(v, c)))
+                .max_by(|(_, delta1), (_, delta2)| delta1.total_cmp(delta2))
+            {
+                if best_c != state.partition().com(v) && delta > tol {
+                    inner_moved = true;
+                    state.move_node(v, best_c);
+                }
+            }
+        }
+    }
+}
+
+/// Merge the nodes that are still on their own in `refined` into the refined community
+/// of their community in `state` that improves modularity the most
+fn refine<M: ModularityFunction>(state: &M, refined: &mut M, rng: &mut StdRng, tol: f64) {
+    let mut nodes: Vec<_> = refined.nodes().collect();
+    nodes.shuffle(rng);
+    for v in nodes.iter() {
+        let own = refined.partition().com(v);
+        if refined.partition().nodes(&own).nth(1).is_some() {
+            // other nodes already merged into this one
+            continue;
+        }
+        let com = state.partition().com(v);
+        let best = refined
+            .candidate_moves(v)
+            .filter(|c| {
+                *c != own
+                    && refined
+                        .partition()
+                        .nodes(c)
+                        .next()
+                        .is_some_and(|u| state.partition().com(u) == com)
+            })
+            .map(|c| (c, refined.move_delta(v, c)))
+            .max_by(|(_, delta1), (_, delta2)| delta1.total_cmp(delta2));
+        if let Some((best_c, delta)) = best {
+            if delta > tol {
+                refined.move_node(v, best_c);
+            }
+        }
+    }
+}
+
+/// Leiden community detection
+///
+/// # Arguments
+///
+/// - `graph`: The graph (edges are treated as undirected by `ModularityUnDir`)
+/// - `resolution`: The resolution parameter for modularity, larger values result in smaller communities
+/// - `weight_prop`: The edge property to use for weights (has to be float)
+/// - `initial_partition`: The starting community of nodes by name, nodes without one start in their own community
+/// - `tol`: The floating point tolerance for deciding if improvements are significant (default: 1e-8)
+/// - `rng_seed`: The seed for the order in which nodes are visited
+///
+/// # Returns
+///
+/// An `AlgorithmResult` with the community of each node, communities are numbered from 0.
+pub fn leiden<'graph, M: RefinableModularityFunction, G: GraphViewOps<'graph>>(
+    graph: &G,
+    resolution: f64,
+    weight_prop: Option<&str>,
+    initial_partition: Option<&HashMap<String, usize>>,
+    tol: Option<f64>,
+    rng_seed: Option<u64>,
+) -> AlgorithmResult<G, usize> {
+    let tol = tol.unwrap_or(1e-8);
+    let mut rng = match rng_seed {
+        None => StdRng::from_entropy(),
+        Some(seed) => StdRng::seed_from_u64(seed),
+    };
+    let nodes = graph.nodes();
+    let partition = match initial_partition {
+        None => Partition::new_singletons(graph.count_nodes()),
+        Some(initial) => {
+            // relabel the communities compactly, nodes without one get a label of their own
+            let mut labels = HashMap::new();
+            let mut num_labels = 0;
+            nodes
+                .iter()
+                .map(|node| {
+                    let seed = initial.get(&node.name());
+                    if let Some(c) = seed.and_then(|c| labels.get(c)) {
+                        return *c;
+                    }
+                    let c = num_labels;
+                    num_labels += 1;
+                    if let Some(seed) = seed {
+                        labels.insert(*seed, c);
+                    }
+                    c
+                })
+                .collect()
+        }
+    };
+    let mut state = M::new(graph, weight_prop, resolution, partition, tol);
+    // the node of the current level of aggregation for each node of the graph
+    let mut levels: Vec<usize> = (0..nodes.len()).collect();
+
+    loop {
+        move_nodes(&mut state, &mut rng, tol);
+        let num_nodes = state.partition().num_nodes();
+        let num_coms = state
+            .partition()
+            .coms()
+            .filter(|(_, c)| !c.is_empty())
+            .count();
+        if num_coms == num_nodes {
+            break;
+        }
+        let mut refined = state.with_partition(Partition::new_singletons(num_nodes));
+        refine(&state, &mut refined, &mut rng, tol);
+        let refined_partition = refined.aggregate();
+        if refined_partition.num_coms() == num_nodes {
+            // nothing left to merge
+            break;
+        }
+        // aggregated nodes start in the community of their nodes before refinement
+        let coarse: Partition = refined_partition
+            .coms()
+            .filter_map(|(_, nodes)| nodes.iter().next())
+            .map(|v| state.partition().com(v))
+            .collect();
+        for c in levels.iter_mut() {
+            *c = refined_partition.com(&VID(*c)).index();
+        }
+        state = refined.with_partition(coarse.compact().0);
+    }
+
+    let mut com_ids = HashMap::new();
+    let communities: HashMap<_, _> = nodes
+        .iter()
+        .zip(levels)
+        .map(|(node, level)| {
+            let com = state.partition().com(&VID(level));
+            let next = com_ids.len();
+            (node.node.index(), *com_ids.entry(com).or_insert(next))
+        })
+        .collect();
+    AlgorithmResult::new(graph.clone(), "leiden", "usize", communities)
+}
+
+/// Match communities to the communities of the previous window with the largest overlap
+///
+/// Communities are matched one-to-one in order of decreasing Jaccard similarity and communities
+/// without a match get a new id larger than all ids used so far.
+fn match_communities(
+    previous: &HashMap<String, usize>,
+    current: HashMap<String, usize>,
+    next_id: &mut usize,
+) -> HashMap<String, usize> {
+    let mut previous_coms: HashMap<usize, HashSet<&String>> = HashMap::new();
+    for (node, c) in previous {
+        previous_coms.entry(*c).or_default().insert(node);
+    }
+    let mut current_coms: HashMap<usize, HashSet<&String>> = HashMap::new();
+    for (node, c) in &current {
+        current_coms.entry(*c).or_default().insert(node);
+    }
+    let mut overlaps: Vec<_> = current_coms
+        .iter()
+        .flat_map(|(c, nodes)| {
+            previous_coms.iter().filter_map(move |(p, previous_nodes)| {
+                let shared = nodes.intersection(previous_nodes).count();
+                (shared > 0).then(|| {
+                    let union = nodes.len() + previous_nodes.len() - shared;
+                    (shared as f64 / union as f64, *c, *p)
+                })
+            })
+        })
+        .collect();
+    // ties are broken by id so the matching does not depend on the iteration order
+    overlaps
+        .sort_by(|(j1, c1, p1), (j2, c2, p2)| j2.total_cmp(j1).then(c1.cmp(c2)).then(p1.cmp(p2)));
+    let mut ids = HashMap::new();
+    let mut used = HashSet::new();
+    for (_, c, p) in overlaps {
+        if !ids.contains_key(&c) && used.insert(p) {
+            ids.insert(c, p);
+        }
+    }
+    let mut unmatched: Vec<_> = current_coms
+        .keys()
+        .filter(|c| !ids.contains_key(c))
+        .collect();
+    unmatched.sort();
+    for c in unmatched {
+        ids.insert(*c, *next_id);
+        *next_id += 1;
+    }
+    current
+        .into_iter()
+        .map(|(node, c)| (node, ids[&c]))
+        .collect()
+}
+
+/// Run Leiden community detection on each window of a `WindowSet` and track the communities
+/// over time
+///
+/// Each window starts from the communities of the previous window and community ids are
+/// matched between consecutive windows by the largest overlap of their nodes, such that a
+/// community keeps its id while it persists and new communities get new ids.
+///
+/// # Arguments
+///
+/// - `windows`: The windows of the graph, e.g., from `graph.rolling(...)`
+/// - `resolution`: The resolution parameter for modularity
+/// - `weight_prop`: The edge property to use for weights (has to be float)
+/// - `tol`: The floating point tolerance for deciding if improvements are significant (default: 1e-8)
+/// - `rng_seed`: The seed for the order in which nodes are visited
+///
+/// # Returns
+///
+/// The end of each window and the community of each node present in the window.
+pub fn leiden_over_windows<'graph, M: RefinableModularityFunction, T>(
+    windows: WindowSet<'graph, T>,
+    resolution: f64,
+    weight_prop: Option<&str>,
+    tol: Option<f64>,
+    rng_seed: Option<u64>,
+) -> Vec<WindowCommunities>
+where
+    T: TimeOps<'graph> + Clone + 'graph,
+    T::WindowedViewType: GraphViewOps<'graph>,
+{
+    let mut previous: HashMap<String, usize> = HashMap::new();
+    let mut next_id = 0;
+    windows
+        .map(|window| {
+            let current: HashMap<String, usize> = leiden::<M, _>(
+                &window,
+                resolution,
+                weight_prop,
+                Some(&previous),
+                tol,
+                rng_seed,
+            )
+            .get_all_with_names()
+            .into_iter()
+            .collect();
+            previous = match_communities(&previous, current, &mut next_id);
+            (window.end(), previous.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            community_detection::{
+                leiden::{leiden, leiden_over_windows},
+                modularity::ModularityUnDir,
+            },
+            components::weakly_connected_components,
+        },
+        prelude::*,
+        test_storage,
+    };
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    fn add_undirected(graph: &Graph, t: i64, src: u64, dst: u64, weight: f64) {
+        graph
+            .add_edge(t, src, dst, [("weight", weight)], None)
+            .unwrap();
+        graph
+            .add_edge(t, dst, src, [("weight", weight)], None)
+            .unwrap();
+    }
+
+    fn cliques(graph: &Graph, t: i64, cliques: &[&[u64]]) {
+        for clique in cliques {
+            for (i, src) in clique.iter().enumerate() {
+                for dst in &clique[i + 1..] {
+                    add_undirected(graph, t, *src, *dst, 1.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_leiden() {
+        let graph = Graph::new();
+        cliques(&graph, 0, &[&[1, 2, 3, 4], &[5, 6, 7, 8]]);
+        add_undirected(&graph, 0, 4, 5, 0.5);
+
+        test_storage!(&graph, |graph| {
+            let result = leiden::<ModularityUnDir, _>(graph, 1.0, None, None, None, Some(42));
+            let coms: Vec<_> = (1..=8).map(|n| *result.get(n).unwrap()).collect();
+            assert_eq!(coms, [0, 0, 0, 0, 1, 1, 1, 1]);
+
+            // a large resolution puts every node on its own
+            let result = leiden::<ModularityUnDir, _>(graph, 100.0, None, None, None, Some(42));
+            assert_eq!(result.group_by().len(), 8);
+        });
+    }
+
+    #[test]
+    fn test_leiden_weighted() {
+        let graph = Graph::new();
+        // a path that is only split into communities by its weights
+        for (src, dst, weight) in [
+            (1, 2, 10.0),
+            (2, 3, 0.1),
+            (3, 4, 10.0),
+            (4, 5, 0.1),
+            (5, 6, 10.0),
+        ] {
+            add_undirected(&graph, 0, src, dst, weight);
+        }
+
+        test_storage!(&graph, |graph| {
+            let result =
+                leiden::<ModularityUnDir, _>(graph, 1.0, Some("weight"), None, None, Some(1));
+            let coms: Vec<_> = (1..=6).map(|n| *result.get(n).unwrap()).collect();
+            assert_eq!(coms, [0, 0, 1, 1, 2, 2]);
+        });
+    }
+
+    #[test]
+    fn test_leiden_initial_partition() {
+        let graph = Graph::new();
+        cliques(&graph, 0, &[&[1, 2, 3, 4], &[5, 6, 7, 8]]);
+        add_undirected(&graph, 0, 4, 5, 0.5);
+
+        test_storage!(&graph, |graph| {
+            // starting from the optimal partition does not change it, unknown nodes are ignored
+            let initial: HashMap<String, usize> = (1..=9)
+                .map(|n| (n.to_string(), if n < 5 { 10 } else { 3 }))
+                .collect();
+            let result =
+                leiden::<ModularityUnDir, _>(graph, 1.0, None, Some(&initial), None, Some(42));
+            let coms: Vec<_> = (1..=8).map(|n| *result.get(n).unwrap()).collect();
+            assert_eq!(coms, [0, 0, 0, 0, 1, 1, 1, 1]);
+
+            // nodes without a community in the initial partition are still assigned
+            let initial = HashMap::from([("1".to_string(), 0), ("8".to_string(), 0)]);
+            let result =
+                leiden::<ModularityUnDir, _>(graph, 1.0, None, Some(&initial), None, Some(42));
+            assert_eq!(result.group_by().len(), 2);
+            assert_eq!(result.get(1), result.get(4));
+            assert_eq!(result.get(5), result.get(8));
+        });
+    }
+
+    #[test]
+    fn test_leiden_over_windows() {
+        let graph = Graph::new();
+        cliques(&graph, 0, &[&[1, 2, 3, 4], &[5, 6, 7, 8]]);
+        add_undirected(&graph, 0, 4, 5, 0.5);
+        // node 4 leaves its community and a new one appears
+        cliques(&graph, 10, &[&[1, 2, 3], &[4, 5, 6, 7, 8], &[11, 12, 13]]);
+
+        test_storage!(&graph, |graph| {
+            let windows = graph.rolling(10, None).unwrap();
+            let result =
+                leiden_over_windows::<ModularityUnDir, _>(windows, 1.0, None, None, Some(7));
+            assert_eq!(result.len(), 2);
+            let (end, first) = &result[0];
+            assert_eq!(*end, Some(10));
+            let a = first["1"];
+            let b = first["5"];
+            assert_ne!(a, b);
+            assert!((1..=4).all(|n| first[&n.to_string()] == a));
+
+            let (end, second) = &result[1];
+            assert_eq!(*end, Some(20));
+            assert_eq!(second.len(), 11);
+            assert!((1..=3).all(|n| second[&n.to_string()] == a));
+            assert!((4..=8).all(|n| second[&n.to_string()] == b));
+            let c = second["11"];
+            assert!(c != a && c != b);
+            assert!((11..=13).all(|n| second[&n.to_string()] == c));
+        });
+    }
+
+    fn test_communities_connected_inner(edges: Vec<(u64, u64)>) {
+        let graph = Graph::new();
+        for (src, dst) in edges {
+            add_undirected(&graph, 1, src, dst, 1.0);
+        }
+
+        test_storage!(&graph, |graph| {
+            let result = leiden::<ModularityUnDir, _>(graph, 1.0, None, None, None, None);
+            assert!(graph.nodes().iter().all(|n| result.get(n).is_some()));
+            for (_, nodes) in result.group_by() {
+                let community = graph.subgraph(nodes);
+                let components = weakly_connected_components(&community, usize::MAX, None);
+                assert_eq!(components.group_by().len(), 1);
+            }
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn test_communities_connected(edges in any::<Vec<(u8, u8)>>().prop_map(|v| v.into_iter().map(|(s, d)|  (s as u64, d as u64)).collect::<Vec<_>>())) {
+            test_communities_connected_inner(edges)
+        }
+    }
+}
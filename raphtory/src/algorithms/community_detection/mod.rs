@@ -1,3 +1,4 @@
 pub mod label_propagation;
+pub mod leiden;
 pub mod louvain;
 pub mod modularity;
@@ -162,6 +162,13 @@ pub trait ModularityFunction {
     fn nodes(&self) -> Box<dyn Iterator<Item = VID>>;
 }
 
+/// Modularity functions that can be copied with a different partition, as needed to refine the
+/// communities in [`leiden`](super::leiden::leiden)
+pub trait RefinableModularityFunction: ModularityFunction + Sized {
+    /// Copy the modularity function (at the current level of aggregation) with a new partition
+    fn with_partition(&self, partition: Partition) -> Self;
+}
+
 /// Undirected modularity function (assumes edges are all present in both directions in the graph)
 pub struct ModularityUnDir {
     resolution: f64,
@@ -175,6 +182,39 @@ pub struct ModularityUnDir {
     tol: f64,
 }
 
+/// The weights of the edges from each node to each community and the total degree of each community
+fn com_weights(
+    adj: &[Vec<(VID, f64)>],
+    self_loops: &[f64],
+    k: &[f64],
+    partition: &Partition,
+) -> (Vec<HashMap<ComID, f64>>, Vec<f64>) {
+    let adj_com: Vec<_> = adj
+        .iter()
+        .enumerate()
+        .map(|(index, neighbours)| {
+            let mut com_neighbours = HashMap::new();
+            for (n, w) in neighbours {
+                com_neighbours
+                    .entry(partition.com(n))
+                    .and_modify(|old_w| *old_w += *w)
+                    .or_insert(*w);
+            }
+            if self_loops[index] != 0.0 {
+                *com_neighbours
+                    .entry(partition.com(&VID(index)))
+                    .or_insert(0.0) += self_loops[index];
+            }
+            com_neighbours
+        })
+        .collect();
+    let k_com: Vec<f64> = partition
+        .coms()
+        .map(|(_, com)| com.iter().map(|node| k[node.index()]).sum())
+        .collect();
+    (adj_com, k_com)
+}
+
 impl ModularityFunction for ModularityUnDir {
     fn new<'graph, G: GraphViewOps<'graph>>(
         graph: G,
@@ -223,29 +263,7 @@ impl ModularityFunction for ModularityUnDir {
             .iter()
             .map(|neighbours| neighbours.iter().map(|(_, w)| w).sum())
             .collect();
-        let adj_com: Vec<_> = adj
-            .iter()
-            .enumerate()
-            .map(|(index, neighbours)| {
-                let mut com_neighbours = HashMap::new();
-                for (n, w) in neighbours {
-                    com_neighbours
-                        .entry(partition.com(n))
-                        .and_modify(|old_w| *old_w += *w)
-                        .or_insert(*w);
-                }
-                if self_loops[index] != 0.0 {
-                    *com_neighbours
-                        .entry(partition.com(&VID(index)))
-                        .or_insert(0.0) += self_loops[index];
-                }
-                com_neighbours
-            })
-            .collect();
-        let k_com: Vec<f64> = partition
-            .coms()
-            .map(|(_, com)| com.iter().map(|node| k[node.index()]).sum())
-            .collect();
+        let (adj_com, k_com) = com_weights(&adj, &self_loops, &k, &partition);
         let m2: f64 = k_com.iter().sum();
         Self {
             partition,
@@ -413,6 +431,23 @@ impl ModularityFunction for ModularityUnDir {
     }
 }
 
+impl RefinableModularityFunction for ModularityUnDir {
+    fn with_partition(&self, partition: Partition) -> Self {
+        let (adj_com, k_com) = com_weights(&self.adj, &self.self_loops, &self.k, &partition);
+        Self {
+            resolution: self.resolution,
+            partition,
+            adj: self.adj.clone(),
+            self_loops: self.self_loops.clone(),
+            k: self.k.clone(),
+            adj_com,
+            k_com,
+            m2: self.m2,
+            tol: self.tol,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
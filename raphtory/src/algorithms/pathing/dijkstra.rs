@@ -4,7 +4,10 @@ use std::{
 };
 
 /// Dijkstra's algorithm
-use crate::{core::entities::nodes::node_ref::AsNodeRef, db::api::view::StaticGraphViewOps};
+use crate::{
+    core::entities::nodes::node_ref::AsNodeRef,
+    db::{api::view::StaticGraphViewOps, task::interrupt::is_interrupted},
+};
 use crate::{
     core::{Direction, PropType},
     prelude::{EdgeViewOps, NodeViewOps, Prop},
@@ -45,6 +48,8 @@ impl PartialOrd for State {
 ///
 /// Returns a `HashMap` where the key is the target node and the value is a tuple containing
 /// the total cost and a vector of nodes representing the shortest path.
+/// The search stops early, without the paths it didn't reach, when it is interrupted
+/// (see [`with_interrupt`](crate::db::task::interrupt::with_interrupt)).
 ///
 pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef>(
     graph: &G,
@@ -150,6 +155,9 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         node: node_name,
     }) = heap.pop()
     {
+        if is_interrupted() {
+            break;
+        }
         if target_nodes.contains(&node_name) && !paths.contains_key(&node_name) {
             let mut path = vec![node_name.clone()];
             let mut current_node_name = node_name.clone();
@@ -1,3 +1,4 @@
 pub mod dijkstra;
 pub mod single_source_shortest_path;
+pub mod temporal_paths;
 pub mod temporal_reachability;
@@ -0,0 +1,640 @@
+//! # Temporal Shortest Paths
+//!
+//! Optimal time-respecting journeys through the exploded edges of a graph.
+//!
+//! An exploded edge `(u, v, t)` departs `u` at `t` and arrives at `v` at `t + d`, where `d` is the
+//! traversal duration read from an edge property (0 if no property is given). A journey is a
+//! sequence of exploded edges where each edge departs from the node the previous one arrived at,
+//! no earlier than its arrival. Without durations consecutive edges may share a timestamp.
+//!
+//! Restrict the graph to a window to only consider journeys within it.
+use crate::{
+    core::entities::{
+        nodes::node_ref::{AsNodeRef, NodeRef},
+        VID,
+    },
+    db::{api::view::StaticGraphViewOps, graph::edge::EdgeView},
+    prelude::*,
+};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+};
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum TemporalPathError {
+    #[error("Node {0} does not exist")]
+    NodeNotFound(String),
+    #[error("Edge {src} -> {dst} at time {time} has no duration property {name}")]
+    MissingDuration {
+        name: String,
+        src: String,
+        dst: String,
+        time: i64,
+    },
+    #[error(
+        "Duration {value} of edge {src} -> {dst} at time {time} is not a non-negative integer"
+    )]
+    InvalidDuration {
+        value: Prop,
+        src: String,
+        dst: String,
+        time: i64,
+    },
+}
+
+/// A time-respecting journey, made of the exploded edges traversed in order
+#[derive(Clone)]
+pub struct TemporalPath<G> {
+    /// Time at which the first edge departs
+    pub departure: i64,
+    /// Time at which the last edge arrives
+    pub arrival: i64,
+    pub edges: Vec<EdgeView<G, G>>,
+}
+
+impl<G: StaticGraphViewOps> TemporalPath<G> {
+    /// The time elapsed between the departure and the arrival
+    pub fn duration(&self) -> i64 {
+        self.arrival - self.departure
+    }
+
+    /// The names of the nodes visited, in order
+    pub fn nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<_> = self.edges.iter().map(|edge| edge.src().name()).collect();
+        nodes.extend(self.edges.last().map(|edge| edge.dst().name()));
+        nodes
+    }
+}
+
+impl<G: StaticGraphViewOps> Debug for TemporalPath<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemporalPath")
+            .field("departure", &self.departure)
+            .field("arrival", &self.arrival)
+            .field("nodes", &self.nodes())
+            .finish()
+    }
+}
+
+/// An exploded edge with its departure and arrival times
+struct Connection<G> {
+    edge: EdgeView<G, G>,
+    src: VID,
+    dst: VID,
+    departure: i64,
+    arrival: i64,
+    /// The duration of the edge, or 1 if durations are not given so that shortest paths have
+    /// the fewest hops
+    cost: i64,
+}
+
+fn duration_value(value: &Prop) -> Option<i64> {
+    match value {
+        Prop::U8(v) => Some(*v as i64),
+        Prop::U16(v) => Some(*v as i64),
+        Prop::U32(v) => Some(*v as i64),
+        Prop::U64(v) => i64::try_from(*v).ok(),
+        Prop::I32(v) => (*v >= 0).then_some(*v as i64),
+        Prop::I64(v) => (*v >= 0).then_some(*v),
+        _ => None,
+    }
+}
+
+/// The exploded edges of the graph sorted by departure, self-loops are never part of an optimal
+/// journey and are skipped
+fn connections<G: StaticGraphViewOps>(
+    g: &G,
+    duration: Option<&str>,
+) -> Result<Vec<Connection<G>>, TemporalPathError> {
+    let mut connections = vec![];
+    for edge in g.edges().explode() {
+        let (src, dst) = (edge.src().node, edge.dst().node);
+        if src == dst {
+            continue;
+        }
+        let Some(departure) = edge.time().ok() else {
+            continue;
+        };
+        let (arrival, cost) = match duration {
+            None => (departure, 1),
+            Some(name) => {
+                let value = edge.properties().get(name).ok_or_else(|| {
+                    TemporalPathError::MissingDuration {
+                        name: name.to_owned(),
+                        src: edge.src().name(),
+                        dst: edge.dst().name(),
+                        time: departure,
+                    }
+                })?;
+                let duration =
+                    duration_value(&value).ok_or_else(|| TemporalPathError::InvalidDuration {
+                        value,
+                        src: edge.src().name(),
+                        dst: edge.dst().name(),
+                        time: departure,
+                    })?;
+                (departure.saturating_add(duration), duration)
+            }
+        };
+        connections.push(Connection {
+            edge,
+            src,
+            dst,
+            departure,
+            arrival,
+            cost,
+        });
+    }
+    connections.sort_by_key(|connection| (connection.departure, connection.arrival));
+    Ok(connections)
+}
+
+/// The ranges of connections that depart at the same time. Edges that take no time can be chained
+/// within a range, so the ranges are relaxed until nothing changes.
+fn same_departure_ranges<G>(connections: &[Connection<G>]) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    for end in 1..=connections.len() {
+        if end == connections.len() || connections[end].departure != connections[start].departure {
+            ranges.push((start, end));
+            start = end;
+        }
+    }
+    ranges
+}
+
+fn node_id<G: StaticGraphViewOps, T: AsNodeRef>(g: &G, node: T) -> Result<VID, TemporalPathError> {
+    let description = match node.as_node_ref() {
+        NodeRef::External(gid) => gid.to_string(),
+        NodeRef::Internal(vid) => format!("{vid:?}"),
+    };
+    g.node(node)
+        .map(|node| node.node)
+        .ok_or(TemporalPathError::NodeNotFound(description))
+}
+
+fn path<G: StaticGraphViewOps>(
+    connections: &[Connection<G>],
+    mut indices: Vec<usize>,
+    forward: bool,
+) -> TemporalPath<G> {
+    if !forward {
+        indices.reverse();
+    }
+    let edges: Vec<_> = indices
+        .iter()
+        .map(|index| connections[*index].edge.clone())
+        .collect();
+    TemporalPath {
+        departure: connections[indices[0]].departure,
+        arrival: connections[indices[indices.len() - 1]].arrival,
+        edges,
+    }
+}
+
+fn named_paths<G: StaticGraphViewOps>(
+    g: &G,
+    paths: impl Iterator<Item = (VID, TemporalPath<G>)>,
+) -> HashMap<String, TemporalPath<G>> {
+    paths
+        .map(|(node, path)| (g.node(node).unwrap().name(), path))
+        .collect()
+}
+
+/// Finds the journeys from a source that arrive the earliest at every node reachable from it.
+///
+/// # Arguments
+///
+/// - `g`: The graph to search in, restrict it to a window to only consider journeys within it.
+/// - `source`: The source node.
+/// - `start_time`: Journeys depart from the source at or after this time. Defaults to any time.
+/// - `duration`: The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+///
+/// # Returns
+///
+/// Returns a `HashMap` from the names of the reachable nodes to their earliest arrival journey.
+/// The source is not included.
+///
+pub fn temporal_earliest_arrival<G: StaticGraphViewOps, T: AsNodeRef>(
+    g: &G,
+    source: T,
+    start_time: Option<i64>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<G>>, TemporalPathError> {
+    let source = node_id(g, source)?;
+    let connections = connections(g, duration)?;
+    let mut arrival: HashMap<VID, i64> = HashMap::from([(source, start_time.unwrap_or(i64::MIN))]);
+    let mut via: HashMap<VID, usize> = HashMap::new();
+
+    for (start, end) in same_departure_ranges(&connections) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, connection) in connections[start..end].iter().enumerate() {
+                let reached = arrival
+                    .get(&connection.src)
+                    .is_some_and(|time| *time <= connection.departure);
+                let improves = arrival
+                    .get(&connection.dst)
+                    .is_none_or(|time| connection.arrival < *time);
+                if reached && improves && connection.dst != source {
+                    arrival.insert(connection.dst, connection.arrival);
+                    via.insert(connection.dst, start + index);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let paths = via.keys().map(|node| {
+        let mut indices = vec![];
+        let mut current = *node;
+        while let Some(index) = via.get(&current) {
+            indices.push(*index);
+            current = connections[*index].src;
+        }
+        (*node, path(&connections, indices, false))
+    });
+    Ok(named_paths(g, paths))
+}
+
+/// Finds the journeys to a target that depart the latest from every node that can reach it.
+///
+/// # Arguments
+///
+/// - `g`: The graph to search in, restrict it to a window to only consider journeys within it.
+/// - `target`: The target node.
+/// - `end_time`: Journeys arrive at the target at or before this time. Defaults to any time.
+/// - `duration`: The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+///
+/// # Returns
+///
+/// Returns a `HashMap` from the names of the nodes that can reach the target to their latest
+/// departure journey. The target is not included.
+///
+pub fn temporal_latest_departure<G: StaticGraphViewOps, T: AsNodeRef>(
+    g: &G,
+    target: T,
+    end_time: Option<i64>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<G>>, TemporalPathError> {
+    let target = node_id(g, target)?;
+    let connections = connections(g, duration)?;
+    let mut departure: HashMap<VID, i64> = HashMap::from([(target, end_time.unwrap_or(i64::MAX))]);
+    let mut via: HashMap<VID, usize> = HashMap::new();
+
+    for (start, end) in same_departure_ranges(&connections).into_iter().rev() {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, connection) in connections[start..end].iter().enumerate() {
+                let reaches = departure
+                    .get(&connection.dst)
+                    .is_some_and(|time| connection.arrival <= *time);
+                let improves = departure
+                    .get(&connection.src)
+                    .is_none_or(|time| connection.departure > *time);
+                if reaches && improves && connection.src != target {
+                    departure.insert(connection.src, connection.departure);
+                    via.insert(connection.src, start + index);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let paths = via.keys().map(|node| {
+        let mut indices = vec![];
+        let mut current = *node;
+        while let Some(index) = via.get(&current) {
+            indices.push(*index);
+            current = connections[*index].dst;
+        }
+        (*node, path(&connections, indices, true))
+    });
+    Ok(named_paths(g, paths))
+}
+
+/// A journey from the source ending with a connection
+struct Label {
+    departure: i64,
+    arrival: i64,
+    cost: i64,
+    connection: usize,
+    previous: Option<usize>,
+}
+
+#[derive(Clone, Copy)]
+enum Criterion {
+    /// Minimum time elapsed between departure and arrival
+    Fastest,
+    /// Minimum sum of the durations of the edges
+    Shortest,
+}
+
+impl Criterion {
+    fn key(self, label: &Label) -> (i64, i64) {
+        match self {
+            Criterion::Fastest => (label.arrival - label.departure, label.arrival),
+            Criterion::Shortest => (label.cost, label.arrival),
+        }
+    }
+
+    /// Whether every journey extending `b` is no better than extending `a` instead
+    fn dominates(self, a: &Label, b: &Label) -> bool {
+        match self {
+            Criterion::Fastest => a.departure >= b.departure && a.arrival <= b.arrival,
+            Criterion::Shortest => a.cost <= b.cost && a.arrival <= b.arrival,
+        }
+    }
+}
+
+/// Keeps the labels of every node that are not dominated, a journey through a node may need to
+/// extend any of them depending on when it leaves the node
+fn label_search<G: StaticGraphViewOps>(
+    g: &G,
+    source: VID,
+    start_time: Option<i64>,
+    connections: &[Connection<G>],
+    criterion: Criterion,
+) -> HashMap<String, TemporalPath<G>> {
+    let start_time = start_time.unwrap_or(i64::MIN);
+    let mut labels: Vec<Label> = vec![];
+    let mut fronts: HashMap<VID, Vec<usize>> = HashMap::new();
+    let mut best: HashMap<VID, usize> = HashMap::new();
+
+    for (start, end) in same_departure_ranges(connections) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, connection) in connections.iter().enumerate().take(end).skip(start) {
+                if connection.dst == source {
+                    continue;
+                }
+                let extend = |previous: Option<&Label>, previous_index| match previous {
+                    None => Label {
+                        departure: connection.departure,
+                        arrival: connection.arrival,
+                        cost: connection.cost,
+                        connection: index,
+                        previous: None,
+                    },
+                    Some(previous) => Label {
+                        departure: previous.departure,
+                        arrival: connection.arrival,
+                        cost: previous.cost + connection.cost,
+                        connection: index,
+                        previous: previous_index,
+                    },
+                };
+                let candidate = if connection.src == source {
+                    (connection.departure >= start_time).then(|| extend(None, None))
+                } else {
+                    fronts
+                        .get(&connection.src)
+                        .into_iter()
+                        .flatten()
+                        .filter(|label| labels[**label].arrival <= connection.departure)
+                        .map(|label| extend(Some(&labels[*label]), Some(*label)))
+                        .min_by_key(|label| criterion.key(label))
+                };
+                let Some(candidate) = candidate else {
+                    continue;
+                };
+                let front = fronts.entry(connection.dst).or_default();
+                if front
+                    .iter()
+                    .any(|label| criterion.dominates(&labels[*label], &candidate))
+                {
+                    continue;
+                }
+                front.retain(|label| !criterion.dominates(&candidate, &labels[*label]));
+                front.push(labels.len());
+                let is_best = best.get(&connection.dst).is_none_or(|label| {
+                    criterion
+                        .key(&candidate)
+                        .cmp(&criterion.key(&labels[*label]))
+                        == Ordering::Less
+                });
+                if is_best {
+                    best.insert(connection.dst, labels.len());
+                }
+                labels.push(candidate);
+                changed = true;
+            }
+        }
+    }
+
+    let paths = best.iter().map(|(node, label)| {
+        let mut indices = vec![];
+        let mut current = Some(*label);
+        while let Some(label) = current {
+            indices.push(labels[label].connection);
+            current = labels[label].previous;
+        }
+        (*node, path(connections, indices, false))
+    });
+    named_paths(g, paths)
+}
+
+/// Finds the journeys from a source with the least time elapsed between departing the source and
+/// arriving at every node reachable from it, including the time spent waiting for connections.
+///
+/// # Arguments
+///
+/// - `g`: The graph to search in, restrict it to a window to only consider journeys within it.
+/// - `source`: The source node.
+/// - `start_time`: Journeys depart from the source at or after this time. Defaults to any time.
+/// - `duration`: The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+///
+/// # Returns
+///
+/// Returns a `HashMap` from the names of the reachable nodes to their fastest journey, ties are
+/// broken by the earliest arrival. The source is not included.
+///
+pub fn temporal_fastest_paths<G: StaticGraphViewOps, T: AsNodeRef>(
+    g: &G,
+    source: T,
+    start_time: Option<i64>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<G>>, TemporalPathError> {
+    let source = node_id(g, source)?;
+    let connections = connections(g, duration)?;
+    Ok(label_search(
+        g,
+        source,
+        start_time,
+        &connections,
+        Criterion::Fastest,
+    ))
+}
+
+/// Finds the journeys from a source with the smallest sum of the traversal durations of their
+/// edges to every node reachable from it, not counting the time spent waiting for connections.
+///
+/// # Arguments
+///
+/// - `g`: The graph to search in, restrict it to a window to only consider journeys within it.
+/// - `source`: The source node.
+/// - `start_time`: Journeys depart from the source at or after this time. Defaults to any time.
+/// - `duration`: The name of the edge property with the traversal time of the edges. If not set,
+///   edges take no time and the journeys with the fewest edges are returned.
+///
+/// # Returns
+///
+/// Returns a `HashMap` from the names of the reachable nodes to their shortest journey, ties are
+/// broken by the earliest arrival. The source is not included.
+///
+pub fn temporal_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef>(
+    g: &G,
+    source: T,
+    start_time: Option<i64>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<G>>, TemporalPathError> {
+    let source = node_id(g, source)?;
+    let connections = connections(g, duration)?;
+    Ok(label_search(
+        g,
+        source,
+        start_time,
+        &connections,
+        Criterion::Shortest,
+    ))
+}
+
+#[cfg(test)]
+mod temporal_paths_tests {
+    use super::*;
+    use crate::test_storage;
+
+    fn summary<G: StaticGraphViewOps>(
+        paths: HashMap<String, TemporalPath<G>>,
+    ) -> HashMap<String, (i64, i64, Vec<String>)> {
+        paths
+            .into_iter()
+            .map(|(node, path)| (node, (path.departure, path.arrival, path.nodes())))
+            .collect()
+    }
+
+    fn names(nodes: &[&str]) -> Vec<String> {
+        nodes.iter().map(|node| node.to_string()).collect()
+    }
+
+    /// a -> b -> d is the earliest arrival, a -> c -> d departs later and takes less time
+    fn logistics_graph() -> Graph {
+        let graph = Graph::new();
+        for (time, src, dst, duration) in [
+            (1, "a", "b", 2i64),
+            (4, "b", "d", 1),
+            (5, "a", "c", 1),
+            (6, "c", "d", 1),
+            (8, "d", "e", 3),
+            (2, "e", "a", 1),
+        ] {
+            graph
+                .add_edge(time, src, dst, [("duration", duration)], None)
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_earliest_arrival() {
+        let graph = logistics_graph();
+        test_storage!(&graph, |graph| {
+            let paths =
+                summary(temporal_earliest_arrival(graph, "a", Some(0), Some("duration")).unwrap());
+            assert_eq!(
+                paths,
+                HashMap::from([
+                    ("b".to_string(), (1, 3, names(&["a", "b"]))),
+                    ("c".to_string(), (5, 6, names(&["a", "c"]))),
+                    ("d".to_string(), (1, 5, names(&["a", "b", "d"]))),
+                    ("e".to_string(), (1, 11, names(&["a", "b", "d", "e"]))),
+                ])
+            );
+
+            // the edge a -> b departs too early
+            let paths =
+                summary(temporal_earliest_arrival(graph, "a", Some(2), Some("duration")).unwrap());
+            assert_eq!(paths["d"], (5, 7, names(&["a", "c", "d"])));
+            assert!(!paths.contains_key("b"));
+
+            // without durations the connection b -> d is missed later in the window
+            let paths =
+                summary(temporal_earliest_arrival(&graph.window(0, 5), "a", None, None).unwrap());
+            assert_eq!(paths["d"], (1, 4, names(&["a", "b", "d"])));
+            assert!(!paths.contains_key("e"));
+        });
+    }
+
+    #[test]
+    fn test_latest_departure() {
+        let graph = logistics_graph();
+        test_storage!(&graph, |graph| {
+            let paths =
+                summary(temporal_latest_departure(graph, "d", Some(7), Some("duration")).unwrap());
+            assert_eq!(
+                paths,
+                HashMap::from([
+                    ("a".to_string(), (5, 7, names(&["a", "c", "d"]))),
+                    ("b".to_string(), (4, 5, names(&["b", "d"]))),
+                    ("c".to_string(), (6, 7, names(&["c", "d"]))),
+                    ("e".to_string(), (2, 7, names(&["e", "a", "c", "d"]))),
+                ])
+            );
+
+            let paths =
+                summary(temporal_latest_departure(graph, "d", Some(6), Some("duration")).unwrap());
+            assert_eq!(paths["a"], (1, 5, names(&["a", "b", "d"])));
+            assert!(!paths.contains_key("c"));
+        });
+    }
+
+    #[test]
+    fn test_fastest_and_shortest() {
+        let graph = logistics_graph();
+        test_storage!(&graph, |graph| {
+            let paths =
+                summary(temporal_fastest_paths(graph, "a", Some(0), Some("duration")).unwrap());
+            assert_eq!(paths["d"], (5, 7, names(&["a", "c", "d"])));
+            assert_eq!(paths["e"], (5, 11, names(&["a", "c", "d", "e"])));
+
+            let paths =
+                summary(temporal_shortest_paths(graph, "a", Some(0), Some("duration")).unwrap());
+            assert_eq!(paths["d"], (5, 7, names(&["a", "c", "d"])));
+
+            // without durations the journeys with the fewest edges are the shortest
+            let graph = Graph::new();
+            graph.add_edge(1, "a", "b", NO_PROPS, None).unwrap();
+            graph.add_edge(1, "b", "c", NO_PROPS, None).unwrap();
+            graph.add_edge(3, "a", "c", NO_PROPS, None).unwrap();
+            let paths = summary(temporal_shortest_paths(&graph, "a", None, None).unwrap());
+            assert_eq!(paths["c"], (3, 3, names(&["a", "c"])));
+            let paths = summary(temporal_fastest_paths(&graph, "a", None, None).unwrap());
+            assert_eq!(paths["c"], (1, 1, names(&["a", "b", "c"])));
+        });
+    }
+
+    #[test]
+    fn test_errors() {
+        let graph = logistics_graph();
+        graph
+            .add_edge(9, "e", "f", [("duration", -1i64)], None)
+            .unwrap();
+        assert_eq!(
+            temporal_earliest_arrival(&graph, "x", None, None).unwrap_err(),
+            TemporalPathError::NodeNotFound("x".to_string())
+        );
+        assert!(matches!(
+            temporal_earliest_arrival(&graph, "a", None, Some("duration")),
+            Err(TemporalPathError::InvalidDuration { time: 9, .. })
+        ));
+        assert!(matches!(
+            temporal_fastest_paths(&graph, "a", None, Some("weight")),
+            Err(TemporalPathError::MissingDuration { .. })
+        ));
+    }
+}
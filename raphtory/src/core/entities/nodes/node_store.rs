@@ -24,6 +24,8 @@ pub struct NodeStore {
     pub(crate) vid: VID,
     // all the timestamps that have been seen by this node
     timestamps: TimeIndex<i64>,
+    // the timestamps at which this node was deleted
+    deletions: TimeIndex<i64>,
     // each layer represents a separate view of the graph
     pub(crate) layers: Vec<Adj>,
     // props for node
@@ -52,6 +54,7 @@ impl NodeStore {
             global_id,
             vid: VID(0),
             timestamps: TimeIndex::Empty,
+            deletions: TimeIndex::Empty,
             layers,
             props: None,
             node_type: 0,
@@ -63,6 +66,7 @@ impl NodeStore {
             global_id,
             vid,
             timestamps: Default::default(),
+            deletions: Default::default(),
             layers: vec![],
             props: None,
             node_type: 0,
@@ -81,6 +85,14 @@ impl NodeStore {
         self.timestamps.insert(t.t());
     }
 
+    pub fn deletions(&self) -> &TimeIndex<i64> {
+        &self.deletions
+    }
+
+    pub fn delete(&mut self, t: TimeIndexEntry) {
+        self.deletions.insert(t.t());
+    }
+
     pub fn update_node_type(&mut self, node_type: usize) -> usize {
         self.node_type = node_type;
         node_type
@@ -262,6 +262,13 @@ pub enum GraphError {
     #[error("Cache is not initialised")]
     CacheNotInnitialised,
 
+    #[cfg(feature = "proto")]
+    #[error("Checkpoint {checkpoint} is past the end of the graph file of length {len}")]
+    InvalidCheckpoint { checkpoint: u64, len: u64 },
+
+    #[error("The graph was restored to a snapshot since it was loaded, retry the update")]
+    GraphRestored,
+
     #[error("Immutable graph is .. immutable!")]
     AttemptToMutateImmutableGraph,
 
@@ -278,6 +285,8 @@ pub enum GraphError {
     SystemTimeError(#[from] SystemTimeError),
     #[error("Property filtering not implemented on PersistentGraph yet")]
     PropertyFilteringNotImplemented,
+    #[error("Invalid regular expression: {0}")]
+    InvalidRegex(#[from] regex::Error),
 }
 
 impl GraphError {
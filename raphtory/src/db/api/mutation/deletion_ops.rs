@@ -1,8 +1,10 @@
 use super::time_from_input;
 use crate::{
     core::{
-        entities::nodes::node_ref::AsNodeRef,
+        entities::{nodes::node_ref::AsNodeRef, LayerIds},
+        storage::timeindex::{TimeIndexEntry, TimeIndexOps},
         utils::{errors::GraphError, time::IntoTimeWithFormat},
+        Direction,
     },
     db::{
         api::{
@@ -10,12 +12,37 @@ use crate::{
                 internal::{InternalAdditionOps, InternalDeletionOps},
                 TryIntoInputTime,
             },
+            storage::graph::{
+                edges::edge_storage_ops::EdgeStorageOps, nodes::node_storage_ops::NodeStorageOps,
+            },
             view::StaticGraphViewOps,
         },
-        graph::edge::EdgeView,
+        graph::{edge::EdgeView, node::NodeView},
     },
 };
-use raphtory_api::core::entities::edges::edge_ref::EdgeRef;
+use raphtory_api::core::entities::{edges::edge_ref::EdgeRef, EID};
+
+/// Check if an edge layer with the given updates is alive just before `t`
+fn alive_before_entry<
+    A: TimeIndexOps<IndexType = TimeIndexEntry>,
+    D: TimeIndexOps<IndexType = TimeIndexEntry>,
+>(
+    additions: &A,
+    deletions: &D,
+    t: TimeIndexEntry,
+) -> bool {
+    let last_addition = additions.range(TimeIndexEntry::MIN..t).last();
+    let last_deletion = deletions.range(TimeIndexEntry::MIN..t).last();
+    match (last_addition, last_deletion) {
+        // an edge that is deleted before it is added is alive from the beginning of time
+        (None, None) => match (additions.first(), deletions.first()) {
+            (Some(a), Some(d)) => d < a,
+            (None, Some(_)) => true,
+            _ => false,
+        },
+        (a, d) => a > d,
+    }
+}
 
 pub trait DeletionOps:
     InternalDeletionOps + InternalAdditionOps + StaticGraphViewOps + Sized
@@ -51,4 +78,64 @@ pub trait DeletionOps:
         let time: i64 = t.parse_time(fmt)?;
         self.delete_edge(time, src, dst, layer)
     }
+
+    /// Delete a node from the graph at time `t`
+    ///
+    /// Every incident edge that is alive at time `t` is deleted in all of its layers as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time of the deletion
+    /// * `v` - The node (can be a string or integer)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use raphtory::prelude::*;
+    /// let g = PersistentGraph::new();
+    /// g.add_edge(0, "Alice", "Bob", NO_PROPS, None).unwrap();
+    /// g.delete_node(5, "Alice").unwrap();
+    /// assert!(g.at(6).node("Alice").is_none());
+    /// assert!(g.at(6).edge("Alice", "Bob").is_none());
+    /// ```
+    fn delete_node<V: AsNodeRef, T: TryIntoInputTime>(
+        &self,
+        t: T,
+        v: V,
+    ) -> Result<NodeView<Self>, GraphError> {
+        let ti = time_from_input(self, t)?;
+        let v_id = self.resolve_node(v)?.inner();
+        let edges: Vec<EID> = self
+            .core_node_entry(v_id)
+            .edges_iter(&LayerIds::All, Direction::BOTH)
+            .map(|e| e.pid())
+            .collect();
+        let alive_edges: Vec<(EID, usize)> = edges
+            .into_iter()
+            .flat_map(|eid| {
+                let edge = self.core_edge(eid);
+                edge.updates_iter(&LayerIds::All)
+                    .filter(|(_, additions, deletions)| {
+                        alive_before_entry(additions, deletions, ti)
+                    })
+                    .map(|(layer, _, _)| (eid, layer))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for (eid, layer) in alive_edges {
+            self.internal_delete_existing_edge(ti, eid, layer)?;
+        }
+        self.internal_delete_node(ti, v_id)?;
+        Ok(NodeView::new_internal(self.clone(), v_id))
+    }
+
+    fn delete_node_with_custom_time_format<V: AsNodeRef>(
+        &self,
+        t: &str,
+        fmt: &str,
+        v: V,
+    ) -> Result<NodeView<Self>, GraphError> {
+        let time: i64 = t.parse_time(fmt)?;
+        self.delete_node(time, v)
+    }
 }
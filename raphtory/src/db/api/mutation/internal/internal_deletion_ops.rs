@@ -19,6 +19,8 @@ pub trait InternalDeletionOps {
         eid: EID,
         layer: usize,
     ) -> Result<(), GraphError>;
+
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError>;
 }
 
 pub trait InheritDeletionOps: Base {}
@@ -61,4 +63,9 @@ impl<G: DelegateDeletionOps> InternalDeletionOps for G {
     ) -> Result<(), GraphError> {
         self.graph().internal_delete_existing_edge(t, eid, layer)
     }
+
+    #[inline]
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError> {
+        self.graph().internal_delete_node(t, v)
+    }
 }
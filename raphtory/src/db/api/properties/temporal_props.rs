@@ -9,6 +9,7 @@ use crate::{
 use chrono::{DateTime, NaiveDateTime, Utc};
 use raphtory_api::core::storage::arc_str::ArcStr;
 use std::{
+    cmp::Ordering,
     collections::{HashMap, HashSet},
     iter::Zip,
     sync::Arc,
@@ -59,6 +60,58 @@ impl<P: PropertiesOps> TemporalPropertyView<P> {
         self.props.temporal_value(self.id)
     }
 
+    /// The sum of the values, `None` if there are no values or they can't be added
+    pub fn sum(&self) -> Option<Prop> {
+        let mut values = self.values();
+        let first = values.next()?;
+        if !first.dtype().has_add() {
+            return None;
+        }
+        values.try_fold(first, |sum, value| sum.add(value))
+    }
+
+    /// The mean of the values as a float, `None` if there are no values or they are not numeric
+    pub fn mean(&self) -> Option<Prop> {
+        let mut count = 0usize;
+        let mut sum = 0f64;
+        for value in self.values() {
+            sum += value.as_f64()?;
+            count += 1;
+        }
+        (count > 0).then(|| Prop::F64(sum / count as f64))
+    }
+
+    /// The smallest value and the time it was set, the earliest one is returned for ties
+    pub fn min(&self) -> Option<(i64, Prop)> {
+        self.reduce_cmp(Ordering::is_le)
+    }
+
+    /// The largest value and the time it was set, the earliest one is returned for ties
+    pub fn max(&self) -> Option<(i64, Prop)> {
+        self.reduce_cmp(Ordering::is_ge)
+    }
+
+    /// The number of updates to the property
+    pub fn count(&self) -> usize {
+        self.history().count()
+    }
+
+    /// Keep the current item if `keep` returns true for its comparison with the next one
+    fn reduce_cmp(&self, keep: impl Fn(Ordering) -> bool) -> Option<(i64, Prop)> {
+        let mut iter = self.iter();
+        let first = iter.next()?;
+        if !first.1.dtype().has_cmp() {
+            return None;
+        }
+        iter.try_fold(first, |current, next| {
+            if keep(current.1.partial_cmp(&next.1)?) {
+                Some(current)
+            } else {
+                Some(next)
+            }
+        })
+    }
+
     pub fn unique(&self) -> Vec<Prop> {
         let unique_props: HashSet<_> = self.values().into_iter().collect();
         unique_props.into_iter().collect()
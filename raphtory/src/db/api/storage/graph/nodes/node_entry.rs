@@ -116,6 +116,10 @@ impl<'a, 'b: 'a> NodeStorageOps<'a> for &'a NodeStorageEntry<'b> {
         self.as_ref().additions()
     }
 
+    fn deletions(self) -> NodeAdditions<'a> {
+        self.as_ref().deletions()
+    }
+
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a> {
         self.as_ref().tprop(prop_id)
     }
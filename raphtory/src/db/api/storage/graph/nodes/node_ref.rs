@@ -75,6 +75,10 @@ impl<'a> NodeStorageOps<'a> for NodeStorageRef<'a> {
         for_all!(self, node => node.additions())
     }
 
+    fn deletions(self) -> NodeAdditions<'a> {
+        for_all!(self, node => NodeStorageOps::deletions(node))
+    }
+
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a> {
         for_all_iter!(self, node => node.tprop(prop_id))
     }
@@ -19,6 +19,8 @@ pub trait NodeStorageOps<'a>: Sized {
 
     fn additions(self) -> NodeAdditions<'a>;
 
+    fn deletions(self) -> NodeAdditions<'a>;
+
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a>;
 
     fn prop(self, prop_id: usize) -> Option<Prop>;
@@ -45,6 +47,10 @@ impl<'a> NodeStorageOps<'a> for &'a NodeStore {
         NodeAdditions::Mem(self.timestamps())
     }
 
+    fn deletions(self) -> NodeAdditions<'a> {
+        NodeAdditions::Mem(NodeStore::deletions(self))
+    }
+
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a> {
         self.temporal_property(prop_id).unwrap_or(&TProp::Empty)
     }
@@ -33,6 +33,13 @@ impl InternalDeletionOps for TemporalGraph {
             Ok(())
         })
     }
+
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError> {
+        self.update_time(t);
+        let mut node = self.storage.get_node_mut(v);
+        node.delete(t);
+        Ok(())
+    }
 }
 
 impl InternalDeletionOps for GraphStorage {
@@ -62,4 +69,12 @@ impl InternalDeletionOps for GraphStorage {
             _ => Err(GraphError::AttemptToMutateImmutableGraph),
         }
     }
+
+    #[inline]
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.internal_delete_node(t, v),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
 }
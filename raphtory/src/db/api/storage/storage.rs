@@ -381,4 +381,13 @@ impl InternalDeletionOps for Storage {
 
         Ok(())
     }
+
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError> {
+        self.graph.internal_delete_node(t, v)?;
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.delete_node(v, t));
+
+        Ok(())
+    }
 }
@@ -27,6 +27,7 @@ impl<'graph, G: GraphViewOps<'graph>> EdgePropertyFilterOps<'graph> for G {}
 #[cfg(test)]
 mod test {
     use crate::{
+        core::IntoPropMap,
         db::graph::views::property_filter::PropertyFilter,
         prelude::*,
         test_utils::{build_edge_list, build_graph_from_edge_list},
@@ -80,6 +81,197 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_filter_composite() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("test", 1i64)], None).unwrap();
+        g.add_edge(1, 2, 3, [("test", 2i64)], None).unwrap();
+        g.add_edge(2, 3, 4, [("test", 3i64)], None).unwrap();
+        g.add_edge(3, 4, 5, NO_PROPS, None).unwrap();
+        g.add_edge(0, 4, 5, [("other", "x")], None).unwrap();
+
+        let gf = g
+            .filter_edges(PropertyFilter::eq("test", 1i64).or(PropertyFilter::eq("test", 3i64)))
+            .unwrap();
+        assert_eq!(
+            gf.edges().id().collect_vec(),
+            vec![(GID::U64(1), GID::U64(2)), (GID::U64(3), GID::U64(4))]
+        );
+
+        let gf = g
+            .filter_edges(PropertyFilter::all_of([
+                PropertyFilter::gt("test", 1i64),
+                !PropertyFilter::eq("test", 3i64),
+            ]))
+            .unwrap();
+        assert_eq!(
+            gf.edges().id().collect_vec(),
+            vec![(GID::U64(2), GID::U64(3))]
+        );
+
+        // negation keeps edges without the property
+        let gf = g.filter_edges(!PropertyFilter::ge("test", 2i64)).unwrap();
+        assert_eq!(
+            gf.edges().id().collect_vec(),
+            vec![(GID::U64(1), GID::U64(2)), (GID::U64(4), GID::U64(5))]
+        );
+
+        let gf = g
+            .filter_edges(
+                PropertyFilter::is_none("test")
+                    .and(PropertyFilter::eq("other", "x"))
+                    .or(PropertyFilter::eq("test", 2i64)),
+            )
+            .unwrap();
+        assert_eq!(
+            gf.edges().id().collect_vec(),
+            vec![(GID::U64(2), GID::U64(3)), (GID::U64(4), GID::U64(5))]
+        );
+    }
+
+    #[test]
+    fn test_filter_str_predicates() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("url", "https://raphtory.com/docs")], None)
+            .unwrap();
+        g.add_edge(0, 2, 3, [("url", "http://example.com/Raphtory")], None)
+            .unwrap();
+        g.add_edge(0, 3, 4, [("url", "HTTPS://RAPHTORY.COM/DOCS")], None)
+            .unwrap();
+        g.add_edge(0, 4, 5, [("other", 1i64)], None).unwrap();
+
+        let ids = |filter: PropertyFilter| {
+            g.filter_edges(filter)
+                .unwrap()
+                .edges()
+                .id()
+                .map(|(src, _)| src.as_u64().unwrap())
+                .collect_vec()
+        };
+
+        assert_eq!(ids(PropertyFilter::starts_with("url", "https://")), [1]);
+        assert_eq!(ids(PropertyFilter::ends_with("url", "Raphtory")), [2]);
+        assert_eq!(ids(PropertyFilter::contains("url", "example")), [2]);
+        assert_eq!(
+            ids(PropertyFilter::eq_ignore_case(
+                "url",
+                "https://Raphtory.com/docs"
+            )),
+            [1, 3]
+        );
+        assert_eq!(
+            ids(PropertyFilter::regex("url", r"^https?://[a-z]+\.com").unwrap()),
+            [1, 2]
+        );
+        assert_eq!(ids(!PropertyFilter::starts_with("url", "http")), [3, 4]);
+        assert!(PropertyFilter::regex("url", "(").is_err());
+        assert!(g
+            .filter_edges(PropertyFilter::starts_with("other", "1"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_filter_list_and_map() {
+        let g = Graph::new();
+        g.add_edge(
+            0,
+            1,
+            2,
+            [("tags", Prop::from(vec![Prop::from("a"), Prop::from("b")]))],
+            None,
+        )
+        .unwrap();
+        g.add_edge(0, 2, 3, [("tags", Prop::from(vec![Prop::from("c")]))], None)
+            .unwrap();
+        g.add_edge(
+            0,
+            3,
+            4,
+            [(
+                "meta",
+                [("city", Prop::from("London")), ("score", Prop::I64(3))].into_prop_map(),
+            )],
+            None,
+        )
+        .unwrap();
+        g.add_edge(
+            0,
+            4,
+            5,
+            [("meta", [("city", "Paris")].into_prop_map())],
+            None,
+        )
+        .unwrap();
+
+        let ids = |filter: PropertyFilter| {
+            g.filter_edges(filter)
+                .unwrap()
+                .edges()
+                .id()
+                .map(|(src, _)| src.as_u64().unwrap())
+                .collect_vec()
+        };
+
+        assert_eq!(ids(PropertyFilter::contains("tags", "b")), [1]);
+        assert_eq!(ids(!PropertyFilter::contains("tags", "b")), [2, 3, 4]);
+        assert_eq!(
+            ids(PropertyFilter::map_lookup(
+                "meta",
+                PropertyFilter::eq("city", "London")
+            )),
+            [3]
+        );
+        assert_eq!(
+            ids(PropertyFilter::map_lookup(
+                "meta",
+                PropertyFilter::is_none("score").and(PropertyFilter::starts_with("city", "P"))
+            )),
+            [4]
+        );
+        assert!(g
+            .filter_edges(PropertyFilter::map_lookup(
+                "tags",
+                PropertyFilter::is_some("a")
+            ))
+            .is_err());
+    }
+
+    #[test]
+    fn test_filter_composite_matches_stacked() {
+        proptest!(|(
+            edges in build_edge_list(100, 100), v1 in any::<i64>(), v2 in any::<i64>()
+        )| {
+            let g = build_graph_from_edge_list(&edges);
+            let composite = g
+                .filter_edges(PropertyFilter::gt("int_prop", v1).and(PropertyFilter::lt("int_prop", v2)))
+                .unwrap();
+            let stacked = g
+                .filter_edges(PropertyFilter::gt("int_prop", v1))
+                .unwrap()
+                .filter_edges(PropertyFilter::lt("int_prop", v2))
+                .unwrap();
+            assert_eq!(composite.edges().id().collect_vec(), stacked.edges().id().collect_vec());
+        })
+    }
+
+    #[test]
+    fn test_filter_matches_edge_views() {
+        proptest!(|(
+            edges in build_edge_list(100, 100), v1 in any::<i64>(), v2 in any::<i64>()
+        )| {
+            let g = build_graph_from_edge_list(&edges);
+            let filter = PropertyFilter::gt("int_prop", v1).or(PropertyFilter::lt("int_prop", v2));
+            let expected = g.filter_edges(filter.clone()).unwrap().edges().id().collect_vec();
+            let matched = g
+                .edges()
+                .iter()
+                .filter(|e| filter.matches(|name| e.properties().get(name)))
+                .map(|e| e.id())
+                .collect_vec();
+            assert_eq!(matched, expected);
+        })
+    }
+
     #[test]
     fn test_filter_gt() {
         proptest!(|(
@@ -159,6 +159,70 @@ mod test {
         })
     }
 
+    #[test]
+    fn test_filter_composite() {
+        proptest!(|(
+            edges in build_edge_list(100, 100), v1 in any::<i64>(), v2 in any::<i64>()
+        )| {
+            let g = build_graph_from_edge_list(&edges);
+            let filter = PropertyFilter::lt("int_prop", v1)
+                .or(PropertyFilter::gt("int_prop", v2))
+                .and(!PropertyFilter::eq("int_prop", 0i64));
+            let filtered = g.filter_exploded_edges(filter).unwrap();
+            let expected_filtered_g =
+                build_filtered_graph(&edges, |vv| (vv < v1 || vv > v2) && vv != 0);
+            assert_graph_equal(&filtered, &expected_filtered_g);
+        })
+    }
+
+    #[test]
+    fn test_filter_composite_str_and_int() {
+        let g = Graph::new();
+        g.add_edge(
+            0,
+            1,
+            2,
+            [("int_prop", Prop::I64(1)), ("str_prop", "a".into())],
+            None,
+        )
+        .unwrap();
+        g.add_edge(1, 1, 2, [("int_prop", 2i64)], None).unwrap();
+        g.add_edge(
+            2,
+            2,
+            3,
+            [("int_prop", Prop::I64(2)), ("str_prop", "b".into())],
+            None,
+        )
+        .unwrap();
+
+        let filtered = g
+            .filter_exploded_edges(
+                PropertyFilter::eq("int_prop", 2i64).and(PropertyFilter::is_some("str_prop")),
+            )
+            .unwrap();
+        assert_eq!(
+            filtered.edges().explode().earliest_time().collect_vec(),
+            vec![Some(2)]
+        );
+
+        let filtered = g
+            .filter_exploded_edges(PropertyFilter::any_of([
+                PropertyFilter::eq("str_prop", "a"),
+                PropertyFilter::eq("str_prop", "b"),
+            ]))
+            .unwrap();
+        assert_eq!(
+            filtered.edges().explode().earliest_time().collect_vec(),
+            vec![Some(0), Some(2)]
+        );
+
+        // type errors are reported for nested filters
+        assert!(g
+            .filter_exploded_edges(!PropertyFilter::eq("int_prop", "a"))
+            .is_err());
+    }
+
     #[test]
     fn test_filter_window() {
         proptest!(|(
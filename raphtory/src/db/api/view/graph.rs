@@ -1,7 +1,7 @@
 use crate::{
     core::{
         entities::{graph::tgraph::TemporalGraph, nodes::node_ref::AsNodeRef, LayerIds, VID},
-        storage::timeindex::AsTime,
+        storage::timeindex::{AsTime, TimeIndexOps},
         utils::errors::GraphError,
     },
     db::{
@@ -243,6 +243,18 @@ impl<'graph, G: BoxableGraphView + Sized + Clone + 'graph> GraphViewOps<'graph>
                         for t in node.history() {
                             new_node.update_time(TimeIndexEntry::start(t));
                         }
+                        if self.include_deletions() {
+                            let start = self.view_start().unwrap_or(i64::MIN);
+                            let end = self.view_end().unwrap_or(i64::MAX);
+                            for t in self
+                                .core_node_entry(node.node)
+                                .deletions()
+                                .range(start..end)
+                                .iter()
+                            {
+                                new_node.delete(TimeIndexEntry::start(t));
+                            }
+                        }
                         for t_prop_id in node.temporal_prop_ids() {
                             for (t, prop_value) in
                                 self.temporal_node_prop_hist(node.node, t_prop_id)
@@ -115,6 +115,13 @@ impl InternalDeletionOps for MaterializedGraph {
             MaterializedGraph::PersistentGraph(g) => g.internal_delete_existing_edge(t, eid, layer),
         }
     }
+
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError> {
+        match self {
+            MaterializedGraph::EventGraph(_) => Err(EventGraphDeletionsNotSupported),
+            MaterializedGraph::PersistentGraph(g) => g.internal_delete_node(t, v),
+        }
+    }
 }
 
 impl DeletionOps for MaterializedGraph {}
@@ -0,0 +1,139 @@
+use crate::{
+    core::utils::errors::GraphError,
+    db::{
+        api::view::internal::OneHopFilter,
+        graph::views::property_filter::internal::InternalNodeFilterOps,
+    },
+    prelude::GraphViewOps,
+};
+
+pub trait NodePropertyFilterOps<'graph>: OneHopFilter<'graph> {
+    /// Restrict the view to nodes whose property value at the end of the view passes the filter
+    ///
+    /// Temporal properties take precedence over constant properties with the same name.
+    /// Edges are only included if both their endpoints pass the filter.
+    fn filter_nodes<F: InternalNodeFilterOps>(
+        &self,
+        filter: F,
+    ) -> Result<Self::Filtered<F::NodeFiltered<'graph, Self::FilteredGraph>>, GraphError> {
+        Ok(self.one_hop_filtered(filter.create_node_filter(self.current_filter().clone())?))
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> NodePropertyFilterOps<'graph> for G {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::utils::errors::GraphError,
+        db::graph::views::{deletion_graph::PersistentGraph, property_filter::PropertyFilter},
+        prelude::*,
+    };
+    use itertools::Itertools;
+
+    fn build_graph() -> Graph {
+        let g = Graph::new();
+        g.add_node(0, 1, [("country", "UK")], None).unwrap();
+        g.add_node(0, 2, [("country", "UK")], None).unwrap();
+        g.add_node(0, 3, [("country", "US")], None).unwrap();
+        g.add_node(2, 2, [("country", "US")], None).unwrap();
+        g.add_edge(1, 1, 2, NO_PROPS, Some("a")).unwrap();
+        g.add_edge(1, 1, 3, NO_PROPS, Some("b")).unwrap();
+        g.add_edge(1, 2, 3, NO_PROPS, Some("a")).unwrap();
+        g
+    }
+
+    #[test]
+    fn test_filter_nodes() {
+        let g = build_graph();
+        let gf = g.filter_nodes(PropertyFilter::eq("country", "UK")).unwrap();
+        assert_eq!(gf.nodes().id().collect_vec(), vec![GID::U64(1)]);
+        assert_eq!(gf.count_edges(), 0);
+
+        let gf = g.filter_nodes(PropertyFilter::eq("country", "US")).unwrap();
+        assert_eq!(
+            gf.nodes().id().collect_vec(),
+            vec![GID::U64(2), GID::U64(3)]
+        );
+        assert_eq!(
+            gf.edges().id().collect_vec(),
+            vec![(GID::U64(2), GID::U64(3))]
+        );
+    }
+
+    #[test]
+    fn test_filter_nodes_window() {
+        let g = build_graph();
+        let gf = g
+            .window(0, 2)
+            .filter_nodes(PropertyFilter::eq("country", "UK"))
+            .unwrap();
+        assert_eq!(
+            gf.nodes().id().collect_vec(),
+            vec![GID::U64(1), GID::U64(2)]
+        );
+        assert_eq!(
+            gf.edges().id().collect_vec(),
+            vec![(GID::U64(1), GID::U64(2))]
+        );
+
+        // filter is evaluated against the view it is applied to
+        let gf = g
+            .filter_nodes(PropertyFilter::eq("country", "UK"))
+            .unwrap()
+            .window(0, 2);
+        assert_eq!(gf.nodes().id().collect_vec(), vec![GID::U64(1)]);
+    }
+
+    #[test]
+    fn test_filter_nodes_layers() {
+        let g = build_graph();
+        let gf = g
+            .layers("b")
+            .unwrap()
+            .filter_nodes(PropertyFilter::ne("country", "UK"))
+            .unwrap();
+        assert_eq!(
+            gf.nodes().id().collect_vec(),
+            vec![GID::U64(2), GID::U64(3)]
+        );
+        assert_eq!(gf.count_edges(), 0);
+    }
+
+    #[test]
+    fn test_filter_nodes_constant_and_missing() {
+        let g = Graph::new();
+        g.add_node(0, 1, NO_PROPS, None)
+            .unwrap()
+            .add_constant_properties([("score", 3i64)])
+            .unwrap();
+        g.add_node(0, 2, NO_PROPS, None).unwrap();
+
+        let gf = g.filter_nodes(PropertyFilter::ge("score", 2i64)).unwrap();
+        assert_eq!(gf.nodes().id().collect_vec(), vec![GID::U64(1)]);
+        let gf = g.filter_nodes(PropertyFilter::is_none("score")).unwrap();
+        assert_eq!(gf.nodes().id().collect_vec(), vec![GID::U64(2)]);
+        assert!(matches!(
+            g.filter_nodes(PropertyFilter::eq("score", "high")),
+            Err(GraphError::PropertyTypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_filter_nodes_persistent() {
+        let g = PersistentGraph::new();
+        g.add_node(0, 1, [("country", "UK")], None).unwrap();
+        g.add_node(2, 1, [("country", "US")], None).unwrap();
+
+        let gf = g
+            .window(1, 2)
+            .filter_nodes(PropertyFilter::eq("country", "UK"))
+            .unwrap();
+        assert_eq!(gf.nodes().id().collect_vec(), vec![GID::U64(1)]);
+        let gf = g
+            .window(1, 3)
+            .filter_nodes(PropertyFilter::eq("country", "UK"))
+            .unwrap();
+        assert!(gf.nodes().is_empty());
+    }
+}
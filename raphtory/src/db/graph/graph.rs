@@ -3204,6 +3204,35 @@ mod db_tests {
         );
     }
 
+    #[test]
+    fn test_temporal_property_aggregates() {
+        let g = Graph::new();
+        g.add_node(1, 1, [("weight", 4i64)], None).unwrap();
+        g.add_node(2, 1, [("weight", 1i64)], None).unwrap();
+        g.add_node(3, 1, [("weight", 7i64)], None).unwrap();
+        g.add_node(4, 1, [("weight", 1i64)], None).unwrap();
+        g.add_node(5, 1, [("status", "open")], None).unwrap();
+
+        let props = g.node(1).unwrap().properties().temporal();
+        let weight = props.get("weight").unwrap();
+        assert_eq!(weight.sum(), Some(Prop::I64(13)));
+        assert_eq!(weight.mean(), Some(Prop::F64(3.25)));
+        assert_eq!(weight.min(), Some((2, Prop::I64(1))));
+        assert_eq!(weight.max(), Some((3, Prop::I64(7))));
+        assert_eq!(weight.count(), 4);
+
+        let windowed = g.window(2, 4).node(1).unwrap().properties().temporal();
+        let weight = windowed.get("weight").unwrap();
+        assert_eq!(weight.sum(), Some(Prop::I64(8)));
+        assert_eq!(weight.mean(), Some(Prop::F64(4.0)));
+        assert_eq!(weight.count(), 2);
+
+        let status = props.get("status").unwrap();
+        assert_eq!(status.mean(), None);
+        assert_eq!(status.min(), Some((5, Prop::str("open"))));
+        assert_eq!(status.count(), 1);
+    }
+
     #[test]
     fn num_locks_same_as_threads() {
         let pool = rayon::ThreadPoolBuilder::new()
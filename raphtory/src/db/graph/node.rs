@@ -404,6 +404,14 @@ impl<G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps>
     }
 }
 
+impl<G: DeletionOps> NodeView<G, G> {
+    /// Delete the node at time `t`, including all of its incident edges that are alive at `t`
+    pub fn delete<T: TryIntoInputTime>(&self, t: T) -> Result<(), GraphError> {
+        self.graph.delete_node(t, self.node)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod node_test {
     use crate::{prelude::*, test_utils::test_graph};
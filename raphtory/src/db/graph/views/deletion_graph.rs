@@ -110,6 +110,59 @@ fn edge_alive_at_start(e: EdgeStorageRef, t: i64, layer_ids: &LayerIds) -> bool
         .any(|(_, additions, deletions)| alive_at(&additions, &deletions, t))
 }
 
+/// Check if a node is alive just before `t`.
+///
+/// Node updates only carry the timestamp, so a deletion wins over an addition at the same time.
+fn node_alive_before<
+    A: TimeIndexOps<IndexType = i64> + ?Sized,
+    D: TimeIndexOps<IndexType = i64> + ?Sized,
+>(
+    additions: &A,
+    deletions: &D,
+    t: i64,
+) -> bool {
+    let last_addition_before_start = additions.range(i64::MIN..t).last();
+    let last_deletion_before_start = deletions.range(i64::MIN..t).last();
+    match (last_addition_before_start, last_deletion_before_start) {
+        (None, None) => match (additions.first(), deletions.first()) {
+            (Some(a), Some(d)) => d < a,
+            (None, Some(_)) => true,
+            _ => false,
+        },
+        (a, d) => a > d,
+    }
+}
+
+fn node_alive_at<
+    A: TimeIndexOps<IndexType = i64> + ?Sized,
+    D: TimeIndexOps<IndexType = i64> + ?Sized,
+>(
+    additions: &A,
+    deletions: &D,
+    t: i64,
+) -> bool {
+    !deletions.active(t..t.saturating_add(1)) && node_alive_before(additions, deletions, t)
+}
+
+/// The first time in the window `w` at which the node is alive
+fn node_first_alive_in_window<
+    A: TimeIndexOps<IndexType = i64> + ?Sized,
+    D: TimeIndexOps<IndexType = i64> + ?Sized,
+>(
+    additions: &A,
+    deletions: &D,
+    w: Range<i64>,
+) -> Option<i64> {
+    if node_alive_at(additions, deletions, w.start) {
+        Some(w.start)
+    } else {
+        additions
+            .range(w)
+            .iter()
+            .find(|&t| !deletions.active(t..t.saturating_add(1)))
+    }
+}
+
 impl PersistentGraph {
     pub fn new() -> Self {
         Self::default()
@@ -176,11 +229,22 @@ impl InheritNodeFilterOps for PersistentGraph {}
 
 impl TimeSemantics for PersistentGraph {
     fn node_earliest_time(&self, v: VID) -> Option<i64> {
-        self.0.node_earliest_time(v)
+        let node = self.core_node_entry(v);
+        if node_alive_at(&node.additions(), &node.deletions(), i64::MIN) {
+            Some(i64::MIN)
+        } else {
+            self.0.node_earliest_time(v)
+        }
     }
 
-    fn node_latest_time(&self, _v: VID) -> Option<i64> {
-        Some(i64::MAX)
+    fn node_latest_time(&self, v: VID) -> Option<i64> {
+        let node = self.core_node_entry(v);
+        let deletions = node.deletions();
+        if node_alive_before(&node.additions(), &deletions, i64::MAX) {
+            Some(i64::MAX)
+        } else {
+            deletions.last()
+        }
     }
 
     fn view_start(&self) -> Option<i64> {
@@ -217,17 +281,21 @@ impl TimeSemantics for PersistentGraph {
     fn node_earliest_time_window(&self, v: VID, start: i64, end: i64) -> Option<i64> {
         let v = self.core_node_entry(v);
         let additions = v.additions();
-        if additions.first_t()? <= start {
-            Some(additions.range_t(start..end).first_t().unwrap_or(start))
-        } else {
-            None
-        }
+        let deletions = v.deletions();
+        node_first_alive_in_window(&additions, &deletions, start..end)
     }
 
-    fn node_latest_time_window(&self, v: VID, _start: i64, end: i64) -> Option<i64> {
+    fn node_latest_time_window(&self, v: VID, start: i64, end: i64) -> Option<i64> {
         let v = self.core_node_entry(v);
-        if v.additions().first_t()? < end {
-            Some(end - 1)
+        let additions = v.additions();
+        let deletions = v.deletions();
+        if node_alive_before(&additions, &deletions, end) {
+            return Some(end - 1);
+        }
+        // node was deleted, latest time is the last deletion if the node was alive in the window
+        let last_deletion = deletions.range(start..end).last()?;
+        if last_deletion > start || additions.active(start..end) {
+            Some(last_deletion)
         } else {
             None
         }
@@ -239,7 +307,7 @@ impl TimeSemantics for PersistentGraph {
         w: Range<i64>,
         _layer_ids: &LayerIds,
     ) -> bool {
-        node.additions().first_t().filter(|&t| t < w.end).is_some()
+        node_first_alive_in_window(&node.additions(), &node.deletions(), w).is_some()
     }
 
     fn include_edge_window(
@@ -1345,6 +1413,91 @@ mod test_deletions {
         assert_eq!(g.window(0, 0).count_nodes(), 0);
     }
 
+    #[test]
+    fn test_node_deletion() {
+        let g = PersistentGraph::new();
+        g.add_node(1, 1, NO_PROPS, None).unwrap();
+        g.delete_node(5, 1).unwrap();
+
+        assert!(g.window(0, 1).node(1).is_none());
+        assert!(g.window(0, 2).node(1).is_some());
+        assert!(g.window(4, 5).node(1).is_some());
+        assert!(g.window(5, 10).node(1).is_none());
+        assert!(g.at(4).node(1).is_some());
+        assert!(g.at(5).node(1).is_none());
+
+        // the unwindowed graph still contains the node
+        assert!(g.node(1).is_some());
+        assert_eq!(g.node(1).unwrap().latest_time(), Some(5));
+        assert_eq!(g.window(2, 10).node(1).unwrap().earliest_time(), Some(2));
+        assert_eq!(g.window(2, 10).node(1).unwrap().latest_time(), Some(5));
+        assert_eq!(g.window(2, 4).node(1).unwrap().latest_time(), Some(3));
+
+        // node comes back after being re-added
+        g.add_node(8, 1, NO_PROPS, None).unwrap();
+        assert!(g.window(5, 8).node(1).is_none());
+        assert!(g.window(5, 9).node(1).is_some());
+        assert_eq!(g.window(5, 10).node(1).unwrap().earliest_time(), Some(8));
+        assert_eq!(g.node(1).unwrap().latest_time(), Some(i64::MAX));
+
+        let gm = g.window(0, 7).materialize().unwrap();
+        assert!(gm.at(6).node(1).is_none());
+        assert!(gm.at(4).node(1).is_some());
+    }
+
+    #[test]
+    fn test_node_deletion_only() {
+        let g = PersistentGraph::new();
+        g.delete_node(5, 1).unwrap();
+
+        assert!(g.window(0, 1).node(1).is_some());
+        assert!(g.at(4).node(1).is_some());
+        assert!(g.at(5).node(1).is_none());
+        assert_eq!(g.node(1).unwrap().earliest_time(), Some(i64::MIN));
+        assert_eq!(g.node(1).unwrap().latest_time(), Some(5));
+    }
+
+    #[test]
+    fn test_node_deletion_cascades_to_edges() {
+        let g = PersistentGraph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.add_edge(2, 3, 1, NO_PROPS, Some("a")).unwrap();
+        g.add_edge(2, 2, 3, NO_PROPS, None).unwrap();
+        g.delete_edge(3, 3, 1, Some("a")).unwrap();
+        g.delete_node(5, 1).unwrap();
+
+        assert_eq!(
+            g.edge(1, 2)
+                .unwrap()
+                .deletions()
+                .iter()
+                .copied()
+                .collect_vec(),
+            [5]
+        );
+        // edge was already deleted, no further deletion is added
+        assert_eq!(
+            g.edge(3, 1)
+                .unwrap()
+                .deletions()
+                .iter()
+                .copied()
+                .collect_vec(),
+            [3]
+        );
+        assert!(g.edge(2, 3).unwrap().deletions().is_empty());
+
+        let gw = g.at(5);
+        assert_eq!(gw.nodes().id().collect_vec(), [GID::U64(2), GID::U64(3)]);
+        assert_eq!(gw.edges().id().collect_vec(), [(GID::U64(2), GID::U64(3))]);
+        assert_eq!(g.at(4).count_edges(), 2);
+
+        // edges added after the node deletion are not affected
+        g.add_edge(10, 1, 2, NO_PROPS, None).unwrap();
+        assert!(g.at(10).edge(1, 2).is_some());
+        assert!(g.at(10).node(1).is_some());
+    }
+
     // #[test]
     // fn test_earliest_latest_only_deletion() {
     //     let g = PersistentGraph::new();
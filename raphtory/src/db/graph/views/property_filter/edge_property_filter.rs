@@ -18,7 +18,7 @@ use crate::{
         },
         graph::{
             edge::EdgeView,
-            views::property_filter::{internal::InternalEdgeFilterOps, PropertyValueFilter},
+            views::property_filter::{internal::InternalEdgeFilterOps, ResolvedPropertyFilter},
         },
     },
     prelude::{EdgeViewOps, GraphViewOps, PropertyFilter},
@@ -27,24 +27,15 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct EdgePropertyFilteredGraph<G> {
     graph: G,
-    t_prop_id: Option<usize>,
-    c_prop_id: Option<usize>,
-    filter: PropertyValueFilter,
+    filter: ResolvedPropertyFilter<(Option<usize>, Option<usize>)>,
 }
 
 impl<'graph, G> EdgePropertyFilteredGraph<G> {
     pub(crate) fn new(
         graph: G,
-        t_prop_id: Option<usize>,
-        c_prop_id: Option<usize>,
-        filter: PropertyValueFilter,
+        filter: ResolvedPropertyFilter<(Option<usize>, Option<usize>)>,
     ) -> Self {
-        Self {
-            graph,
-            t_prop_id,
-            c_prop_id,
-            filter,
-        }
+        Self { graph, filter }
     }
 }
 
@@ -73,18 +64,12 @@ impl InternalEdgeFilterOps for PropertyFilter {
         self,
         graph: G,
     ) -> Result<Self::EdgeFiltered<'graph, G>, GraphError> {
-        let (t_prop_id, c_prop_id) = match &self.filter {
-            PropertyValueFilter::ByValue(filter) => {
-                get_ids_and_check_type(graph.edge_meta(), &self.name, filter.dtype())?
-            }
-            _ => get_ids(graph.edge_meta(), &self.name),
-        };
-        Ok(EdgePropertyFilteredGraph::new(
-            graph,
-            t_prop_id,
-            c_prop_id,
-            self.filter,
-        ))
+        let meta = graph.edge_meta();
+        let filter = self.resolve(&|name, dtype| match dtype {
+            Some(dtype) => get_ids_and_check_type(meta, name, dtype),
+            None => Ok(get_ids(meta, name)),
+        })?;
+        Ok(EdgePropertyFilteredGraph::new(graph, filter))
     }
 }
 
@@ -123,19 +108,16 @@ impl<'graph, G: GraphViewOps<'graph>> EdgeFilterOps for EdgePropertyFilteredGrap
     fn filter_edge(&self, edge: EdgeStorageRef, layer_ids: &LayerIds) -> bool {
         if self.graph.filter_edge(edge, layer_ids) {
             let props = EdgeView::new(&self.graph, edge.out_ref()).properties();
-            let prop_value = self
-                .t_prop_id
-                .and_then(|prop_id| {
-                    props
-                        .temporal()
-                        .get_by_id(prop_id)
-                        .and_then(|prop_view| prop_view.latest())
-                })
-                .or_else(|| {
-                    self.c_prop_id
-                        .and_then(|prop_id| props.constant().get_by_id(prop_id))
-                });
-            self.filter.filter(prop_value.as_ref())
+            self.filter.matches(&|&(t_prop_id, c_prop_id)| {
+                t_prop_id
+                    .and_then(|prop_id| {
+                        props
+                            .temporal()
+                            .get_by_id(prop_id)
+                            .and_then(|prop_view| prop_view.latest())
+                    })
+                    .or_else(|| c_prop_id.and_then(|prop_id| props.constant().get_by_id(prop_id)))
+            })
         } else {
             false
         }
@@ -21,7 +21,7 @@ use crate::{
             },
         },
         graph::views::property_filter::{
-            internal::InternalExplodedEdgeFilterOps, PropertyValueFilter,
+            internal::InternalExplodedEdgeFilterOps, ResolvedPropertyFilter,
         },
     },
     prelude::{GraphViewOps, PropertyFilter},
@@ -35,32 +35,21 @@ use std::ops::Range;
 #[derive(Debug, Clone)]
 pub struct ExplodedEdgePropertyFilteredGraph<G> {
     graph: G,
-    prop_id: Option<usize>,
-    filter: PropertyValueFilter,
+    filter: ResolvedPropertyFilter<Option<usize>>,
 }
 
 impl<G> Static for ExplodedEdgePropertyFilteredGraph<G> {}
 impl<G> Immutable for ExplodedEdgePropertyFilteredGraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> ExplodedEdgePropertyFilteredGraph<G> {
-    pub(crate) fn new(
-        graph: G,
-        prop_id: Option<usize>,
-        filter: impl Into<PropertyValueFilter>,
-    ) -> Self {
-        Self {
-            graph,
-            prop_id,
-            filter: filter.into(),
-        }
+    pub(crate) fn new(graph: G, filter: ResolvedPropertyFilter<Option<usize>>) -> Self {
+        Self { graph, filter }
     }
 
     fn filter(&self, e: EdgeRef, t: TimeIndexEntry, layer_ids: &LayerIds) -> bool {
-        self.filter.filter(
-            self.prop_id
-                .and_then(|prop_id| self.graph.temporal_edge_prop_at(e, prop_id, t, layer_ids))
-                .as_ref(),
-        )
+        self.filter.matches(&|prop_id| {
+            prop_id.and_then(|prop_id| self.graph.temporal_edge_prop_at(e, prop_id, t, layer_ids))
+        })
     }
 }
 
@@ -88,16 +77,14 @@ impl InternalExplodedEdgeFilterOps for PropertyFilter {
         self,
         graph: G,
     ) -> Result<Self::ExplodedEdgeFiltered<'graph, G>, GraphError> {
-        let t_prop_id = match &self.filter {
-            PropertyValueFilter::ByValue(filter) => {
-                get_id_and_check_type(graph.edge_meta(), &self.name, filter.dtype())?
-            }
-            _ => get_id(graph.edge_meta(), &self.name),
-        };
+        let meta = graph.edge_meta();
+        let filter = self.resolve(&|name, dtype| match dtype {
+            Some(dtype) => get_id_and_check_type(meta, name, dtype),
+            None => Ok(get_id(meta, name)),
+        })?;
         Ok(ExplodedEdgePropertyFilteredGraph::new(
             graph.clone(),
-            t_prop_id,
-            self.filter,
+            filter,
         ))
     }
 }
@@ -22,3 +22,14 @@ pub trait InternalExplodedEdgeFilterOps: Sized {
         graph: G,
     ) -> Result<Self::ExplodedEdgeFiltered<'graph, G>, GraphError>;
 }
+
+pub trait InternalNodeFilterOps: Sized {
+    type NodeFiltered<'graph, G: GraphViewOps<'graph>>: GraphViewOps<'graph>
+    where
+        Self: 'graph;
+
+    fn create_node_filter<'graph, G: GraphViewOps<'graph>>(
+        self,
+        graph: G,
+    ) -> Result<Self::NodeFiltered<'graph, G>, GraphError>;
+}
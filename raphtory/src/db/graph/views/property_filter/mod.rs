@@ -1,9 +1,12 @@
-use crate::core::{Prop, PropType};
-use std::{collections::HashSet, sync::Arc};
+use crate::core::{utils::errors::GraphError, Prop, PropType};
+use raphtory_api::core::storage::arc_str::ArcStr;
+use regex::Regex;
+use std::{collections::HashSet, convert::Infallible, ops::Not, sync::Arc};
 
 pub mod edge_property_filter;
 pub mod exploded_edge_property_filter;
 pub(crate) mod internal;
+pub mod node_property_filter;
 
 #[derive(Debug, Clone)]
 pub(crate) struct PropValueCmp {
@@ -18,21 +21,116 @@ pub(crate) enum PropertyValueFilter {
     HasNot,
     In(Arc<HashSet<Prop>>),
     NotIn(Arc<HashSet<Prop>>),
+    Str(StrFilter),
+    Contains(Prop),
+    MapLookup(Arc<ResolvedPropertyFilter<ArcStr>>),
 }
 
+#[derive(Debug, Clone)]
+pub(crate) enum StrFilter {
+    StartsWith(String),
+    EndsWith(String),
+    /// the value is stored in lowercase
+    EqIgnoreCase(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum FilterExpr {
+    Property {
+        name: String,
+        filter: PropertyValueFilter,
+    },
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+    Not(Box<FilterExpr>),
+}
+
+/// A filter on property values
+///
+/// Filters on a single property are constructed with the associated functions (e.g.,
+/// [`PropertyFilter::eq`]) and can be combined with [`PropertyFilter::and`], [`PropertyFilter::or`]
+/// and `!` into arbitrarily nested expressions that are evaluated in a single pass.
 #[derive(Debug, Clone)]
 pub struct PropertyFilter {
-    name: String,
-    filter: PropertyValueFilter,
+    expr: FilterExpr,
+}
+
+/// A `PropertyFilter` where property names are resolved to ids for a particular graph
+#[derive(Debug, Clone)]
+pub(crate) enum ResolvedPropertyFilter<Id> {
+    Property { id: Id, filter: PropertyValueFilter },
+    And(Vec<ResolvedPropertyFilter<Id>>),
+    Or(Vec<ResolvedPropertyFilter<Id>>),
+    Not(Box<ResolvedPropertyFilter<Id>>),
 }
 
 impl PropertyFilter {
     fn new(name: impl Into<String>, filter: PropertyValueFilter) -> Self {
         Self {
-            name: name.into(),
-            filter,
+            expr: FilterExpr::Property {
+                name: name.into(),
+                filter,
+            },
+        }
+    }
+
+    /// Keep entities that pass both `self` and `other`
+    pub fn and(self, other: PropertyFilter) -> Self {
+        let expr = match (self.expr, other.expr) {
+            (FilterExpr::And(mut left), FilterExpr::And(right)) => {
+                left.extend(right);
+                FilterExpr::And(left)
+            }
+            (FilterExpr::And(mut left), right) => {
+                left.push(right);
+                FilterExpr::And(left)
+            }
+            (left, right) => FilterExpr::And(vec![left, right]),
+        };
+        Self { expr }
+    }
+
+    /// Keep entities that pass `self` or `other`
+    pub fn or(self, other: PropertyFilter) -> Self {
+        let expr = match (self.expr, other.expr) {
+            (FilterExpr::Or(mut left), FilterExpr::Or(right)) => {
+                left.extend(right);
+                FilterExpr::Or(left)
+            }
+            (FilterExpr::Or(mut left), right) => {
+                left.push(right);
+                FilterExpr::Or(left)
+            }
+            (left, right) => FilterExpr::Or(vec![left, right]),
+        };
+        Self { expr }
+    }
+
+    /// Keep entities that pass all the `filters` (an empty list keeps everything)
+    pub fn all_of(filters: impl IntoIterator<Item = PropertyFilter>) -> Self {
+        Self {
+            expr: FilterExpr::And(filters.into_iter().map(|f| f.expr).collect()),
         }
     }
+
+    /// Keep entities that pass at least one of the `filters` (an empty list keeps nothing)
+    pub fn any_of(filters: impl IntoIterator<Item = PropertyFilter>) -> Self {
+        Self {
+            expr: FilterExpr::Or(filters.into_iter().map(|f| f.expr).collect()),
+        }
+    }
+
+    /// Resolve property names to ids
+    ///
+    /// `resolve_id` is called with the property name and, for filters that compare against a
+    /// value, the type of that value.
+    pub(crate) fn resolve<Id, E>(
+        &self,
+        resolve_id: &impl Fn(&str, Option<PropType>) -> Result<Id, E>,
+    ) -> Result<ResolvedPropertyFilter<Id>, E> {
+        self.expr.resolve(resolve_id)
+    }
     pub fn eq(name: impl Into<String>, value: impl Into<Prop>) -> Self {
         Self::new(
             name,
@@ -107,6 +205,127 @@ impl PropertyFilter {
         let filter = PropertyValueFilter::Has;
         Self::new(name, filter)
     }
+
+    /// Keep entities where the string property starts with `prefix`
+    pub fn starts_with(name: impl Into<String>, prefix: impl Into<String>) -> Self {
+        let filter = PropertyValueFilter::Str(StrFilter::StartsWith(prefix.into()));
+        Self::new(name, filter)
+    }
+
+    /// Keep entities where the string property ends with `suffix`
+    pub fn ends_with(name: impl Into<String>, suffix: impl Into<String>) -> Self {
+        let filter = PropertyValueFilter::Str(StrFilter::EndsWith(suffix.into()));
+        Self::new(name, filter)
+    }
+
+    /// Keep entities where the string property is equal to `value` ignoring case
+    pub fn eq_ignore_case(name: impl Into<String>, value: impl AsRef<str>) -> Self {
+        let filter =
+            PropertyValueFilter::Str(StrFilter::EqIgnoreCase(value.as_ref().to_lowercase()));
+        Self::new(name, filter)
+    }
+
+    /// Keep entities where the string property matches the regular expression `pattern`
+    ///
+    /// The pattern is not anchored, use `^` and `$` to match the full string.
+    pub fn regex(name: impl Into<String>, pattern: &str) -> Result<Self, GraphError> {
+        let filter = PropertyValueFilter::Str(StrFilter::Regex(Regex::new(pattern)?));
+        Ok(Self::new(name, filter))
+    }
+
+    /// Keep entities where the property contains `value`
+    ///
+    /// For string properties this checks for a substring, for list properties it checks if `value`
+    /// is an element of the list.
+    pub fn contains(name: impl Into<String>, value: impl Into<Prop>) -> Self {
+        let filter = PropertyValueFilter::Contains(value.into());
+        Self::new(name, filter)
+    }
+
+    /// Keep entities where the map property passes `filter`
+    ///
+    /// The property names in `filter` refer to keys of the map, e.g.,
+    /// `PropertyFilter::map_lookup("address", PropertyFilter::eq("city", "London"))`.
+    /// Entities where the property is not a map are excluded.
+    pub fn map_lookup(name: impl Into<String>, filter: PropertyFilter) -> Self {
+        let inner = match filter
+            .expr
+            .resolve(&|key, _| Ok::<_, Infallible>(ArcStr::from(key)))
+        {
+            Ok(inner) => inner,
+            Err(never) => match never {},
+        };
+        Self::new(name, PropertyValueFilter::MapLookup(Arc::new(inner)))
+    }
+
+    /// Evaluate the filter directly on the values returned by `prop_value` for each property
+    /// name, e.g., on the properties of a single edge view
+    pub fn matches(&self, prop_value: impl Fn(&str) -> Option<Prop>) -> bool {
+        let resolved = match self
+            .expr
+            .resolve(&|name, _| Ok::<_, Infallible>(ArcStr::from(name)))
+        {
+            Ok(resolved) => resolved,
+            Err(never) => match never {},
+        };
+        resolved.matches(&|name: &ArcStr| prop_value(name))
+    }
+}
+
+impl Not for PropertyFilter {
+    type Output = PropertyFilter;
+
+    fn not(self) -> Self::Output {
+        let expr = match self.expr {
+            FilterExpr::Not(inner) => *inner,
+            expr => FilterExpr::Not(Box::new(expr)),
+        };
+        Self { expr }
+    }
+}
+
+impl FilterExpr {
+    fn resolve<Id, E>(
+        &self,
+        resolve_id: &impl Fn(&str, Option<PropType>) -> Result<Id, E>,
+    ) -> Result<ResolvedPropertyFilter<Id>, E> {
+        let resolved = match self {
+            FilterExpr::Property { name, filter } => ResolvedPropertyFilter::Property {
+                id: resolve_id(name, filter.dtype())?,
+                filter: filter.clone(),
+            },
+            FilterExpr::And(filters) => ResolvedPropertyFilter::And(
+                filters
+                    .iter()
+                    .map(|f| f.resolve(resolve_id))
+                    .collect::<Result<_, _>>()?,
+            ),
+            FilterExpr::Or(filters) => ResolvedPropertyFilter::Or(
+                filters
+                    .iter()
+                    .map(|f| f.resolve(resolve_id))
+                    .collect::<Result<_, _>>()?,
+            ),
+            FilterExpr::Not(filter) => {
+                ResolvedPropertyFilter::Not(Box::new(filter.resolve(resolve_id)?))
+            }
+        };
+        Ok(resolved)
+    }
+}
+
+impl<Id> ResolvedPropertyFilter<Id> {
+    /// Evaluate the filter, `prop_value` looks up the value of a property by id
+    pub(crate) fn matches(&self, prop_value: &impl Fn(&Id) -> Option<Prop>) -> bool {
+        match self {
+            ResolvedPropertyFilter::Property { id, filter } => {
+                filter.filter(prop_value(id).as_ref())
+            }
+            ResolvedPropertyFilter::And(filters) => filters.iter().all(|f| f.matches(prop_value)),
+            ResolvedPropertyFilter::Or(filters) => filters.iter().any(|f| f.matches(prop_value)),
+            ResolvedPropertyFilter::Not(filter) => !filter.matches(prop_value),
+        }
+    }
 }
 
 impl PropertyValueFilter {
@@ -120,6 +339,40 @@ impl PropertyValueFilter {
                 Some(value) => !set.contains(value),
                 None => true,
             },
+            PropertyValueFilter::Str(filter) => match value {
+                Some(Prop::Str(value)) => filter.filter(value),
+                _ => false,
+            },
+            PropertyValueFilter::Contains(needle) => match (value, needle) {
+                (Some(Prop::Str(value)), Prop::Str(needle)) => value.contains(needle.as_ref()),
+                (Some(Prop::List(values)), needle) => values.contains(needle),
+                _ => false,
+            },
+            PropertyValueFilter::MapLookup(filter) => match value {
+                Some(Prop::Map(map)) => filter.matches(&|key| map.get(key).cloned()),
+                _ => false,
+            },
+        }
+    }
+
+    /// The type the property needs to have for the filter to be valid (if there is a single one)
+    fn dtype(&self) -> Option<PropType> {
+        match self {
+            PropertyValueFilter::ByValue(cmp) => Some(cmp.dtype()),
+            PropertyValueFilter::Str(_) => Some(PropType::Str),
+            PropertyValueFilter::MapLookup(_) => Some(PropType::Map),
+            _ => None,
+        }
+    }
+}
+
+impl StrFilter {
+    fn filter(&self, value: &str) -> bool {
+        match self {
+            StrFilter::StartsWith(prefix) => value.starts_with(prefix.as_str()),
+            StrFilter::EndsWith(suffix) => value.ends_with(suffix.as_str()),
+            StrFilter::EqIgnoreCase(lower) => value.to_lowercase() == *lower,
+            StrFilter::Regex(regex) => regex.is_match(value),
         }
     }
 }
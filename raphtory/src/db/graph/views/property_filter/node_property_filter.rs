@@ -0,0 +1,148 @@
+use crate::{
+    core::{
+        entities::{properties::props::Meta, LayerIds},
+        utils::errors::GraphError,
+        PropType,
+    },
+    db::{
+        api::{
+            properties::internal::InheritPropertiesOps,
+            storage::graph::{
+                edges::{edge_ref::EdgeStorageRef, edge_storage_ops::EdgeStorageOps},
+                nodes::{node_ref::NodeStorageRef, node_storage_ops::NodeStorageOps},
+            },
+            view::{
+                internal::{
+                    CoreGraphOps, EdgeFilterOps, Immutable, InheritCoreOps, InheritLayerOps,
+                    InheritListOps, InheritMaterialize, InheritTimeSemantics, NodeFilterOps,
+                    Static,
+                },
+                Base,
+            },
+        },
+        graph::{
+            node::NodeView,
+            views::property_filter::{internal::InternalNodeFilterOps, ResolvedPropertyFilter},
+        },
+    },
+    prelude::{GraphViewOps, NodeViewOps, PropertyFilter},
+};
+
+#[derive(Debug, Clone)]
+pub struct NodePropertyFilteredGraph<G> {
+    graph: G,
+    filter: ResolvedPropertyFilter<(Option<usize>, Option<usize>)>,
+}
+
+impl<'graph, G> NodePropertyFilteredGraph<G> {
+    pub(crate) fn new(
+        graph: G,
+        filter: ResolvedPropertyFilter<(Option<usize>, Option<usize>)>,
+    ) -> Self {
+        Self { graph, filter }
+    }
+}
+
+fn get_ids_and_check_type(
+    meta: &Meta,
+    property: &str,
+    dtype: PropType,
+) -> Result<(Option<usize>, Option<usize>), GraphError> {
+    let t_prop_id = meta
+        .temporal_prop_meta()
+        .get_and_validate(property, dtype)?;
+    let c_prop_id = meta.const_prop_meta().get_and_validate(property, dtype)?;
+    Ok((t_prop_id, c_prop_id))
+}
+
+fn get_ids(meta: &Meta, property: &str) -> (Option<usize>, Option<usize>) {
+    let t_prop_id = meta.temporal_prop_meta().get_id(property);
+    let c_prop_id = meta.const_prop_meta().get_id(property);
+    (t_prop_id, c_prop_id)
+}
+
+impl InternalNodeFilterOps for PropertyFilter {
+    type NodeFiltered<'graph, G: GraphViewOps<'graph>> = NodePropertyFilteredGraph<G>;
+
+    fn create_node_filter<'graph, G: GraphViewOps<'graph>>(
+        self,
+        graph: G,
+    ) -> Result<Self::NodeFiltered<'graph, G>, GraphError> {
+        let meta = graph.node_meta();
+        let filter = self.resolve(&|name, dtype| match dtype {
+            Some(dtype) => get_ids_and_check_type(meta, name, dtype),
+            None => Ok(get_ids(meta, name)),
+        })?;
+        Ok(NodePropertyFilteredGraph::new(graph, filter))
+    }
+}
+
+impl<G> Static for NodePropertyFilteredGraph<G> {}
+impl<G> Immutable for NodePropertyFilteredGraph<G> {}
+
+impl<'graph, G> Base for NodePropertyFilteredGraph<G> {
+    type Base = G;
+
+    fn base(&self) -> &Self::Base {
+        &self.graph
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritCoreOps for NodePropertyFilteredGraph<G> {}
+impl<'graph, G: GraphViewOps<'graph>> InheritLayerOps for NodePropertyFilteredGraph<G> {}
+impl<'graph, G: GraphViewOps<'graph>> InheritListOps for NodePropertyFilteredGraph<G> {}
+impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for NodePropertyFilteredGraph<G> {}
+impl<'graph, G: GraphViewOps<'graph>> InheritPropertiesOps for NodePropertyFilteredGraph<G> {}
+impl<'graph, G: GraphViewOps<'graph>> InheritTimeSemantics for NodePropertyFilteredGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> NodeFilterOps for NodePropertyFilteredGraph<G> {
+    fn nodes_filtered(&self) -> bool {
+        true
+    }
+
+    fn node_list_trusted(&self) -> bool {
+        false
+    }
+
+    fn filter_node(&self, node: NodeStorageRef, layer_ids: &LayerIds) -> bool {
+        if self.graph.filter_node(node, layer_ids) {
+            let props = NodeView::new_internal(&self.graph, node.vid()).properties();
+            self.filter.matches(&|&(t_prop_id, c_prop_id)| {
+                t_prop_id
+                    .and_then(|prop_id| {
+                        props
+                            .temporal()
+                            .get_by_id(prop_id)
+                            .and_then(|prop_view| prop_view.latest())
+                    })
+                    .or_else(|| c_prop_id.and_then(|prop_id| props.constant().get_by_id(prop_id)))
+            })
+        } else {
+            false
+        }
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> EdgeFilterOps for NodePropertyFilteredGraph<G> {
+    #[inline]
+    fn edges_filtered(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn edge_list_trusted(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn edge_filter_includes_node_filter(&self) -> bool {
+        self.graph.edge_filter_includes_node_filter()
+    }
+
+    #[inline]
+    fn filter_edge(&self, edge: EdgeStorageRef, layer_ids: &LayerIds) -> bool {
+        self.graph.filter_edge(edge, layer_ids)
+            && self.filter_node(self.core_node_entry(edge.src()).as_ref(), layer_ids)
+            && self.filter_node(self.core_node_entry(edge.dst()).as_ref(), layer_ids)
+    }
+}
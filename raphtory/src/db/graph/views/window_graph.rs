@@ -165,6 +165,8 @@ impl<'graph, G: GraphViewOps<'graph>> NodeFilterOps for WindowedGraph<G> {
     fn nodes_filtered(&self) -> bool {
         self.window_is_empty()
             || self.graph.nodes_filtered()
+            // nodes can be deleted inside the window
+            || self.graph.include_deletions()
             || self.start_bound() > self.graph.earliest_time().unwrap_or(i64::MAX)
             || self.end_bound() <= self.graph.latest_time().unwrap_or(i64::MIN)
     }
@@ -0,0 +1,98 @@
+//! Stop long running algorithms early
+//!
+//! Algorithms run with [`with_interrupt`] poll the interrupt between their steps and return early,
+//! with incomplete results, once it is set. The caller is expected to discard those results.
+
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static INTERRUPT: RefCell<Option<Rc<dyn Fn() -> bool>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` and interrupt the algorithms it runs on this thread once `interrupted` returns true
+pub fn with_interrupt<R>(interrupted: impl Fn() -> bool + 'static, f: impl FnOnce() -> R) -> R {
+    let previous = INTERRUPT.with(|interrupt| interrupt.replace(Some(Rc::new(interrupted))));
+    // restore the previous interrupt even if `f` panics
+    let _guard = RestoreInterrupt(previous);
+    f()
+}
+
+/// Whether the algorithm running on this thread should stop
+pub(crate) fn is_interrupted() -> bool {
+    INTERRUPT.with(|interrupt| {
+        interrupt
+            .borrow()
+            .clone()
+            .is_some_and(|interrupted| interrupted())
+    })
+}
+
+struct RestoreInterrupt(Option<Rc<dyn Fn() -> bool>>);
+
+impl Drop for RestoreInterrupt {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        INTERRUPT.with(|interrupt| interrupt.replace(previous));
+    }
+}
+
+#[cfg(test)]
+mod interrupt_test {
+    use super::{is_interrupted, with_interrupt};
+    use crate::{
+        algorithms::{
+            centrality::pagerank::unweighted_page_rank,
+            pathing::dijkstra::dijkstra_single_source_shortest_paths,
+        },
+        prelude::*,
+    };
+    use raphtory_api::core::Direction;
+    use std::{cell::Cell, rc::Rc};
+
+    fn make_graph() -> Graph {
+        let graph = Graph::new();
+        for (src, dst) in [(1, 2), (2, 3), (3, 1), (3, 4)] {
+            graph.add_edge(0, src, dst, NO_PROPS, None).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn interrupt_is_scoped() {
+        assert!(!is_interrupted());
+        with_interrupt(|| true, || assert!(is_interrupted()));
+        assert!(!is_interrupted());
+    }
+
+    #[test]
+    fn interrupted_page_rank_stops_between_steps() {
+        let graph = make_graph();
+        let checks = Rc::new(Cell::new(0));
+        let interrupted = {
+            let checks = checks.clone();
+            move || {
+                checks.set(checks.get() + 1);
+                checks.get() >= 3
+            }
+        };
+        with_interrupt(interrupted, || {
+            unweighted_page_rank(&graph, Some(100), Some(1), Some(0.0), true, None)
+        });
+        assert_eq!(checks.get(), 3);
+    }
+
+    #[test]
+    fn interrupted_shortest_paths_stop() {
+        let graph = make_graph();
+        let paths = with_interrupt(
+            || true,
+            || dijkstra_single_source_shortest_paths(&graph, 1, vec![4], None, Direction::OUT),
+        )
+        .unwrap();
+        assert!(paths.is_empty());
+
+        let paths = dijkstra_single_source_shortest_paths(&graph, 1, vec![4], None, Direction::OUT)
+            .unwrap();
+        assert_eq!(paths["4"].1, vec!["1", "2", "3", "4"]);
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 pub mod context;
 pub mod edge;
 mod eval_graph;
+pub mod interrupt;
 pub mod node;
 pub mod task;
 pub mod task_runner;
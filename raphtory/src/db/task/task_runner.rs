@@ -1,6 +1,7 @@
 use super::{
     context::{Context, GlobalState},
     custom_pool,
+    interrupt::is_interrupted,
     task::{Job, Step, Task},
     task_state::{Global, PrevLocalState, Shard},
     POOL,
@@ -258,7 +259,8 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         // To allow the init step to cache stuff we will copy everything from cur_local_state to prev_local_state
         prev_local_state.clone_from_slice(&cur_local_state);
 
-        while !_done && self.ctx.ss() < steps && !tasks.is_empty() {
+        // an interrupted algorithm stops at the end of the current step
+        while !_done && self.ctx.ss() < steps && !tasks.is_empty() && !is_interrupted() {
             (_done, shard_state, global_state, cur_local_state) = self.run_task_list(
                 &tasks,
                 &pool,
@@ -1,6 +1,7 @@
 use crate::{
     core::{
         entities::{edges::edge_ref::EdgeRef, LayerIds, VID},
+        storage::timeindex::TimeIndex,
         Direction,
     },
     db::api::{
@@ -237,6 +238,11 @@ impl<'a> NodeStorageOps<'a> for DiskNode<'a> {
         self.additions_for_layers(LayerIds::All)
     }
 
+    fn deletions(self) -> NodeAdditions<'a> {
+        // disk graphs are immutable and do not support node deletions
+        NodeAdditions::Mem(&TimeIndex::Empty)
+    }
+
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a> {
         self.graph
             .node_properties()
@@ -446,6 +452,11 @@ impl<'a> NodeStorageOps<'a> for &'a DiskOwnedNode {
         self.as_ref().additions()
     }
 
+    #[inline]
+    fn deletions(self) -> NodeAdditions<'a> {
+        self.as_ref().deletions()
+    }
+
     #[inline]
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a> {
         self.as_ref().tprop(prop_id)
@@ -127,7 +127,8 @@ pub mod prelude {
                 state::{AsOrderedNodeStateOps, NodeStateOps, OrderedNodeStateOps},
                 view::{
                     EdgePropertyFilterOps, EdgeViewOps, ExplodedEdgePropertyFilterOps,
-                    GraphViewOps, Layer, LayerOps, NodeViewOps, ResetFilter, TimeOps,
+                    GraphViewOps, Layer, LayerOps, NodePropertyFilterOps, NodeViewOps, ResetFilter,
+                    TimeOps,
                 },
             },
             graph::{graph::Graph, views::property_filter::PropertyFilter},
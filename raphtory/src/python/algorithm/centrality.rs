@@ -0,0 +1,17 @@
+use crate::{
+    algorithms::centrality::{edge_weights::EdgeWeightError, eigenvector::PowerIterationError},
+    python::utils::errors::adapt_err_value,
+};
+use pyo3::prelude::*;
+
+impl From<EdgeWeightError> for PyErr {
+    fn from(value: EdgeWeightError) -> Self {
+        adapt_err_value(&value)
+    }
+}
+
+impl From<PowerIterationError> for PyErr {
+    fn from(value: PowerIterationError) -> Self {
+        adapt_err_value(&value)
+    }
+}
@@ -1 +1,4 @@
+pub(crate) mod centrality;
 pub(crate) mod epidemics;
+pub(crate) mod pagerank;
+pub(crate) mod temporal_paths;
@@ -0,0 +1,32 @@
+use crate::{
+    algorithms::centrality::pagerank::PageRankError, core::entities::nodes::node_ref::NodeRef,
+    python::utils::errors::adapt_err_value,
+};
+use pyo3::{prelude::*, types::PyDict};
+
+/// The personalisation of PageRank, either a mapping from nodes to weights or a list of seed nodes
+/// which all get the same weight
+pub struct PyPersonalisation<'a>(pub Vec<(NodeRef<'a>, f64)>);
+
+impl<'source> FromPyObject<'source> for PyPersonalisation<'source> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let personalisation = if let Ok(weights) = ob.downcast::<PyDict>() {
+            weights
+                .iter()
+                .map(|(node, weight)| Ok((node.extract()?, weight.extract()?)))
+                .collect::<PyResult<_>>()?
+        } else {
+            ob.extract::<Vec<NodeRef>>()?
+                .into_iter()
+                .map(|node| (node, 1.0))
+                .collect()
+        };
+        Ok(Self(personalisation))
+    }
+}
+
+impl From<PageRankError> for PyErr {
+    fn from(value: PageRankError) -> Self {
+        adapt_err_value(&value)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    algorithms::pathing::temporal_paths::{TemporalPath, TemporalPathError},
+    db::{api::view::DynamicGraph, graph::edge::EdgeView},
+    python::{
+        types::repr::{Repr, StructReprBuilder},
+        utils::errors::adapt_err_value,
+    },
+};
+use pyo3::prelude::*;
+
+impl Repr for TemporalPath<DynamicGraph> {
+    fn repr(&self) -> String {
+        StructReprBuilder::new("TemporalPath")
+            .add_field("departure", self.departure)
+            .add_field("arrival", self.arrival)
+            .add_field("nodes", self.nodes())
+            .finish()
+    }
+}
+
+/// A time-respecting journey, made of the exploded edges traversed in order
+#[pyclass(name = "TemporalPath")]
+struct PyTemporalPath {
+    inner: TemporalPath<DynamicGraph>,
+}
+
+#[pymethods]
+impl PyTemporalPath {
+    /// Time at which the first edge departs
+    #[getter]
+    fn departure(&self) -> i64 {
+        self.inner.departure
+    }
+
+    /// Time at which the last edge arrives
+    #[getter]
+    fn arrival(&self) -> i64 {
+        self.inner.arrival
+    }
+
+    /// The time elapsed between the departure and the arrival
+    #[getter]
+    fn duration(&self) -> i64 {
+        self.inner.duration()
+    }
+
+    /// The exploded edges of the journey, in order
+    #[getter]
+    fn edges(&self) -> Vec<EdgeView<DynamicGraph, DynamicGraph>> {
+        self.inner.edges.clone()
+    }
+
+    /// The names of the nodes visited, in order
+    #[getter]
+    fn nodes(&self) -> Vec<String> {
+        self.inner.nodes()
+    }
+
+    fn __repr__(&self) -> String {
+        self.inner.repr()
+    }
+}
+
+impl IntoPy<PyObject> for TemporalPath<DynamicGraph> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyTemporalPath { inner: self }.into_py(py)
+    }
+}
+
+impl From<TemporalPathError> for PyErr {
+    fn from(value: TemporalPathError) -> Self {
+        adapt_err_value(&value)
+    }
+}
@@ -219,6 +219,23 @@ impl PyPersistentGraph {
         self.graph.delete_edge(timestamp, src, dst, layer)
     }
 
+    /// Deletes a node given the timestamp and the node id.
+    /// All edges incident to the node that are alive at the time of the deletion are deleted as well.
+    ///
+    /// Arguments:
+    ///   timestamp (int): The timestamp of the deletion.
+    ///   id (str | int): The id of the node.
+    ///
+    /// Returns:
+    ///  The deleted node
+    pub fn delete_node(
+        &self,
+        timestamp: PyTime,
+        id: GID,
+    ) -> Result<NodeView<PersistentGraph>, GraphError> {
+        self.graph.delete_node(timestamp, id)
+    }
+
     //FIXME: This is reimplemented here to get mutable views. If we switch the underlying graph to enum dispatch, this won't be necessary!
     /// Gets the node with the specified id
     ///
@@ -276,7 +276,7 @@ impl PyTemporalProp {
     /// Returns:
     ///     Prop: The sum of all property values.
     pub fn sum(&self) -> Option<Prop> {
-        compute_generalised_sum(self.prop.values(), |a, b| a.add(b), |d| d.dtype().has_add())
+        self.prop.sum()
     }
 
     /// Find the minimum property value and its associated time.
@@ -284,17 +284,7 @@ impl PyTemporalProp {
     /// Returns:
     ///     (i64, Prop): A tuple containing the time and the minimum property value.
     pub fn min(&self) -> Option<(i64, Prop)> {
-        compute_generalised_sum(
-            self.prop.iter(),
-            |a, b| {
-                if a.1.partial_cmp(&b.1)?.is_le() {
-                    Some(a)
-                } else {
-                    Some(b)
-                }
-            },
-            |d| d.1.dtype().has_cmp(),
-        )
+        self.prop.min()
     }
 
     /// Find the maximum property value and its associated time.
@@ -302,17 +292,7 @@ impl PyTemporalProp {
     /// Returns:
     ///     (i64, Prop): A tuple containing the time and the maximum property value.
     pub fn max(&self) -> Option<(i64, Prop)> {
-        compute_generalised_sum(
-            self.prop.iter(),
-            |a, b| {
-                if a.1.partial_cmp(&b.1)?.is_ge() {
-                    Some(a)
-                } else {
-                    Some(b)
-                }
-            },
-            |d| d.1.dtype().has_cmp(),
-        )
+        self.prop.max()
     }
 
     /// Count the number of properties.
@@ -320,7 +300,7 @@ impl PyTemporalProp {
     /// Returns:
     ///     int: The number of properties.
     pub fn count(&self) -> usize {
-        self.prop.iter().count()
+        self.prop.count()
     }
 
     /// Compute the average of all property values. Alias for mean().
@@ -336,7 +316,7 @@ impl PyTemporalProp {
     /// Returns:
     ///     Prop: The mean of each property values, or None if count is zero.
     pub fn mean(&self) -> Option<Prop> {
-        compute_mean(self.prop.values())
+        self.prop.mean()
     }
 
     /// Compute the median of all property values.
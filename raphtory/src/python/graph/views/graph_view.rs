@@ -26,6 +26,7 @@ use crate::{
                 property_filter::{
                     edge_property_filter::EdgePropertyFilteredGraph,
                     exploded_edge_property_filter::ExplodedEdgePropertyFilteredGraph, internal::*,
+                    node_property_filter::NodePropertyFilteredGraph,
                 },
                 window_graph::WindowedGraph,
             },
@@ -77,6 +78,7 @@ pub struct PyGraphView {
 impl_timeops!(PyGraphView, graph, DynamicGraph, "GraphView");
 impl_layerops!(PyGraphView, graph, DynamicGraph, "GraphView");
 impl_edge_property_filter_ops!(PyGraphView<DynamicGraph>, graph, "GraphView");
+impl_node_property_filter_ops!(PyGraphView<DynamicGraph>, graph, "GraphView");
 
 /// Graph view is a read-only version of a graph at a certain point in time.
 impl<G: StaticGraphViewOps + IntoDynamic> From<G> for PyGraphView {
@@ -117,6 +119,12 @@ impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject> for EdgePropertyFilte
     }
 }
 
+impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject> for NodePropertyFilteredGraph<G> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyGraphView::from(self).into_py(py)
+    }
+}
+
 impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject>
     for ExplodedEdgePropertyFilteredGraph<G>
 {
@@ -5,13 +5,30 @@ use crate::{
     algorithms::{
         algorithm_result::AlgorithmResult,
         centrality::{
-            betweenness::betweenness_centrality as betweenness_rs,
-            degree_centrality::degree_centrality as degree_centrality_rs, hits::hits as hits_rs,
-            pagerank::unweighted_page_rank,
+            betweenness::{
+                edge_betweenness_centrality as edge_betweenness_rs,
+                weighted_betweenness_centrality as betweenness_rs,
+            },
+            closeness::{
+                closeness_centrality as closeness_centrality_rs,
+                harmonic_centrality as harmonic_centrality_rs,
+            },
+            degree_centrality::degree_centrality as degree_centrality_rs,
+            edge_weights::EdgeWeightError,
+            eigenvector::{
+                eigenvector_centrality as eigenvector_centrality_rs, PowerIterationError,
+            },
+            hits::hits as hits_rs,
+            katz::katz_centrality as katz_centrality_rs,
+            pagerank::{
+                unweighted_page_rank, weighted_page_rank as weighted_page_rank_rs, PageRankError,
+            },
         },
         community_detection::{
             label_propagation::label_propagation as label_propagation_rs,
-            louvain::louvain as louvain_rs, modularity::ModularityUnDir,
+            leiden::{leiden as leiden_rs, leiden_over_windows, WindowCommunities},
+            louvain::louvain as louvain_rs,
+            modularity::ModularityUnDir,
         },
         components,
         dynamics::temporal::epidemics::{temporal_SEIR as temporal_SEIR_rs, Infected, SeedError},
@@ -46,15 +63,30 @@ use crate::{
         pathing::{
             dijkstra::dijkstra_single_source_shortest_paths as dijkstra_single_source_shortest_paths_rs,
             single_source_shortest_path::single_source_shortest_path as single_source_shortest_path_rs,
+            temporal_paths::{
+                temporal_earliest_arrival as temporal_earliest_arrival_rs,
+                temporal_fastest_paths as temporal_fastest_paths_rs,
+                temporal_latest_departure as temporal_latest_departure_rs,
+                temporal_shortest_paths as temporal_shortest_paths_rs, TemporalPath,
+                TemporalPathError,
+            },
             temporal_reachability::temporally_reachable_nodes as temporal_reachability_rs,
         },
         projections::temporal_bipartite_projection::temporal_bipartite_projection as temporal_bipartite_rs,
     },
-    core::{entities::nodes::node_ref::NodeRef, Prop},
-    db::{api::view::internal::DynamicGraph, graph::node::NodeView},
+    core::{
+        entities::nodes::node_ref::NodeRef,
+        utils::time::{error::ParseTimeError, IntoTime},
+        Prop,
+    },
+    db::{
+        api::view::{internal::DynamicGraph, TimeOps},
+        graph::node::NodeView,
+    },
     python::{
+        algorithm::pagerank::PyPersonalisation,
         graph::{node::PyNode, views::graph_view::PyGraphView},
-        utils::PyTime,
+        utils::{PyInterval, PyTime},
     },
 };
 use ordered_float::OrderedFloat;
@@ -216,6 +248,54 @@ pub fn pagerank(
     )
 }
 
+/// Weighted and personalised Pagerank -- pagerank centrality where the score flows along the edges in proportion to their weights
+///
+/// Nodes pass their score to their out-neighbours in proportion to the weights of the edges, and random jumps (and the score of nodes
+/// without outgoing weight) land on the nodes of the personalisation. Giving a set of seed nodes as personalisation ranks the nodes by
+/// their proximity to the seeds.
+///
+/// Arguments:
+///     g (GraphView) : Raphtory graph
+///     weight (Optional[str]) : The name of the edge property with the weights. All edges have the same weight if not set.
+///     aggregation (str) : How the values of the weight property across the updates of an edge are combined, one of "sum", "latest" or "count".
+///         "count" uses the number of updates of the edge as its weight. Defaults to "sum".
+///     personalisation (Optional[dict[InputNode, float] | list[InputNode]]) : The teleport weight of each node, or a list of seed nodes
+///         which all have the same weight. Random jumps are uniform if not set.
+///     iter_count (int) : Maximum number of iterations to run. Note that this will terminate early if convergence is reached.
+///     max_diff (Optional[float]) : Optional parameter providing an alternative stopping condition.
+///         The algorithm will terminate if the sum of the absolute difference in pagerank values between iterations
+///         is less than the max diff value given.
+///     use_l2_norm (bool) : Whether to use the L2 norm of the differences for the stopping condition
+///     damping_factor (float) : Probability of following an edge rather than jumping
+///
+/// Returns:
+///     AlgorithmResult : AlgorithmResult with string keys and float values mapping node names to their pagerank value.
+#[pyfunction]
+#[pyo3(signature = (g, weight=None, aggregation="sum", personalisation=None, iter_count=20, max_diff=None, use_l2_norm=true, damping_factor=0.85))]
+#[allow(clippy::too_many_arguments)]
+pub fn weighted_pagerank(
+    g: &PyGraphView,
+    weight: Option<&str>,
+    aggregation: &str,
+    personalisation: Option<PyPersonalisation>,
+    iter_count: usize,
+    max_diff: Option<f64>,
+    use_l2_norm: bool,
+    damping_factor: Option<f64>,
+) -> Result<AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>>, PageRankError> {
+    weighted_page_rank_rs(
+        &g.graph,
+        weight,
+        aggregation.parse()?,
+        personalisation.map(|p| p.0),
+        Some(iter_count),
+        None,
+        max_diff,
+        use_l2_norm,
+        damping_factor,
+    )
+}
+
 /// Temporally reachable nodes -- the nodes that are reachable by a time respecting path followed out from a set of seed nodes at a starting time.
 ///
 /// This function starts at a set of seed nodes and follows all time respecting paths until either a) a maximum number of hops is reached, b) one of a set of
@@ -652,6 +732,117 @@ pub fn dijkstra_single_source_shortest_paths(
     }
 }
 
+/// Finds the journeys from a source that arrive the earliest at every node reachable from it.
+///
+/// A journey is a sequence of exploded edges where each edge departs from the node the previous one arrived at,
+/// no earlier than its arrival. An edge departing at `t` arrives at `t + duration`. Restrict the graph to a window
+/// to only consider journeys within it.
+///
+/// Arguments:
+///     g (GraphView): The graph to search in.
+///     source (InputNode): The source node.
+///     start_time (TimeInput, optional): Journeys depart from the source at or after this time. Defaults to any time.
+///     duration (str, optional): The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+///
+/// Returns:
+///     dict[str, TemporalPath]: The earliest arrival journey to every reachable node, the source is not included.
+#[pyfunction]
+#[pyo3[signature = (g, source, start_time=None, duration=None)]]
+pub fn temporal_earliest_arrival(
+    g: &PyGraphView,
+    source: NodeRef,
+    start_time: Option<PyTime>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<DynamicGraph>>, TemporalPathError> {
+    temporal_earliest_arrival_rs(
+        &g.graph,
+        source,
+        start_time.map(|time| time.into_time()),
+        duration,
+    )
+}
+
+/// Finds the journeys to a target that depart the latest from every node that can reach it.
+///
+/// Arguments:
+///     g (GraphView): The graph to search in.
+///     target (InputNode): The target node.
+///     end_time (TimeInput, optional): Journeys arrive at the target at or before this time. Defaults to any time.
+///     duration (str, optional): The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+///
+/// Returns:
+///     dict[str, TemporalPath]: The latest departure journey from every node that can reach the target, the target is not included.
+#[pyfunction]
+#[pyo3[signature = (g, target, end_time=None, duration=None)]]
+pub fn temporal_latest_departure(
+    g: &PyGraphView,
+    target: NodeRef,
+    end_time: Option<PyTime>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<DynamicGraph>>, TemporalPathError> {
+    temporal_latest_departure_rs(
+        &g.graph,
+        target,
+        end_time.map(|time| time.into_time()),
+        duration,
+    )
+}
+
+/// Finds the journeys from a source with the least time elapsed between departing the source and arriving at every
+/// node reachable from it, including the time spent waiting for connections.
+///
+/// Arguments:
+///     g (GraphView): The graph to search in.
+///     source (InputNode): The source node.
+///     start_time (TimeInput, optional): Journeys depart from the source at or after this time. Defaults to any time.
+///     duration (str, optional): The name of the edge property with the traversal time of the edges. Edges take no time if not set.
+///
+/// Returns:
+///     dict[str, TemporalPath]: The fastest journey to every reachable node, ties are broken by the earliest arrival.
+#[pyfunction]
+#[pyo3[signature = (g, source, start_time=None, duration=None)]]
+pub fn temporal_fastest_paths(
+    g: &PyGraphView,
+    source: NodeRef,
+    start_time: Option<PyTime>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<DynamicGraph>>, TemporalPathError> {
+    temporal_fastest_paths_rs(
+        &g.graph,
+        source,
+        start_time.map(|time| time.into_time()),
+        duration,
+    )
+}
+
+/// Finds the journeys from a source with the smallest sum of the traversal durations of their edges to every node
+/// reachable from it, not counting the time spent waiting for connections.
+///
+/// Arguments:
+///     g (GraphView): The graph to search in.
+///     source (InputNode): The source node.
+///     start_time (TimeInput, optional): Journeys depart from the source at or after this time. Defaults to any time.
+///     duration (str, optional): The name of the edge property with the traversal time of the edges. If not set, edges
+///         take no time and the journeys with the fewest edges are returned.
+///
+/// Returns:
+///     dict[str, TemporalPath]: The shortest journey to every reachable node, ties are broken by the earliest arrival.
+#[pyfunction]
+#[pyo3[signature = (g, source, start_time=None, duration=None)]]
+pub fn temporal_shortest_paths(
+    g: &PyGraphView,
+    source: NodeRef,
+    start_time: Option<PyTime>,
+    duration: Option<&str>,
+) -> Result<HashMap<String, TemporalPath<DynamicGraph>>, TemporalPathError> {
+    temporal_shortest_paths_rs(
+        &g.graph,
+        source,
+        start_time.map(|time| time.into_time()),
+        duration,
+    )
+}
+
 /// Computes the betweenness centrality for nodes in a given graph.
 ///
 /// Arguments:
@@ -659,17 +850,179 @@ pub fn dijkstra_single_source_shortest_paths(
 ///     k (int, optional): Specifies the number of nodes to consider for the centrality computation.
 ///         All nodes are considered by default.
 ///     normalized (bool): Indicates whether to normalize the centrality values.
+///     weight (str, optional): The name of the edge property with the lengths of the edges.
+///         Edges have length 1 if not set or if they don't have the property.
+///     threads (int, optional): The number of threads to use for the computation.
 ///
 /// Returns:
 ///     AlgorithmResult: Returns an `AlgorithmResult` containing the betweenness centrality of each node.
 #[pyfunction]
-#[pyo3[signature = (g, k=None, normalized=true)]]
+#[pyo3[signature = (g, k=None, normalized=true, weight=None, threads=None)]]
 pub fn betweenness_centrality(
     g: &PyGraphView,
     k: Option<usize>,
     normalized: bool,
-) -> AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>> {
-    betweenness_rs(&g.graph, k, normalized)
+    weight: Option<&str>,
+    threads: Option<usize>,
+) -> Result<AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>>, EdgeWeightError> {
+    betweenness_rs(&g.graph, weight, k, normalized, threads)
+}
+
+/// Computes the betweenness centrality for edges in a given graph, the number of shortest paths passing
+/// through each edge.
+///
+/// Arguments:
+///     g (GraphView): A reference to the graph.
+///     k (int, optional): Specifies the number of nodes to consider for the centrality computation.
+///         All nodes are considered by default.
+///     normalized (bool): Indicates whether to normalize the centrality values.
+///     weight (str, optional): The name of the edge property with the lengths of the edges.
+///         Edges have length 1 if not set or if they don't have the property.
+///     threads (int, optional): The number of threads to use for the computation.
+///
+/// Returns:
+///     dict[tuple[str, str], float]: The betweenness centrality of each edge, keyed by the names of its source
+///     and destination.
+#[pyfunction]
+#[pyo3[signature = (g, k=None, normalized=true, weight=None, threads=None)]]
+pub fn edge_betweenness_centrality(
+    g: &PyGraphView,
+    k: Option<usize>,
+    normalized: bool,
+    weight: Option<&str>,
+    threads: Option<usize>,
+) -> Result<HashMap<(String, String), f64>, EdgeWeightError> {
+    edge_betweenness_rs(&g.graph, weight, k, normalized, threads)
+}
+
+/// Computes the closeness centrality of the nodes of a graph.
+///
+/// The closeness of a node reached from `r` other nodes at a total distance `d` is `(r / d) * (r / (n - 1))`,
+/// the inverse of the average distance to the node scaled by the fraction of the graph that reaches it.
+/// In a directed graph the distances to a node from the other nodes are used.
+///
+/// Arguments:
+///     g (GraphView): A reference to the graph.
+///     weight (str, optional): The name of the edge property with the lengths of the edges.
+///         Edges have length 1 if not set or if they don't have the property.
+///     directed (bool): If false the direction of the edges is ignored. Defaults to true.
+///     sample (int, optional): Estimate the distances from this number of randomly sampled source nodes
+///         instead of all of them.
+///     rng_seed (int, optional): The seed of the sampling of the source nodes.
+///
+/// Returns:
+///     AlgorithmResult: Returns an `AlgorithmResult` containing the closeness centrality of each node.
+#[pyfunction]
+#[pyo3[signature = (g, weight=None, directed=true, sample=None, rng_seed=None)]]
+pub fn closeness_centrality(
+    g: &PyGraphView,
+    weight: Option<&str>,
+    directed: bool,
+    sample: Option<usize>,
+    rng_seed: Option<u64>,
+) -> Result<AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>>, EdgeWeightError> {
+    closeness_centrality_rs(&g.graph, weight, directed, sample, rng_seed)
+}
+
+/// Computes the harmonic centrality of the nodes of a graph, the sum of the inverse distances to a node from
+/// all other nodes.
+///
+/// Arguments:
+///     g (GraphView): A reference to the graph.
+///     weight (str, optional): The name of the edge property with the lengths of the edges.
+///         Edges have length 1 if not set or if they don't have the property.
+///     directed (bool): If false the direction of the edges is ignored. Defaults to true.
+///     sample (int, optional): Estimate the distances from this number of randomly sampled source nodes
+///         instead of all of them.
+///     rng_seed (int, optional): The seed of the sampling of the source nodes.
+///
+/// Returns:
+///     AlgorithmResult: Returns an `AlgorithmResult` containing the harmonic centrality of each node.
+#[pyfunction]
+#[pyo3[signature = (g, weight=None, directed=true, sample=None, rng_seed=None)]]
+pub fn harmonic_centrality(
+    g: &PyGraphView,
+    weight: Option<&str>,
+    directed: bool,
+    sample: Option<usize>,
+    rng_seed: Option<u64>,
+) -> Result<AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>>, EdgeWeightError> {
+    harmonic_centrality_rs(&g.graph, weight, directed, sample, rng_seed)
+}
+
+/// Computes the eigenvector centrality of the nodes of a graph.
+///
+/// The score of a node is proportional to the sum of the scores of the nodes pointing at it. The scores are
+/// normalised to unit L2 norm.
+/// An exception is raised if the scores have not converged after `iter_count` iterations.
+///
+/// Arguments:
+///     g (GraphView): A reference to the graph.
+///     weight (str, optional): The name of the edge property with the weights of the edges.
+///         Edges have weight 1 if not set or if they don't have the property.
+///     directed (bool): If false the direction of the edges is ignored. Defaults to true.
+///     iter_count (int): Maximum number of iterations to run. Note that this will terminate early if convergence is reached.
+///     max_diff (float, optional): The algorithm will terminate if the sum of the absolute difference in scores between
+///         iterations is less than the number of nodes times this value. Defaults to 1e-6.
+///
+/// Returns:
+///     AlgorithmResult: Returns an `AlgorithmResult` containing the eigenvector centrality of each node.
+#[pyfunction]
+#[pyo3[signature = (g, weight=None, directed=true, iter_count=100, max_diff=None)]]
+pub fn eigenvector_centrality(
+    g: &PyGraphView,
+    weight: Option<&str>,
+    directed: bool,
+    iter_count: usize,
+    max_diff: Option<f64>,
+) -> Result<AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>>, PowerIterationError> {
+    eigenvector_centrality_rs(&g.graph, weight, directed, Some(iter_count), None, max_diff)
+}
+
+/// Computes the Katz centrality of the nodes of a graph.
+///
+/// The score of a node is `alpha` times the sum of the scores of the nodes pointing at it plus `beta`. The iteration
+/// only converges if `alpha` is smaller than the inverse of the largest eigenvalue of the adjacency matrix.
+/// An exception is raised if the scores have not converged after `iter_count` iterations.
+///
+/// Arguments:
+///     g (GraphView): A reference to the graph.
+///     weight (str, optional): The name of the edge property with the weights of the edges.
+///         Edges have weight 1 if not set or if they don't have the property.
+///     directed (bool): If false the direction of the edges is ignored. Defaults to true.
+///     alpha (float): The attenuation factor. Defaults to 0.1.
+///     beta (float): The score every node receives regardless of its neighbours. Defaults to 1.
+///     normalized (bool): If true the scores are normalised to unit L2 norm. Defaults to true.
+///     iter_count (int): Maximum number of iterations to run. Note that this will terminate early if convergence is reached.
+///     max_diff (float, optional): The algorithm will terminate if the sum of the absolute difference in scores between
+///         iterations is less than the number of nodes times this value. Defaults to 1e-6.
+///
+/// Returns:
+///     AlgorithmResult: Returns an `AlgorithmResult` containing the Katz centrality of each node.
+#[pyfunction]
+#[pyo3[signature = (g, weight=None, directed=true, alpha=0.1, beta=1.0, normalized=true, iter_count=1000, max_diff=None)]]
+#[allow(clippy::too_many_arguments)]
+pub fn katz_centrality(
+    g: &PyGraphView,
+    weight: Option<&str>,
+    directed: bool,
+    alpha: f64,
+    beta: f64,
+    normalized: bool,
+    iter_count: usize,
+    max_diff: Option<f64>,
+) -> Result<AlgorithmResult<DynamicGraph, f64, OrderedFloat<f64>>, PowerIterationError> {
+    katz_centrality_rs(
+        &g.graph,
+        weight,
+        directed,
+        Some(alpha),
+        Some(beta),
+        normalized,
+        Some(iter_count),
+        None,
+        max_diff,
+    )
 }
 
 /// Computes components using a label propagation algorithm
@@ -765,6 +1118,77 @@ pub fn louvain(
     louvain_rs::<ModularityUnDir, _>(&graph.graph, resolution, weight_prop, tol)
 }
 
+/// Leiden algorithm for community detection
+///
+/// Unlike Louvain, the communities found by Leiden are guaranteed to be connected.
+///
+/// Arguments:
+///     graph (GraphView): the graph view
+///     resolution (float): the resolution parameter for modularity, larger values result in smaller communities
+///     weight_prop (str | None): the edge property to use for weights (has to be float)
+///     initial_partition (dict[str, int] | None): the starting community of nodes by name, nodes without one start in their own community
+///     tol (None | float): the floating point tolerance for deciding if improvements are significant (default: 1e-8)
+///     rng_seed (int | None): the seed for the order in which nodes are visited
+///
+/// Returns:
+///     AlgorithmResult: the community of each node, numbered from 0
+#[pyfunction]
+#[pyo3[signature=(graph, resolution=1.0, weight_prop=None, initial_partition=None, tol=None, rng_seed=None)]]
+pub fn leiden(
+    graph: &PyGraphView,
+    resolution: f64,
+    weight_prop: Option<&str>,
+    initial_partition: Option<HashMap<String, usize>>,
+    tol: Option<f64>,
+    rng_seed: Option<u64>,
+) -> AlgorithmResult<DynamicGraph, usize> {
+    leiden_rs::<ModularityUnDir, _>(
+        &graph.graph,
+        resolution,
+        weight_prop,
+        initial_partition.as_ref(),
+        tol,
+        rng_seed,
+    )
+}
+
+/// Track communities over time by running the Leiden algorithm on rolling windows of the graph
+///
+/// Each window starts from the communities of the previous window and communities keep their id
+/// from one window to the next while they overlap, new communities get new ids.
+///
+/// Arguments:
+///     graph (GraphView): the graph view
+///     window (int | str): the size of the windows
+///     step (int | str | None): the step between windows, defaults to `window`
+///     resolution (float): the resolution parameter for modularity
+///     weight_prop (str | None): the edge property to use for weights (has to be float)
+///     tol (None | float): the floating point tolerance for deciding if improvements are significant (default: 1e-8)
+///     rng_seed (int | None): the seed for the order in which nodes are visited
+///
+/// Returns:
+///     list[tuple[int | None, dict[str, int]]]: the end of each window and the community of each node in the window
+#[pyfunction]
+#[pyo3[signature=(graph, window, step=None, resolution=1.0, weight_prop=None, tol=None, rng_seed=None)]]
+pub(crate) fn leiden_rolling(
+    graph: &PyGraphView,
+    window: PyInterval,
+    step: Option<PyInterval>,
+    resolution: f64,
+    weight_prop: Option<&str>,
+    tol: Option<f64>,
+    rng_seed: Option<u64>,
+) -> Result<Vec<WindowCommunities>, ParseTimeError> {
+    let windows = graph.graph.rolling(window, step)?;
+    Ok(leiden_over_windows::<ModularityUnDir, _>(
+        windows,
+        resolution,
+        weight_prop,
+        tol,
+        rng_seed,
+    ))
+}
+
 /// Fruchterman Reingold layout algorithm
 ///
 /// Arguments:
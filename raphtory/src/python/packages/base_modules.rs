@@ -66,6 +66,11 @@ pub fn base_algorithm_module(py: Python<'_>) -> Result<&PyModule, PyErr> {
         dijkstra_single_source_shortest_paths,
         global_reciprocity,
         betweenness_centrality,
+        edge_betweenness_centrality,
+        closeness_centrality,
+        harmonic_centrality,
+        eigenvector_centrality,
+        katz_centrality,
         all_local_reciprocity,
         triplet_count,
         local_triangle_count,
@@ -79,9 +84,14 @@ pub fn base_algorithm_module(py: Python<'_>) -> Result<&PyModule, PyErr> {
         min_out_degree,
         min_in_degree,
         pagerank,
+        weighted_pagerank,
         single_source_shortest_path,
         global_clustering_coefficient,
         temporally_reachable_nodes,
+        temporal_earliest_arrival,
+        temporal_latest_departure,
+        temporal_fastest_paths,
+        temporal_shortest_paths,
         temporal_bipartite_graph_projection,
         local_clustering_coefficient,
         weakly_connected_components,
@@ -98,6 +108,8 @@ pub fn base_algorithm_module(py: Python<'_>) -> Result<&PyModule, PyErr> {
         label_propagation,
         temporal_SEIR,
         louvain,
+        leiden,
+        leiden_rolling,
         fruchterman_reingold,
         cohesive_fruchterman_reingold,
     );
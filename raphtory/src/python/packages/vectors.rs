@@ -317,6 +317,24 @@ impl IntoPy<PyObject> for DynamicVectorSelection {
 /// over those documents
 #[pymethods]
 impl PyVectorisedGraph {
+    /// Build an approximate nearest-neighbour index over the node and edge documents
+    ///
+    /// Once built, the index is used by the similarity searches on this graph. Searches fall back
+    /// to an exact scan if the index can't provide enough results matching the window.
+    fn build_ann_index(&self) {
+        self.0.build_ann_index()
+    }
+
+    /// Remove the approximate nearest-neighbour index so searches go back to being exact
+    fn drop_ann_index(&self) {
+        self.0.drop_ann_index()
+    }
+
+    /// Whether similarity searches use an approximate nearest-neighbour index
+    fn has_ann_index(&self) -> bool {
+        self.0.has_ann_index()
+    }
+
     /// Save the embeddings present in this graph to `file` so they can be further used in a call to `vectorise`
     fn save_embeddings(&self, file: String) {
         self.0.save_embeddings(file.into());
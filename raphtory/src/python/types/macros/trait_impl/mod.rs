@@ -22,3 +22,6 @@ mod serialise;
 
 #[macro_use]
 mod edge_property_filter_ops;
+
+#[macro_use]
+mod node_property_filter_ops;
@@ -3,7 +3,9 @@ use crate::{
     core::{utils::errors::GraphError, DocumentInput, Prop},
     db::graph::views::{
         deletion_graph::PersistentGraph,
-        property_filter::internal::{InternalEdgeFilterOps, InternalExplodedEdgeFilterOps},
+        property_filter::internal::{
+            InternalEdgeFilterOps, InternalExplodedEdgeFilterOps, InternalNodeFilterOps,
+        },
     },
     prelude::{GraphViewOps, PropertyFilter},
     python::{graph::views::graph_view::PyGraphView, types::repr::Repr},
@@ -132,10 +134,30 @@ impl Repr for Prop {
 pub type PropValue = Option<Prop>;
 pub type PropHistItems = Vec<(i64, Prop)>;
 
+/// A filter on property values
+///
+/// Construct filters using `Prop` and combine them with `&` (and), `|` (or) and `~` (not).
+/// Note that `&` and `|` bind more tightly than comparisons in python, so the individual
+/// filters need to be wrapped in parentheses, e.g., `(Prop("a") > 1) & (Prop("b") == "x")`.
 #[pyclass(frozen, name = "PropertyFilter")]
 #[derive(Clone)]
 pub struct PyPropertyFilter(PropertyFilter);
 
+#[pymethods]
+impl PyPropertyFilter {
+    fn __and__(&self, other: PyPropertyFilter) -> PyPropertyFilter {
+        PyPropertyFilter(self.0.clone().and(other.0))
+    }
+
+    fn __or__(&self, other: PyPropertyFilter) -> PyPropertyFilter {
+        PyPropertyFilter(self.0.clone().or(other.0))
+    }
+
+    fn __invert__(&self) -> PyPropertyFilter {
+        PyPropertyFilter(!self.0.clone())
+    }
+}
+
 impl InternalEdgeFilterOps for PyPropertyFilter {
     type EdgeFiltered<'graph, G>
         = <PropertyFilter as InternalEdgeFilterOps>::EdgeFiltered<'graph, G>
@@ -166,77 +188,139 @@ impl InternalExplodedEdgeFilterOps for PyPropertyFilter {
     }
 }
 
+impl InternalNodeFilterOps for PyPropertyFilter {
+    type NodeFiltered<'graph, G>
+        = <PropertyFilter as InternalNodeFilterOps>::NodeFiltered<'graph, G>
+    where
+        G: GraphViewOps<'graph>,
+        Self: 'graph;
+
+    fn create_node_filter<'graph, G: GraphViewOps<'graph>>(
+        self,
+        graph: G,
+    ) -> Result<Self::NodeFiltered<'graph, G>, GraphError> {
+        self.0.create_node_filter(graph)
+    }
+}
+
 /// A reference to a property used for constructing filters
 ///
 /// Use `==`, `!=`, `<`, `<=`, `>`, `>=` to filter based on
 /// property value (these filters always exclude entities that do not
 /// have the property) or use one of the methods to construct
 /// other kinds of filters.
+///
+/// Index into map properties using `Prop("name")["key"]`.
 #[pyclass(frozen, name = "Prop")]
 #[derive(Clone)]
 pub struct PyPropertyRef {
     name: String,
+    keys: Vec<String>,
+}
+
+impl PyPropertyRef {
+    /// Build the filter for the referenced property, wrapping it in map lookups if needed
+    fn build(&self, filter: impl FnOnce(&str) -> PropertyFilter) -> PyPropertyFilter {
+        match self.keys.split_last() {
+            None => PyPropertyFilter(filter(&self.name)),
+            Some((last, keys)) => {
+                let inner = keys.iter().rev().fold(filter(last), |inner, key| {
+                    PropertyFilter::map_lookup(key, inner)
+                });
+                PyPropertyFilter(PropertyFilter::map_lookup(&self.name, inner))
+            }
+        }
+    }
 }
 
 #[pymethods]
 impl PyPropertyRef {
     #[new]
     fn new(name: String) -> Self {
-        PyPropertyRef { name }
+        PyPropertyRef { name, keys: vec![] }
+    }
+
+    fn __getitem__(&self, key: String) -> PyPropertyRef {
+        let mut keys = self.keys.clone();
+        keys.push(key);
+        PyPropertyRef {
+            name: self.name.clone(),
+            keys,
+        }
     }
 
     fn __eq__(&self, value: Prop) -> PyPropertyFilter {
-        let filter = PropertyFilter::eq(&self.name, value);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::eq(name, value))
     }
 
     fn __ne__(&self, value: Prop) -> PyPropertyFilter {
-        let filter = PropertyFilter::ne(&self.name, value);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::ne(name, value))
     }
 
     fn __lt__(&self, value: Prop) -> PyPropertyFilter {
-        let filter = PropertyFilter::lt(&self.name, value);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::lt(name, value))
     }
 
     fn __le__(&self, value: Prop) -> PyPropertyFilter {
-        let filter = PropertyFilter::le(&self.name, value);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::le(name, value))
     }
 
     fn __gt__(&self, value: Prop) -> PyPropertyFilter {
-        let filter = PropertyFilter::gt(&self.name, value);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::gt(name, value))
     }
 
     fn __ge__(&self, value: Prop) -> PyPropertyFilter {
-        let filter = PropertyFilter::ge(&self.name, value);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::ge(name, value))
     }
 
     /// Create a filter that only keeps entities if they have the property
     fn is_some(&self) -> PyPropertyFilter {
-        let filter = PropertyFilter::is_some(&self.name);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::is_some(name))
     }
 
     /// Create a filter that only keeps entities that do not have the property
     fn is_none(&self) -> PyPropertyFilter {
-        let filter = PropertyFilter::is_none(&self.name);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::is_none(name))
     }
 
     /// Create a filter that keeps entities if their property value is in the set
     fn any(&self, values: HashSet<Prop>) -> PyPropertyFilter {
-        let filter = PropertyFilter::any(&self.name, values);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::any(name, values))
     }
 
     /// Create a filter that keeps entities if their property value is not in the set or
     /// if they don't have the property
     fn not_any(&self, values: HashSet<Prop>) -> PyPropertyFilter {
-        let filter = PropertyFilter::not_any(&self.name, values);
-        PyPropertyFilter(filter)
+        self.build(|name| PropertyFilter::not_any(name, values))
+    }
+
+    /// Create a filter that keeps entities if their string property starts with `prefix`
+    fn starts_with(&self, prefix: String) -> PyPropertyFilter {
+        self.build(|name| PropertyFilter::starts_with(name, prefix))
+    }
+
+    /// Create a filter that keeps entities if their string property ends with `suffix`
+    fn ends_with(&self, suffix: String) -> PyPropertyFilter {
+        self.build(|name| PropertyFilter::ends_with(name, suffix))
+    }
+
+    /// Create a filter that keeps entities if their string property is equal to `value` ignoring case
+    fn eq_ignore_case(&self, value: String) -> PyPropertyFilter {
+        self.build(|name| PropertyFilter::eq_ignore_case(name, value))
+    }
+
+    /// Create a filter that keeps entities if their string property matches the regular expression
+    ///
+    /// The pattern is not anchored, use `^` and `$` to match the full string.
+    fn regex(&self, pattern: &str) -> Result<PyPropertyFilter, GraphError> {
+        let name = self.keys.last().unwrap_or(&self.name);
+        let filter = PropertyFilter::regex(name, pattern)?;
+        Ok(self.build(|_| filter))
+    }
+
+    /// Create a filter that keeps entities if their string property contains `value` as a substring
+    /// or if their list property contains `value` as an element
+    fn contains(&self, value: Prop) -> PyPropertyFilter {
+        self.build(|name| PropertyFilter::contains(name, value))
     }
 }
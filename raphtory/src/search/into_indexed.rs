@@ -1,13 +1,15 @@
 use crate::{
     db::{
         api::view::{
-            internal::{DynamicGraph, IntoDynamic, OneHopFilter},
+            internal::{Base, DynamicGraph, IntoDynamic, OneHopFilter},
             time::internal::InternalTimeOps,
             StaticGraphViewOps,
         },
         graph::views::{
             layer_graph::LayeredGraph, node_subgraph::NodeSubgraph,
-            node_type_filtered_subgraph::TypeFilteredSubgraph, window_graph::WindowedGraph,
+            node_type_filtered_subgraph::TypeFilteredSubgraph,
+            property_filter::node_property_filter::NodePropertyFilteredGraph,
+            window_graph::WindowedGraph,
         },
     },
     prelude::GraphViewOps,
@@ -77,6 +79,22 @@ impl<G: StaticGraphViewOps + IntoDynamic> DynamicIndexedGraph
     }
 }
 
+impl<G: StaticGraphViewOps + IntoDynamic> DynamicIndexedGraph
+    for NodePropertyFilteredGraph<IndexedGraph<G>>
+{
+    fn into_dynamic_indexed(self) -> IndexedGraph<DynamicGraph> {
+        let g = self.base().graph.subgraph(self.nodes());
+        let index = self.base();
+        IndexedGraph {
+            graph: g.into_dynamic(),
+            node_index: index.node_index.clone(),
+            edge_index: index.edge_index.clone(),
+            reader: index.reader.clone(),
+            edge_reader: index.edge_reader.clone(),
+        }
+    }
+}
+
 impl<G: StaticGraphViewOps + IntoDynamic> DynamicIndexedGraph for IndexedGraph<G> {
     fn into_dynamic_indexed(self) -> IndexedGraph<DynamicGraph> {
         IndexedGraph {
@@ -938,6 +938,10 @@ impl<G: InternalDeletionOps> InternalDeletionOps for IndexedGraph<G> {
     ) -> Result<(), GraphError> {
         self.graph.internal_delete_existing_edge(t, eid, layer)
     }
+
+    fn internal_delete_node(&self, t: TimeIndexEntry, v: VID) -> Result<(), GraphError> {
+        self.graph.internal_delete_node(t, v)
+    }
 }
 
 impl<G: DeletionOps> DeletionOps for IndexedGraph<G> {}
@@ -92,6 +92,19 @@ impl GraphWriter {
         Ok(())
     }
 
+    /// Persist the pending updates and return the length of the cache file
+    ///
+    /// The file is only ever appended to, so the graph as it is now can be decoded from the first
+    /// `checkpoint` bytes of the file until it is truncated.
+    pub fn checkpoint(&self) -> Result<u64, GraphError> {
+        self.write()?;
+        let mut writer = self.writer.lock();
+        let len = writer
+            .seek(SeekFrom::End(0))
+            .map_err(WriteError::WriteError)?;
+        Ok(len)
+    }
+
     #[inline]
     pub fn resolve_layer(&self, layer: Option<&str>, layer_id: MaybeNew<usize>) {
         layer_id.if_new(|id| {
@@ -230,6 +243,10 @@ impl GraphWriter {
     pub fn delete_edge(&self, edge: EID, t: TimeIndexEntry, layer: usize) {
         self.proto_delta.lock().del_edge(edge, layer, t)
     }
+
+    pub fn delete_node(&self, node: VID, t: TimeIndexEntry) {
+        self.proto_delta.lock().del_node(node, t)
+    }
 }
 
 pub(crate) trait InternalCache {
@@ -303,6 +320,11 @@ impl<G: InternalCache + StableDecode + StableEncode> CacheOps for G {
         cache.write()
     }
 
+    fn checkpoint(&self) -> Result<u64, GraphError> {
+        let cache = self.get_cache().ok_or(GraphError::CacheNotInnitialised)?;
+        cache.checkpoint()
+    }
+
     fn load_cached(path: impl Into<GraphFolder>) -> Result<Self, GraphError> {
         let folder = path.into();
         let graph = Self::decode(&folder)?;
@@ -313,14 +335,18 @@ impl<G: InternalCache + StableDecode + StableEncode> CacheOps for G {
 
 #[cfg(test)]
 mod test {
-    use crate::serialise::incremental::GraphWriter;
+    use crate::{
+        core::utils::errors::GraphError,
+        prelude::*,
+        serialise::{incremental::GraphWriter, GraphFolder},
+    };
     use raphtory_api::core::{
         entities::{GidRef, VID},
         storage::dict_mapper::MaybeNew,
         utils::logging::global_info_logger,
     };
     use std::fs::File;
-    use tempfile::NamedTempFile;
+    use tempfile::{NamedTempFile, TempDir};
 
     #[test]
     fn test_write_failure() {
@@ -334,4 +360,39 @@ mod test {
         assert!(res.is_err());
         assert_eq!(cache.proto_delta.lock().nodes.len(), 1);
     }
+
+    #[test]
+    fn test_checkpoint_and_truncate() {
+        let tmp_dir = TempDir::new().unwrap();
+        let folder = GraphFolder::from(tmp_dir.path().join("g"));
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        graph.cache(&folder).unwrap();
+        let first = graph.checkpoint().unwrap();
+
+        graph.add_edge(1, 2, 3, [("weight", 1.0)], None).unwrap();
+        let second = graph.checkpoint().unwrap();
+        assert!(second > first);
+        graph.add_node(2, 4, NO_PROPS, None).unwrap();
+        graph.write_updates().unwrap();
+
+        let at_first = Graph::decode_checkpoint(&folder, first).unwrap();
+        assert_eq!(at_first.count_nodes(), 2);
+        let at_second = Graph::decode_checkpoint(&folder, second).unwrap();
+        assert_eq!(at_second.count_edges(), 2);
+        assert_eq!(at_second.count_nodes(), 3);
+        assert!(matches!(
+            Graph::decode_checkpoint(&folder, u64::MAX),
+            Err(GraphError::InvalidCheckpoint { .. })
+        ));
+
+        folder.truncate_graph(second).unwrap();
+        let restored = Graph::load_cached(&folder).unwrap();
+        assert_eq!(restored.count_nodes(), 3);
+        restored.add_node(3, 5, NO_PROPS, None).unwrap();
+        restored.write_updates().unwrap();
+        let reloaded = Graph::decode(&folder).unwrap();
+        assert!(reloaded.has_node(5));
+        assert!(!reloaded.has_node(4));
+    }
 }
@@ -99,6 +99,19 @@ impl GraphFolder {
         }
     }
 
+    /// Discard the updates appended to the graph file after `checkpoint`, see
+    /// [`CacheOps::checkpoint`]
+    ///
+    /// Graphs loaded from this folder before truncating it must not be written to again.
+    pub fn truncate_graph(&self, checkpoint: u64) -> Result<(), GraphError> {
+        let file = OpenOptions::new().write(true).open(self.get_graph_path())?;
+        let len = file.metadata()?.len();
+        if checkpoint > len {
+            return Err(GraphError::InvalidCheckpoint { checkpoint, len });
+        }
+        Ok(file.set_len(checkpoint)?)
+    }
+
     fn get_appendable_graph_file(&self) -> Result<File, GraphError> {
         let path = self.get_graph_path();
         Ok(OpenOptions::new().append(true).open(path)?)
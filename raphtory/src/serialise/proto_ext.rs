@@ -6,8 +6,9 @@ use crate::{
         proto,
         proto::{
             graph_update::{
-                DelEdge, PropPair, Update, UpdateEdgeCProps, UpdateEdgeTProps, UpdateGraphCProps,
-                UpdateGraphTProps, UpdateNodeCProps, UpdateNodeTProps, UpdateNodeType,
+                DelEdge, DelNode, PropPair, Update, UpdateEdgeCProps, UpdateEdgeTProps,
+                UpdateGraphCProps, UpdateGraphTProps, UpdateNodeCProps, UpdateNodeTProps,
+                UpdateNodeType,
             },
             new_meta::{
                 Meta, NewEdgeCProp, NewEdgeTProp, NewGraphCProp, NewGraphTProp, NewLayer,
@@ -102,6 +103,16 @@ impl DelEdge {
     }
 }
 
+impl DelNode {
+    pub fn vid(&self) -> VID {
+        VID(self.id as usize)
+    }
+
+    pub fn time(&self) -> TimeIndexEntry {
+        TimeIndexEntry(self.time, self.secondary as usize)
+    }
+}
+
 impl UpdateEdgeCProps {
     pub fn eid(&self) -> EID {
         EID(self.eid as usize)
@@ -337,6 +348,15 @@ impl GraphUpdate {
         };
         Self::new(Update::DelEdge(inner))
     }
+
+    fn del_node(node_id: VID, time: TimeIndexEntry) -> Self {
+        let inner = DelNode {
+            id: node_id.as_u64(),
+            time: time.t(),
+            secondary: time.i() as u64,
+        };
+        Self::new(Update::DelNode(inner))
+    }
 }
 
 impl UpdateGraphCProps {
@@ -490,6 +510,10 @@ impl proto::Graph {
         self.updates
             .push(GraphUpdate::del_edge(eid, layer_id, time))
     }
+
+    pub fn del_node(&mut self, node_id: VID, time: TimeIndexEntry) {
+        self.updates.push(GraphUpdate::del_node(node_id, time))
+    }
 }
 
 fn as_prop(prop_pair: &PropPair) -> Result<(usize, Prop), GraphError> {
@@ -68,6 +68,22 @@ pub trait StableDecode: Sized {
         let bytes = path.into().read_graph()?;
         Self::decode_from_bytes(bytes.as_ref())
     }
+
+    /// Decode the graph as it was when `checkpoint` was taken, see [`CacheOps::checkpoint`]
+    fn decode_checkpoint(
+        path: impl Into<GraphFolder>,
+        checkpoint: u64,
+    ) -> Result<Self, GraphError> {
+        let bytes = path.into().read_graph()?;
+        let bytes = bytes.as_ref();
+        let prefix = bytes
+            .get(..checkpoint as usize)
+            .ok_or(GraphError::InvalidCheckpoint {
+                checkpoint,
+                len: bytes.len() as u64,
+            })?;
+        Self::decode_from_bytes(prefix)
+    }
 }
 
 pub trait CacheOps: Sized {
@@ -79,6 +95,12 @@ pub trait CacheOps: Sized {
     /// Persist the new updates by appending them to the cache file.
     fn write_updates(&self) -> Result<(), GraphError>;
 
+    /// Persist the new updates and return a checkpoint of the cache file.
+    ///
+    /// The graph as it is now can be read back with [`StableDecode::decode_checkpoint`] and
+    /// restored with [`GraphFolder::truncate_graph`].
+    fn checkpoint(&self) -> Result<u64, GraphError>;
+
     /// Load graph from file and append future updates to the same file
     fn load_cached(path: impl Into<GraphFolder>) -> Result<Self, GraphError>;
 }
@@ -185,6 +207,9 @@ impl StableEncode for GraphStorage {
                     iter::empty::<(usize, Prop)>(),
                 );
             }
+            for t in node.deletions().iter() {
+                graph.del_node(node.vid(), TimeIndexEntry::start(t));
+            }
             graph.update_node_cprops(
                 node.vid(),
                 (0..n_const_meta.len()).flat_map(|i| node.prop(i).map(|v| (i, v))),
@@ -450,6 +475,12 @@ impl StableDecode for TemporalGraph {
                                     node.node_type = update.type_id();
                                 }
                             }
+                            Update::DelNode(del_node) => {
+                                if let Some(node) = shard.get_mut(del_node.vid()) {
+                                    node.delete(del_node.time());
+                                    storage.update_time(del_node.time());
+                                }
+                            }
                             _ => {}
                         }
                     }
@@ -648,6 +679,24 @@ mod proto_test {
         assert_eq!(deletions, vec![19]);
     }
 
+    #[test]
+    fn node_no_props_delete() {
+        let tempdir = TempDir::new().unwrap();
+        let temp_file = tempdir.path().join("graph");
+        let g1 = PersistentGraph::new();
+        g1.add_edge(3, "Alice", "Bob", NO_PROPS, None).unwrap();
+        g1.delete_node(19, "Alice").unwrap();
+        g1.encode(&temp_file).unwrap();
+        let g2 = PersistentGraph::decode(&temp_file).unwrap();
+        assert_graph_equal(&g1, &g2);
+
+        assert!(g2.at(18).node("Alice").is_some());
+        assert!(g2.at(19).node("Alice").is_none());
+        let edge = g2.edge("Alice", "Bob").expect("Failed to get edge");
+        let deletions = edge.deletions().iter().copied().collect::<Vec<_>>();
+        assert_eq!(deletions, vec![19]);
+    }
+
     #[test]
     fn edge_t_props() {
         let tempdir = TempDir::new().unwrap();
@@ -1009,9 +1058,14 @@ mod proto_test {
             .unwrap();
         g.write_updates().unwrap();
 
+        g.delete_node(8, "Bob").unwrap();
+        g.write_updates().unwrap();
+
         let g2 = PersistentGraph::decode(&temp_cache_file).unwrap();
 
         assert_graph_equal(&g, &g2);
+        assert!(g2.at(8).node("Bob").is_none());
+        assert!(g2.at(8).edge("Bob", "Charlie").is_none());
     }
 
     // we rely on this to make sure writing no updates does not actually write anything to file
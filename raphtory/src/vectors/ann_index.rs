@@ -0,0 +1,389 @@
+use crate::vectors::{
+    document_ref::DocumentRef, entity_id::EntityId, similarity_search_utils::cosine, Embedding,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+/// Number of neighbours kept per node on the upper layers. Layer 0 keeps twice as many.
+const MAX_NEIGHBOURS: usize = 16;
+const EF_CONSTRUCTION: usize = 100;
+/// Number of node and edge documents from which `vectorise` builds an index automatically
+pub(crate) const ANN_INDEX_THRESHOLD: usize = 10_000;
+const RNG_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/// Approximate nearest-neighbour index over document embeddings (HNSW)
+///
+/// Documents are never removed from the navigation graph, they are only marked as deleted so they
+/// are skipped in the results. The index is rebuilt from scratch once deleted documents
+/// outnumber the live ones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct AnnIndex {
+    items: Vec<Item>,
+    entities: HashMap<EntityId, Vec<usize>>,
+    entry_point: Option<usize>,
+    deleted: usize,
+    rng_state: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Item {
+    doc: DocumentRef,
+    deleted: bool,
+    /// neighbours for each layer this item belongs to, starting from layer 0
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl Item {
+    fn level(&self) -> usize {
+        self.neighbours.len() - 1
+    }
+}
+
+/// `distance` is the negated cosine similarity so closer candidates compare as smaller
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    id: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+impl AnnIndex {
+    pub(crate) fn new() -> Self {
+        Self {
+            items: vec![],
+            entities: HashMap::new(),
+            entry_point: None,
+            deleted: 0,
+            rng_state: RNG_SEED,
+        }
+    }
+
+    pub(crate) fn from_documents<'a, I>(documents: I) -> Self
+    where
+        I: IntoIterator<Item = &'a DocumentRef>,
+    {
+        let mut index = Self::new();
+        for doc in documents {
+            index.insert(doc.clone());
+        }
+        index
+    }
+
+    /// Number of documents that can be returned by a search
+    pub(crate) fn len(&self) -> usize {
+        self.items.len() - self.deleted
+    }
+
+    /// Replace all the documents for `entity_id` with `documents`
+    pub(crate) fn replace_entity(&mut self, entity_id: &EntityId, documents: &[DocumentRef]) {
+        if let Some(ids) = self.entities.remove(entity_id) {
+            for id in ids {
+                self.items[id].deleted = true;
+                self.deleted += 1;
+            }
+        }
+        if self.deleted > self.len() {
+            let live = self
+                .items
+                .iter()
+                .filter(|item| !item.deleted)
+                .map(|item| item.doc.clone())
+                .collect::<Vec<_>>();
+            *self = Self::from_documents(&live);
+        }
+        for doc in documents {
+            self.insert(doc.clone());
+        }
+    }
+
+    /// Search the `ef` closest documents to `query`, returning the ones passing `filter` sorted
+    /// by descending similarity
+    pub(crate) fn search<F>(
+        &self,
+        query: &Embedding,
+        ef: usize,
+        filter: F,
+    ) -> Vec<(DocumentRef, f32)>
+    where
+        F: Fn(&DocumentRef) -> bool,
+    {
+        let Some(entry_point) = self.entry_point else {
+            return vec![];
+        };
+        let mut entry = self.candidate(query, entry_point);
+        for layer in (1..=self.items[entry_point].level()).rev() {
+            entry = self.greedy_closest(query, entry, layer);
+        }
+        self.search_layer(query, entry, ef, 0)
+            .into_iter()
+            .filter(|candidate| {
+                let item = &self.items[candidate.id];
+                !item.deleted && filter(&item.doc)
+            })
+            .map(|candidate| (self.items[candidate.id].doc.clone(), -candidate.distance))
+            .collect()
+    }
+
+    fn insert(&mut self, doc: DocumentRef) {
+        let id = self.items.len();
+        let level = self.random_level();
+        let entity_id = doc.entity_id.clone();
+        self.items.push(Item {
+            doc,
+            deleted: false,
+            neighbours: vec![vec![]; level + 1],
+        });
+        self.entities.entry(entity_id).or_default().push(id);
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(id);
+            return;
+        };
+        let query = self.items[id].doc.embedding.clone();
+        let top_level = self.items[entry_point].level();
+        let mut entry = self.candidate(&query, entry_point);
+        for layer in (level + 1..=top_level).rev() {
+            entry = self.greedy_closest(&query, entry, layer);
+        }
+        for layer in (0..=level.min(top_level)).rev() {
+            let found = self.search_layer(&query, entry, EF_CONSTRUCTION, layer);
+            entry = found[0];
+            let neighbours = found
+                .iter()
+                .take(MAX_NEIGHBOURS)
+                .map(|candidate| candidate.id)
+                .collect::<Vec<_>>();
+            for &neighbour in &neighbours {
+                self.connect(neighbour, id, layer);
+            }
+            self.items[id].neighbours[layer] = neighbours;
+        }
+        if level > top_level {
+            self.entry_point = Some(id);
+        }
+    }
+
+    /// Add `new` to the neighbours of `id`, dropping the furthest one if the list is full
+    fn connect(&mut self, id: usize, new: usize, layer: usize) {
+        let max_neighbours = if layer == 0 {
+            2 * MAX_NEIGHBOURS
+        } else {
+            MAX_NEIGHBOURS
+        };
+        self.items[id].neighbours[layer].push(new);
+        if self.items[id].neighbours[layer].len() > max_neighbours {
+            let embedding = self.items[id].doc.embedding.clone();
+            let mut candidates = self.items[id].neighbours[layer]
+                .iter()
+                .map(|&neighbour| self.candidate(&embedding, neighbour))
+                .collect::<Vec<_>>();
+            candidates.sort();
+            self.items[id].neighbours[layer] = candidates
+                .into_iter()
+                .take(max_neighbours)
+                .map(|candidate| candidate.id)
+                .collect();
+        }
+    }
+
+    fn greedy_closest(&self, query: &Embedding, mut current: Candidate, layer: usize) -> Candidate {
+        loop {
+            let closest = self.items[current.id].neighbours[layer]
+                .iter()
+                .map(|&neighbour| self.candidate(query, neighbour))
+                .min();
+            match closest {
+                Some(closest) if closest < current => current = closest,
+                _ => return current,
+            }
+        }
+    }
+
+    /// Returns up to `ef` candidates sorted by ascending distance
+    fn search_layer(
+        &self,
+        query: &Embedding,
+        entry: Candidate,
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited = HashSet::from([entry.id]);
+        let mut to_visit = BinaryHeap::from([Reverse(entry)]);
+        let mut found = BinaryHeap::from([entry]);
+        while let Some(Reverse(current)) = to_visit.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|furthest| current > *furthest) {
+                break;
+            }
+            for &neighbour in &self.items[current.id].neighbours[layer] {
+                if visited.insert(neighbour) {
+                    let candidate = self.candidate(query, neighbour);
+                    if found.len() < ef
+                        || found.peek().is_some_and(|furthest| candidate < *furthest)
+                    {
+                        to_visit.push(Reverse(candidate));
+                        found.push(candidate);
+                        if found.len() > ef {
+                            found.pop();
+                        }
+                    }
+                }
+            }
+        }
+        found.into_sorted_vec()
+    }
+
+    fn candidate(&self, query: &Embedding, id: usize) -> Candidate {
+        Candidate {
+            distance: -cosine(query, &self.items[id].doc.embedding),
+            id,
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        // xorshift64*, we only need a cheap and reproducible source of randomness here
+        self.rng_state ^= self.rng_state >> 12;
+        self.rng_state ^= self.rng_state << 25;
+        self.rng_state ^= self.rng_state >> 27;
+        let random = self.rng_state.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        let uniform = ((random >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level_multiplier = 1.0 / (MAX_NEIGHBOURS as f64).ln();
+        (-uniform.ln() * level_multiplier) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AnnIndex;
+    use crate::{
+        core::Lifespan,
+        vectors::{
+            document_ref::DocumentRef, entity_id::EntityId, similarity_search_utils::cosine,
+            Embedding,
+        },
+    };
+    use itertools::Itertools;
+    use raphtory_api::core::entities::GID;
+
+    fn random_embeddings(n: usize, dim: usize, seed: u64) -> Vec<Embedding> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) as f32 / (1u64 << 31) as f32) * 2.0 - 1.0
+        };
+        (0..n)
+            .map(|_| (0..dim).map(|_| next()).collect_vec().into())
+            .collect()
+    }
+
+    fn documents(embeddings: &[Embedding]) -> Vec<DocumentRef> {
+        embeddings
+            .iter()
+            .enumerate()
+            .map(|(id, embedding)| {
+                let entity_id = EntityId::Node {
+                    id: GID::U64((id / 2) as u64),
+                };
+                DocumentRef::new(entity_id, id % 2, embedding.clone(), Lifespan::Inherited)
+            })
+            .collect()
+    }
+
+    fn exact_top_k(docs: &[DocumentRef], query: &Embedding, k: usize) -> Vec<DocumentRef> {
+        docs.iter()
+            .sorted_by(|a, b| cosine(query, &b.embedding).total_cmp(&cosine(query, &a.embedding)))
+            .take(k)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_recall_against_brute_force() {
+        let docs = documents(&random_embeddings(2000, 16, 1));
+        let index = AnnIndex::from_documents(&docs);
+        assert_eq!(index.len(), 2000);
+
+        let queries = random_embeddings(20, 16, 2);
+        let mut hits = 0;
+        for query in &queries {
+            let expected = exact_top_k(&docs, query, 10);
+            let found = index.search(query, 64, |_| true);
+            assert!(found
+                .iter()
+                .tuple_windows()
+                .all(|((_, score1), (_, score2))| score1 >= score2));
+            hits += expected
+                .iter()
+                .filter(|doc| found.iter().take(10).any(|(found, _)| found == *doc))
+                .count();
+        }
+        let recall = hits as f32 / (queries.len() * 10) as f32;
+        assert!(recall > 0.9, "recall was {recall}");
+    }
+
+    #[test]
+    fn test_exhaustive_search_is_exact() {
+        let docs = documents(&random_embeddings(300, 8, 3));
+        let index = AnnIndex::from_documents(&docs);
+        let query = &random_embeddings(1, 8, 4)[0];
+        let found = index
+            .search(query, 300, |_| true)
+            .into_iter()
+            .map(|(doc, _)| doc)
+            .collect_vec();
+        assert_eq!(found, exact_top_k(&docs, query, 300));
+    }
+
+    #[test]
+    fn test_filter_and_replace_entity() {
+        let embeddings = random_embeddings(400, 8, 5);
+        let docs = documents(&embeddings);
+        let mut index = AnnIndex::from_documents(&docs);
+        let query = &embeddings[0];
+
+        let found = index.search(query, 400, |doc| doc.entity_id != docs[0].entity_id);
+        assert!(found
+            .iter()
+            .all(|(doc, _)| doc.entity_id != docs[0].entity_id));
+        assert_eq!(found.len(), 398);
+
+        // replacing the entity of the closest document with a single opposite document
+        let opposite: Embedding = query.iter().map(|x| -x).collect_vec().into();
+        let replacement =
+            DocumentRef::new(docs[0].entity_id.clone(), 0, opposite, Lifespan::Inherited);
+        index.replace_entity(&docs[0].entity_id, &[replacement.clone()]);
+        assert_eq!(index.len(), 399);
+        let found = index.search(query, 1000, |_| true);
+        assert_eq!(found.len(), 399);
+        assert_eq!(found.last().unwrap().0, replacement);
+        assert!(!found.iter().any(|(doc, _)| doc == &docs[1]));
+
+        // removing most of the entities triggers a rebuild
+        for doc in &docs[..300] {
+            index.replace_entity(&doc.entity_id, &[]);
+        }
+        assert_eq!(index.len(), 100);
+        let found = index.search(query, 1000, |_| true);
+        assert_eq!(found.len(), 100);
+        assert!(found.iter().all(|(doc, _)| docs[300..].contains(doc)));
+    }
+}
@@ -2,6 +2,7 @@ use crate::core::{DocumentInput, Lifespan};
 use futures_util::future::BoxFuture;
 use std::{error, future::Future, ops::Deref, sync::Arc};
 
+mod ann_index;
 pub mod datetimeformat;
 mod document_ref;
 pub mod embedding_cache;
@@ -118,11 +119,18 @@ mod vector_tests {
     use crate::{
         core::Prop,
         prelude::{AdditionOps, Graph, GraphViewOps},
-        vectors::{embeddings::openai_embedding, vectorisable::Vectorisable},
+        vectors::{
+            embeddings::openai_embedding, vector_selection::VectorSelection,
+            vectorisable::Vectorisable, vectorised_graph::VectorisedGraph,
+        },
     };
     use dotenv::dotenv;
     use itertools::Itertools;
-    use std::fs::remove_file;
+    use std::{
+        collections::hash_map::DefaultHasher,
+        fs::remove_file,
+        hash::{Hash, Hasher},
+    };
     use template::DocumentTemplate;
     use tokio;
 
@@ -139,6 +147,23 @@ mod vector_tests {
             .collect_vec())
     }
 
+    /// deterministic embedding that spreads different texts over the space
+    async fn hashed_embedding(texts: Vec<String>) -> EmbeddingResult<Vec<Embedding>> {
+        Ok(texts
+            .into_iter()
+            .map(|text| {
+                (0..8u64)
+                    .map(|dim| {
+                        let mut hasher = DefaultHasher::new();
+                        (dim, &text).hash(&mut hasher);
+                        (hasher.finish() % 1000) as f32 / 500.0 - 1.0
+                    })
+                    .collect_vec()
+                    .into()
+            })
+            .collect_vec())
+    }
+
     async fn panicking_embedding(_texts: Vec<String>) -> EmbeddingResult<Vec<Embedding>> {
         panic!("embedding function was called")
     }
@@ -474,4 +499,87 @@ mod vector_tests {
             .get_documents();
         assert!(docs[0].content().contains("Frodo appeared with Gandalf"));
     }
+
+    #[tokio::test]
+    async fn test_ann_index_matches_brute_force() {
+        let g = Graph::new();
+        for node in 0..300 {
+            g.add_node(
+                node,
+                node as u64,
+                [("age", Prop::str("30"))],
+                Some("hobbit"),
+            )
+            .unwrap();
+        }
+        for node in 0..100 {
+            g.add_edge(node, node as u64, node as u64 + 1, NO_PROPS, None)
+                .unwrap();
+        }
+        let vectors = g
+            .vectorise(
+                Box::new(hashed_embedding),
+                None.into(),
+                false,
+                custom_template(),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(!vectors.has_ann_index());
+        let query = hashed_embedding(vec!["query".to_owned()])
+            .await
+            .unwrap()
+            .remove(0);
+
+        let search = |vectors: &VectorisedGraph<Graph>| {
+            let docs = |selection: VectorSelection<Graph>| {
+                selection
+                    .get_documents_with_scores()
+                    .into_iter()
+                    .map(|(doc, score)| (doc.content().to_owned(), score))
+                    .collect_vec()
+            };
+            [
+                docs(vectors.documents_by_similarity(&query, 5, None)),
+                docs(vectors.documents_by_similarity(&query, 5, Some((50, 150)))),
+                docs(vectors.entities_by_similarity(&query, 5, None)),
+                docs(vectors.nodes_by_similarity(&query, 5, Some((0, 100)))),
+                docs(vectors.edges_by_similarity(&query, 5, Some((20, 40)))),
+            ]
+        };
+        let exact = search(&vectors);
+        vectors.build_ann_index();
+        assert!(vectors.has_ann_index());
+        let approximate = search(&vectors);
+        for (exact, approximate) in exact.iter().zip(approximate.iter()) {
+            assert_eq!(exact.len(), approximate.len());
+            // scores must be the same, but documents with equal scores might come in any order
+            for ((_, exact_score), (_, approximate_score)) in exact.iter().zip(approximate) {
+                assert_eq!(exact_score, approximate_score);
+            }
+        }
+        for (content, _) in &approximate[3] {
+            let name: i64 = content.split(' ').next().unwrap().parse().unwrap();
+            assert!(name < 100);
+        }
+
+        // the index survives a roundtrip to disk
+        let path = "/tmp/raphtory/vectors-ann-index-test";
+        std::fs::create_dir_all("/tmp/raphtory").unwrap();
+        vectors.write_to_path(path.as_ref()).unwrap();
+        let loaded = VectorisedGraph::read_from_path(
+            path.as_ref(),
+            g.clone(),
+            vectors.embedding.clone(),
+            None.into(),
+        )
+        .unwrap();
+        assert!(loaded.has_ann_index());
+        assert_eq!(search(&loaded), approximate);
+
+        vectors.drop_ann_index();
+        assert_eq!(search(&vectors), exact);
+    }
 }
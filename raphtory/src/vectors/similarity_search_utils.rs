@@ -44,7 +44,7 @@ where
         .take(k)
 }
 
-fn cosine(vector1: &Embedding, vector2: &Embedding) -> f32 {
+pub(crate) fn cosine(vector1: &Embedding, vector2: &Embedding) -> f32 {
     assert_eq!(vector1.len(), vector2.len());
 
     let dot_product: f32 = vector1.iter().zip(vector2.iter()).map(|(x, y)| x * y).sum();
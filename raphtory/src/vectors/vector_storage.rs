@@ -1,17 +1,12 @@
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::Path,
-    sync::Arc,
-};
+use std::{collections::HashMap, fs::File, io::BufWriter, path::Path, sync::Arc};
 
 use crate::{core::utils::errors::GraphError, db::api::view::StaticGraphViewOps};
 
 use super::{
-    document_ref::DocumentRef, embedding_cache::EmbeddingCache, entity_id::EntityId,
-    template::DocumentTemplate, vectorised_graph::VectorisedGraph, EmbeddingFunction,
+    ann_index::AnnIndex, document_ref::DocumentRef, embedding_cache::EmbeddingCache,
+    entity_id::EntityId, template::DocumentTemplate, vectorised_graph::VectorisedGraph,
+    EmbeddingFunction,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -20,6 +15,28 @@ struct VectorStorage {
     graph_documents: Vec<DocumentRef>,
     node_documents: HashMap<EntityId, Vec<DocumentRef>>,
     edge_documents: HashMap<EntityId, Vec<DocumentRef>>,
+    ann_index: Option<AnnIndex>,
+}
+
+/// Format used before the approximate nearest-neighbour index was persisted
+#[derive(Deserialize)]
+struct LegacyVectorStorage {
+    template: DocumentTemplate,
+    graph_documents: Vec<DocumentRef>,
+    node_documents: HashMap<EntityId, Vec<DocumentRef>>,
+    edge_documents: HashMap<EntityId, Vec<DocumentRef>>,
+}
+
+impl From<LegacyVectorStorage> for VectorStorage {
+    fn from(value: LegacyVectorStorage) -> Self {
+        Self {
+            template: value.template,
+            graph_documents: value.graph_documents,
+            node_documents: value.node_documents,
+            edge_documents: value.edge_documents,
+            ann_index: None,
+        }
+    }
 }
 
 impl<G: StaticGraphViewOps> VectorisedGraph<G> {
@@ -30,14 +47,16 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         cache_storage: Arc<Option<EmbeddingCache>>,
     ) -> Option<Self> {
         // TODO: return Result instead of Option
-        let file = File::open(&path).ok()?;
-        let mut reader = BufReader::new(file);
+        let bytes = std::fs::read(path).ok()?;
         let VectorStorage {
             template,
             graph_documents,
             node_documents,
             edge_documents,
-        } = bincode::deserialize_from(&mut reader).ok()?;
+            ann_index,
+        } = bincode::deserialize(&bytes)
+            .or_else(|_| bincode::deserialize::<LegacyVectorStorage>(&bytes).map(Into::into))
+            .ok()?;
 
         Some(VectorisedGraph::new(
             graph,
@@ -47,6 +66,7 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
             Arc::new(graph_documents.into()),
             Arc::new(node_documents.into()),
             Arc::new(edge_documents.into()),
+            Arc::new(ann_index.into()),
         ))
     }
 
@@ -56,6 +76,7 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
             graph_documents: self.graph_documents.read().clone(),
             node_documents: self.node_documents.read().clone(),
             edge_documents: self.edge_documents.read().clone(),
+            ann_index: self.ann_index.read().clone(),
         };
         let file = File::create(path)?;
         let mut writer = BufWriter::new(file);
@@ -5,12 +5,13 @@ use crate::{
         graph::{edge::EdgeView, node::NodeView},
     },
     vectors::{
-        document_ref::DocumentRef, embedding_cache::EmbeddingCache, entity_id::EntityId,
-        template::DocumentTemplate, vectorised_graph::VectorisedGraph, EmbeddingFunction, Lifespan,
+        ann_index::ANN_INDEX_THRESHOLD, document_ref::DocumentRef, embedding_cache::EmbeddingCache,
+        entity_id::EntityId, template::DocumentTemplate, vectorised_graph::VectorisedGraph,
+        EmbeddingFunction, Lifespan,
     },
 };
 use async_trait::async_trait;
-use itertools::Itertools;
+use itertools::{chain, Itertools};
 use parking_lot::RwLock;
 use std::{collections::HashMap, sync::Arc};
 use tracing::info;
@@ -87,7 +88,10 @@ impl<G: StaticGraphViewOps + IntoDynamic + Send> Vectorisable<G> for G {
             cache.iter().for_each(|cache| cache.dump_to_disk());
         }
 
-        Ok(VectorisedGraph::new(
+        let num_documents = chain!(node_refs.values(), edge_refs.values())
+            .map(|refs| refs.len())
+            .sum::<usize>();
+        let vectors = VectorisedGraph::new(
             self.clone(),
             template,
             embedding.into(),
@@ -95,7 +99,15 @@ impl<G: StaticGraphViewOps + IntoDynamic + Send> Vectorisable<G> for G {
             RwLock::new(graph_refs).into(),
             RwLock::new(node_refs).into(),
             RwLock::new(edge_refs).into(),
-        ))
+            RwLock::new(None).into(),
+        );
+        if num_documents >= ANN_INDEX_THRESHOLD {
+            if verbose {
+                info!("building approximate nearest-neighbour index");
+            }
+            vectors.build_ann_index();
+        }
+        Ok(vectors)
     }
 }
 
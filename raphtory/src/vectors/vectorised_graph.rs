@@ -4,6 +4,7 @@ use crate::{
     db::api::view::{DynamicGraph, IntoDynamic, StaticGraphViewOps},
     prelude::*,
     vectors::{
+        ann_index::AnnIndex,
         document_ref::DocumentRef,
         embedding_cache::EmbeddingCache,
         entity_id::EntityId,
@@ -32,9 +33,13 @@ pub struct VectorisedGraph<G: StaticGraphViewOps> {
     pub(crate) graph_documents: Arc<RwLock<Vec<DocumentRef>>>,
     pub(crate) node_documents: Arc<RwLock<HashMap<EntityId, Vec<DocumentRef>>>>, // TODO: replace with FxHashMap
     pub(crate) edge_documents: Arc<RwLock<HashMap<EntityId, Vec<DocumentRef>>>>,
+    pub(crate) ann_index: Arc<RwLock<Option<AnnIndex>>>,
     pub(crate) empty_vec: Vec<DocumentRef>,
 }
 
+/// Minimum number of candidates explored when searching the approximate nearest-neighbour index
+const MIN_EF: usize = 64;
+
 // This has to be here so it is shared between python and graphql
 pub type DynamicVectorisedGraph = VectorisedGraph<DynamicGraph>;
 
@@ -49,6 +54,7 @@ impl<G: StaticGraphViewOps> Clone for VectorisedGraph<G> {
             self.graph_documents.clone(),
             self.node_documents.clone(),
             self.edge_documents.clone(),
+            self.ann_index.clone(),
         )
     }
 }
@@ -63,6 +69,7 @@ impl<G: StaticGraphViewOps + IntoDynamic> VectorisedGraph<G> {
             self.graph_documents.clone(),
             self.node_documents.clone(),
             self.edge_documents.clone(),
+            self.ann_index.clone(),
         )
     }
 }
@@ -76,6 +83,7 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         graph_documents: Arc<RwLock<Vec<DocumentRef>>>,
         node_documents: Arc<RwLock<HashMap<EntityId, Vec<DocumentRef>>>>,
         edge_documents: Arc<RwLock<HashMap<EntityId, Vec<DocumentRef>>>>,
+        ann_index: Arc<RwLock<Option<AnnIndex>>>,
     ) -> Self {
         Self {
             source_graph: graph,
@@ -85,6 +93,7 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
             graph_documents,
             node_documents,
             edge_documents,
+            ann_index,
             empty_vec: vec![],
         }
     }
@@ -99,6 +108,9 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
                 self.cache_storage.as_ref(),
             )
             .await?;
+            if let Some(index) = self.ann_index.write().as_mut() {
+                index.replace_entity(&entity_id, &refs);
+            }
             self.node_documents.write().insert(entity_id, refs);
         }
         Ok(())
@@ -114,6 +126,9 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
                 self.cache_storage.as_ref(),
             )
             .await?;
+            if let Some(index) = self.ann_index.write().as_mut() {
+                index.replace_entity(&entity_id, &refs);
+            }
             self.edge_documents.write().insert(entity_id, refs);
         }
         Ok(())
@@ -132,6 +147,31 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         Ok(())
     }
 
+    /// Build an approximate nearest-neighbour index over the node and edge documents
+    ///
+    /// Once built, the index is used by the similarity searches on this graph and kept up to date
+    /// by `update_node` and `update_edge`. Searches fall back to an exact brute-force scan
+    /// if the index can't provide enough results matching the window.
+    pub fn build_ann_index(&self) {
+        let index = {
+            let node_documents = self.node_documents.read();
+            let edge_documents = self.edge_documents.read();
+            let documents = chain!(node_documents.values(), edge_documents.values()).flatten();
+            AnnIndex::from_documents(documents)
+        };
+        *self.ann_index.write() = Some(index);
+    }
+
+    /// Remove the approximate nearest-neighbour index so searches go back to being exact
+    pub fn drop_ann_index(&self) {
+        *self.ann_index.write() = None;
+    }
+
+    /// Whether similarity searches use an approximate nearest-neighbour index
+    pub fn has_ann_index(&self) -> bool {
+        self.ann_index.read().is_some()
+    }
+
     /// Save the embeddings present in this graph to `file` so they can be further used in a call to `vectorise`
     pub fn save_embeddings(&self, file: PathBuf) {
         let cache = EmbeddingCache::new(file);
@@ -166,10 +206,14 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         limit: usize,
         window: Option<(i64, i64)>,
     ) -> VectorSelection<G> {
-        let node_documents = self.node_documents.read();
-        let edge_documents = self.edge_documents.read();
-        let joined = chain!(node_documents.iter(), edge_documents.iter());
-        let docs = self.search_top_documents(joined, query, limit, window);
+        let docs = self
+            .ann_search_documents(query, limit, window)
+            .unwrap_or_else(|| {
+                let node_documents = self.node_documents.read();
+                let edge_documents = self.edge_documents.read();
+                let joined = chain!(node_documents.iter(), edge_documents.iter());
+                self.search_top_documents(joined, query, limit, window)
+            });
         VectorSelection::new_with_preselection(self.clone(), docs)
     }
 
@@ -188,10 +232,14 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         limit: usize,
         window: Option<(i64, i64)>,
     ) -> VectorSelection<G> {
-        let node_documents = self.node_documents.read();
-        let edge_documents = self.edge_documents.read();
-        let joined = chain!(node_documents.iter(), edge_documents.iter());
-        let docs = self.search_top_document_groups(joined, query, limit, window);
+        let docs = self
+            .ann_search_document_groups(query, limit, window, |_| true)
+            .unwrap_or_else(|| {
+                let node_documents = self.node_documents.read();
+                let edge_documents = self.edge_documents.read();
+                let joined = chain!(node_documents.iter(), edge_documents.iter());
+                self.search_top_document_groups(joined, query, limit, window)
+            });
         VectorSelection::new_with_preselection(self.clone(), docs)
     }
 
@@ -210,8 +258,12 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         limit: usize,
         window: Option<(i64, i64)>,
     ) -> VectorSelection<G> {
-        let node_documents = self.node_documents.read();
-        let docs = self.search_top_document_groups(node_documents.deref(), query, limit, window);
+        let docs = self
+            .ann_search_document_groups(query, limit, window, EntityId::is_node)
+            .unwrap_or_else(|| {
+                let node_documents = self.node_documents.read();
+                self.search_top_document_groups(node_documents.deref(), query, limit, window)
+            });
         VectorSelection::new_with_preselection(self.clone(), docs)
     }
 
@@ -230,11 +282,101 @@ impl<G: StaticGraphViewOps> VectorisedGraph<G> {
         limit: usize,
         window: Option<(i64, i64)>,
     ) -> VectorSelection<G> {
-        let edge_documents = self.edge_documents.read();
-        let docs = self.search_top_document_groups(edge_documents.deref(), query, limit, window);
+        let docs = self
+            .ann_search_document_groups(query, limit, window, EntityId::is_edge)
+            .unwrap_or_else(|| {
+                let edge_documents = self.edge_documents.read();
+                self.search_top_document_groups(edge_documents.deref(), query, limit, window)
+            });
         VectorSelection::new_with_preselection(self.clone(), docs)
     }
 
+    fn window_filter(&self, window: Option<(i64, i64)>) -> impl Fn(&DocumentRef) -> bool + '_ {
+        let windowed_graph = window.map(|(start, end)| self.source_graph.window(start, end));
+        move |doc| match &windowed_graph {
+            None => true,
+            Some(windowed_graph) => doc.exists_on_window(Some(windowed_graph), &window),
+        }
+    }
+
+    /// Search the index with an increasing `ef` until `done` is satisfied, returning `None` if
+    /// there is no index or if it is not worth searching it anymore compared to brute force
+    fn ann_search<F, D>(
+        &self,
+        query: &Embedding,
+        limit: usize,
+        filter: F,
+        done: D,
+    ) -> Option<Vec<(DocumentRef, f32)>>
+    where
+        F: Fn(&DocumentRef) -> bool,
+        D: Fn(&[(DocumentRef, f32)]) -> bool,
+    {
+        let index = self.ann_index.read();
+        let index = index.as_ref()?;
+        let mut ef = limit.max(MIN_EF);
+        while ef < index.len() {
+            let found = index.search(query, ef, &filter);
+            if done(&found) {
+                return Some(found);
+            }
+            ef *= 2;
+        }
+        None
+    }
+
+    fn ann_search_documents(
+        &self,
+        query: &Embedding,
+        limit: usize,
+        window: Option<(i64, i64)>,
+    ) -> Option<Vec<(DocumentRef, f32)>> {
+        let filter = self.window_filter(window);
+        let mut found = self.ann_search(query, limit, filter, |found| found.len() >= limit)?;
+        found.truncate(limit);
+        Some(found)
+    }
+
+    fn ann_search_document_groups(
+        &self,
+        query: &Embedding,
+        limit: usize,
+        window: Option<(i64, i64)>,
+        entity_filter: fn(&EntityId) -> bool,
+    ) -> Option<Vec<(DocumentRef, f32)>> {
+        let window_filter = self.window_filter(window);
+        let found = self.ann_search(
+            query,
+            limit,
+            |doc| entity_filter(&doc.entity_id) && window_filter(doc),
+            |found| found.iter().map(|(doc, _)| &doc.entity_id).unique().count() >= limit,
+        )?;
+
+        // same as for the brute force search, every document of the top entities matching the
+        // window is returned, with the score of the best one
+        let node_documents = self.node_documents.read();
+        let edge_documents = self.edge_documents.read();
+        let top_entities = found
+            .into_iter()
+            .unique_by(|(doc, _)| doc.entity_id.clone())
+            .take(limit);
+        let docs = top_entities
+            .flat_map(|(doc, score)| {
+                let group = if doc.entity_id.is_node() {
+                    node_documents.get(&doc.entity_id)
+                } else {
+                    edge_documents.get(&doc.entity_id)
+                };
+                group
+                    .into_iter()
+                    .flatten()
+                    .filter(|doc| window_filter(doc))
+                    .map(move |doc| (doc.clone(), score))
+            })
+            .collect();
+        Some(docs)
+    }
+
     fn search_top_documents<'a, I>(
         &self,
         document_groups: I,